@@ -3,6 +3,7 @@
 //! Benchmarks cover:
 //!   - Tool dispatch (XML parsing, native parsing)
 //!   - Memory store/recall cycles (SQLite backend)
+//!   - Vector recall at scale (SQLite brute force vs IVF index)
 //!   - Agent turn cycle (full orchestration loop)
 //!
 //! Run: `cargo bench`
@@ -16,7 +17,8 @@ use zeroclaw::agent::agent::Agent;
 use zeroclaw::agent::dispatcher::{NativeToolDispatcher, ToolDispatcher, XmlToolDispatcher};
 use zeroclaw::config::MemoryConfig;
 use zeroclaw::memory;
-use zeroclaw::memory::{Memory, MemoryCategory};
+use zeroclaw::memory::embeddings::EmbeddingProvider;
+use zeroclaw::memory::{Memory, MemoryCategory, SqliteMemory};
 use zeroclaw::observability::{NoopObserver, Observer};
 use zeroclaw::providers::{ChatRequest, ChatResponse, Provider, ToolCall};
 use zeroclaw::tools::{Tool, ToolResult};
@@ -38,6 +40,7 @@ impl BenchProvider {
             responses: Mutex::new(vec![ChatResponse {
                 text: Some(text.into()),
                 tool_calls: vec![],
+                usage: None,
             }]),
         }
     }
@@ -52,10 +55,12 @@ impl BenchProvider {
                         name: "noop".into(),
                         arguments: "{}".into(),
                     }],
+                    usage: None,
                 },
                 ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
+                    usage: None,
                 },
             ]),
         }
@@ -85,6 +90,7 @@ impl Provider for BenchProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -109,6 +115,7 @@ impl Tool for NoopTool {
             success: true,
             output: String::new(),
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -150,6 +157,7 @@ Let me know if you need more."#
                 .into(),
         ),
         tool_calls: vec![],
+        usage: None,
     };
 
     let multi_tool = ChatResponse {
@@ -166,6 +174,7 @@ Let me know if you need more."#
                 .into(),
         ),
         tool_calls: vec![],
+        usage: None,
     };
 
     c.bench_function("xml_parse_single_tool_call", |b| {
@@ -198,6 +207,7 @@ fn bench_native_parsing(c: &mut Criterion) {
                 arguments: r#"{"path": "src/main.rs"}"#.into(),
             },
         ],
+        usage: None,
     };
 
     c.bench_function("native_parse_tool_calls", |b| {
@@ -260,6 +270,135 @@ fn bench_memory_operations(c: &mut Criterion) {
     });
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Benchmark: Vector recall at scale (brute force vs IVF)
+// ─────────────────────────────────────────────────────────────────────────────
+
+const VECTOR_BENCH_ROWS: usize = 5_000;
+const VECTOR_BENCH_DIMS: usize = 128;
+const VECTOR_BENCH_CLUSTERS: u64 = 64;
+
+/// Deterministic embedder producing clustered vectors from the trailing
+/// number of the text, so recall quality can be compared across indexes.
+struct ClusteredEmbedding;
+
+fn splitmix(state: &mut u64) -> f32 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    #[allow(clippy::cast_precision_loss)]
+    let unit = (z >> 40) as f32 / (1u64 << 24) as f32;
+    unit * 2.0 - 1.0
+}
+
+impl ClusteredEmbedding {
+    fn vector(n: u64) -> Vec<f32> {
+        let mut center_state = n % VECTOR_BENCH_CLUSTERS;
+        let mut noise_state = n.wrapping_mul(31).wrapping_add(7);
+        (0..VECTOR_BENCH_DIMS)
+            .map(|_| splitmix(&mut center_state) + 0.4 * splitmix(&mut noise_state))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for ClusteredEmbedding {
+    fn name(&self) -> &str {
+        "bench-clustered"
+    }
+
+    fn dimensions(&self) -> usize {
+        VECTOR_BENCH_DIMS
+    }
+
+    async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts
+            .iter()
+            .map(|text| {
+                let n = text
+                    .rsplit(' ')
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(0);
+                Self::vector(n)
+            })
+            .collect())
+    }
+}
+
+fn make_vector_memory(dir: &std::path::Path, ann_min_rows: usize) -> SqliteMemory {
+    SqliteMemory::with_embedder(dir, Arc::new(ClusteredEmbedding), 1.0, 0.0, 10_000, None)
+        .unwrap()
+        .with_ann_min_rows(ann_min_rows)
+}
+
+fn bench_vector_recall(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let brute_dir = tempfile::TempDir::new().unwrap();
+    let ivf_dir = tempfile::TempDir::new().unwrap();
+    let brute = make_vector_memory(brute_dir.path(), 0);
+    let ivf = make_vector_memory(ivf_dir.path(), 1_000);
+
+    rt.block_on(async {
+        for mem in [&brute, &ivf] {
+            for i in 0..VECTOR_BENCH_ROWS {
+                mem.store(
+                    &format!("vec_{i}"),
+                    &format!("entry {i}"),
+                    MemoryCategory::Core,
+                    None,
+                )
+                .await
+                .unwrap();
+            }
+            mem.reindex().await.unwrap();
+        }
+    });
+
+    // Query numbers never occur in stored content, so only vectors match.
+    let queries: Vec<String> = (0..50).map(|i| format!("lookup {}", 100_000 + i)).collect();
+    let (overlap, total) = rt.block_on(async {
+        let mut overlap = 0;
+        let mut total = 0;
+        for query in &queries {
+            let exact = brute.recall(query, 10, None).await.unwrap();
+            let approx = ivf.recall(query, 10, None).await.unwrap();
+            total += exact.len();
+            overlap += exact
+                .iter()
+                .filter(|e| approx.iter().any(|a| a.key == e.key))
+                .count();
+        }
+        (overlap, total)
+    });
+    eprintln!("IVF recall@10 vs brute force: {overlap}/{total}");
+
+    let counter = std::sync::atomic::AtomicUsize::new(0);
+    let next_query = || {
+        let idx = counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        queries[idx % queries.len()].as_str()
+    };
+
+    c.bench_function("memory_vector_recall_brute_force", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                brute
+                    .recall(black_box(next_query()), 10, None)
+                    .await
+                    .unwrap()
+            })
+        });
+    });
+
+    c.bench_function("memory_vector_recall_ivf", |b| {
+        b.iter(|| {
+            rt.block_on(async { ivf.recall(black_box(next_query()), 10, None).await.unwrap() })
+        });
+    });
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Benchmark: Full agent turn cycle
 // ─────────────────────────────────────────────────────────────────────────────
@@ -309,6 +448,7 @@ criterion_group!(
     bench_xml_parsing,
     bench_native_parsing,
     bench_memory_operations,
+    bench_vector_recall,
     bench_agent_turn,
 );
 criterion_main!(benches);
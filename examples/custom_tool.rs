@@ -15,11 +15,36 @@ pub struct ToolResult {
     pub error: Option<String>,
 }
 
+/// Mirrors src/tools/traits.rs — the default is treated as a mutating action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolEffect {
+    ReadOnly,
+    Mutating,
+}
+
+/// Mirrors src/tools/traits.rs
+#[derive(Debug, Clone, Copy)]
+pub struct ToolMetadata {
+    pub effect: ToolEffect,
+    pub network: bool,
+    pub default_timeout_secs: Option<u64>,
+    pub idempotent: bool,
+}
+
 #[async_trait]
 pub trait Tool: Send + Sync {
     fn name(&self) -> &str;
     fn description(&self) -> &str;
     fn parameters_schema(&self) -> Value;
+    /// Declared side effects drive approval prompts, timeouts and delegate selectors
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata {
+            effect: ToolEffect::Mutating,
+            network: false,
+            default_timeout_secs: None,
+            idempotent: false,
+        }
+    }
     async fn execute(&self, args: Value) -> Result<ToolResult>;
 }
 
@@ -46,6 +71,16 @@ impl Tool for HttpGetTool {
         })
     }
 
+    // A GET only observes, so supervised mode runs it without a prompt.
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata {
+            effect: ToolEffect::ReadOnly,
+            network: true,
+            default_timeout_secs: Some(30),
+            idempotent: true,
+        }
+    }
+
     async fn execute(&self, args: Value) -> Result<ToolResult> {
         let url = args["url"]
             .as_str()
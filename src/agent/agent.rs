@@ -1,6 +1,9 @@
 use crate::agent::dispatcher::{
     NativeToolDispatcher, ParsedToolCall, ToolDispatcher, ToolExecutionResult, XmlToolDispatcher,
 };
+use crate::agent::loop_::{
+    build_compaction_transcript, summarize_transcript, COMPACTION_KEEP_RECENT_MESSAGES,
+};
 use crate::agent::memory_loader::{DefaultMemoryLoader, MemoryLoader};
 use crate::agent::prompt::{PromptContext, SystemPromptBuilder};
 use crate::config::Config;
@@ -15,6 +18,9 @@ use std::io::Write as IoWrite;
 use std::sync::Arc;
 use std::time::Instant;
 
+/// Marks the system message that carries the rolling history summary.
+const HISTORY_SUMMARY_PREFIX: &str = "[Conversation summary]";
+
 pub struct Agent {
     provider: Box<dyn Provider>,
     tools: Vec<Box<dyn Tool>>,
@@ -33,6 +39,8 @@ pub struct Agent {
     skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
     auto_save: bool,
     history: Vec<ConversationMessage>,
+    history_summary: Option<String>,
+    summary_memory_key: String,
     classification_config: crate::config::QueryClassificationConfig,
     available_hints: Vec<String>,
 }
@@ -211,6 +219,8 @@ impl AgentBuilder {
             skills_prompt_mode: self.skills_prompt_mode.unwrap_or_default(),
             auto_save: self.auto_save.unwrap_or(false),
             history: Vec::new(),
+            history_summary: None,
+            summary_memory_key: format!("conversation_summary_{}", uuid::Uuid::new_v4()),
             classification_config: self.classification_config.unwrap_or_default(),
             available_hints: self.available_hints.unwrap_or_default(),
         })
@@ -228,6 +238,12 @@ impl Agent {
 
     pub fn clear_history(&mut self) {
         self.history.clear();
+        self.history_summary = None;
+    }
+
+    /// Running summary of turns rolled out of the history, if any.
+    pub fn history_summary(&self) -> Option<&str> {
+        self.history_summary.as_deref()
     }
 
     pub fn from_config(config: &Config) -> Result<Self> {
@@ -290,6 +306,7 @@ impl Agent {
             &config.model_routes,
             &model_name,
         )?;
+        let provider = providers::with_response_cache(provider, config, Some(observer.clone()));
 
         let dispatcher_choice = config.agent.tool_dispatcher.as_str();
         let tool_dispatcher: Box<dyn ToolDispatcher> = match dispatcher_choice {
@@ -356,6 +373,84 @@ impl Agent {
         self.history.extend(other_messages);
     }
 
+    /// Keep the history within `max_history_messages`, rolling the oldest
+    /// turns into a running summary when `summarize_history` is enabled.
+    ///
+    /// The summary lives in a system message right after the system prompt, so
+    /// it survives later trims. Only whole turns are summarized: the kept tail
+    /// always starts at a user message, which keeps native tool calls paired
+    /// with their results.
+    async fn compact_history(&mut self) {
+        let max = self.config.max_history_messages;
+        let conversation_len = self
+            .history
+            .iter()
+            .filter(|msg| !is_system_message(msg))
+            .count();
+        if conversation_len <= max {
+            return;
+        }
+        if !self.config.summarize_history {
+            self.trim_history();
+            return;
+        }
+
+        let (mut system_messages, conversation): (Vec<_>, Vec<_>) =
+            self.history.drain(..).partition(is_system_message);
+        system_messages.retain(|msg| {
+            !matches!(msg, ConversationMessage::Chat(chat) if chat.content.starts_with(HISTORY_SUMMARY_PREFIX))
+        });
+
+        let keep_recent = COMPACTION_KEEP_RECENT_MESSAGES.min(max / 2);
+        let mut split = conversation.len().saturating_sub(keep_recent);
+        while split < conversation.len() && !is_user_turn(&conversation[split]) {
+            split += 1;
+        }
+        if split == conversation.len() {
+            // A long tool chain is still running; keep the current turn whole.
+            split = conversation.iter().rposition(is_user_turn).unwrap_or(0);
+        }
+
+        if split > 0 {
+            let older = self
+                .tool_dispatcher
+                .to_provider_messages(&conversation[..split]);
+            let transcript = build_compaction_transcript(&older);
+            let summary = summarize_transcript(
+                self.provider.as_ref(),
+                &self.model_name,
+                self.history_summary.as_deref(),
+                &transcript,
+            )
+            .await;
+
+            if self.config.persist_history_summaries {
+                if let Err(e) = self
+                    .memory
+                    .store(
+                        &self.summary_memory_key,
+                        &summary,
+                        MemoryCategory::Conversation,
+                        None,
+                    )
+                    .await
+                {
+                    tracing::warn!("Failed to persist conversation summary: {e}");
+                }
+            }
+            self.history_summary = Some(summary);
+        }
+
+        self.history = system_messages;
+        if let Some(summary) = &self.history_summary {
+            self.history
+                .push(ConversationMessage::Chat(ChatMessage::system(format!(
+                    "{HISTORY_SUMMARY_PREFIX}\n{summary}"
+                ))));
+        }
+        self.history.extend(conversation.into_iter().skip(split));
+    }
+
     fn build_system_prompt(&self) -> Result<String> {
         let instructions = self.tool_dispatcher.prompt_instructions(&self.tools);
         let ctx = PromptContext {
@@ -466,6 +561,7 @@ impl Agent {
             .push(ConversationMessage::Chat(ChatMessage::user(enriched)));
 
         let effective_model = self.classify_model(user_message);
+        let response_format = crate::agent::loop_::current_response_format();
 
         for _ in 0..self.config.max_tool_iterations {
             let messages = self.tool_dispatcher.to_provider_messages(&self.history);
@@ -479,6 +575,7 @@ impl Agent {
                         } else {
                             None
                         },
+                        response_format: response_format.as_deref(),
                     },
                     &effective_model,
                     self.temperature,
@@ -501,7 +598,7 @@ impl Agent {
                     .push(ConversationMessage::Chat(ChatMessage::assistant(
                         final_text.clone(),
                     )));
-                self.trim_history();
+                self.compact_history().await;
 
                 return Ok(final_text);
             }
@@ -523,7 +620,7 @@ impl Agent {
             let results = self.execute_tools(&calls).await;
             let formatted = self.tool_dispatcher.format_results(&results);
             self.history.push(formatted);
-            self.trim_history();
+            self.compact_history().await;
         }
 
         anyhow::bail!(
@@ -563,6 +660,19 @@ impl Agent {
     }
 }
 
+fn is_system_message(msg: &ConversationMessage) -> bool {
+    matches!(msg, ConversationMessage::Chat(chat) if chat.role == "system")
+}
+
+/// A user message that starts a turn (not an XML-dispatcher tool result).
+fn is_user_turn(msg: &ConversationMessage) -> bool {
+    matches!(
+        msg,
+        ConversationMessage::Chat(chat)
+            if chat.role == "user" && !chat.content.starts_with("[Tool results]")
+    )
+}
+
 pub async fn run(
     config: Config,
     message: Option<String>,
@@ -650,6 +760,7 @@ mod tests {
                 return Ok(crate::providers::ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
+                    usage: None,
                 });
             }
             Ok(guard.remove(0))
@@ -677,6 +788,7 @@ mod tests {
                 success: true,
                 output: "tool-out".into(),
                 error: None,
+                parts: Vec::new(),
             })
         }
     }
@@ -687,6 +799,7 @@ mod tests {
             responses: Mutex::new(vec![crate::providers::ChatResponse {
                 text: Some("hello".into()),
                 tool_calls: vec![],
+                usage: None,
             }]),
         });
 
@@ -725,10 +838,12 @@ mod tests {
                         name: "echo".into(),
                         arguments: "{}".into(),
                     }],
+                    usage: None,
                 },
                 crate::providers::ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
+                    usage: None,
                 },
             ]),
         });
@@ -0,0 +1,471 @@
+use crate::multimodal;
+use crate::providers::ChatMessage;
+
+/// Context window assumed for models missing from [`MODEL_CONTEXT_WINDOWS`].
+pub const DEFAULT_CONTEXT_WINDOW_TOKENS: usize = 32_768;
+
+/// Known context windows. A pattern matches when it appears in the model id at
+/// the start of a name segment (after `/`, `.`, `-`, `:` or `_`), so
+/// `anthropic/claude-sonnet-4` and `us.anthropic.claude-3-5-haiku` both match
+/// `claude`. More specific patterns come first.
+const MODEL_CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("claude", 200_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-5", 400_000),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4", 8_192),
+    ("gpt-3.5", 16_385),
+    ("gpt-oss", 131_072),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4-mini", 200_000),
+    ("gemini", 1_048_576),
+    ("deepseek", 128_000),
+    ("grok", 131_072),
+    ("mistral-large", 128_000),
+    ("kimi", 131_072),
+    ("moonshot", 131_072),
+    ("glm-4", 128_000),
+    ("minimax", 1_000_000),
+    ("llama-4", 1_000_000),
+    ("llama-3.1", 128_000),
+    ("llama3.1", 128_000),
+    ("llama-3.3", 128_000),
+    ("llama3.3", 128_000),
+    ("llama3", 8_192),
+    ("qwen", 32_768),
+];
+
+/// Output tokens kept free in the window, as a fraction of the window size.
+const OUTPUT_RESERVE_DIVISOR: usize = 8;
+const MIN_OUTPUT_RESERVE_TOKENS: usize = 1_024;
+const MAX_OUTPUT_RESERVE_TOKENS: usize = 16_384;
+
+/// Per-message framing overhead (role markers, separators).
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Rough cost of one attached image; providers bill images by resolution, not
+/// by the size of the inlined data URI.
+const IMAGE_TOKEN_ESTIMATE: usize = 1_000;
+
+/// The most recent tool results are never shrunk; the model usually still
+/// needs them to finish the current task.
+const KEEP_RECENT_TOOL_RESULTS: usize = 2;
+
+/// Characters kept from the head of a shrunk tool result.
+const SHRUNK_TOOL_RESULT_CHARS: usize = 600;
+
+const TOOL_RESULTS_PREFIX: &str = "[Tool results]";
+
+/// Look up the context window (in tokens) for `model`.
+pub fn context_window_for_model(model: &str) -> usize {
+    let model = model.to_ascii_lowercase();
+    MODEL_CONTEXT_WINDOWS
+        .iter()
+        .find(|(pattern, _)| matches_segment_start(&model, pattern))
+        .map_or(DEFAULT_CONTEXT_WINDOW_TOKENS, |(_, tokens)| *tokens)
+}
+
+fn matches_segment_start(model: &str, pattern: &str) -> bool {
+    model.match_indices(pattern).any(|(index, _)| {
+        index == 0
+            || matches!(
+                model.as_bytes()[index - 1],
+                b'/' | b'.' | b'-' | b':' | b'_'
+            )
+    })
+}
+
+/// Prompt tokens available for messages once output space and tool
+/// definitions are accounted for.
+pub fn prompt_budget(model: &str, tool_definition_tokens: usize) -> usize {
+    let window = context_window_for_model(model);
+    let reserve = (window / OUTPUT_RESERVE_DIVISOR)
+        .clamp(MIN_OUTPUT_RESERVE_TOKENS, MAX_OUTPUT_RESERVE_TOKENS);
+    window
+        .saturating_sub(reserve)
+        .saturating_sub(tool_definition_tokens)
+}
+
+/// Estimate the token count of `text` without a tokenizer.
+///
+/// ASCII text averages about four characters per token across the BPE
+/// vocabularies in use; other scripts (CJK in particular) are closer to one
+/// token per character, so they are counted individually to stay on the safe
+/// side.
+pub fn estimate_tokens(text: &str) -> usize {
+    let (ascii, other) = text.chars().fold((0usize, 0usize), |(ascii, other), ch| {
+        if ch.is_ascii() {
+            (ascii + 1, other)
+        } else {
+            (ascii, other + 1)
+        }
+    });
+    ascii.div_ceil(4) + other
+}
+
+/// Estimate the prompt tokens of a single message, including image markers.
+pub fn estimate_message_tokens(message: &ChatMessage) -> usize {
+    let content_tokens = if message.content.contains("[IMAGE:") {
+        let (text, images) = multimodal::parse_image_markers(&message.content);
+        estimate_tokens(&text) + images.len() * IMAGE_TOKEN_ESTIMATE
+    } else {
+        estimate_tokens(&message.content)
+    };
+    content_tokens + MESSAGE_OVERHEAD_TOKENS
+}
+
+/// Estimate the prompt tokens of a whole conversation.
+pub fn estimate_messages_tokens(messages: &[ChatMessage]) -> usize {
+    messages.iter().map(estimate_message_tokens).sum()
+}
+
+/// What [`fit_messages_to_budget`] changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContextTrim {
+    pub tokens_before: usize,
+    pub tokens_after: usize,
+    pub shrunk_tool_results: usize,
+    pub dropped_messages: usize,
+}
+
+impl ContextTrim {
+    pub fn is_noop(&self) -> bool {
+        self.shrunk_tool_results == 0 && self.dropped_messages == 0
+    }
+}
+
+/// Shrink `messages` until their estimated size fits `budget` tokens.
+///
+/// Older tool results are cut down first (oldest first, keeping the most
+/// recent ones intact). If that is not enough, the oldest turns are dropped,
+/// then the oldest tool rounds of the current turn. An assistant message is
+/// always removed together with the tool results that answer it, and the
+/// conversation is kept starting at a user message. System messages, the user
+/// message that opened the current turn and its latest round are never
+/// removed, so the result may still exceed the budget; the provider's own
+/// overflow error is the backstop.
+pub fn fit_messages_to_budget(messages: &mut Vec<ChatMessage>, budget: usize) -> ContextTrim {
+    let tokens_before = estimate_messages_tokens(messages);
+    let mut trim = ContextTrim {
+        tokens_before,
+        tokens_after: tokens_before,
+        ..ContextTrim::default()
+    };
+    if tokens_before <= budget {
+        return trim;
+    }
+
+    let mut total = tokens_before;
+
+    let tool_result_indices: Vec<usize> = messages
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| is_tool_result(message))
+        .map(|(index, _)| index)
+        .collect();
+    let shrinkable = tool_result_indices
+        .len()
+        .saturating_sub(KEEP_RECENT_TOOL_RESULTS);
+    for &index in &tool_result_indices[..shrinkable] {
+        if total <= budget {
+            break;
+        }
+        let Some(shrunk) = shrink_tool_result(&messages[index]) else {
+            continue;
+        };
+        let before = estimate_message_tokens(&messages[index]);
+        messages[index].content = shrunk;
+        let after = estimate_message_tokens(&messages[index]);
+        total = total - before + after;
+        trim.shrunk_tool_results += 1;
+    }
+
+    // Earlier turns go first, whole, so the conversation still starts at a
+    // user message.
+    let mut turn_start = current_turn_start(messages);
+    let mut first_dropped = None;
+    while let Some(start) = messages.iter().position(|m| m.role != "system") {
+        let starts_mid_turn = first_dropped.is_some() && !is_user_prompt(&messages[start]);
+        if start >= turn_start || !(total > budget || starts_mid_turn) {
+            break;
+        }
+        let end = round_end(messages, start).min(turn_start);
+        if end >= messages.len() {
+            break;
+        }
+        total -= estimate_messages_tokens(&messages[start..end]);
+        messages.drain(start..end);
+        trim.dropped_messages += end - start;
+        turn_start -= end - start;
+        first_dropped.get_or_insert(start);
+    }
+
+    // Then the current turn's older tool rounds. Its user message and the
+    // latest round stay so the model keeps the task and its newest results.
+    let rounds_start = turn_start + 1;
+    while total > budget && rounds_start < messages.len() {
+        let end = round_end(messages, rounds_start);
+        if end >= messages.len() {
+            break;
+        }
+        total -= estimate_messages_tokens(&messages[rounds_start..end]);
+        messages.drain(rounds_start..end);
+        trim.dropped_messages += end - rounds_start;
+        first_dropped.get_or_insert(rounds_start);
+    }
+
+    if let Some(index) = first_dropped {
+        let notice = ChatMessage::system(format!(
+            "[Context trimmed] {} earlier message(s) were omitted to fit the model context window.",
+            trim.dropped_messages
+        ));
+        total += estimate_message_tokens(&notice);
+        messages.insert(index, notice);
+    }
+
+    trim.tokens_after = total;
+    trim
+}
+
+/// Index of the user message that opened the current turn, or `len` if none.
+fn current_turn_start(messages: &[ChatMessage]) -> usize {
+    messages
+        .iter()
+        .rposition(is_user_prompt)
+        .unwrap_or(messages.len())
+}
+
+/// End (exclusive) of the message at `start` plus the tool results answering it.
+fn round_end(messages: &[ChatMessage], start: usize) -> usize {
+    let mut end = start + 1;
+    while end < messages.len() && is_tool_result(&messages[end]) {
+        end += 1;
+    }
+    end
+}
+
+fn is_user_prompt(message: &ChatMessage) -> bool {
+    message.role == "user" && !is_tool_result(message)
+}
+
+fn is_tool_result(message: &ChatMessage) -> bool {
+    message.role == "tool"
+        || (message.role == "user" && message.content.starts_with(TOOL_RESULTS_PREFIX))
+}
+
+/// Cut a tool result down to its head plus an elision note. Native tool
+/// messages keep their JSON envelope so the `tool_call_id` survives.
+fn shrink_tool_result(message: &ChatMessage) -> Option<String> {
+    if message.role == "tool" {
+        if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&message.content) {
+            if let Some(output) = value.get("content").and_then(serde_json::Value::as_str) {
+                let shrunk = shrink_text(output)?;
+                value["content"] = serde_json::Value::String(shrunk);
+                return Some(value.to_string());
+            }
+        }
+    }
+    shrink_text(&message.content)
+}
+
+fn shrink_text(text: &str) -> Option<String> {
+    let total_chars = text.chars().count();
+    if total_chars <= SHRUNK_TOOL_RESULT_CHARS {
+        return None;
+    }
+    let head: String = text.chars().take(SHRUNK_TOOL_RESULT_CHARS).collect();
+    Some(format!(
+        "{head}\n[... {} characters elided to fit the context window]",
+        total_chars - SHRUNK_TOOL_RESULT_CHARS
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn native_tool_result(id: &str, output: &str) -> ChatMessage {
+        ChatMessage::tool(serde_json::json!({"tool_call_id": id, "content": output}).to_string())
+    }
+
+    #[test]
+    fn context_window_matches_model_ids_with_provider_prefixes() {
+        assert_eq!(
+            context_window_for_model("claude-sonnet-4-20250514"),
+            200_000
+        );
+        assert_eq!(
+            context_window_for_model("anthropic/claude-3.5-haiku"),
+            200_000
+        );
+        assert_eq!(
+            context_window_for_model("us.anthropic.claude-3-5-sonnet-20241022-v2:0"),
+            200_000
+        );
+        assert_eq!(context_window_for_model("gpt-4o-mini"), 128_000);
+        assert_eq!(context_window_for_model("gpt-4"), 8_192);
+        assert_eq!(context_window_for_model("openai/o3-mini"), 200_000);
+        assert_eq!(context_window_for_model("llama3.1:8b"), 128_000);
+        assert_eq!(
+            context_window_for_model("some-local-model"),
+            DEFAULT_CONTEXT_WINDOW_TOKENS
+        );
+    }
+
+    #[test]
+    fn prompt_budget_reserves_output_and_tool_definitions() {
+        assert_eq!(prompt_budget("gpt-4", 0), 8_192 - 1_024);
+        assert_eq!(
+            prompt_budget("claude-sonnet-4", 1_000),
+            200_000 - 16_384 - 1_000
+        );
+    }
+
+    #[test]
+    fn estimate_tokens_counts_non_ascii_per_character() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_tokens("你好"), 2);
+    }
+
+    #[test]
+    fn image_markers_are_estimated_by_count_not_payload() {
+        let payload = "A".repeat(100_000);
+        let message = ChatMessage::user(format!("look [IMAGE:data:image/png;base64,{payload}]"));
+        assert!(estimate_message_tokens(&message) < 2 * IMAGE_TOKEN_ESTIMATE);
+    }
+
+    #[test]
+    fn fit_is_noop_within_budget() {
+        let mut messages = vec![ChatMessage::system("sys"), ChatMessage::user("hi")];
+        let trim = fit_messages_to_budget(&mut messages, 1_000);
+        assert!(trim.is_noop());
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn fit_shrinks_oldest_tool_results_first() {
+        let big = "x".repeat(8_000);
+        let mut messages = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("task"),
+            ChatMessage::assistant("calling"),
+            native_tool_result("call_1", &big),
+            ChatMessage::assistant("calling"),
+            ChatMessage::user(format!("{TOOL_RESULTS_PREFIX}\n{big}")),
+            ChatMessage::assistant("calling"),
+            native_tool_result("call_2", &big),
+            ChatMessage::assistant("calling"),
+            native_tool_result("call_3", &big),
+        ];
+
+        let trim = fit_messages_to_budget(&mut messages, 6_000);
+
+        assert_eq!(trim.shrunk_tool_results, 2);
+        assert_eq!(trim.dropped_messages, 0);
+        assert!(trim.tokens_after <= 6_000);
+        let first: serde_json::Value = serde_json::from_str(&messages[3].content).unwrap();
+        assert_eq!(first["tool_call_id"], "call_1");
+        assert!(first["content"]
+            .as_str()
+            .unwrap()
+            .ends_with("characters elided to fit the context window]"));
+        assert!(messages[5].content.starts_with(TOOL_RESULTS_PREFIX));
+        assert!(messages[5].content.len() < 1_000);
+        assert_eq!(
+            messages[9].content.len(),
+            native_tool_result("call_3", &big).content.len()
+        );
+    }
+
+    #[test]
+    fn fit_drops_whole_oldest_turns_and_leaves_a_notice() {
+        let long = "y".repeat(4_000);
+        let mut messages = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user(long.clone()),
+            ChatMessage::assistant(long.clone()),
+            native_tool_result("call_1", "ok"),
+            ChatMessage::user(long.clone()),
+            ChatMessage::assistant(long.clone()),
+            ChatMessage::user("latest question"),
+        ];
+
+        let trim = fit_messages_to_budget(&mut messages, 2_200);
+
+        assert_eq!(trim.dropped_messages, 3);
+        assert_eq!(messages[0].content, "sys");
+        assert_eq!(messages[1].role, "system");
+        assert!(messages[1]
+            .content
+            .starts_with("[Context trimmed] 3 earlier"));
+        assert_eq!(messages[2].role, "user");
+        assert_eq!(messages.last().unwrap().content, "latest question");
+        assert!(trim.tokens_after <= 2_200);
+    }
+
+    #[test]
+    fn fit_keeps_current_task_and_drops_whole_tool_rounds() {
+        let long = "w".repeat(4_000);
+        let mut messages = vec![ChatMessage::system("sys"), ChatMessage::user("the task")];
+        for i in 0..6 {
+            messages.push(ChatMessage::assistant(format!("round {i}: {long}")));
+            messages.push(native_tool_result(&format!("call_{i}"), "ok"));
+        }
+
+        let trim = fit_messages_to_budget(&mut messages, 2_500);
+
+        assert!(trim.dropped_messages > 0);
+        assert_eq!(trim.dropped_messages % 2, 0);
+        assert_eq!(messages[0].content, "sys");
+        assert_eq!(messages[1].content, "the task");
+        assert_eq!(messages[2].role, "system");
+        assert!(messages[2].content.starts_with("[Context trimmed]"));
+        assert!(messages[3].content.starts_with("round "));
+        for pair in messages[3..].chunks(2) {
+            assert_eq!(pair[0].role, "assistant");
+            assert_eq!(pair[1].role, "tool");
+        }
+        assert!(messages[messages.len() - 2].content.starts_with("round 5"));
+        assert!(trim.tokens_after <= 2_500);
+    }
+
+    #[test]
+    fn fit_keeps_latest_round_even_when_over_budget() {
+        let long = "v".repeat(8_000);
+        let mut messages = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("the task"),
+            ChatMessage::assistant(long.clone()),
+            native_tool_result("call_1", "ok"),
+            ChatMessage::assistant(long),
+            native_tool_result("call_2", "ok"),
+            native_tool_result("call_3", "ok"),
+        ];
+
+        let trim = fit_messages_to_budget(&mut messages, 100);
+
+        assert_eq!(trim.dropped_messages, 2);
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(
+            roles,
+            vec!["system", "user", "system", "assistant", "tool", "tool"]
+        );
+        assert_eq!(messages[1].content, "the task");
+        assert!(trim.tokens_after > 100);
+    }
+
+    #[test]
+    fn fit_never_drops_the_final_message() {
+        let mut messages = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("z".repeat(40_000)),
+        ];
+        let trim = fit_messages_to_budget(&mut messages, 100);
+        assert_eq!(trim.dropped_messages, 0);
+        assert_eq!(messages.len(), 2);
+        assert!(trim.tokens_after > 100);
+    }
+}
@@ -1,30 +1,44 @@
+use crate::agent::context_budget;
 use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
 use crate::config::Config;
+use crate::cost::{BudgetCheck, CostTracker};
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
 use crate::observability::{self, Observer, ObserverEvent};
+use crate::providers::traits::{StreamOptions, ToolCallAssembler};
 use crate::providers::{
-    self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
+    self, ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilityError,
+    ResponseFormat, ToolCall,
 };
 use crate::runtime;
 use crate::security::SecurityPolicy;
-use crate::tools::{self, Tool};
+use crate::tools::{self, plan, Tool, ToolContent};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
+use futures_util::StreamExt;
 use regex::{Regex, RegexSet};
 use serde_json::Value;
 use std::collections::{HashSet, VecDeque};
 use std::fmt::Write;
 use std::hash::{Hash, Hasher};
 use std::io::Write as _;
+use std::path::PathBuf;
 use std::sync::{Arc, LazyLock};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 /// Minimum characters per chunk when relaying LLM text to a streaming draft.
 const STREAM_CHUNK_MIN_CHARS: usize = 80;
 
+/// Sent on the draft channel to discard the accumulated draft text, so a
+/// streamed turn that follows tool progress starts from an empty draft.
+pub(crate) const DRAFT_CLEAR_SENTINEL: &str = "\u{0}draft:clear\u{0}";
+
+/// Prefix for draft messages carrying the current task-plan checklist, which
+/// channels render above the streamed reply instead of appending it.
+pub(crate) const DRAFT_PLAN_SENTINEL: &str = "\u{0}draft:plan\u{0}";
+
 /// Default maximum agentic tool-use iterations per user message to prevent runaway loops.
 /// Used as a safe fallback when `max_tool_iterations` is unset or configured as zero.
 const DEFAULT_MAX_TOOL_ITERATIONS: usize = 10;
@@ -99,7 +113,7 @@ fn scrub_credentials(input: &str) -> String {
 const DEFAULT_MAX_HISTORY_MESSAGES: usize = 50;
 
 /// Keep this many most-recent non-system messages after compaction.
-const COMPACTION_KEEP_RECENT_MESSAGES: usize = 20;
+pub(crate) const COMPACTION_KEEP_RECENT_MESSAGES: usize = 20;
 
 /// Safety cap for compaction source transcript passed to the summarizer.
 const COMPACTION_MAX_SOURCE_CHARS: usize = 12_000;
@@ -107,6 +121,144 @@ const COMPACTION_MAX_SOURCE_CHARS: usize = 12_000;
 /// Max characters retained in stored compaction summary.
 const COMPACTION_MAX_SUMMARY_CHARS: usize = 2_000;
 
+tokio::task_local! {
+    /// Cost tracker for LLM calls made by the tool loop on this task.
+    ///
+    /// Installed by the CLI entrypoints and the channel dispatcher so nested
+    /// callers (including delegate sub-agents) share one budget without every
+    /// loop variant growing another parameter.
+    pub(crate) static TOOL_LOOP_COST_TRACKER: Option<Arc<CostTracker>>;
+
+    /// JSON schema the tool loop's LLM calls must answer in.
+    ///
+    /// Installed by callers that need machine-readable output, such as cron
+    /// agent jobs with a `response_format`.
+    pub(crate) static TOOL_LOOP_RESPONSE_FORMAT: Option<Arc<ResponseFormat>>;
+
+    /// Plan directory of the workspace the tool loop's registry was built for.
+    ///
+    /// Installed next to the cost tracker so the prompt shows the plan the
+    /// registry's `plan` tool writes, not one from another workspace.
+    pub(crate) static TOOL_LOOP_PLAN_DIR: Option<PathBuf>;
+}
+
+/// Create the cost tracker for `config`, or `None` when `[cost]` is disabled
+/// or the ledger cannot be opened.
+pub(crate) fn create_cost_tracker(config: &Config) -> Option<Arc<CostTracker>> {
+    if !config.cost.enabled {
+        return None;
+    }
+    match CostTracker::new(config.cost.clone(), &config.workspace_dir) {
+        Ok(tracker) => Some(Arc::new(tracker)),
+        Err(error) => {
+            tracing::warn!("Cost tracking disabled: {error:#}");
+            None
+        }
+    }
+}
+
+fn current_cost_tracker() -> Option<Arc<CostTracker>> {
+    TOOL_LOOP_COST_TRACKER.try_with(Clone::clone).ok().flatten()
+}
+
+fn current_plan_dir() -> Option<PathBuf> {
+    TOOL_LOOP_PLAN_DIR.try_with(Clone::clone).ok().flatten()
+}
+
+pub(crate) fn current_response_format() -> Option<Arc<ResponseFormat>> {
+    TOOL_LOOP_RESPONSE_FORMAT
+        .try_with(Clone::clone)
+        .ok()
+        .flatten()
+}
+
+/// Refuse the next LLM call when it would push spending past a `[cost]` limit.
+///
+/// The estimate only prices the outgoing prompt (see
+/// [`context_budget::estimate_messages_tokens`]); output cost is unknown until
+/// the provider answers.
+fn enforce_cost_budget(
+    tracker: &CostTracker,
+    provider_name: &str,
+    model: &str,
+    messages: &[ChatMessage],
+) -> Result<()> {
+    let estimated_input_tokens =
+        u64::try_from(context_budget::estimate_messages_tokens(messages)).unwrap_or(u64::MAX);
+    let estimate = tracker
+        .usage_for(provider_name, model, estimated_input_tokens, 0)
+        .cost_usd;
+
+    match tracker.check_budget(estimate)? {
+        BudgetCheck::Allowed => Ok(()),
+        BudgetCheck::Warning {
+            current_usd,
+            limit_usd,
+            period,
+        } => {
+            tracing::warn!(
+                "Cost budget warning: ${current_usd:.4} spent of ${limit_usd:.2} {period:?} limit"
+            );
+            Ok(())
+        }
+        BudgetCheck::Exceeded {
+            current_usd,
+            limit_usd,
+            period,
+        } => anyhow::bail!(
+            "Cost budget exceeded: ${current_usd:.4} spent of ${limit_usd:.2} {period:?} limit; refusing LLM call"
+        ),
+    }
+}
+
+/// Record provider-reported usage against the active cost tracker.
+fn record_cost_usage(
+    tracker: &CostTracker,
+    provider_name: &str,
+    model: &str,
+    usage: Option<&providers::TokenUsage>,
+) {
+    let Some(usage) = usage else {
+        return;
+    };
+    let record = tracker.cached_usage_for(
+        provider_name,
+        model,
+        usage.input_tokens.unwrap_or(0),
+        usage.output_tokens.unwrap_or(0),
+        usage.cached_input_tokens.unwrap_or(0),
+        usage.cache_write_input_tokens.unwrap_or(0),
+    );
+    if let Err(error) = tracker.record_usage(record) {
+        tracing::warn!("Failed to record LLM usage: {error:#}");
+    }
+}
+
+/// Emit prompt-cache hit/miss counts when the provider reported any.
+fn record_prompt_cache(
+    observer: &dyn Observer,
+    provider_name: &str,
+    model: &str,
+    usage: Option<&providers::TokenUsage>,
+) {
+    let Some(usage) = usage.filter(|usage| usage.has_cache_activity()) else {
+        return;
+    };
+    let cache_read_tokens = usage.cached_input_tokens.unwrap_or(0);
+    let cache_write_tokens = usage.cache_write_input_tokens.unwrap_or(0);
+    observer.record_event(&ObserverEvent::PromptCache {
+        provider: provider_name.to_string(),
+        model: model.to_string(),
+        cache_read_tokens,
+        cache_write_tokens,
+        uncached_input_tokens: usage
+            .input_tokens
+            .unwrap_or(0)
+            .saturating_sub(cache_read_tokens)
+            .saturating_sub(cache_write_tokens),
+    });
+}
+
 /// Convert a tool registry to OpenAI function-calling format for native tool support.
 fn tools_to_openai_format(tools_registry: &[Box<dyn Tool>]) -> Vec<serde_json::Value> {
     tools_registry
@@ -148,7 +300,7 @@ fn trim_history(history: &mut Vec<ChatMessage>, max_history: usize) {
     history.drain(start..start + to_remove);
 }
 
-fn build_compaction_transcript(messages: &[ChatMessage]) -> String {
+pub(crate) fn build_compaction_transcript(messages: &[ChatMessage]) -> String {
     let mut transcript = String::new();
     for msg in messages {
         let role = msg.role.to_uppercase();
@@ -199,26 +351,45 @@ async fn auto_compact_history(
     let compact_end = start + compact_count;
     let to_compact: Vec<ChatMessage> = history[start..compact_end].to_vec();
     let transcript = build_compaction_transcript(&to_compact);
+    let summary = summarize_transcript(provider, model, None, &transcript).await;
+    apply_compaction_summary(history, start, compact_end, &summary);
 
+    Ok(true)
+}
+
+/// Summarize a compaction transcript with the model.
+///
+/// When `previous` holds an earlier summary it is rolled forward together
+/// with the new transcript, so long sessions keep a single running summary.
+pub(crate) async fn summarize_transcript(
+    provider: &dyn Provider,
+    model: &str,
+    previous: Option<&str>,
+    transcript: &str,
+) -> String {
     let summarizer_system = "You are a conversation compaction engine. Summarize older chat history into concise context for future turns. Preserve: user preferences, commitments, decisions, unresolved tasks, key facts. Omit: filler, repeated chit-chat, verbose tool logs. Output plain text bullet points only.";
 
-    let summarizer_user = format!(
-        "Summarize the following conversation history for context preservation. Keep it short (max 12 bullet points).\n\n{}",
-        transcript
-    );
+    let summarizer_user = match previous {
+        Some(previous) => format!(
+            "Update the running summary below with the newer conversation history that follows it. Keep it short (max 12 bullet points).\n\nRunning summary:\n{previous}\n\nNewer history:\n{transcript}"
+        ),
+        None => format!(
+            "Summarize the following conversation history for context preservation. Keep it short (max 12 bullet points).\n\n{transcript}"
+        ),
+    };
 
     let summary_raw = provider
         .chat_with_system(Some(summarizer_system), &summarizer_user, model, 0.2)
         .await
         .unwrap_or_else(|_| {
             // Fallback to deterministic local truncation when summarization fails.
-            truncate_with_ellipsis(&transcript, COMPACTION_MAX_SUMMARY_CHARS)
+            match previous {
+                Some(previous) => format!("{previous}\n{transcript}"),
+                None => transcript.to_string(),
+            }
         });
 
-    let summary = truncate_with_ellipsis(&summary_raw, COMPACTION_MAX_SUMMARY_CHARS);
-    apply_compaction_summary(history, start, compact_end, &summary);
-
-    Ok(true)
+    truncate_with_ellipsis(&summary_raw, COMPACTION_MAX_SUMMARY_CHARS)
 }
 
 /// Build context preamble by searching memory for relevant entries.
@@ -301,6 +472,14 @@ fn find_tool<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> Option<&'a dyn Tool>
     tools.iter().find(|t| t.name() == name).map(|t| t.as_ref())
 }
 
+/// Approval check that honours the tool's declared metadata when it is registered.
+fn call_needs_approval(mgr: &ApprovalManager, tools: &[Box<dyn Tool>], name: &str) -> bool {
+    match find_tool(tools, name) {
+        Some(tool) => mgr.needs_approval_for(name, &tool.metadata()),
+        None => mgr.needs_approval(name),
+    }
+}
+
 fn parse_arguments_value(raw: Option<&serde_json::Value>) -> serde_json::Value {
     match raw {
         Some(serde_json::Value::String(s)) => serde_json::from_str::<serde_json::Value>(s)
@@ -997,12 +1176,19 @@ pub struct AgentTrace {
     pub trajectory_states: Vec<TrajectoryState>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub early_stop_reason: Option<String>,
+    /// Task plan maintained through the `plan` tool, as of the end of the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<plan::TaskPlan>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ToolLoopOutput {
     pub response: String,
     pub trace: AgentTrace,
+    /// Image and file parts produced by non-read-only tools during the run,
+    /// for channels to deliver alongside the reply.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<ToolContent>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -1017,6 +1203,7 @@ struct ToolExecutionRecord {
     duration_ms: u128,
     success: bool,
     error: Option<String>,
+    parts: Vec<ToolContent>,
 }
 
 #[derive(Debug, Clone)]
@@ -1196,6 +1383,7 @@ fn build_tool_call_round_records(
                     duration_ms: 0,
                     success: true,
                     error: None,
+                    parts: Vec::new(),
                 },
                 is_duplicate: true,
             });
@@ -1209,6 +1397,7 @@ fn build_tool_call_round_records(
             duration_ms: 0,
             success: false,
             error: Some("missing_execution_result".to_string()),
+            parts: Vec::new(),
         });
         records.push(ToolCallRoundRecord {
             call: call.clone(),
@@ -1303,6 +1492,7 @@ async fn execute_one_tool(
             duration_ms: 0,
             success: false,
             error: Some("unknown_tool".to_string()),
+            parts: Vec::new(),
         });
     };
 
@@ -1311,7 +1501,17 @@ async fn execute_one_tool(
     });
     let start = Instant::now();
 
-    let tool_future = tool.execute(call_arguments);
+    let timeout_secs = tool.metadata().default_timeout_secs;
+    let tool_future = async {
+        match timeout_secs {
+            Some(secs) => {
+                tokio::time::timeout(Duration::from_secs(secs), tool.execute(call_arguments))
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out after {secs}s")))
+            }
+            None => tool.execute(call_arguments).await,
+        }
+    };
     let tool_result = if let Some(token) = cancellation_token {
         tokio::select! {
             () = token.cancelled() => return Err(ToolLoopCancelled.into()),
@@ -1347,6 +1547,7 @@ async fn execute_one_tool(
                 } else {
                     Some("tool_execution_failed".to_string())
                 },
+                parts: r.parts,
             })
         }
         Err(e) => {
@@ -1361,13 +1562,49 @@ async fn execute_one_tool(
                 duration_ms: duration.as_millis(),
                 success: false,
                 error: Some(e.to_string()),
+                parts: Vec::new(),
             })
         }
     }
 }
 
+/// `[IMAGE:...]` markers for a round's image parts, so vision providers receive
+/// them as native image blocks via [`multimodal::prepare_messages_for_provider`].
+/// Bounded by what the conversation's multimodal limits still allow.
+fn tool_image_markers(
+    round_records: &[ToolCallRoundRecord],
+    history: &[ChatMessage],
+    multimodal_config: &crate::config::MultimodalConfig,
+) -> String {
+    let (max_images, max_image_size_mb) = multimodal_config.effective_limits();
+    let max_encoded_bytes = max_image_size_mb.saturating_mul(1024 * 1024) / 3 * 4;
+    let mut budget = max_images.saturating_sub(multimodal::count_image_markers(history));
+    let mut markers = String::new();
+    for record in round_records {
+        for part in &record.result.parts {
+            let ToolContent::Image { mime_type, data } = part else {
+                continue;
+            };
+            if budget == 0 {
+                return markers;
+            }
+            if !multimodal::is_supported_image_mime(mime_type) || data.len() > max_encoded_bytes {
+                continue;
+            }
+            let _ = writeln!(
+                markers,
+                "{}: [IMAGE:data:{mime_type};base64,{data}]",
+                record.call.name
+            );
+            budget -= 1;
+        }
+    }
+    markers
+}
+
 fn should_execute_tools_in_parallel(
     tool_calls: &[ParsedToolCall],
+    tools_registry: &[Box<dyn Tool>],
     approval: Option<&ApprovalManager>,
 ) -> bool {
     if tool_calls.len() <= 1 {
@@ -1375,7 +1612,10 @@ fn should_execute_tools_in_parallel(
     }
 
     if let Some(mgr) = approval {
-        if tool_calls.iter().any(|call| mgr.needs_approval(&call.name)) {
+        if tool_calls
+            .iter()
+            .any(|call| call_needs_approval(mgr, tools_registry, &call.name))
+        {
             // Approval-gated calls must keep sequential handling so the caller can
             // enforce CLI prompt/deny policy consistently.
             return false;
@@ -1420,26 +1660,28 @@ async fn execute_tools_sequential(
 
     for call in tool_calls {
         if let Some(mgr) = approval {
-            if mgr.needs_approval(&call.name) {
+            if call_needs_approval(mgr, tools_registry, &call.name) {
                 let request = ApprovalRequest {
                     tool_name: call.name.clone(),
                     arguments: call.arguments.clone(),
                 };
 
                 let decision = if channel_name == "cli" {
-                    mgr.prompt_cli(&request)
+                    let decision = mgr.prompt_cli(&request);
+                    mgr.record_decision(&call.name, &call.arguments, decision, channel_name);
+                    decision
                 } else {
-                    ApprovalResponse::No
+                    // Parked until the originating chat or the gateway answers.
+                    mgr.request_approval(&request, channel_name).await
                 };
 
-                mgr.record_decision(&call.name, &call.arguments, decision, channel_name);
-
                 if decision == ApprovalResponse::No {
                     individual_results.push(ToolExecutionRecord {
                         output: "Denied by user.".to_string(),
                         duration_ms: 0,
                         success: false,
                         error: Some("denied_by_user".to_string()),
+                        parts: Vec::new(),
                     });
                     continue;
                 }
@@ -1460,6 +1702,66 @@ async fn execute_tools_sequential(
     Ok(individual_results)
 }
 
+/// Stream one model turn into a draft channel.
+///
+/// Text deltas are relayed as they arrive, and a progress line is added as
+/// soon as each native tool call's name is known. Tool-call fragments are
+/// assembled into the returned [`ChatResponse`]. If the stream fails before
+/// producing anything, the turn is retried through [`Provider::chat`]. The
+/// flag reports whether anything reached the draft.
+async fn stream_chat_to_draft(
+    provider: &dyn Provider,
+    request: ChatRequest<'_>,
+    model: &str,
+    temperature: f64,
+    tx: &tokio::sync::mpsc::Sender<String>,
+    clear_first: bool,
+) -> Result<(ChatResponse, bool)> {
+    let mut stream = provider.stream_chat(request, model, temperature, StreamOptions::new(true));
+    let mut text = String::new();
+    let mut assembler = ToolCallAssembler::default();
+    let mut usage = None;
+    let mut relayed = false;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) if !relayed && text.is_empty() && assembler.is_empty() => {
+                tracing::debug!("Streaming turn failed, retrying without streaming: {e}");
+                let response = provider.chat(request, model, temperature).await?;
+                return Ok((response, false));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        text.push_str(&chunk.delta);
+        let mut update = chunk.delta;
+        for delta in &chunk.tool_calls {
+            if let Some(name) = assembler.push(delta) {
+                let _ = write!(update, "\n\u{23f3} `{name}`\n");
+            }
+        }
+        if chunk.usage.is_some() {
+            usage = chunk.usage;
+        }
+
+        if !update.is_empty() {
+            if clear_first && !relayed {
+                let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
+            }
+            relayed = true;
+            let _ = tx.send(update).await;
+        }
+    }
+
+    let response = ChatResponse {
+        text: (!text.is_empty()).then_some(text),
+        tool_calls: assembler.finish(),
+        usage,
+    };
+    Ok((response, relayed))
+}
+
 // ── Agent Tool-Call Loop ──────────────────────────────────────────────────
 // Core agentic iteration: send conversation to the LLM, parse any tool
 // calls from the response, execute them, append results to history, and
@@ -1637,6 +1939,8 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
     cancellation_token: Option<CancellationToken>,
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
 ) -> Result<ToolLoopOutput> {
+    let plan_dir = current_plan_dir();
+    let current_plan = || plan_dir.as_deref().and_then(plan::current_plan);
     let iteration_limit = if max_tool_iterations == 0 {
         DEFAULT_MAX_TOOL_ITERATIONS
     } else {
@@ -1650,6 +1954,7 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
     let max_iterations = iteration_limit.min(round_limit);
     let loop_started_at = Instant::now();
     let mut trace_steps: Vec<AgentStepTrace> = Vec::new();
+    let mut attachments: Vec<ToolContent> = Vec::new();
     let mut trajectory_states: Vec<TrajectoryState> = Vec::new();
     let mut recent_tool_call_signatures: VecDeque<HashSet<String>> = VecDeque::new();
     let mut previous_trajectory_signature: Option<u64> = None;
@@ -1664,6 +1969,23 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
     let tool_specs: Vec<crate::tools::ToolSpec> =
         tools_registry.iter().map(|tool| tool.spec()).collect();
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
+    let cost_tracker = current_cost_tracker();
+    let response_format = current_response_format();
+    // Native tool definitions travel outside the messages, so they are taken
+    // off the prompt budget up front.
+    let tool_definition_tokens = if use_native_tools {
+        context_budget::estimate_tokens(&serde_json::to_string(&tool_specs).unwrap_or_default())
+    } else {
+        0
+    };
+    let prompt_budget = context_budget::prompt_budget(model, tool_definition_tokens);
+    // Stream turns straight into the draft when the provider can assemble
+    // native tool calls from a stream; otherwise the final text is relayed
+    // in chunks after the fact.
+    let stream_tx = on_delta
+        .as_ref()
+        .filter(|_| provider.supports_streaming_tool_calls());
+    let mut draft_dirty = false;
 
     for iteration in 0..max_iterations {
         if cancellation_token
@@ -1685,22 +2007,48 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
             .into());
         }
 
-        let prepared_history: Vec<ChatMessage> = if trajectory_enabled && iteration >= min_rounds {
-            if let Some(prev_state) = trajectory_states.last() {
-                let mut with_state = history.clone();
-                with_state.push(ChatMessage::system(trajectory_state_prompt_block(
-                    prev_state,
-                )));
-                with_state
+        let mut prepared_history: Vec<ChatMessage> =
+            if trajectory_enabled && iteration >= min_rounds {
+                if let Some(prev_state) = trajectory_states.last() {
+                    let mut with_state = history.clone();
+                    with_state.push(ChatMessage::system(trajectory_state_prompt_block(
+                        prev_state,
+                    )));
+                    with_state
+                } else {
+                    history.clone()
+                }
             } else {
                 history.clone()
-            }
-        } else {
-            history.clone()
-        };
-        let prepared_messages =
+            };
+        // The plan lives outside the transcript so every round sees its latest state.
+        if let Some(task_plan) = current_plan() {
+            prepared_history.push(ChatMessage::system(task_plan.prompt_block()));
+        }
+        let mut prepared_messages =
             multimodal::prepare_messages_for_provider(&prepared_history, multimodal_config).await?;
 
+        // Trim proactively instead of paying for a request the provider will
+        // reject as too long. Only the outgoing copy is trimmed; `history`
+        // keeps the full transcript.
+        let context_trim =
+            context_budget::fit_messages_to_budget(&mut prepared_messages.messages, prompt_budget);
+        if !context_trim.is_noop() {
+            tracing::info!(
+                model,
+                prompt_budget,
+                tokens_before = context_trim.tokens_before,
+                tokens_after = context_trim.tokens_after,
+                shrunk_tool_results = context_trim.shrunk_tool_results,
+                dropped_messages = context_trim.dropped_messages,
+                "Trimmed request to fit the model context window"
+            );
+        }
+
+        if let Some(tracker) = cost_tracker.as_deref() {
+            enforce_cost_budget(tracker, provider_name, model, &prepared_messages.messages)?;
+        }
+
         observer.record_event(&ObserverEvent::LlmRequest {
             provider: provider_name.to_string(),
             model: model.to_string(),
@@ -1717,14 +2065,30 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
             None
         };
 
-        let chat_future = provider.chat(
-            ChatRequest {
-                messages: &prepared_messages.messages,
-                tools: request_tools,
-            },
-            model,
-            temperature,
-        );
+        let chat_request = ChatRequest {
+            messages: &prepared_messages.messages,
+            tools: request_tools,
+            response_format: response_format.as_deref(),
+        };
+        let chat_future = async {
+            match stream_tx {
+                Some(tx) => {
+                    stream_chat_to_draft(
+                        provider,
+                        chat_request,
+                        model,
+                        temperature,
+                        tx,
+                        draft_dirty,
+                    )
+                    .await
+                }
+                None => provider
+                    .chat(chat_request, model, temperature)
+                    .await
+                    .map(|resp| (resp, false)),
+            }
+        };
 
         let chat_result = if let Some(token) = cancellation_token.as_ref() {
             tokio::select! {
@@ -1735,9 +2099,12 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
             chat_future.await
         };
 
+        let turn_streamed;
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
             match chat_result {
-                Ok(resp) => {
+                Ok((resp, streamed)) => {
+                    turn_streamed = streamed;
+                    draft_dirty |= streamed;
                     let llm_duration = llm_started_at.elapsed();
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
@@ -1746,6 +2113,10 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
                         success: true,
                         error_message: None,
                     });
+                    record_prompt_cache(observer, provider_name, model, resp.usage.as_ref());
+                    if let Some(tracker) = cost_tracker.as_deref() {
+                        record_cost_usage(tracker, provider_name, model, resp.usage.as_ref());
+                    }
 
                     let response_text = resp.text_or_empty().to_string();
                     // First try native structured tool calls (OpenAI-format).
@@ -1819,9 +2190,10 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
 
         if tool_calls.is_empty() {
             // No tool calls — this is the final response.
-            // If a streaming sender is provided, relay the text in small chunks
-            // so the channel can progressively update the draft message.
-            if let Some(ref tx) = on_delta {
+            // If a streaming sender is provided and the turn was not already
+            // streamed, relay the text in small chunks so the channel can
+            // progressively update the draft message.
+            if let (Some(tx), false) = (on_delta.as_ref(), turn_streamed) {
                 // Split on whitespace boundaries, accumulating chunks of at least
                 // STREAM_CHUNK_MIN_CHARS characters for progressive draft updates.
                 let mut chunk = String::new();
@@ -1852,7 +2224,9 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
                     iterations: iteration + 1,
                     trajectory_states,
                     early_stop_reason: None,
+                    plan: current_plan(),
                 },
+                attachments,
             });
         }
 
@@ -1879,7 +2253,8 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
             seen_executable_signatures.insert(signature);
             executable_calls.push(call.clone());
         }
-        let should_parallel = should_execute_tools_in_parallel(&executable_calls, approval);
+        let should_parallel =
+            should_execute_tools_in_parallel(&executable_calls, tools_registry, approval);
         let execution_results = if should_parallel {
             execute_tools_parallel(
                 &executable_calls,
@@ -1916,6 +2291,19 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
                 "<tool_result name=\"{}\">\n{}\n</tool_result>",
                 record.call.name, record.result.output
             );
+            // Read-only tools only inspect existing content; don't echo it back.
+            if find_tool(tools_registry, &record.call.name)
+                .is_some_and(|tool| !tool.metadata().is_read_only())
+            {
+                attachments.extend(
+                    record
+                        .result
+                        .parts
+                        .iter()
+                        .filter(|part| part.is_attachment())
+                        .cloned(),
+                );
+            }
             trace_steps.push(AgentStepTrace::ToolCall {
                 tool: record.call.name.clone(),
                 arguments: record.call.arguments.clone(),
@@ -1927,6 +2315,16 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
             });
         }
 
+        if let Some(tx) = on_delta.as_ref() {
+            let plan_changed = round_records
+                .iter()
+                .any(|record| record.call.name == plan::PLAN_TOOL_NAME && record.result.success);
+            if plan_changed {
+                let checklist = current_plan().map(|p| p.checklist()).unwrap_or_default();
+                let _ = tx.send(format!("{DRAFT_PLAN_SENTINEL}{checklist}")).await;
+            }
+        }
+
         let mut early_stop_reason: Option<String> = None;
         if trajectory_enabled && iteration >= min_rounds {
             let current_state = build_trajectory_state(
@@ -1957,8 +2355,18 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
         // Native mode: use JSON-structured messages so convert_messages() can
         // reconstruct proper OpenAI-format tool_calls and tool result messages.
         // Prompt mode: use XML-based text format as before.
+        // Images ride along as markers only when the provider can see them;
+        // the text output already describes them otherwise.
+        let image_markers = if provider.supports_vision() {
+            tool_image_markers(&round_records, history, multimodal_config)
+        } else {
+            String::new()
+        };
         history.push(ChatMessage::assistant(assistant_history_content));
         if native_tool_calls.is_empty() {
+            if !image_markers.is_empty() {
+                let _ = write!(tool_results, "[Tool images]\n{image_markers}");
+            }
             history.push(ChatMessage::user(format!("[Tool results]\n{tool_results}")));
         } else {
             for (native_call, record) in native_tool_calls.iter().zip(round_records.iter()) {
@@ -1968,6 +2376,9 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
                 });
                 history.push(ChatMessage::tool(tool_msg.to_string()));
             }
+            if !image_markers.is_empty() {
+                history.push(ChatMessage::user(format!("[Tool images]\n{image_markers}")));
+            }
         }
 
         if let Some(reason) = early_stop_reason {
@@ -1984,7 +2395,9 @@ pub(crate) async fn run_tool_call_loop_with_trace_and_policy(
                     iterations: iteration + 1,
                     trajectory_states,
                     early_stop_reason: Some(reason),
+                    plan: current_plan(),
                 },
+                attachments,
             });
         }
     }
@@ -2122,6 +2535,7 @@ pub async fn run(
         model_name,
         &provider_runtime_options,
     )?;
+    let provider = providers::with_response_cache(provider, &config, Some(observer.clone()));
 
     observer.record_event(&ObserverEvent::AgentStart {
         provider: provider_name.to_string(),
@@ -2155,6 +2569,10 @@ pub async fn run(
             "shell",
             "Execute terminal commands. Use when: running local checks, build/test commands, diagnostics. Don't use when: a safer dedicated tool exists, or command is destructive without approval.",
         ),
+        (
+            "process",
+            "Run long-lived commands in the background and manage them (start/list/read/send/kill). Use when: starting dev servers, watchers, or builds you need to check on later. Don't use when: a command finishes quickly (use shell).",
+        ),
         (
             "file_read",
             "Read file contents. Use when: inspecting project files, configs, logs. Don't use when: a targeted search is enough.",
@@ -2163,6 +2581,10 @@ pub async fn run(
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "file_edit",
+            "Edit an existing file in place via exact-string replacement or a unified diff, with optional dry-run preview. Use when: changing a few lines of a larger file. Don't use when: creating a new file or rewriting it wholesale (use file_write).",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -2202,6 +2624,10 @@ pub async fn run(
         "image_info",
         "Read image file metadata (format, dimensions, size) and optionally base64-encode it. Use when: inspecting images, preparing visual data for analysis.",
     ));
+    tool_descs.push((
+        "send_file",
+        "Attach a workspace file to the chat reply. Use when: the user should receive a generated chart, screenshot, log, or document.",
+    ));
     if config.browser.enabled {
         tool_descs.push((
             "browser_open",
@@ -2281,6 +2707,8 @@ pub async fn run(
 
     // ── Execute ──────────────────────────────────────────────────
     let start = Instant::now();
+    let cost_tracker = create_cost_tracker(&config);
+    let plan_dir = Some(plan::plan_dir(&config.workspace_dir));
 
     let mut final_output = String::new();
 
@@ -2313,29 +2741,36 @@ pub async fn run(
             ChatMessage::user(&enriched),
         ];
 
-        let response = run_tool_call_loop_with_policy(
-            provider.as_ref(),
-            &mut history,
-            &tools_registry,
-            observer.as_ref(),
-            provider_name,
-            model_name,
-            temperature,
-            false,
-            Some(&approval_manager),
-            "cli",
-            &config.multimodal,
-            config.agent.max_tool_iterations,
-            config.agent.trajectory_compression_enabled,
-            config.agent.trajectory_state_max_items,
-            config.agent.trajectory_max_rounds,
-            config.agent.trajectory_stop_on_redundant_rounds,
-            config.agent.trajectory_tool_call_dedup_window,
-            config.agent.trajectory_min_rounds,
-            None,
-            None,
-        )
-        .await?;
+        let response = TOOL_LOOP_COST_TRACKER
+            .scope(
+                cost_tracker.clone(),
+                TOOL_LOOP_PLAN_DIR.scope(
+                    plan_dir.clone(),
+                    run_tool_call_loop_with_policy(
+                        provider.as_ref(),
+                        &mut history,
+                        &tools_registry,
+                        observer.as_ref(),
+                        provider_name,
+                        model_name,
+                        temperature,
+                        false,
+                        Some(&approval_manager),
+                        "cli",
+                        &config.multimodal,
+                        config.agent.max_tool_iterations,
+                        config.agent.trajectory_compression_enabled,
+                        config.agent.trajectory_state_max_items,
+                        config.agent.trajectory_max_rounds,
+                        config.agent.trajectory_stop_on_redundant_rounds,
+                        config.agent.trajectory_tool_call_dedup_window,
+                        config.agent.trajectory_min_rounds,
+                        None,
+                        None,
+                    ),
+                ),
+            )
+            .await?;
         final_output = response.clone();
         println!("{response}");
         observer.record_event(&ObserverEvent::TurnComplete);
@@ -2438,29 +2873,36 @@ pub async fn run(
 
             history.push(ChatMessage::user(&enriched));
 
-            let response = match run_tool_call_loop_with_policy(
-                provider.as_ref(),
-                &mut history,
-                &tools_registry,
-                observer.as_ref(),
-                provider_name,
-                model_name,
-                temperature,
-                false,
-                Some(&approval_manager),
-                "cli",
-                &config.multimodal,
-                config.agent.max_tool_iterations,
-                config.agent.trajectory_compression_enabled,
-                config.agent.trajectory_state_max_items,
-                config.agent.trajectory_max_rounds,
-                config.agent.trajectory_stop_on_redundant_rounds,
-                config.agent.trajectory_tool_call_dedup_window,
-                config.agent.trajectory_min_rounds,
-                None,
-                None,
-            )
-            .await
+            let response = match TOOL_LOOP_COST_TRACKER
+                .scope(
+                    cost_tracker.clone(),
+                    TOOL_LOOP_PLAN_DIR.scope(
+                        plan_dir.clone(),
+                        run_tool_call_loop_with_policy(
+                            provider.as_ref(),
+                            &mut history,
+                            &tools_registry,
+                            observer.as_ref(),
+                            provider_name,
+                            model_name,
+                            temperature,
+                            false,
+                            Some(&approval_manager),
+                            "cli",
+                            &config.multimodal,
+                            config.agent.max_tool_iterations,
+                            config.agent.trajectory_compression_enabled,
+                            config.agent.trajectory_state_max_items,
+                            config.agent.trajectory_max_rounds,
+                            config.agent.trajectory_stop_on_redundant_rounds,
+                            config.agent.trajectory_tool_call_dedup_window,
+                            config.agent.trajectory_min_rounds,
+                            None,
+                            None,
+                        ),
+                    ),
+                )
+                .await
             {
                 Ok(resp) => resp,
                 Err(e) => {
@@ -2499,12 +2941,15 @@ pub async fn run(
     }
 
     let duration = start.elapsed();
+    let cost_summary = cost_tracker
+        .as_ref()
+        .and_then(|tracker| tracker.get_summary().ok());
     observer.record_event(&ObserverEvent::AgentEnd {
         provider: provider_name.to_string(),
         model: model_name.to_string(),
         duration,
-        tokens_used: None,
-        cost_usd: None,
+        tokens_used: cost_summary.as_ref().map(|summary| summary.total_tokens),
+        cost_usd: cost_summary.map(|summary| summary.session_cost_usd),
     });
 
     Ok(final_output)
@@ -2577,6 +3022,7 @@ pub async fn process_message_with_trace(
         &model_name,
         &provider_runtime_options,
     )?;
+    let provider = providers::with_response_cache(provider, &config, Some(observer.clone()));
 
     let hardware_rag: Option<crate::rag::HardwareRag> = config
         .peripherals
@@ -2596,13 +3042,16 @@ pub async fn process_message_with_trace(
     let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
     let mut tool_descs: Vec<(&str, &str)> = vec![
         ("shell", "Execute terminal commands."),
+        ("process", "Manage background commands."),
         ("file_read", "Read file contents."),
         ("file_write", "Write file contents."),
+        ("file_edit", "Edit part of a file by replacement or patch."),
         ("memory_store", "Save to memory."),
         ("memory_recall", "Search memory."),
         ("memory_forget", "Delete a memory entry."),
         ("screenshot", "Capture a screenshot."),
         ("image_info", "Read image metadata."),
+        ("send_file", "Attach a workspace file to the reply."),
     ];
     if config.browser.enabled {
         tool_descs.push(("browser_open", "Open approved URLs in browser."));
@@ -2675,29 +3124,36 @@ pub async fn process_message_with_trace(
         ChatMessage::user(&enriched),
     ];
 
-    let output = run_tool_call_loop_with_trace_and_policy(
-        provider.as_ref(),
-        &mut history,
-        &tools_registry,
-        observer.as_ref(),
-        provider_name,
-        &model_name,
-        config.default_temperature,
-        true,
-        None,
-        "channel",
-        &config.multimodal,
-        config.agent.max_tool_iterations,
-        config.agent.trajectory_compression_enabled,
-        config.agent.trajectory_state_max_items,
-        config.agent.trajectory_max_rounds,
-        config.agent.trajectory_stop_on_redundant_rounds,
-        config.agent.trajectory_tool_call_dedup_window,
-        config.agent.trajectory_min_rounds,
-        None,
-        None,
-    )
-    .await?;
+    let output = TOOL_LOOP_COST_TRACKER
+        .scope(
+            create_cost_tracker(&config),
+            TOOL_LOOP_PLAN_DIR.scope(
+                Some(plan::plan_dir(&config.workspace_dir)),
+                run_tool_call_loop_with_trace_and_policy(
+                    provider.as_ref(),
+                    &mut history,
+                    &tools_registry,
+                    observer.as_ref(),
+                    provider_name,
+                    &model_name,
+                    config.default_temperature,
+                    true,
+                    None,
+                    "channel",
+                    &config.multimodal,
+                    config.agent.max_tool_iterations,
+                    config.agent.trajectory_compression_enabled,
+                    config.agent.trajectory_state_max_items,
+                    config.agent.trajectory_max_rounds,
+                    config.agent.trajectory_stop_on_redundant_rounds,
+                    config.agent.trajectory_tool_call_dedup_window,
+                    config.agent.trajectory_min_rounds,
+                    None,
+                    None,
+                ),
+            ),
+        )
+        .await?;
     Ok(ProcessMessageOutput {
         response: output.response,
         trace: output.trace,
@@ -2803,10 +3259,83 @@ mod tests {
             Ok(ChatResponse {
                 text: Some("vision-ok".to_string()),
                 tool_calls: Vec::new(),
+                usage: None,
+            })
+        }
+    }
+
+    /// Vision provider that calls `snap` once, then reports whether the
+    /// follow-up request carried the image.
+    struct SnapshotVisionProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Provider for SnapshotVisionProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: false,
+                vision: true,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("chat_with_system should not be used in this test");
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            let text = if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                "<tool_call>\n{\"name\":\"snap\",\"arguments\":{}}\n</tool_call>".to_string()
+            } else {
+                format!(
+                    "images:{}",
+                    crate::multimodal::count_image_markers(request.messages)
+                )
+            };
+            Ok(ChatResponse {
+                text: Some(text),
+                tool_calls: Vec::new(),
+                usage: None,
             })
         }
     }
 
+    struct SnapTool;
+
+    #[async_trait]
+    impl Tool for SnapTool {
+        fn name(&self) -> &str {
+            "snap"
+        }
+
+        fn description(&self) -> &str {
+            "Returns a tiny image part"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+        ) -> anyhow::Result<crate::tools::ToolResult> {
+            Ok(crate::tools::ToolResult::ok("Image attached (image/png)")
+                .with_part(ToolContent::image("image/png", b"\x89PNG")))
+        }
+    }
+
     struct ScriptedProvider {
         responses: Arc<Mutex<VecDeque<ChatResponse>>>,
     }
@@ -2818,6 +3347,7 @@ mod tests {
                 .map(|text| ChatResponse {
                     text: Some(text.to_string()),
                     tool_calls: Vec::new(),
+                    usage: None,
                 })
                 .collect();
             Self {
@@ -2854,6 +3384,53 @@ mod tests {
         }
     }
 
+    struct StreamingProvider {
+        turns: Mutex<VecDeque<Vec<crate::providers::traits::StreamChunk>>>,
+    }
+
+    #[async_trait]
+    impl Provider for StreamingProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                vision: false,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("streaming provider tests should not fall back to chat");
+        }
+
+        fn supports_streaming_tool_calls(&self) -> bool {
+            true
+        }
+
+        fn stream_chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+            _options: StreamOptions,
+        ) -> futures_util::stream::BoxStream<
+            'static,
+            crate::providers::traits::StreamResult<crate::providers::traits::StreamChunk>,
+        > {
+            let chunks = self
+                .turns
+                .lock()
+                .expect("turns lock should be valid")
+                .pop_front()
+                .unwrap_or_default();
+            futures_util::stream::iter(chunks.into_iter().map(Ok)).boxed()
+        }
+    }
+
     struct DelayTool {
         name: String,
         delay_ms: u64,
@@ -2907,10 +3484,43 @@ mod tests {
                 success: true,
                 output: format!("counted:{value}"),
                 error: None,
+                parts: Vec::new(),
             })
         }
     }
 
+    /// Read-only tool that never finishes on its own.
+    struct HangingTool {
+        name: String,
+    }
+
+    #[async_trait]
+    impl Tool for HangingTool {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn description(&self) -> &str {
+            "Hanging tool for metadata tests"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        fn metadata(&self) -> crate::tools::ToolMetadata {
+            crate::tools::ToolMetadata::read_only().with_timeout_secs(1)
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+        ) -> anyhow::Result<crate::tools::ToolResult> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            unreachable!("dispatcher should time out first")
+        }
+    }
+
     impl DelayTool {
         fn new(
             name: &str,
@@ -2968,6 +3578,7 @@ mod tests {
                 success: true,
                 output: format!("ok:{value}"),
                 error: None,
+                parts: Vec::new(),
             })
         }
     }
@@ -3030,6 +3641,7 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 1,
             allow_remote_fetch: false,
+            ..crate::config::MultimodalConfig::default()
         };
 
         let err = run_tool_call_loop(
@@ -3106,7 +3718,7 @@ mod tests {
             arguments: serde_json::json!({"path": "a.txt"}),
         }];
 
-        assert!(!should_execute_tools_in_parallel(&calls, None));
+        assert!(!should_execute_tools_in_parallel(&calls, &[], None));
     }
 
     #[test]
@@ -3126,10 +3738,103 @@ mod tests {
 
         assert!(!should_execute_tools_in_parallel(
             &calls,
+            &[],
+            Some(&approval_mgr)
+        ));
+    }
+
+    #[test]
+    fn should_execute_tools_in_parallel_allows_read_only_tools_in_supervised_mode() {
+        let calls = vec![
+            ParsedToolCall {
+                name: "probe_a".to_string(),
+                arguments: serde_json::json!({}),
+            },
+            ParsedToolCall {
+                name: "probe_b".to_string(),
+                arguments: serde_json::json!({}),
+            },
+        ];
+        let tools_registry: Vec<Box<dyn Tool>> = vec![
+            Box::new(HangingTool {
+                name: "probe_a".to_string(),
+            }),
+            Box::new(HangingTool {
+                name: "probe_b".to_string(),
+            }),
+        ];
+        let approval_mgr = ApprovalManager::from_config(&crate::config::AutonomyConfig::default());
+
+        assert!(should_execute_tools_in_parallel(
+            &calls,
+            &tools_registry,
+            Some(&approval_mgr)
+        ));
+        assert!(!should_execute_tools_in_parallel(
+            &calls,
+            &[],
             Some(&approval_mgr)
         ));
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_forwards_tool_images_to_vision_provider_and_attachments() {
+        let provider = SnapshotVisionProvider {
+            calls: AtomicUsize::new(0),
+        };
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(SnapTool)];
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("take a screenshot"),
+        ];
+
+        let output = run_tool_call_loop_with_trace(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            false,
+            6,
+            8,
+            None,
+            None,
+        )
+        .await
+        .expect("loop should complete");
+
+        assert_eq!(output.response, "images:1");
+        assert_eq!(output.attachments.len(), 1);
+        assert!(matches!(output.attachments[0], ToolContent::Image { .. }));
+    }
+
+    #[tokio::test]
+    async fn execute_one_tool_enforces_declared_timeout() {
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(HangingTool {
+            name: "hang".to_string(),
+        })];
+
+        let record = execute_one_tool(
+            "hang",
+            serde_json::json!({}),
+            &tools_registry,
+            &NoopObserver,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(!record.success);
+        assert!(record.output.contains("timed out after 1s"));
+    }
+
     #[test]
     fn should_execute_tools_in_parallel_returns_true_when_cli_has_no_interactive_approvals() {
         let calls = vec![
@@ -3150,10 +3855,74 @@ mod tests {
 
         assert!(should_execute_tools_in_parallel(
             &calls,
+            &[],
             Some(&approval_mgr)
         ));
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_refuses_llm_call_when_cost_budget_exceeded() {
+        let provider = ScriptedProvider::from_text_responses(vec!["should not be sent"]);
+        let remaining = Arc::clone(&provider.responses);
+
+        let tmp = TempDir::new().unwrap();
+        let cost_config = crate::config::CostConfig {
+            enabled: true,
+            daily_limit_usd: 0.5,
+            ..crate::config::CostConfig::default()
+        };
+        let tracker = Arc::new(CostTracker::new(cost_config, tmp.path()).unwrap());
+        tracker
+            .record_usage(crate::cost::TokenUsage::new(
+                "mock-model",
+                1_000_000,
+                0,
+                1.0,
+                0.0,
+            ))
+            .unwrap();
+
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("hello"),
+        ];
+        let observer = NoopObserver;
+        let tools_registry: Vec<Box<dyn Tool>> = Vec::new();
+
+        let err = TOOL_LOOP_COST_TRACKER
+            .scope(
+                Some(tracker),
+                run_tool_call_loop(
+                    &provider,
+                    &mut history,
+                    &tools_registry,
+                    &observer,
+                    "mock-provider",
+                    "mock-model",
+                    0.0,
+                    true,
+                    None,
+                    "cli",
+                    &crate::config::MultimodalConfig::default(),
+                    4,
+                    true,
+                    6,
+                    8,
+                    None,
+                    None,
+                ),
+            )
+            .await
+            .expect_err("exceeded budget should refuse the call");
+
+        assert!(err.to_string().contains("Cost budget exceeded"));
+        assert_eq!(
+            remaining.lock().unwrap().len(),
+            1,
+            "provider must not be called"
+        );
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_executes_multiple_tools_in_parallel_with_ordered_results() {
         let provider = ScriptedProvider::from_text_responses(vec![
@@ -3247,6 +4016,87 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_streams_tool_progress_into_draft() {
+        use crate::providers::traits::{StreamChunk, ToolCallDelta};
+
+        let provider = StreamingProvider {
+            turns: Mutex::new(VecDeque::from([
+                vec![
+                    StreamChunk::delta("Let me check."),
+                    StreamChunk::tool_call(ToolCallDelta {
+                        index: 0,
+                        id: Some("call_1".into()),
+                        name: Some("echo".into()),
+                        arguments: String::new(),
+                    }),
+                    StreamChunk::tool_call(ToolCallDelta {
+                        index: 0,
+                        arguments: r#"{"value":"A"}"#.into(),
+                        ..ToolCallDelta::default()
+                    }),
+                    StreamChunk::final_chunk(),
+                ],
+                vec![
+                    StreamChunk::delta("All "),
+                    StreamChunk::delta("done"),
+                    StreamChunk::final_chunk(),
+                ],
+            ])),
+        };
+        let calls = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> =
+            vec![Box::new(CountingTool::new("echo", Arc::clone(&calls)))];
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("check something"),
+        ];
+        let (tx, mut rx) = tokio::sync::mpsc::channel(64);
+
+        let output = run_tool_call_loop_with_trace_and_policy(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "channel",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            false,
+            6,
+            8,
+            0,
+            3,
+            0,
+            None,
+            Some(tx),
+        )
+        .await
+        .expect("tool loop should succeed");
+
+        assert_eq!(output.response, "All done");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let mut deltas = Vec::new();
+        while let Ok(delta) = rx.try_recv() {
+            deltas.push(delta);
+        }
+        assert_eq!(
+            deltas,
+            vec![
+                "Let me check.".to_string(),
+                "\n\u{23f3} `echo`\n".to_string(),
+                DRAFT_CLEAR_SENTINEL.to_string(),
+                "All ".to_string(),
+                "done".to_string(),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_deduplicates_tool_calls_in_same_round() {
         let provider = ScriptedProvider::from_text_responses(vec![
@@ -10,7 +10,7 @@
 //!   5. Unknown tool name recovery
 //!   6. Tool execution failure recovery
 //!   7. Parallel tool dispatch
-//!   8. History trimming during long conversations
+//!   8. History trimming and rolling summaries during long conversations
 //!   9. Memory auto-save round-trip
 //!  10. Native vs XML dispatcher integration
 //!  11. Empty / whitespace-only LLM responses
@@ -93,6 +93,7 @@ impl Provider for ScriptedProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -156,6 +157,7 @@ impl Tool for EchoTool {
             success: true,
             output: msg,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -182,6 +184,7 @@ impl Tool for FailingTool {
             success: false,
             output: String::new(),
             error: Some("intentional failure".into()),
+            parts: Vec::new(),
         })
     }
 }
@@ -246,6 +249,7 @@ impl Tool for CountingTool {
             success: true,
             output: format!("call #{}", *c),
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -328,6 +332,7 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
     ChatResponse {
         text: Some(String::new()),
         tool_calls: calls,
+        usage: None,
     }
 }
 
@@ -336,6 +341,7 @@ fn text_response(text: &str) -> ChatResponse {
     ChatResponse {
         text: Some(text.into()),
         tool_calls: vec![],
+        usage: None,
     }
 }
 
@@ -346,6 +352,7 @@ fn xml_tool_response(name: &str, args: &str) -> ChatResponse {
             "<tool_call>\n{{\"name\": \"{name}\", \"arguments\": {args}}}\n</tool_call>"
         )),
         tool_calls: vec![],
+        usage: None,
     }
 }
 
@@ -619,6 +626,87 @@ async fn history_trims_after_max_messages() {
     assert!(matches!(first, ConversationMessage::Chat(c) if c.role == "system"));
 }
 
+#[tokio::test]
+async fn history_rolls_old_turns_into_running_summary() {
+    let max_history = 6;
+    let provider = Box::new(ScriptedProvider::new(vec![]));
+    let config = AgentConfig {
+        max_history_messages: max_history,
+        ..AgentConfig::default()
+    };
+    let mut agent = build_agent_with_config(provider, vec![], config);
+
+    for i in 0..10 {
+        let _ = agent.turn(&format!("msg {i}")).await.unwrap();
+    }
+
+    // ScriptedProvider answers the summarizer's chat_with_system with "fallback".
+    assert_eq!(agent.history_summary(), Some("fallback"));
+    assert!(matches!(
+        &agent.history()[1],
+        ConversationMessage::Chat(c)
+            if c.role == "system" && c.content == "[Conversation summary]\nfallback"
+    ));
+    assert!(matches!(
+        &agent.history()[2],
+        ConversationMessage::Chat(c) if c.role == "user"
+    ));
+    assert!(agent.history().len() <= max_history + 2);
+    assert!(matches!(
+        agent.history().last(),
+        Some(ConversationMessage::Chat(c)) if c.role == "assistant"
+    ));
+}
+
+#[tokio::test]
+async fn history_summary_is_persisted_to_memory_when_enabled() {
+    let (mem, _tmp) = make_sqlite_memory();
+    let mut agent = Agent::builder()
+        .provider(Box::new(ScriptedProvider::new(vec![])))
+        .tools(vec![])
+        .memory(mem.clone())
+        .observer(make_observer())
+        .tool_dispatcher(Box::new(NativeToolDispatcher))
+        .workspace_dir(std::env::temp_dir())
+        .config(AgentConfig {
+            max_history_messages: 4,
+            persist_history_summaries: true,
+            ..AgentConfig::default()
+        })
+        .build()
+        .unwrap();
+
+    for i in 0..6 {
+        let _ = agent.turn(&format!("msg {i}")).await.unwrap();
+    }
+
+    let entries = mem
+        .list(Some(&memory::MemoryCategory::Conversation), None)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1, "summary should be upserted under one key");
+    assert!(entries[0].key.starts_with("conversation_summary_"));
+    assert_eq!(entries[0].content, "fallback");
+}
+
+#[tokio::test]
+async fn history_summary_disabled_drops_old_turns() {
+    let provider = Box::new(ScriptedProvider::new(vec![]));
+    let config = AgentConfig {
+        max_history_messages: 4,
+        summarize_history: false,
+        ..AgentConfig::default()
+    };
+    let mut agent = build_agent_with_config(provider, vec![], config);
+
+    for i in 0..6 {
+        let _ = agent.turn(&format!("msg {i}")).await.unwrap();
+    }
+
+    assert!(agent.history_summary().is_none());
+    assert!(agent.history().len() <= 5);
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // 9. Memory auto-save round-trip
 // ═══════════════════════════════════════════════════════════════════════════
@@ -734,6 +822,7 @@ async fn turn_handles_empty_text_response() {
     let provider = Box::new(ScriptedProvider::new(vec![ChatResponse {
         text: Some(String::new()),
         tool_calls: vec![],
+        usage: None,
     }]));
 
     let mut agent = build_agent_with(provider, vec![], Box::new(NativeToolDispatcher));
@@ -747,6 +836,7 @@ async fn turn_handles_none_text_response() {
     let provider = Box::new(ScriptedProvider::new(vec![ChatResponse {
         text: None,
         tool_calls: vec![],
+        usage: None,
     }]));
 
     let mut agent = build_agent_with(provider, vec![], Box::new(NativeToolDispatcher));
@@ -770,6 +860,7 @@ async fn turn_preserves_text_alongside_tool_calls() {
                 name: "echo".into(),
                 arguments: r#"{"message": "hi"}"#.into(),
             }],
+            usage: None,
         },
         text_response("Here are the results"),
     ]));
@@ -1006,6 +1097,7 @@ async fn native_dispatcher_handles_stringified_arguments() {
             name: "echo".into(),
             arguments: r#"{"message": "hello"}"#.into(),
         }],
+        usage: None,
     };
 
     let (_, calls) = dispatcher.parse_response(&response);
@@ -1031,6 +1123,7 @@ fn xml_dispatcher_handles_nested_json() {
                 .into(),
         ),
         tool_calls: vec![],
+        usage: None,
     };
 
     let dispatcher = XmlToolDispatcher;
@@ -1048,6 +1141,7 @@ fn xml_dispatcher_handles_empty_tool_call_tag() {
     let response = ChatResponse {
         text: Some("<tool_call>\n</tool_call>\nSome text".into()),
         tool_calls: vec![],
+        usage: None,
     };
 
     let dispatcher = XmlToolDispatcher;
@@ -1061,6 +1155,7 @@ fn xml_dispatcher_handles_unclosed_tool_call() {
     let response = ChatResponse {
         text: Some("Before\n<tool_call>\n{\"name\": \"shell\"}".into()),
         tool_calls: vec![],
+        usage: None,
     };
 
     let dispatcher = XmlToolDispatcher;
@@ -2,14 +2,24 @@
 //!
 //! Provides a pre-execution hook that prompts the user before tool calls,
 //! with session-scoped "Always" allowlists and audit logging.
+//!
+//! Outside the CLI, approvals are asynchronous: the request is parked in a
+//! process-wide pending table, announced to the originating channel through an
+//! [`ApprovalNotifier`], and resolved by a channel reply, a button press, or
+//! the gateway `/approvals` API — or denied when the timeout elapses.
 
 use crate::config::AutonomyConfig;
-use crate::security::AutonomyLevel;
+use crate::security::{AuditLogger, AutonomyLevel};
+use crate::tools::ToolMetadata;
+use async_trait::async_trait;
 use chrono::Utc;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, BufRead, Write};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::oneshot;
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -40,6 +50,134 @@ pub struct ApprovalLogEntry {
     pub arguments_summary: String,
     pub decision: ApprovalResponse,
     pub channel: String,
+    /// Who resolved the request: `cli`, `<channel>:<sender>`, `gateway`, or `timeout`.
+    #[serde(default)]
+    pub resolved_by: String,
+}
+
+/// An approval request waiting for a decision from a channel or the gateway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingApproval {
+    /// Short identifier users type in replies (`/approve <id>`).
+    pub id: String,
+    pub tool_name: String,
+    pub arguments_summary: String,
+    pub channel: String,
+    /// Conversation key (`<channel>_<sender>`) allowed to answer from chat.
+    /// The gateway may resolve any request.
+    pub requester: Option<String>,
+    pub requested_at: String,
+    pub expires_at: String,
+}
+
+/// Delivers a pending approval to wherever the request originated.
+#[async_trait]
+pub trait ApprovalNotifier: Send + Sync {
+    /// Conversation key of the sender who may answer from chat.
+    fn requester(&self) -> Option<String> {
+        None
+    }
+
+    async fn notify(&self, pending: &PendingApproval) -> anyhow::Result<()>;
+}
+
+tokio::task_local! {
+    /// Notifier for approvals raised by the tool loop running in this task.
+    pub static APPROVAL_NOTIFIER: Option<Arc<dyn ApprovalNotifier>>;
+}
+
+fn current_approval_notifier() -> Option<Arc<dyn ApprovalNotifier>> {
+    APPROVAL_NOTIFIER.try_with(Clone::clone).ok().flatten()
+}
+
+/// Conversation key of the chat sender the current tool loop runs for.
+fn current_requester() -> Option<String> {
+    current_approval_notifier().and_then(|notifier| notifier.requester())
+}
+
+struct PendingSlot {
+    approval: PendingApproval,
+    responder: oneshot::Sender<(ApprovalResponse, String)>,
+}
+
+// Process-wide so the gateway can resolve approvals raised by channel tasks
+// running in the same daemon.
+fn pending_store() -> &'static Mutex<HashMap<String, PendingSlot>> {
+    static STORE: OnceLock<Mutex<HashMap<String, PendingSlot>>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Snapshot of all pending approvals, oldest first.
+pub fn pending_approvals() -> Vec<PendingApproval> {
+    let mut pending: Vec<PendingApproval> = pending_store()
+        .lock()
+        .values()
+        .map(|slot| slot.approval.clone())
+        .collect();
+    pending.sort_by(|a, b| a.requested_at.cmp(&b.requested_at));
+    pending
+}
+
+/// Resolve a pending approval.
+///
+/// `requester` is the conversation key of a chat reply; it must match the
+/// sender that triggered the request, so chat replies cannot resolve requests
+/// without one. Pass `None` for trusted callers such as the authenticated
+/// gateway.
+pub fn resolve_pending_approval(
+    id: &str,
+    decision: ApprovalResponse,
+    resolved_by: &str,
+    requester: Option<&str>,
+) -> anyhow::Result<PendingApproval> {
+    let mut store = pending_store().lock();
+    let Some(slot) = store.get(id) else {
+        anyhow::bail!("No pending approval with id `{id}`");
+    };
+    if let Some(actual) = requester {
+        if slot.approval.requester.as_deref() != Some(actual) {
+            anyhow::bail!("Approval `{id}` was requested by another sender");
+        }
+    }
+    let slot = store.remove(id).expect("pending approval checked above");
+    drop(store);
+
+    // The waiting task may have timed out in the meantime; nothing to do then.
+    let _ = slot.responder.send((decision, resolved_by.to_string()));
+    Ok(slot.approval)
+}
+
+/// Parse a chat reply such as `/approve ab12cd34`, `deny ab12cd34` or `always ab12cd34`.
+pub fn parse_approval_reply(content: &str) -> Option<(String, ApprovalResponse)> {
+    let mut parts = content.split_whitespace();
+    let verb = parts.next()?.trim_start_matches('/').to_ascii_lowercase();
+    let id = parts.next()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let decision = match verb.as_str() {
+        "approve" | "yes" => ApprovalResponse::Yes,
+        "deny" | "no" => ApprovalResponse::No,
+        "always" => ApprovalResponse::Always,
+        _ => return None,
+    };
+    let id = id.trim().to_ascii_lowercase();
+    // Ids are 8 hex chars; anything else is ordinary conversation.
+    if id.len() != 8 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((id, decision))
+}
+
+/// Plain-text prompt used by channels without native buttons.
+pub fn format_approval_prompt(pending: &PendingApproval) -> String {
+    format!(
+        "🔧 Approval needed: `{tool}`\n{summary}\n\nReply `/approve {id}`, `/deny {id}`, or `/always {id}` (expires {expires}).",
+        tool = pending.tool_name,
+        summary = pending.arguments_summary,
+        id = pending.id,
+        expires = pending.expires_at,
+    )
 }
 
 // ── ApprovalManager ──────────────────────────────────────────────
@@ -47,7 +185,8 @@ pub struct ApprovalLogEntry {
 /// Manages the interactive approval workflow.
 ///
 /// - Checks config-level `auto_approve` / `always_ask` lists
-/// - Maintains a session-scoped "always" allowlist
+/// - Maintains a session-scoped "always" allowlist, kept per sender for chat
+///   approvals
 /// - Records an audit trail of all decisions
 pub struct ApprovalManager {
     /// Tools that never need approval (from config).
@@ -58,8 +197,14 @@ pub struct ApprovalManager {
     autonomy_level: AutonomyLevel,
     /// Session-scoped allowlist built from "Always" responses.
     session_allowlist: Mutex<HashSet<String>>,
+    /// "Always" responses from chat senders, keyed by `(requester, tool)`.
+    requester_allowlist: Mutex<HashSet<(String, String)>>,
     /// Audit trail of approval decisions.
     audit_log: Mutex<Vec<ApprovalLogEntry>>,
+    /// How long a channel/gateway approval may stay pending before it is denied.
+    timeout: Duration,
+    /// Optional persistent audit log for decisions.
+    audit_logger: Option<Arc<AuditLogger>>,
 }
 
 impl ApprovalManager {
@@ -70,10 +215,19 @@ impl ApprovalManager {
             always_ask: config.always_ask.iter().cloned().collect(),
             autonomy_level: config.level,
             session_allowlist: Mutex::new(HashSet::new()),
+            requester_allowlist: Mutex::new(HashSet::new()),
             audit_log: Mutex::new(Vec::new()),
+            timeout: Duration::from_secs(config.approval_timeout_secs.max(1)),
+            audit_logger: None,
         }
     }
 
+    /// Also write every decision to the security audit log.
+    pub fn with_audit_logger(mut self, logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
     /// Check whether a tool call requires interactive approval.
     ///
     /// Returns `true` if the call needs a prompt, `false` if it can proceed.
@@ -98,9 +252,16 @@ impl ApprovalManager {
             return false;
         }
 
-        // Session allowlist (from prior "Always" responses).
-        let allowlist = self.session_allowlist.lock();
-        if allowlist.contains(tool_name) {
+        // Session allowlist (from prior "Always" responses). Chat senders
+        // only get the tools they allowed themselves.
+        let allowed = match current_requester() {
+            Some(requester) => self
+                .requester_allowlist
+                .lock()
+                .contains(&(requester, tool_name.to_string())),
+            None => self.session_allowlist.lock().contains(tool_name),
+        };
+        if allowed {
             return false;
         }
 
@@ -108,6 +269,15 @@ impl ApprovalManager {
         true
     }
 
+    /// Like [`Self::needs_approval`], but honours the tool's declared metadata:
+    /// read-only tools run unprompted unless listed in `always_ask`.
+    pub fn needs_approval_for(&self, tool_name: &str, metadata: &ToolMetadata) -> bool {
+        if metadata.is_read_only() && !self.always_ask.contains(tool_name) {
+            return false;
+        }
+        self.needs_approval(tool_name)
+    }
+
     /// Record an approval decision and update session state.
     pub fn record_decision(
         &self,
@@ -116,10 +286,30 @@ impl ApprovalManager {
         decision: ApprovalResponse,
         channel: &str,
     ) {
-        // If "Always", add to session allowlist.
+        self.record_resolution(tool_name, args, decision, channel, channel);
+    }
+
+    /// Record a decision together with who resolved it.
+    pub fn record_resolution(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+        decision: ApprovalResponse,
+        channel: &str,
+        resolved_by: &str,
+    ) {
+        // If "Always", add to the session allowlist (per sender for chat).
         if decision == ApprovalResponse::Always {
-            let mut allowlist = self.session_allowlist.lock();
-            allowlist.insert(tool_name.to_string());
+            match current_requester() {
+                Some(requester) => {
+                    self.requester_allowlist
+                        .lock()
+                        .insert((requester, tool_name.to_string()));
+                }
+                None => {
+                    self.session_allowlist.lock().insert(tool_name.to_string());
+                }
+            }
         }
 
         // Append to audit log.
@@ -130,7 +320,23 @@ impl ApprovalManager {
             arguments_summary: summary,
             decision,
             channel: channel.to_string(),
+            resolved_by: resolved_by.to_string(),
         };
+
+        if let Some(logger) = &self.audit_logger {
+            if let Err(err) =
+                logger.log_approval_event(crate::security::audit::ApprovalDecisionLog {
+                    channel,
+                    tool: tool_name,
+                    arguments: &entry.arguments_summary,
+                    approved: decision != ApprovalResponse::No,
+                    resolved_by,
+                })
+            {
+                tracing::warn!("Failed to write approval audit entry: {err}");
+            }
+        }
+
         let mut log = self.audit_log.lock();
         log.push(entry);
     }
@@ -152,6 +358,79 @@ impl ApprovalManager {
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
         prompt_cli_interactive(request)
     }
+
+    /// Park the request until a channel reply or the gateway resolves it.
+    ///
+    /// The task-local [`APPROVAL_NOTIFIER`] (if any) announces the request to
+    /// the originating conversation. Unanswered requests are denied after the
+    /// configured timeout. The decision is recorded before returning.
+    pub async fn request_approval(
+        &self,
+        request: &ApprovalRequest,
+        channel: &str,
+    ) -> ApprovalResponse {
+        let notifier = current_approval_notifier();
+        let now = Utc::now();
+        let expires_at =
+            now + chrono::Duration::from_std(self.timeout).unwrap_or(chrono::Duration::MAX);
+        let pending = PendingApproval {
+            id: new_approval_id(),
+            tool_name: request.tool_name.clone(),
+            arguments_summary: summarize_args(&request.arguments),
+            channel: channel.to_string(),
+            requester: notifier.as_ref().and_then(|n| n.requester()),
+            requested_at: now.to_rfc3339(),
+            expires_at: expires_at.to_rfc3339(),
+        };
+
+        let (tx, rx) = oneshot::channel();
+        pending_store().lock().insert(
+            pending.id.clone(),
+            PendingSlot {
+                approval: pending.clone(),
+                responder: tx,
+            },
+        );
+        // Drops the slot if the tool loop is cancelled while waiting.
+        let _guard = PendingGuard(pending.id.clone());
+
+        if let Some(notifier) = &notifier {
+            if let Err(err) = notifier.notify(&pending).await {
+                tracing::warn!(
+                    "Failed to deliver approval request {} to {channel}: {err:#}",
+                    pending.id
+                );
+            }
+        }
+
+        let (decision, resolved_by) = match tokio::time::timeout(self.timeout, rx).await {
+            Ok(Ok(resolution)) => resolution,
+            _ => (ApprovalResponse::No, "timeout".to_string()),
+        };
+
+        self.record_resolution(
+            &request.tool_name,
+            &request.arguments,
+            decision,
+            channel,
+            &resolved_by,
+        );
+        decision
+    }
+}
+
+struct PendingGuard(String);
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        pending_store().lock().remove(&self.0);
+    }
+}
+
+fn new_approval_id() -> String {
+    let mut id = uuid::Uuid::new_v4().simple().to_string();
+    id.truncate(8);
+    id
 }
 
 // ── CLI prompt ───────────────────────────────────────────────────
@@ -179,7 +458,7 @@ fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
 }
 
 /// Produce a short human-readable summary of tool arguments.
-fn summarize_args(args: &serde_json::Value) -> String {
+pub(crate) fn summarize_args(args: &serde_json::Value) -> String {
     match args {
         serde_json::Value::Object(map) => {
             let parts: Vec<String> = map
@@ -277,6 +556,14 @@ mod tests {
         assert!(!mgr.needs_approval("shell"));
     }
 
+    #[test]
+    fn read_only_metadata_skips_prompt_unless_always_ask() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        assert!(!mgr.needs_approval_for("content_search", &ToolMetadata::read_only()));
+        assert!(mgr.needs_approval_for("custom_tool", &ToolMetadata::mutating()));
+        assert!(mgr.needs_approval_for("shell", &ToolMetadata::read_only()));
+    }
+
     // ── session allowlist ────────────────────────────────────
 
     #[test]
@@ -366,6 +653,164 @@ mod tests {
         assert_eq!(log[0].channel, "telegram");
     }
 
+    // ── remote approvals ─────────────────────────────────────
+
+    struct RecordingNotifier(tokio::sync::mpsc::UnboundedSender<PendingApproval>);
+
+    #[async_trait]
+    impl ApprovalNotifier for RecordingNotifier {
+        fn requester(&self) -> Option<String> {
+            Some("telegram_alice".into())
+        }
+
+        async fn notify(&self, pending: &PendingApproval) -> anyhow::Result<()> {
+            let _ = self.0.send(pending.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_approval_is_resolved_by_matching_requester() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let notifier: Arc<dyn ApprovalNotifier> = Arc::new(RecordingNotifier(tx));
+        let request = ApprovalRequest {
+            tool_name: "file_write".into(),
+            arguments: serde_json::json!({"path": "a.txt"}),
+        };
+
+        let waiter = tokio::spawn(async move {
+            let decision = APPROVAL_NOTIFIER
+                .scope(Some(notifier), mgr.request_approval(&request, "telegram"))
+                .await;
+            (decision, mgr.audit_log())
+        });
+
+        let pending = rx.recv().await.unwrap();
+        assert!(pending_approvals().iter().any(|p| p.id == pending.id));
+        assert!(resolve_pending_approval(
+            &pending.id,
+            ApprovalResponse::Yes,
+            "x",
+            Some("slack_bob")
+        )
+        .is_err());
+        resolve_pending_approval(
+            &pending.id,
+            ApprovalResponse::Always,
+            "telegram:alice",
+            Some("telegram_alice"),
+        )
+        .unwrap();
+
+        let (decision, log) = waiter.await.unwrap();
+        assert_eq!(decision, ApprovalResponse::Always);
+        assert_eq!(log[0].resolved_by, "telegram:alice");
+        assert!(!pending_approvals().iter().any(|p| p.id == pending.id));
+    }
+
+    struct SenderNotifier(&'static str);
+
+    #[async_trait]
+    impl ApprovalNotifier for SenderNotifier {
+        fn requester(&self) -> Option<String> {
+            Some(self.0.to_string())
+        }
+
+        async fn notify(&self, _pending: &PendingApproval) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn always_from_one_sender_does_not_approve_for_another() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        let alice: Arc<dyn ApprovalNotifier> = Arc::new(SenderNotifier("telegram_alice"));
+        let bob: Arc<dyn ApprovalNotifier> = Arc::new(SenderNotifier("discord_bob"));
+
+        APPROVAL_NOTIFIER.sync_scope(Some(alice.clone()), || {
+            mgr.record_resolution(
+                "file_write",
+                &serde_json::json!({"path": "a.txt"}),
+                ApprovalResponse::Always,
+                "telegram",
+                "telegram:alice",
+            );
+        });
+
+        assert!(!APPROVAL_NOTIFIER.sync_scope(Some(alice), || mgr.needs_approval("file_write")));
+        assert!(APPROVAL_NOTIFIER.sync_scope(Some(bob), || mgr.needs_approval("file_write")));
+        assert!(mgr.needs_approval("file_write"));
+        assert!(mgr.session_allowlist().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unowned_approval_cannot_be_resolved_from_chat() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        let request = ApprovalRequest {
+            tool_name: "unowned_probe".into(),
+            arguments: serde_json::json!({}),
+        };
+        let waiter = tokio::spawn(async move { mgr.request_approval(&request, "gateway").await });
+
+        let id = loop {
+            if let Some(pending) = pending_approvals()
+                .into_iter()
+                .find(|p| p.tool_name == "unowned_probe")
+            {
+                break pending.id;
+            }
+            tokio::task::yield_now().await;
+        };
+
+        let err = resolve_pending_approval(
+            &id,
+            ApprovalResponse::Yes,
+            "telegram:alice",
+            Some("telegram_alice"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("another sender"));
+
+        resolve_pending_approval(&id, ApprovalResponse::Yes, "gateway", None).unwrap();
+        assert_eq!(waiter.await.unwrap(), ApprovalResponse::Yes);
+    }
+
+    #[tokio::test]
+    async fn remote_approval_times_out_as_denied() {
+        let config = AutonomyConfig {
+            approval_timeout_secs: 1,
+            ..supervised_config()
+        };
+        let mgr = ApprovalManager::from_config(&config);
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        };
+
+        let decision = mgr.request_approval(&request, "gateway").await;
+        assert_eq!(decision, ApprovalResponse::No);
+        assert_eq!(mgr.audit_log()[0].resolved_by, "timeout");
+    }
+
+    #[test]
+    fn parse_approval_reply_requires_keyword_and_id() {
+        assert_eq!(
+            parse_approval_reply("/approve AB12cd34"),
+            Some(("ab12cd34".into(), ApprovalResponse::Yes))
+        );
+        assert_eq!(
+            parse_approval_reply("deny ab12cd34"),
+            Some(("ab12cd34".into(), ApprovalResponse::No))
+        );
+        assert_eq!(
+            parse_approval_reply("always ab12cd34"),
+            Some(("ab12cd34".into(), ApprovalResponse::Always))
+        );
+        assert!(parse_approval_reply("no thanks").is_none());
+        assert!(parse_approval_reply("/approve ab12cd34 now").is_none());
+    }
+
     // ── summarize_args ───────────────────────────────────────
 
     #[test]
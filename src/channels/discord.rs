@@ -1,4 +1,5 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelAttachment, ChannelMessage, OutboundAttachment, SendMessage};
+use crate::approval::PendingApproval;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
@@ -7,6 +8,9 @@ use std::collections::HashMap;
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 
+/// `custom_id` prefix for approval buttons: `approval:<verb>:<id>`.
+const APPROVAL_CUSTOM_ID_PREFIX: &str = "approval";
+
 /// Discord channel — connects via Gateway WebSocket for real-time messages
 pub struct DiscordChannel {
     bot_token: String,
@@ -39,6 +43,46 @@ impl DiscordChannel {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
 
+    /// Upload files as one message (`multipart/form-data` with `files[n]` parts).
+    async fn upload_files(
+        &self,
+        channel_id: &str,
+        attachments: &[OutboundAttachment],
+    ) -> anyhow::Result<()> {
+        let mut form = reqwest::multipart::Form::new();
+        let mut descriptors = Vec::with_capacity(attachments.len());
+        for (index, attachment) in attachments.iter().enumerate() {
+            let part = reqwest::multipart::Part::bytes(attachment.read().await?)
+                .file_name(attachment.file_name.clone())
+                .mime_str(&attachment.mime_type)?;
+            form = form.part(format!("files[{index}]"), part);
+            descriptors.push(json!({ "id": index, "filename": attachment.file_name }));
+        }
+        form = form.text(
+            "payload_json",
+            json!({ "attachments": descriptors }).to_string(),
+        );
+
+        let resp = self
+            .http_client()
+            .post(format!(
+                "https://discord.com/api/v10/channels/{channel_id}/messages"
+            ))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .multipart(form)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord file upload failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
     /// Check if a Discord user ID is in the allowlist.
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
@@ -46,6 +90,64 @@ impl DiscordChannel {
         self.allowed_users.iter().any(|u| u == "*" || u == user_id)
     }
 
+    /// Turn an approval button press (`INTERACTION_CREATE`, component type) into
+    /// the equivalent `/approve <id>` message. Returns the message plus the
+    /// interaction ID and token needed to acknowledge it.
+    fn parse_approval_interaction(
+        &self,
+        d: &serde_json::Value,
+    ) -> Option<(ChannelMessage, String, String)> {
+        // 3 = MESSAGE_COMPONENT
+        if d.get("type").and_then(serde_json::Value::as_u64) != Some(3) {
+            return None;
+        }
+        let custom_id = d.get("data")?.get("custom_id")?.as_str()?;
+        let mut parts = custom_id.splitn(3, ':');
+        if parts.next()? != APPROVAL_CUSTOM_ID_PREFIX {
+            return None;
+        }
+        let verb = parts.next()?;
+        let approval_id = parts.next()?;
+
+        // Guild interactions carry `member.user`, DMs carry `user`.
+        let user_id = d
+            .get("member")
+            .and_then(|m| m.get("user"))
+            .or_else(|| d.get("user"))
+            .and_then(|u| u.get("id"))
+            .and_then(serde_json::Value::as_str)?;
+        if !self.is_user_allowed(user_id) {
+            tracing::warn!("Discord: ignoring approval from unauthorized user: {user_id}");
+            return None;
+        }
+
+        let interaction_id = d.get("id")?.as_str()?.to_string();
+        let interaction_token = d.get("token")?.as_str()?.to_string();
+        let channel_id = d
+            .get("channel_id")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or(user_id)
+            .to_string();
+
+        Some((
+            ChannelMessage {
+                id: format!("discord_interaction_{interaction_id}"),
+                sender: user_id.to_string(),
+                reply_target: channel_id,
+                content: format!("/{verb} {approval_id}"),
+                channel: "discord".to_string(),
+                timestamp: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                thread_ts: None,
+                attachments: Vec::new(),
+            },
+            interaction_id,
+            interaction_token,
+        ))
+    }
+
     fn bot_user_id_from_token(token: &str) -> Option<String> {
         // Discord bot tokens are base64(bot_user_id).timestamp.hmac
         let part = token.split('.').next()?;
@@ -60,6 +162,9 @@ const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstu
 /// Discord rejects longer payloads with `50035 Invalid Form Body`.
 const DISCORD_MAX_MESSAGE_LENGTH: usize = 2000;
 
+/// Discord accepts at most 10 files per message.
+const DISCORD_MAX_FILES_PER_MESSAGE: usize = 10;
+
 /// Split a message into chunks that respect Discord's 2000-character limit.
 /// Tries to split at word boundaries when possible.
 fn split_message_for_discord(message: &str) -> Vec<String> {
@@ -145,6 +250,32 @@ fn normalize_incoming_content(
     Some(normalized)
 }
 
+/// Collect the `attachments` array of a `MESSAGE_CREATE` payload.
+/// Discord CDN URLs are pre-signed, so no auth header is needed.
+fn parse_discord_attachments(d: &serde_json::Value) -> Vec<ChannelAttachment> {
+    d.get("attachments")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let url = item.get("url").and_then(serde_json::Value::as_str)?;
+            let mime_type = item
+                .get("content_type")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("application/octet-stream");
+            Some(
+                ChannelAttachment::url(mime_type, url, None, "channel.discord")
+                    .with_file_name(
+                        item.get("filename")
+                            .and_then(serde_json::Value::as_str)
+                            .map(str::to_string),
+                    )
+                    .with_size(item.get("size").and_then(serde_json::Value::as_u64)),
+            )
+        })
+        .collect()
+}
+
 /// Minimal base64 decode (no extra dep) — only needs to decode the user ID portion
 #[allow(clippy::cast_possible_truncation)]
 fn base64_decode(input: &str) -> Option<String> {
@@ -189,8 +320,16 @@ impl Channel for DiscordChannel {
         "discord"
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let chunks = split_message_for_discord(&message.content);
+        let chunks = if message.content.is_empty() && !message.attachments.is_empty() {
+            Vec::new()
+        } else {
+            split_message_for_discord(&message.content)
+        };
 
         for (i, chunk) in chunks.iter().enumerate() {
             let url = format!(
@@ -223,6 +362,10 @@ impl Channel for DiscordChannel {
             }
         }
 
+        for batch in message.attachments.chunks(DISCORD_MAX_FILES_PER_MESSAGE) {
+            self.upload_files(&message.recipient, batch).await?;
+        }
+
         Ok(())
     }
 
@@ -350,8 +493,26 @@ impl Channel for DiscordChannel {
                         _ => {}
                     }
 
-                    // Only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
                     let event_type = event.get("t").and_then(|t| t.as_str()).unwrap_or("");
+
+                    // Approval button presses arrive as component interactions
+                    if event_type == "INTERACTION_CREATE" {
+                        if let Some((channel_msg, interaction_id, interaction_token)) =
+                            event.get("d").and_then(|d| self.parse_approval_interaction(d))
+                        {
+                            // 6 = DEFERRED_UPDATE_MESSAGE: acknowledge without a visible reply
+                            let url = format!(
+                                "https://discord.com/api/v10/interactions/{interaction_id}/{interaction_token}/callback"
+                            );
+                            let _ = self.http_client().post(&url).json(&json!({"type": 6})).send().await;
+                            if tx.send(channel_msg).await.is_err() {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+
+                    // Only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
                     if event_type != "MESSAGE_CREATE" {
                         continue;
                     }
@@ -389,10 +550,16 @@ impl Channel for DiscordChannel {
                     }
 
                     let content = d.get("content").and_then(|c| c.as_str()).unwrap_or("");
-                    let Some(clean_content) =
-                        normalize_incoming_content(content, self.mention_only, &bot_user_id)
-                    else {
-                        continue;
+                    let attachments = parse_discord_attachments(d);
+                    let clean_content = match normalize_incoming_content(content, self.mention_only, &bot_user_id) {
+                        Some(clean_content) => clean_content,
+                        // Attachment-only messages (a bare screenshot) carry no text.
+                        None if !attachments.is_empty()
+                            && (!self.mention_only || contains_bot_mention(content, &bot_user_id)) =>
+                        {
+                            String::new()
+                        }
+                        None => continue,
                     };
 
                     let message_id = d.get("id").and_then(|i| i.as_str()).unwrap_or("");
@@ -417,6 +584,7 @@ impl Channel for DiscordChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        attachments,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -471,6 +639,51 @@ impl Channel for DiscordChannel {
         }
         Ok(())
     }
+
+    async fn send_approval_prompt(
+        &self,
+        message: &SendMessage,
+        approval: &PendingApproval,
+    ) -> anyhow::Result<()> {
+        // Button styles: 3 = success, 4 = danger, 2 = secondary
+        let button = |label: &str, verb: &str, style: u8| {
+            json!({
+                "type": 2,
+                "style": style,
+                "label": label,
+                "custom_id": format!("{APPROVAL_CUSTOM_ID_PREFIX}:{verb}:{}", approval.id),
+            })
+        };
+        let body = json!({
+            "content": message.content,
+            "components": [{
+                "type": 1,
+                "components": [
+                    button("Approve", "approve", 3),
+                    button("Deny", "deny", 4),
+                    button("Always", "always", 2),
+                ]
+            }]
+        });
+
+        let url = format!(
+            "https://discord.com/api/v10/channels/{}/messages",
+            message.recipient
+        );
+        let resp = self
+            .http_client()
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Discord approval prompt failed ({status}): {err}");
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -483,6 +696,28 @@ mod tests {
         assert_eq!(ch.name(), "discord");
     }
 
+    #[test]
+    fn approval_interaction_maps_button_to_reply_keyword() {
+        let ch = DiscordChannel::new("fake".into(), None, vec!["111".into()], false, false);
+        let d = json!({
+            "type": 3,
+            "id": "900",
+            "token": "tok",
+            "channel_id": "42",
+            "member": { "user": { "id": "111" } },
+            "data": { "custom_id": "approval:deny:ab12cd34" }
+        });
+
+        let (msg, id, token) = ch.parse_approval_interaction(&d).unwrap();
+        assert_eq!(msg.content, "/deny ab12cd34");
+        assert_eq!(msg.sender, "111");
+        assert_eq!(msg.reply_target, "42");
+        assert_eq!((id.as_str(), token.as_str()), ("900", "tok"));
+
+        let stranger = DiscordChannel::new("fake".into(), None, vec!["222".into()], false, false);
+        assert!(stranger.parse_approval_interaction(&d).is_none());
+    }
+
     #[test]
     fn base64_decode_bot_id() {
         // "MTIzNDU2" decodes to "123456"
@@ -605,6 +840,27 @@ mod tests {
         assert!(cleaned.is_none());
     }
 
+    #[test]
+    fn parse_discord_attachments_reads_cdn_metadata() {
+        let d = serde_json::json!({
+            "attachments": [
+                {
+                    "url": "https://cdn.discordapp.com/attachments/1/2/shot.png",
+                    "filename": "shot.png",
+                    "content_type": "image/png",
+                    "size": 2048
+                },
+                { "filename": "missing-url.txt" }
+            ]
+        });
+        let attachments = parse_discord_attachments(&d);
+        assert_eq!(attachments.len(), 1);
+        assert!(attachments[0].is_image());
+        assert_eq!(attachments[0].file_name.as_deref(), Some("shot.png"));
+        assert_eq!(attachments[0].size_bytes, Some(2048));
+        assert!(parse_discord_attachments(&serde_json::json!({})).is_empty());
+    }
+
     // Message splitting tests
 
     #[test]
@@ -14,7 +14,8 @@ use async_imap::types::Fetch;
 use async_imap::Session;
 use async_trait::async_trait;
 use futures_util::TryStreamExt;
-use lettre::message::SinglePart;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, MultiPart, SinglePart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
@@ -33,7 +34,7 @@ use tokio_rustls::TlsConnector;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelAttachment, ChannelMessage, OutboundAttachment, SendMessage};
 
 /// Email channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -196,6 +197,41 @@ impl EmailChannel {
         "(no readable content)".to_string()
     }
 
+    /// Collect non-text MIME attachments (images, PDFs, audio) as inline bytes
+    fn extract_attachments(parsed: &mail_parser::Message) -> Vec<ChannelAttachment> {
+        parsed
+            .attachments()
+            .filter_map(|part| {
+                let ct = MimeHeaders::content_type(part)?;
+                if ct.ctype() == "text" {
+                    return None;
+                }
+                let mime_type = match ct.subtype() {
+                    Some(subtype) => format!("{}/{}", ct.ctype(), subtype),
+                    None => ct.ctype().to_string(),
+                };
+                Some(
+                    ChannelAttachment::inline(mime_type, part.contents().to_vec())
+                        .with_file_name(MimeHeaders::attachment_name(part).map(str::to_string)),
+                )
+            })
+            .collect()
+    }
+
+    /// `multipart/mixed` body: the text part followed by one part per file
+    async fn mixed_body(body: &str, attachments: &[OutboundAttachment]) -> Result<MultiPart> {
+        let mut mixed = MultiPart::mixed().singlepart(SinglePart::plain(body.to_string()));
+        for attachment in attachments {
+            let content_type = ContentType::parse(&attachment.mime_type)
+                .or_else(|_| ContentType::parse("application/octet-stream"))?;
+            mixed = mixed.singlepart(
+                Attachment::new(attachment.file_name.clone())
+                    .body(attachment.read().await?, content_type),
+            );
+        }
+        Ok(mixed)
+    }
+
     /// Connect to IMAP server with TLS and authenticate
     async fn connect_imap(&self) -> Result<ImapSession> {
         let addr = format!("{}:{}", self.config.imap_host, self.config.imap_port);
@@ -256,6 +292,7 @@ impl EmailChannel {
                     let sender = Self::extract_sender(&parsed);
                     let subject = parsed.subject().unwrap_or("(no subject)").to_string();
                     let body_text = Self::extract_text(&parsed);
+                    let attachments = Self::extract_attachments(&parsed);
                     let content = format!("Subject: {}\n\n{}", subject, body_text);
                     let msg_id = parsed
                         .message_id()
@@ -293,6 +330,7 @@ impl EmailChannel {
                         sender,
                         content,
                         timestamp: ts,
+                        attachments,
                     });
                 }
             }
@@ -451,6 +489,7 @@ impl EmailChannel {
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
                 thread_ts: None,
+                attachments: email.attachments,
             };
 
             if tx.send(msg).await.is_err() {
@@ -486,6 +525,7 @@ struct ParsedEmail {
     sender: String,
     content: String,
     timestamp: u64,
+    attachments: Vec<ChannelAttachment>,
 }
 
 /// Result from waiting on IDLE
@@ -501,6 +541,10 @@ impl Channel for EmailChannel {
         "email"
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> Result<()> {
         // Use explicit subject if provided, otherwise fall back to legacy parsing or default
         let (subject, body) = if let Some(ref subj) = message.subject {
@@ -515,11 +559,15 @@ impl Channel for EmailChannel {
             ("ZeroClaw Message", message.content.as_str())
         };
 
-        let email = Message::builder()
+        let builder = Message::builder()
             .from(self.config.from_address.parse()?)
             .to(message.recipient.parse()?)
-            .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
+            .subject(subject);
+        let email = if message.attachments.is_empty() {
+            builder.singlepart(SinglePart::plain(body.to_string()))?
+        } else {
+            builder.multipart(Self::mixed_body(body, &message.attachments).await?)?
+        };
 
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
@@ -965,4 +1013,50 @@ mod tests {
         let debug_str = format!("{:?}", config);
         assert!(debug_str.contains("imap.debug.com"));
     }
+
+    #[tokio::test]
+    async fn mixed_body_adds_attachment_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.csv");
+        std::fs::write(&path, "a,b\n1,2\n").unwrap();
+
+        let body =
+            EmailChannel::mixed_body("see attached", &[OutboundAttachment::from_path(&path)])
+                .await
+                .unwrap();
+        let formatted = String::from_utf8(body.formatted()).unwrap();
+        assert!(formatted.contains("multipart/mixed"));
+        assert!(formatted.contains("see attached"));
+        assert!(formatted.contains("filename=\"report.csv\""));
+        assert!(formatted.contains("text/csv"));
+    }
+
+    #[test]
+    fn extract_attachments_keeps_binary_parts_only() {
+        let raw = concat!(
+            "From: alice@example.com\r\n",
+            "Subject: report\r\n",
+            "MIME-Version: 1.0\r\n",
+            "Content-Type: multipart/mixed; boundary=\"b\"\r\n\r\n",
+            "--b\r\n",
+            "Content-Type: text/plain\r\n\r\n",
+            "See attached.\r\n",
+            "--b\r\n",
+            "Content-Type: application/pdf; name=\"q3.pdf\"\r\n",
+            "Content-Disposition: attachment; filename=\"q3.pdf\"\r\n",
+            "Content-Transfer-Encoding: base64\r\n\r\n",
+            "JVBERi0xLjQ=\r\n",
+            "--b\r\n",
+            "Content-Type: text/plain; name=\"notes.txt\"\r\n",
+            "Content-Disposition: attachment; filename=\"notes.txt\"\r\n\r\n",
+            "plain notes\r\n",
+            "--b--\r\n"
+        );
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+        let attachments = EmailChannel::extract_attachments(&parsed);
+        assert_eq!(attachments.len(), 1);
+        assert!(attachments[0].is_pdf());
+        assert_eq!(attachments[0].file_name.as_deref(), Some("q3.pdf"));
+        assert_eq!(attachments[0].size_bytes, Some(8));
+    }
 }
@@ -1,11 +1,17 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    AttachmentFetcher, AttachmentSource, Channel, ChannelAttachment, ChannelMessage,
+    OutboundAttachment, SendMessage,
+};
 use async_trait::async_trait;
 use matrix_sdk::{
+    attachment::AttachmentConfig,
     authentication::matrix::MatrixSession,
     config::SyncSettings,
+    media::{MediaFormat, MediaRequestParameters},
     ruma::{
-        events::room::message::{
-            MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent,
+        events::room::{
+            message::{MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent},
+            MediaSource,
         },
         OwnedRoomId, OwnedUserId,
     },
@@ -31,6 +37,27 @@ pub struct MatrixChannel {
     http_client: Client,
 }
 
+/// Downloads (and decrypts, for encrypted rooms) an `m.image`/`m.file`/`m.audio` payload.
+struct MatrixMediaFetcher {
+    client: MatrixSdkClient,
+    source: MediaSource,
+}
+
+#[async_trait]
+impl AttachmentFetcher for MatrixMediaFetcher {
+    async fn fetch(&self) -> anyhow::Result<Vec<u8>> {
+        let request = MediaRequestParameters {
+            source: self.source.clone(),
+            format: MediaFormat::File,
+        };
+        Ok(self
+            .client
+            .media()
+            .get_media_content(&request, true)
+            .await?)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct SyncResponse {
     next_batch: String,
@@ -174,6 +201,72 @@ impl MatrixChannel {
         matches!(msgtype, "m.text" | "m.notice")
     }
 
+    /// Split a media message into its caption and a lazily-downloaded attachment.
+    ///
+    /// Per the spec, `body` is a caption only when `filename` is set and differs from it.
+    fn media_attachment(
+        client: &MatrixSdkClient,
+        msgtype: &MessageType,
+    ) -> Option<(String, ChannelAttachment)> {
+        let (body, filename, source, mime_type, size) = match msgtype {
+            MessageType::Image(content) => (
+                &content.body,
+                content.filename.clone(),
+                content.source.clone(),
+                content.info.as_ref().and_then(|i| i.mimetype.clone()),
+                content.info.as_ref().and_then(|i| i.size).map(u64::from),
+            ),
+            MessageType::File(content) => (
+                &content.body,
+                content.filename.clone(),
+                content.source.clone(),
+                content.info.as_ref().and_then(|i| i.mimetype.clone()),
+                content.info.as_ref().and_then(|i| i.size).map(u64::from),
+            ),
+            MessageType::Audio(content) => (
+                &content.body,
+                content.filename.clone(),
+                content.source.clone(),
+                content.info.as_ref().and_then(|i| i.mimetype.clone()),
+                content.info.as_ref().and_then(|i| i.size).map(u64::from),
+            ),
+            _ => return None,
+        };
+
+        let caption = match filename.as_deref() {
+            Some(name) if name != body.as_str() => body.clone(),
+            _ => String::new(),
+        };
+        let file_name = filename.or_else(|| Some(body.clone()));
+        let attachment = ChannelAttachment::new(
+            mime_type.unwrap_or_default(),
+            AttachmentSource::Fetcher(Arc::new(MatrixMediaFetcher {
+                client: client.clone(),
+                source,
+            })),
+        )
+        .with_file_name(file_name)
+        .with_size(size);
+        Some((caption, attachment))
+    }
+
+    /// Upload a file as `m.image`/`m.audio`/`m.file`; the SDK encrypts it for E2EE rooms.
+    async fn send_attachment(room: &Room, attachment: &OutboundAttachment) -> anyhow::Result<()> {
+        let content_type: mime::Mime = attachment
+            .mime_type
+            .parse()
+            .unwrap_or(mime::APPLICATION_OCTET_STREAM);
+        let data = attachment.read().await?;
+        room.send_attachment(
+            attachment.file_name.as_str(),
+            &content_type,
+            data,
+            AttachmentConfig::new(),
+        )
+        .await?;
+        Ok(())
+    }
+
     fn has_non_empty_body(body: &str) -> bool {
         !body.trim().is_empty()
     }
@@ -480,6 +573,10 @@ impl Channel for MatrixChannel {
         "matrix"
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let client = self.matrix_client().await?;
         let target_room_id = self.target_room_id().await?;
@@ -499,8 +596,14 @@ impl Channel for MatrixChannel {
             anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
         }
 
-        room.send(RoomMessageEventContent::text_markdown(&message.content))
-            .await?;
+        if !message.content.is_empty() || message.attachments.is_empty() {
+            room.send(RoomMessageEventContent::text_markdown(&message.content))
+                .await?;
+        }
+
+        for attachment in &message.attachments {
+            Self::send_attachment(&room, attachment).await?;
+        }
 
         Ok(())
     }
@@ -567,13 +670,16 @@ impl Channel for MatrixChannel {
                     return;
                 }
 
-                let body = match &event.content.msgtype {
-                    MessageType::Text(content) => content.body.clone(),
-                    MessageType::Notice(content) => content.body.clone(),
-                    _ => return,
+                let (body, attachments) = match &event.content.msgtype {
+                    MessageType::Text(content) => (content.body.clone(), Vec::new()),
+                    MessageType::Notice(content) => (content.body.clone(), Vec::new()),
+                    other => match MatrixChannel::media_attachment(&room.client(), other) {
+                        Some((caption, attachment)) => (caption, vec![attachment]),
+                        None => return,
+                    },
                 };
 
-                if !MatrixChannel::has_non_empty_body(&body) {
+                if attachments.is_empty() && !MatrixChannel::has_non_empty_body(&body) {
                     return;
                 }
 
@@ -597,6 +703,7 @@ impl Channel for MatrixChannel {
                         .unwrap_or_default()
                         .as_secs(),
                     thread_ts: None,
+                    attachments,
                 };
 
                 let _ = tx.send(msg).await;
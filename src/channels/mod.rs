@@ -28,6 +28,8 @@ pub mod matrix;
 pub mod mattermost;
 pub mod nextcloud_talk;
 pub mod qq;
+pub mod session_cli;
+pub mod session_store;
 pub mod signal;
 pub mod slack;
 pub mod telegram;
@@ -52,6 +54,7 @@ pub use matrix::MatrixChannel;
 pub use mattermost::MattermostChannel;
 pub use nextcloud_talk::NextcloudTalkChannel;
 pub use qq::QQChannel;
+pub use session_store::ChannelSessionStore;
 pub use signal::SignalChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
@@ -60,17 +63,29 @@ pub use whatsapp::WhatsAppChannel;
 #[cfg(feature = "whatsapp-web")]
 pub use whatsapp_web::WhatsAppWebChannel;
 
-use crate::agent::loop_::{build_tool_instructions, run_tool_call_loop_with_policy};
-use crate::config::Config;
+use crate::agent::loop_::{
+    build_tool_instructions, create_cost_tracker, run_tool_call_loop_with_trace_and_policy,
+    ToolLoopOutput, DRAFT_CLEAR_SENTINEL, DRAFT_PLAN_SENTINEL, TOOL_LOOP_COST_TRACKER,
+    TOOL_LOOP_PLAN_DIR,
+};
+use crate::approval::{
+    self, ApprovalManager, ApprovalNotifier, ApprovalResponse, PendingApproval, APPROVAL_NOTIFIER,
+};
+use crate::config::{ApprovalTransport, Config};
+use crate::cost::CostTracker;
+use crate::gateway::CURRENT_SESSION_ID;
 use crate::identity;
 use crate::memory::{self, Memory};
 use crate::observability::{self, Observer};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
-use crate::tools::{self, Tool};
+use crate::tools::{self, Tool, ToolContent};
+use crate::transcription::Transcriber;
+use crate::tts::SpeechSynthesizer;
 use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
@@ -116,6 +131,8 @@ const CHANNEL_HISTORY_COMPACT_CONTENT_CHARS: usize = 600;
 
 type ProviderCacheMap = Arc<Mutex<HashMap<String, Arc<dyn Provider>>>>;
 type RouteSelectionMap = Arc<Mutex<HashMap<String, ChannelRouteSelection>>>;
+/// Per-sender `/voice on|off` choices that override `[multimodal.tts].channels`.
+type VoiceReplyMap = Arc<Mutex<HashMap<String, bool>>>;
 
 fn effective_channel_message_timeout_secs(configured: u64) -> u64 {
     configured.max(MIN_CHANNEL_MESSAGE_TIMEOUT_SECS)
@@ -142,6 +159,8 @@ enum ChannelRuntimeCommand {
     SetProvider(String),
     ShowModel,
     SetModel(String),
+    ShowVoice,
+    SetVoice(bool),
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -212,6 +231,17 @@ struct ChannelRuntimeContext {
     message_timeout_secs: u64,
     interrupt_on_new_message: bool,
     multimodal: crate::config::MultimodalConfig,
+    cost_tracker: Option<Arc<CostTracker>>,
+    session_store: Option<Arc<ChannelSessionStore>>,
+    /// Set when `autonomy.approval_transport = "channel"` routes gated tools through chat approvals.
+    approval_manager: Option<Arc<ApprovalManager>>,
+    /// Render the agent's task plan above streamed draft text.
+    show_plan_in_drafts: bool,
+    /// Speech-to-text backend for inbound voice messages, when enabled.
+    transcriber: Option<Arc<dyn Transcriber>>,
+    /// Text-to-speech backend for voice replies, when enabled.
+    synthesizer: Option<Arc<dyn SpeechSynthesizer>>,
+    voice_overrides: VoiceReplyMap,
 }
 
 #[derive(Clone)]
@@ -268,6 +298,316 @@ fn channel_delivery_instructions(channel_name: &str) -> Option<&'static str> {
     }
 }
 
+/// Files produced by tools during the turn, ready to upload with the reply.
+///
+/// Inline image data is written under `workspace/attachments` first; file
+/// parts must resolve inside the workspace.
+async fn stage_outbound_attachments(
+    attachments: &[ToolContent],
+    workspace_dir: &Path,
+) -> Vec<traits::OutboundAttachment> {
+    let Ok(workspace_root) = tokio::fs::canonicalize(workspace_dir).await else {
+        return Vec::new();
+    };
+
+    let mut staged = Vec::new();
+    for attachment in attachments {
+        match attachment {
+            ToolContent::Image { mime_type, data } => {
+                use base64::Engine;
+                let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(data) else {
+                    continue;
+                };
+                let ext = traits::extension_for_mime(mime_type).unwrap_or("bin");
+                let dir = workspace_root.join("attachments");
+                let path = dir.join(format!("tool-{}.{ext}", uuid::Uuid::new_v4()));
+                let written = match tokio::fs::create_dir_all(&dir).await {
+                    Ok(()) => tokio::fs::write(&path, bytes).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = written {
+                    tracing::warn!("Failed to stage tool image attachment: {e}");
+                    continue;
+                }
+                staged.push(traits::OutboundAttachment::from_path(path).with_mime_type(mime_type));
+            }
+            ToolContent::File { path, mime_type } => {
+                let Ok(resolved) = tokio::fs::canonicalize(workspace_root.join(path)).await else {
+                    tracing::warn!("Skipping missing tool attachment {path}");
+                    continue;
+                };
+                let is_file = tokio::fs::metadata(&resolved)
+                    .await
+                    .is_ok_and(|meta| meta.is_file());
+                if !resolved.starts_with(&workspace_root) || !is_file {
+                    tracing::warn!("Skipping tool attachment outside the workspace: {path}");
+                    continue;
+                }
+                let outbound = traits::OutboundAttachment::from_path(resolved);
+                staged.push(match mime_type {
+                    Some(mime_type) => outbound.with_mime_type(mime_type),
+                    None => outbound,
+                });
+            }
+            ToolContent::Text { .. } | ToolContent::Json { .. } => {}
+        }
+    }
+    staged
+}
+
+/// Attach files to a reply, or list their paths on channels that cannot upload.
+fn with_outbound_attachments(
+    channel: &dyn Channel,
+    mut message: SendMessage,
+    attachments: Vec<traits::OutboundAttachment>,
+) -> SendMessage {
+    if attachments.is_empty() {
+        return message;
+    }
+    if channel.supports_attachments() {
+        return message.with_attachments(attachments);
+    }
+
+    let mut note = String::from("Files:");
+    for attachment in &attachments {
+        let _ = write!(note, "\n- {}", attachment.path.display());
+    }
+    message.content = if message.content.trim().is_empty() {
+        note
+    } else {
+        format!("{}\n\n{note}", message.content)
+    };
+    message
+}
+
+/// Workspace-relative directory synthesized voice replies are staged in.
+const VOICE_REPLY_DIR: &str = "attachments/voice";
+
+/// Render the final reply to audio and send it as a voice note.
+///
+/// Replies that are empty once code and markup are stripped, or longer than
+/// `[multimodal.tts].max_chars`, stay text-only.
+async fn send_voice_reply(
+    ctx: &ChannelRuntimeContext,
+    channel: &dyn Channel,
+    msg: &traits::ChannelMessage,
+    response: &str,
+) {
+    let Some(synthesizer) = ctx.synthesizer.as_ref() else {
+        return;
+    };
+    if !channel.supports_attachments() {
+        return;
+    }
+    let text = crate::tts::speakable_text(response);
+    if text.is_empty() || text.chars().count() > ctx.multimodal.tts.max_chars {
+        tracing::debug!(
+            chars = text.chars().count(),
+            "Skipping voice reply outside the text-to-speech length limit"
+        );
+        return;
+    }
+
+    let audio = match synthesizer.synthesize(&text).await {
+        Ok(audio) => audio,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to synthesize voice reply with {}: {e:#}",
+                synthesizer.name()
+            );
+            return;
+        }
+    };
+    let format = synthesizer.format();
+    let dir = ctx.workspace_dir.join(VOICE_REPLY_DIR);
+    let path = dir.join(format!(
+        "reply-{}.{}",
+        uuid::Uuid::new_v4(),
+        format.extension()
+    ));
+    if let Err(e) = async {
+        tokio::fs::create_dir_all(&dir).await?;
+        tokio::fs::write(&path, audio).await
+    }
+    .await
+    {
+        tracing::warn!("Failed to stage voice reply: {e}");
+        return;
+    }
+
+    let voice = SendMessage::new("", &msg.reply_target)
+        .in_thread(msg.thread_ts.clone())
+        .with_attachments(vec![
+            traits::OutboundAttachment::from_path(path.clone()).with_mime_type(format.mime_type())
+        ]);
+    if let Err(e) = channel.send(&voice).await {
+        tracing::warn!("Failed to send voice reply on {}: {e}", channel.name());
+    }
+    let _ = tokio::fs::remove_file(&path).await;
+}
+
+/// Workspace-relative directory inbound attachments are downloaded into.
+const INBOUND_ATTACHMENT_DIR: &str = "attachments/inbound";
+/// Inbound attachments older than this are deleted before new ones are saved.
+const INBOUND_ATTACHMENT_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+/// Total size kept in the inbound directory; the oldest files are deleted first.
+const INBOUND_ATTACHMENT_MAX_TOTAL_BYTES: u64 = 512 * 1024 * 1024;
+/// Longest side of images downscaled to fit the multimodal size limit.
+const INBOUND_IMAGE_MAX_DIM: u32 = 1024;
+
+/// Fold a message's attachments into the text of the user turn.
+///
+/// Images become `[IMAGE:<path>]` markers (picked up by
+/// `multimodal::prepare_messages_for_provider`) when the provider supports
+/// vision, PDFs are extracted with the registered `pdf_read` tool, and other
+/// files are announced with their saved workspace path. With a transcriber,
+/// voice messages are transcribed into the message text as well.
+pub(crate) async fn content_with_inbound_attachments(
+    msg: &traits::ChannelMessage,
+    workspace_dir: &Path,
+    tools_registry: &[Box<dyn Tool>],
+    supports_vision: bool,
+    multimodal: &crate::config::MultimodalConfig,
+    transcriber: Option<&dyn Transcriber>,
+) -> String {
+    if msg.attachments.is_empty() {
+        return msg.content.clone();
+    }
+
+    let dir = workspace_dir.join(INBOUND_ATTACHMENT_DIR);
+    prune_inbound_attachments(&dir, SystemTime::now()).await;
+    let (_, max_image_size_mb) = multimodal.effective_limits();
+    let mut text = Vec::new();
+    if !msg.content.trim().is_empty() {
+        text.push(msg.content.clone());
+    }
+    let mut lines = Vec::new();
+
+    for attachment in &msg.attachments {
+        let name = attachment.display_name();
+        let label = match attachment.file_name.as_deref() {
+            Some(file_name) => format!("{file_name} ({})", attachment.mime_type),
+            None => attachment.mime_type.clone(),
+        };
+        let path = match attachment.local_path(&dir).await {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::warn!("Failed to fetch {} attachment {name}: {e:#}", msg.channel);
+                lines.push(format!("[Attachment {label} could not be downloaded]"));
+                continue;
+            }
+        };
+        let relative = path
+            .strip_prefix(workspace_dir)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+
+        if attachment.is_image()
+            && supports_vision
+            && crate::multimodal::is_supported_image_mime(&attachment.mime_type)
+        {
+            match fit_image_for_vision(&path, max_image_size_mb * 1024 * 1024).await {
+                Ok(vision_path) => lines.push(format!("[IMAGE:{}]", vision_path.display())),
+                Err(e) => {
+                    tracing::warn!("Failed to prepare image attachment {name}: {e:#}");
+                    lines.push(format!("[Image attachment {name} saved to {relative}]"));
+                }
+            }
+        } else if attachment.is_pdf() {
+            lines.push(inbound_pdf_text(tools_registry, name, &relative).await);
+        } else {
+            if let Some(transcriber) = transcriber.filter(|_| attachment.is_audio()) {
+                match transcriber.transcribe(&path, &attachment.mime_type).await {
+                    Ok(transcript) if !transcript.is_empty() => text.push(transcript),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!(
+                        "Failed to transcribe {} voice message {name}: {e:#}",
+                        msg.channel
+                    ),
+                }
+            }
+            lines.push(format!("[Attachment {label} saved to {relative}]"));
+        }
+    }
+
+    text.extend(lines);
+    text.join("\n")
+}
+
+/// Apply inbound attachment retention: delete expired files, then the oldest
+/// remaining ones until the directory fits `INBOUND_ATTACHMENT_MAX_TOTAL_BYTES`.
+async fn prune_inbound_attachments(dir: &Path, now: SystemTime) {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return;
+    };
+    let mut kept = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(metadata) = entry.metadata().await else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata.modified().unwrap_or(now);
+        let age = now.duration_since(modified).unwrap_or_default();
+        if age > INBOUND_ATTACHMENT_RETENTION {
+            let _ = tokio::fs::remove_file(entry.path()).await;
+        } else {
+            kept.push((modified, metadata.len(), entry.path()));
+        }
+    }
+
+    kept.sort_by_key(|(modified, _, _)| *modified);
+    let mut total: u64 = kept.iter().map(|(_, len, _)| len).sum();
+    for (_, len, path) in kept {
+        if total <= INBOUND_ATTACHMENT_MAX_TOTAL_BYTES {
+            break;
+        }
+        if tokio::fs::remove_file(&path).await.is_ok() {
+            total -= len;
+        }
+    }
+}
+
+/// Downscale an image to JPEG when it exceeds the multimodal size limit.
+async fn fit_image_for_vision(path: &Path, max_bytes: usize) -> Result<PathBuf> {
+    let size = tokio::fs::metadata(path).await?.len();
+    if usize::try_from(size).unwrap_or(usize::MAX) <= max_bytes {
+        return Ok(path.to_path_buf());
+    }
+
+    let source = path.to_path_buf();
+    let target = path.with_extension("vision.jpg");
+    let output = target.clone();
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let img = image::open(&source)?
+            .thumbnail(INBOUND_IMAGE_MAX_DIM, INBOUND_IMAGE_MAX_DIM)
+            .into_rgb8();
+        img.save_with_format(&output, image::ImageFormat::Jpeg)?;
+        Ok(())
+    })
+    .await??;
+    Ok(target)
+}
+
+async fn inbound_pdf_text(tools_registry: &[Box<dyn Tool>], name: &str, relative: &str) -> String {
+    let Some(pdf_read) = tools_registry.iter().find(|tool| tool.name() == "pdf_read") else {
+        return format!("[Document {name} saved to {relative}]");
+    };
+    match pdf_read
+        .execute(serde_json::json!({ "path": relative }))
+        .await
+    {
+        Ok(result) if result.success => format!("[Document {name}]\n{}", result.output),
+        Ok(result) => format!(
+            "[Document {name} saved to {relative}; text extraction failed: {}]",
+            result.error.unwrap_or_default()
+        ),
+        Err(e) => format!("[Document {name} saved to {relative}; text extraction failed: {e}]"),
+    }
+}
+
 fn build_channel_system_prompt(base_prompt: &str, channel_name: &str) -> String {
     if let Some(instructions) = channel_delivery_instructions(channel_name) {
         if base_prompt.is_empty() {
@@ -317,8 +657,13 @@ fn supports_runtime_model_switch(channel_name: &str) -> bool {
     matches!(channel_name, "telegram" | "discord")
 }
 
+fn supports_voice_replies(channel_name: &str) -> bool {
+    matches!(channel_name, "telegram" | "discord" | "slack" | "matrix")
+}
+
 fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRuntimeCommand> {
-    if !supports_runtime_model_switch(channel_name) {
+    let model_switch = supports_runtime_model_switch(channel_name);
+    if !model_switch && !supports_voice_replies(channel_name) {
         return None;
     }
 
@@ -336,7 +681,7 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
         .to_ascii_lowercase();
 
     match base_command.as_str() {
-        "/models" => {
+        "/models" if model_switch => {
             if let Some(provider) = parts.next() {
                 Some(ChannelRuntimeCommand::SetProvider(
                     provider.trim().to_string(),
@@ -345,7 +690,7 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
                 Some(ChannelRuntimeCommand::ShowProviders)
             }
         }
-        "/model" => {
+        "/model" if model_switch => {
             let model = parts.collect::<Vec<_>>().join(" ").trim().to_string();
             if model.is_empty() {
                 Some(ChannelRuntimeCommand::ShowModel)
@@ -353,6 +698,13 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
                 Some(ChannelRuntimeCommand::SetModel(model))
             }
         }
+        "/voice" if supports_voice_replies(channel_name) => {
+            match parts.next().map(str::to_ascii_lowercase).as_deref() {
+                Some("on" | "enable") => Some(ChannelRuntimeCommand::SetVoice(true)),
+                Some("off" | "disable") => Some(ChannelRuntimeCommand::SetVoice(false)),
+                _ => Some(ChannelRuntimeCommand::ShowVoice),
+            }
+        }
         _ => None,
     }
 }
@@ -573,6 +925,40 @@ fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: Chan
     }
 }
 
+fn voice_replies_default(ctx: &ChannelRuntimeContext, channel_name: &str) -> bool {
+    ctx.multimodal
+        .tts
+        .channels
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(channel_name))
+}
+
+fn voice_replies_enabled(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage) -> bool {
+    if ctx.synthesizer.is_none() {
+        return false;
+    }
+    let channel_default = voice_replies_default(ctx, &msg.channel);
+    ctx.voice_overrides
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&conversation_history_key(msg))
+        .copied()
+        .unwrap_or(channel_default)
+}
+
+fn set_voice_replies(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage, enabled: bool) {
+    let channel_default = voice_replies_default(ctx, &msg.channel);
+    let mut overrides = ctx
+        .voice_overrides
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if enabled == channel_default {
+        overrides.remove(&conversation_history_key(msg));
+    } else {
+        overrides.insert(conversation_history_key(msg), enabled);
+    }
+}
+
 fn clear_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
     ctx.conversation_histories
         .lock()
@@ -627,6 +1013,46 @@ fn append_sender_turn(ctx: &ChannelRuntimeContext, sender_key: &str, turn: ChatM
     }
 }
 
+/// Write the sender's current in-memory history through to the session store.
+fn persist_sender_history(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage) {
+    let Some(store) = ctx.session_store.as_ref() else {
+        return;
+    };
+    let sender_key = conversation_history_key(msg);
+    let turns = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&sender_key)
+        .cloned()
+        .unwrap_or_default();
+    let mut removed = match store.save(&sender_key, &msg.channel, &msg.sender, &turns) {
+        Ok(evicted) => evicted,
+        Err(err) => {
+            tracing::warn!("Failed to persist channel session {sender_key}: {err:#}");
+            Vec::new()
+        }
+    };
+
+    // Enforce the TTL for long-running daemons, not just at startup.
+    match store.prune_if_due() {
+        Ok(expired) => removed.extend(expired),
+        Err(err) => tracing::warn!("Failed to prune channel sessions: {err:#}"),
+    }
+
+    // Drop cached copies too, or the next save writes them back.
+    if !removed.is_empty() {
+        let mut histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        for key in &removed {
+            histories.remove(key);
+        }
+        tracing::info!("Pruned {} channel session(s)", removed.len());
+    }
+}
+
 fn should_skip_memory_context_entry(key: &str, content: &str) -> bool {
     if memory::is_assistant_autosave_key(key) {
         return true;
@@ -826,6 +1252,7 @@ async fn handle_runtime_command_if_needed(
                             current.provider = provider_name.clone();
                             set_route_selection(ctx, &sender_key, current.clone());
                             clear_sender_history(ctx, &sender_key);
+                            persist_sender_history(ctx, msg);
                         }
 
                         format!(
@@ -856,6 +1283,7 @@ async fn handle_runtime_command_if_needed(
                 current.model = model.clone();
                 set_route_selection(ctx, &sender_key, current.clone());
                 clear_sender_history(ctx, &sender_key);
+                persist_sender_history(ctx, msg);
 
                 format!(
                     "Model switched to `{model}` for provider `{}` in this sender session.",
@@ -863,6 +1291,27 @@ async fn handle_runtime_command_if_needed(
                 )
             }
         }
+        ChannelRuntimeCommand::ShowVoice | ChannelRuntimeCommand::SetVoice(_)
+            if ctx.synthesizer.is_none() =>
+        {
+            "Voice replies are not configured. Enable `[multimodal.tts]` in config.toml."
+                .to_string()
+        }
+        ChannelRuntimeCommand::ShowVoice => {
+            let state = if voice_replies_enabled(ctx, msg) {
+                "on"
+            } else {
+                "off"
+            };
+            format!(
+                "Voice replies are {state} for this sender session.\nUse `/voice on` or `/voice off` to change."
+            )
+        }
+        ChannelRuntimeCommand::SetVoice(enabled) => {
+            set_voice_replies(ctx, msg, enabled);
+            let state = if enabled { "on" } else { "off" };
+            format!("Voice replies turned {state} for this sender session.")
+        }
     };
 
     if let Err(err) = channel
@@ -1311,7 +1760,7 @@ fn spawn_scoped_typing_task(
 
 async fn process_channel_message(
     ctx: Arc<ChannelRuntimeContext>,
-    msg: traits::ChannelMessage,
+    mut msg: traits::ChannelMessage,
     cancellation_token: CancellationToken,
 ) {
     if cancellation_token.is_cancelled() {
@@ -1355,6 +1804,17 @@ async fn process_channel_message(
             return;
         }
     };
+    if !msg.attachments.is_empty() {
+        msg.content = content_with_inbound_attachments(
+            &msg,
+            ctx.workspace_dir.as_path(),
+            ctx.tools_registry.as_slice(),
+            active_provider.supports_vision(),
+            &ctx.multimodal,
+            ctx.transcriber.as_deref(),
+        )
+        .await;
+    }
     if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
         let autosave_key = conversation_memory_key(&msg);
         let _ = ctx
@@ -1380,6 +1840,7 @@ async fn process_channel_message(
 
     // Preserve user turn before the LLM call so interrupted requests keep context.
     append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&msg.content));
+    persist_sender_history(ctx.as_ref(), &msg);
 
     // Build history from per-sender conversation cache.
     let prior_turns_raw = ctx
@@ -1446,14 +1907,29 @@ async fn process_channel_message(
         let channel = Arc::clone(channel_ref);
         let reply_target = msg.reply_target.clone();
         let draft_id = draft_id_ref.to_string();
+        let show_plan = ctx.show_plan_in_drafts;
         Some(tokio::spawn(async move {
             let mut accumulated = String::new();
+            let mut plan_checklist = String::new();
             while let Some(delta) = rx.recv().await {
-                accumulated.push_str(&delta);
-                if let Err(e) = channel
-                    .update_draft(&reply_target, &draft_id, &accumulated)
-                    .await
-                {
+                if delta == DRAFT_CLEAR_SENTINEL {
+                    accumulated.clear();
+                    continue;
+                }
+                if let Some(checklist) = delta.strip_prefix(DRAFT_PLAN_SENTINEL) {
+                    if !show_plan {
+                        continue;
+                    }
+                    plan_checklist = checklist.to_string();
+                } else {
+                    accumulated.push_str(&delta);
+                }
+                let draft = match (plan_checklist.is_empty(), accumulated.is_empty()) {
+                    (true, _) => accumulated.clone(),
+                    (false, true) => plan_checklist.clone(),
+                    (false, false) => format!("{plan_checklist}\n\n{accumulated}"),
+                };
+                if let Err(e) = channel.update_draft(&reply_target, &draft_id, &draft).await {
                     tracing::debug!("Draft update failed: {e}");
                 }
             }
@@ -1475,40 +1951,58 @@ async fn process_channel_message(
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
 
+    let approval_notifier = target_channel.as_ref().map(|channel| {
+        Arc::new(ChannelApprovalNotifier {
+            channel: Arc::clone(channel),
+            reply_target: msg.reply_target.clone(),
+            thread_ts: msg.thread_ts.clone(),
+            requester: history_key.clone(),
+        }) as Arc<dyn ApprovalNotifier>
+    });
+
     enum LlmExecutionResult {
-        Completed(Result<Result<String, anyhow::Error>, tokio::time::error::Elapsed>),
+        Completed(Box<Result<Result<ToolLoopOutput, anyhow::Error>, tokio::time::error::Elapsed>>),
         Cancelled,
     }
 
+    let plan_dir = Some(tools::plan::plan_dir(&ctx.workspace_dir));
     let timeout_budget_secs =
         channel_message_timeout_budget_secs(ctx.message_timeout_secs, ctx.max_tool_iterations);
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop_with_policy(
-                active_provider.as_ref(),
-                &mut history,
-                ctx.tools_registry.as_ref(),
-                ctx.observer.as_ref(),
-                route.provider.as_str(),
-                route.model.as_str(),
-                runtime_defaults.temperature,
-                true,
-                None,
-                msg.channel.as_str(),
-                &ctx.multimodal,
-                ctx.max_tool_iterations,
-                true,
-                6,
-                ctx.max_tool_iterations,
-                2,
-                3,
-                2,
-                Some(cancellation_token.clone()),
-                delta_tx,
+            TOOL_LOOP_COST_TRACKER.scope(
+                ctx.cost_tracker.clone(),
+                APPROVAL_NOTIFIER.scope(
+                    approval_notifier,
+                    // Per-conversation session so plans and background
+                    // processes stay with the conversation that made them.
+                    CURRENT_SESSION_ID.scope(history_key.clone(), TOOL_LOOP_PLAN_DIR.scope(plan_dir, run_tool_call_loop_with_trace_and_policy(
+                        active_provider.as_ref(),
+                        &mut history,
+                        ctx.tools_registry.as_ref(),
+                        ctx.observer.as_ref(),
+                        route.provider.as_str(),
+                        route.model.as_str(),
+                        runtime_defaults.temperature,
+                        true,
+                        ctx.approval_manager.as_deref(),
+                        msg.channel.as_str(),
+                        &ctx.multimodal,
+                        ctx.max_tool_iterations,
+                        true,
+                        6,
+                        ctx.max_tool_iterations,
+                        2,
+                        3,
+                        2,
+                        Some(cancellation_token.clone()),
+                        delta_tx,
+                    ))),
+                ),
             ),
-        ) => LlmExecutionResult::Completed(result),
+        ) => LlmExecutionResult::Completed(Box::new(result)),
     };
 
     if let Some(handle) = draft_updater {
@@ -1537,92 +2031,181 @@ async fn process_channel_message(
                 }
             }
         }
-        LlmExecutionResult::Completed(Ok(Ok(response))) => {
-            let sanitized_response =
-                sanitize_channel_response(&response, ctx.tools_registry.as_ref());
-            let delivered_response = if sanitized_response.is_empty() && !response.trim().is_empty()
-            {
-                "I encountered malformed tool-call output and could not produce a safe reply. Please try again.".to_string()
-            } else {
-                sanitized_response
-            };
+        LlmExecutionResult::Completed(result) => match *result {
+            Ok(Ok(ToolLoopOutput {
+                response,
+                attachments,
+                ..
+            })) => {
+                let sanitized_response =
+                    sanitize_channel_response(&response, ctx.tools_registry.as_ref());
+                let delivered_response = if sanitized_response.is_empty()
+                    && !response.trim().is_empty()
+                {
+                    "I encountered malformed tool-call output and could not produce a safe reply. Please try again.".to_string()
+                } else {
+                    sanitized_response
+                };
 
-            // Extract condensed tool-use context from the history messages
-            // added during run_tool_call_loop, so the LLM retains awareness
-            // of what it did on subsequent turns.
-            let tool_summary = extract_tool_context_summary(&history, history_len_before_tools);
-            let history_response = if tool_summary.is_empty() {
-                delivered_response.clone()
-            } else {
-                format!("{tool_summary}\n{delivered_response}")
-            };
+                // Extract condensed tool-use context from the history messages
+                // added during run_tool_call_loop, so the LLM retains awareness
+                // of what it did on subsequent turns.
+                let tool_summary = extract_tool_context_summary(&history, history_len_before_tools);
+                let history_response = if tool_summary.is_empty() {
+                    delivered_response.clone()
+                } else {
+                    format!("{tool_summary}\n{delivered_response}")
+                };
 
-            append_sender_turn(
-                ctx.as_ref(),
-                &history_key,
-                ChatMessage::assistant(&history_response),
-            );
-            println!(
-                "  🤖 Reply ({}ms): {}",
-                started_at.elapsed().as_millis(),
-                truncate_with_ellipsis(&delivered_response, 80)
-            );
-            if let Some(channel) = target_channel.as_ref() {
-                if let Some(ref draft_id) = draft_message_id {
-                    if let Err(e) = channel
-                        .finalize_draft(&msg.reply_target, draft_id, &delivered_response)
+                append_sender_turn(
+                    ctx.as_ref(),
+                    &history_key,
+                    ChatMessage::assistant(&history_response),
+                );
+                persist_sender_history(ctx.as_ref(), &msg);
+                println!(
+                    "  🤖 Reply ({}ms): {}",
+                    started_at.elapsed().as_millis(),
+                    truncate_with_ellipsis(&delivered_response, 80)
+                );
+                if let Some(channel) = target_channel.as_ref() {
+                    let outbound =
+                        stage_outbound_attachments(&attachments, ctx.workspace_dir.as_path())
+                            .await;
+                    if let Some(ref draft_id) = draft_message_id {
+                        if let Err(e) = channel
+                            .finalize_draft(&msg.reply_target, draft_id, &delivered_response)
+                            .await
+                        {
+                            tracing::warn!("Failed to finalize draft: {e}; sending as new message");
+                            let _ = channel
+                                .send(
+                                    &SendMessage::new(&delivered_response, &msg.reply_target)
+                                        .in_thread(msg.thread_ts.clone()),
+                                )
+                                .await;
+                        }
+                        // The draft already carries the text; files follow on their own.
+                        if !outbound.is_empty() {
+                            let files = with_outbound_attachments(
+                                channel.as_ref(),
+                                SendMessage::new("", &msg.reply_target)
+                                    .in_thread(msg.thread_ts.clone()),
+                                outbound,
+                            );
+                            if let Err(e) = channel.send(&files).await {
+                                tracing::warn!(
+                                    "Failed to deliver attachments on {}: {e}",
+                                    channel.name()
+                                );
+                            }
+                        }
+                    } else if let Err(e) = channel
+                        .send(&with_outbound_attachments(
+                            channel.as_ref(),
+                            SendMessage::new(&delivered_response, &msg.reply_target)
+                                .in_thread(msg.thread_ts.clone()),
+                            outbound,
+                        ))
                         .await
                     {
-                        tracing::warn!("Failed to finalize draft: {e}; sending as new message");
+                        eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                    }
+
+                    if voice_replies_enabled(ctx.as_ref(), &msg) {
+                        send_voice_reply(ctx.as_ref(), channel.as_ref(), &msg, &delivered_response)
+                            .await;
+                    }
+                }
+            }
+            Ok(Err(e)) => {
+                if crate::agent::loop_::is_tool_loop_cancelled(&e)
+                    || cancellation_token.is_cancelled()
+                {
+                    tracing::info!(
+                        channel = %msg.channel,
+                        sender = %msg.sender,
+                        "Cancelled in-flight channel request due to newer message"
+                    );
+                    if let (Some(channel), Some(draft_id)) =
+                        (target_channel.as_ref(), draft_message_id.as_deref())
+                    {
+                        if let Err(err) = channel.cancel_draft(&msg.reply_target, draft_id).await {
+                            tracing::debug!("Failed to cancel draft on {}: {err}", channel.name());
+                        }
+                    }
+                    return;
+                }
+
+                if is_context_window_overflow_error(&e) {
+                    let compacted = compact_sender_history(ctx.as_ref(), &history_key);
+                    persist_sender_history(ctx.as_ref(), &msg);
+                    if compacted {
+                        if let Some(store) = ctx.session_store.as_ref() {
+                            if let Err(err) = store.record_compaction(&history_key) {
+                                tracing::warn!("Failed to record session compaction: {err:#}");
+                            }
+                        }
+                    }
+                    let error_text = if compacted {
+                        "⚠️ Context window exceeded for this conversation. I compacted recent history and kept the latest context. Please resend your last message."
+                    } else {
+                        "⚠️ Context window exceeded for this conversation. Please resend your last message."
+                    };
+                    eprintln!(
+                        "  ⚠️ Context window exceeded after {}ms; sender history compacted={}",
+                        started_at.elapsed().as_millis(),
+                        compacted
+                    );
+                    if let Some(channel) = target_channel.as_ref() {
+                        if let Some(ref draft_id) = draft_message_id {
+                            let _ = channel
+                                .finalize_draft(&msg.reply_target, draft_id, error_text)
+                                .await;
+                        } else {
+                            let _ = channel
+                                .send(
+                                    &SendMessage::new(error_text, &msg.reply_target)
+                                        .in_thread(msg.thread_ts.clone()),
+                                )
+                                .await;
+                        }
+                    }
+                    return;
+                }
+
+                eprintln!(
+                    "  ❌ LLM error after {}ms: {e}",
+                    started_at.elapsed().as_millis()
+                );
+                if let Some(channel) = target_channel.as_ref() {
+                    if let Some(ref draft_id) = draft_message_id {
+                        let _ = channel
+                            .finalize_draft(&msg.reply_target, draft_id, &format!("⚠️ Error: {e}"))
+                            .await;
+                    } else {
                         let _ = channel
                             .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
+                                &SendMessage::new(format!("⚠️ Error: {e}"), &msg.reply_target)
                                     .in_thread(msg.thread_ts.clone()),
                             )
                             .await;
                     }
-                } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await
-                {
-                    eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
                 }
             }
-        }
-        LlmExecutionResult::Completed(Ok(Err(e))) => {
-            if crate::agent::loop_::is_tool_loop_cancelled(&e) || cancellation_token.is_cancelled()
-            {
-                tracing::info!(
-                    channel = %msg.channel,
-                    sender = %msg.sender,
-                    "Cancelled in-flight channel request due to newer message"
+            Err(_) => {
+                let timeout_msg = format!(
+                    "LLM response timed out after {}s (base={}s, max_tool_iterations={})",
+                    timeout_budget_secs, ctx.message_timeout_secs, ctx.max_tool_iterations
                 );
-                if let (Some(channel), Some(draft_id)) =
-                    (target_channel.as_ref(), draft_message_id.as_deref())
-                {
-                    if let Err(err) = channel.cancel_draft(&msg.reply_target, draft_id).await {
-                        tracing::debug!("Failed to cancel draft on {}: {err}", channel.name());
-                    }
-                }
-                return;
-            }
-
-            if is_context_window_overflow_error(&e) {
-                let compacted = compact_sender_history(ctx.as_ref(), &history_key);
-                let error_text = if compacted {
-                    "⚠️ Context window exceeded for this conversation. I compacted recent history and kept the latest context. Please resend your last message."
-                } else {
-                    "⚠️ Context window exceeded for this conversation. Please resend your last message."
-                };
                 eprintln!(
-                    "  ⚠️ Context window exceeded after {}ms; sender history compacted={}",
-                    started_at.elapsed().as_millis(),
-                    compacted
+                    "  ❌ {} (elapsed: {}ms)",
+                    timeout_msg,
+                    started_at.elapsed().as_millis()
                 );
                 if let Some(channel) = target_channel.as_ref() {
+                    let error_text =
+                        "⚠️ Request timed out while waiting for the model. Please try again.";
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
                             .finalize_draft(&msg.reply_target, draft_id, error_text)
@@ -1636,54 +2219,66 @@ async fn process_channel_message(
                             .await;
                     }
                 }
-                return;
             }
+        },
+    }
+}
 
-            eprintln!(
-                "  ❌ LLM error after {}ms: {e}",
-                started_at.elapsed().as_millis()
-            );
-            if let Some(channel) = target_channel.as_ref() {
-                if let Some(ref draft_id) = draft_message_id {
-                    let _ = channel
-                        .finalize_draft(&msg.reply_target, draft_id, &format!("⚠️ Error: {e}"))
-                        .await;
-                } else {
-                    let _ = channel
-                        .send(
-                            &SendMessage::new(format!("⚠️ Error: {e}"), &msg.reply_target)
-                                .in_thread(msg.thread_ts.clone()),
-                        )
-                        .await;
-                }
-            }
-        }
-        LlmExecutionResult::Completed(Err(_)) => {
-            let timeout_msg = format!(
-                "LLM response timed out after {}s (base={}s, max_tool_iterations={})",
-                timeout_budget_secs, ctx.message_timeout_secs, ctx.max_tool_iterations
-            );
-            eprintln!(
-                "  ❌ {} (elapsed: {}ms)",
-                timeout_msg,
-                started_at.elapsed().as_millis()
+/// Announces pending tool approvals in the conversation that triggered them.
+struct ChannelApprovalNotifier {
+    channel: Arc<dyn Channel>,
+    reply_target: String,
+    thread_ts: Option<String>,
+    requester: String,
+}
+
+#[async_trait]
+impl ApprovalNotifier for ChannelApprovalNotifier {
+    fn requester(&self) -> Option<String> {
+        Some(self.requester.clone())
+    }
+
+    async fn notify(&self, pending: &PendingApproval) -> anyhow::Result<()> {
+        let message = SendMessage::new(
+            approval::format_approval_prompt(pending),
+            &self.reply_target,
+        )
+        .in_thread(self.thread_ts.clone());
+        self.channel.send_approval_prompt(&message, pending).await
+    }
+}
+
+/// Resolve a pending approval from a chat reply (or button press) and acknowledge it.
+async fn handle_approval_reply(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    id: &str,
+    decision: ApprovalResponse,
+) {
+    let resolved_by = format!("{}:{}", msg.channel, msg.sender);
+    let requester = conversation_history_key(msg);
+    let response =
+        match approval::resolve_pending_approval(id, decision, &resolved_by, Some(&requester)) {
+            Ok(pending) => match decision {
+                ApprovalResponse::Yes => format!("✅ Approved `{}`.", pending.tool_name),
+                ApprovalResponse::Always => format!(
+                    "✅ Approved `{}` for the rest of this session.",
+                    pending.tool_name
+                ),
+                ApprovalResponse::No => format!("🚫 Denied `{}`.", pending.tool_name),
+            },
+            Err(err) => format!("⚠️ {err}"),
+        };
+
+    if let Some(channel) = ctx.channels_by_name.get(&msg.channel) {
+        if let Err(err) = channel
+            .send(&SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+            .await
+        {
+            tracing::warn!(
+                "Failed to acknowledge approval reply on {}: {err}",
+                msg.channel
             );
-            if let Some(channel) = target_channel.as_ref() {
-                let error_text =
-                    "⚠️ Request timed out while waiting for the model. Please try again.";
-                if let Some(ref draft_id) = draft_message_id {
-                    let _ = channel
-                        .finalize_draft(&msg.reply_target, draft_id, error_text)
-                        .await;
-                } else {
-                    let _ = channel
-                        .send(
-                            &SendMessage::new(error_text, &msg.reply_target)
-                                .in_thread(msg.thread_ts.clone()),
-                        )
-                        .await;
-                }
-            }
         }
     }
 }
@@ -1702,6 +2297,18 @@ async fn run_message_dispatch_loop(
     let task_sequence = Arc::new(AtomicU64::new(1));
 
     while let Some(msg) = rx.recv().await {
+        // Approval replies bypass the in-flight limit: the tool loops holding
+        // those slots are the ones waiting for this answer.
+        if ctx.approval_manager.is_some() {
+            if let Some((id, decision)) = approval::parse_approval_reply(&msg.content) {
+                let reply_ctx = Arc::clone(&ctx);
+                workers.spawn(async move {
+                    handle_approval_reply(reply_ctx.as_ref(), &msg, &id, decision).await;
+                });
+                continue;
+            }
+        }
+
         let permit = match Arc::clone(&semaphore).acquire_owned().await {
             Ok(permit) => permit,
             Err(_) => break,
@@ -2538,7 +3145,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
     };
-    let provider: Arc<dyn Provider> = Arc::from(
+    let observer: Arc<dyn Observer> =
+        Arc::from(observability::create_observer(&config.observability));
+    let provider: Arc<dyn Provider> = Arc::from(providers::with_response_cache(
         create_resilient_provider_nonblocking(
             &provider_name,
             config.api_key.clone(),
@@ -2547,7 +3156,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
             provider_runtime_options.clone(),
         )
         .await?,
-    );
+        &config,
+        Some(observer.clone()),
+    ));
 
     // Warm up the provider connection pool (TLS handshake, DNS, HTTP/2 setup)
     // so the first real message doesn't hit a cold-start timeout.
@@ -2569,8 +3180,6 @@ pub async fn start_channels(config: Config) -> Result<()> {
         );
     }
 
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -2618,6 +3227,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "shell",
             "Execute terminal commands. Use when: running local checks, build/test commands, diagnostics. Don't use when: a safer dedicated tool exists, or command is destructive without approval.",
         ),
+        (
+            "process",
+            "Run long-lived commands in the background and manage them (start/list/read/send/kill). Use when: starting dev servers, watchers, or builds you need to check on later. Don't use when: a command finishes quickly (use shell).",
+        ),
         (
             "file_read",
             "Read file contents. Use when: inspecting project files, configs, logs. Don't use when: a targeted search is enough.",
@@ -2626,6 +3239,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "file_edit",
+            "Edit an existing file in place via exact-string replacement or a unified diff, with optional dry-run preview. Use when: changing a few lines of a larger file. Don't use when: creating a new file or rewriting it wholesale (use file_write).",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -2723,11 +3340,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
 
     if let Some(ref sl) = config.channels_config.slack {
-        channels.push(Arc::new(SlackChannel::new(
-            sl.bot_token.clone(),
-            sl.channel_id.clone(),
-            sl.allowed_users.clone(),
-        )));
+        channels.push(Arc::new(
+            SlackChannel::new(
+                sl.bot_token.clone(),
+                sl.channel_id.clone(),
+                sl.allowed_users.clone(),
+            )
+            .with_interactive_approvals(sl.signing_secret.is_some()),
+        ));
     }
 
     if let Some(ref mm) = config.channels_config.mattermost {
@@ -2957,6 +3577,47 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .as_ref()
         .is_some_and(|tg| tg.interrupt_on_new_message);
 
+    let session_store = if config.channels_config.sessions.persist {
+        match ChannelSessionStore::open(&config.workspace_dir, &config.channels_config.sessions) {
+            Ok(store) => Some(Arc::new(store)),
+            Err(err) => {
+                tracing::warn!("Channel session persistence disabled: {err:#}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let restored_histories = match session_store.as_ref().map(|store| store.load_active()) {
+        Some(Ok(histories)) => {
+            if !histories.is_empty() {
+                println!("  💾 Restored {} channel session(s)", histories.len());
+            }
+            histories
+        }
+        Some(Err(err)) => {
+            tracing::warn!("Failed to restore channel sessions: {err:#}");
+            HashMap::new()
+        }
+        None => HashMap::new(),
+    };
+
+    let approval_manager = if config.autonomy.approval_transport == ApprovalTransport::Channel {
+        let mut manager = ApprovalManager::from_config(&config.autonomy);
+        if let Some(dir) = config.config_path.parent() {
+            match crate::security::AuditLogger::new(
+                config.security.audit.clone(),
+                dir.to_path_buf(),
+            ) {
+                Ok(logger) => manager = manager.with_audit_logger(Arc::new(logger)),
+                Err(err) => tracing::warn!("Approval audit logging disabled: {err:#}"),
+            }
+        }
+        Some(Arc::new(manager))
+    } else {
+        None
+    };
+
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
@@ -2970,7 +3631,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         auto_save_memory: config.memory.auto_save,
         max_tool_iterations: config.agent.max_tool_iterations,
         min_relevance_score: config.memory.min_relevance_score,
-        conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+        conversation_histories: Arc::new(Mutex::new(restored_histories)),
         provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
         route_overrides: Arc::new(Mutex::new(HashMap::new())),
         api_key: config.api_key.clone(),
@@ -2981,6 +3642,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
         message_timeout_secs,
         interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
+        cost_tracker: create_cost_tracker(&config),
+        session_store,
+        approval_manager,
+        show_plan_in_drafts: config.agent.show_plan_in_drafts,
+        transcriber: crate::transcription::create_transcriber(&config.multimodal.transcription)?,
+        synthesizer: crate::tts::create_synthesizer(&config.multimodal.tts)?,
+        voice_overrides: Arc::new(Mutex::new(HashMap::new())),
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
@@ -3039,6 +3707,149 @@ mod tests {
         assert_eq!(effective_channel_message_timeout_secs(300), 300);
     }
 
+    #[tokio::test]
+    async fn outbound_attachments_stage_images_and_keep_files_inside_workspace() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("report.log"), "ok").unwrap();
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "no").unwrap();
+        let attachments = vec![
+            ToolContent::image("image/png", b"\x89PNG"),
+            ToolContent::image("image/svg+xml", b"<svg/>"),
+            ToolContent::image("image/x-unknown", b"??"),
+            ToolContent::File {
+                path: "report.log".into(),
+                mime_type: None,
+            },
+            ToolContent::File {
+                path: outside.path().join("secret.txt").display().to_string(),
+                mime_type: None,
+            },
+        ];
+
+        let staged = stage_outbound_attachments(&attachments, tmp.path()).await;
+        assert_eq!(staged.len(), 4);
+        assert_eq!(staged[0].mime_type, "image/png");
+        assert_eq!(std::fs::read(&staged[0].path).unwrap(), b"\x89PNG");
+        assert!(staged[0].file_name.ends_with(".png"));
+        assert!(staged[1].file_name.ends_with(".svg"));
+        assert!(staged[2].file_name.ends_with(".bin"));
+        assert_eq!(staged[3].file_name, "report.log");
+        assert_eq!(staged[3].mime_type, "text/plain");
+    }
+
+    #[test]
+    fn outbound_attachments_fall_back_to_paths_without_upload_support() {
+        let files = vec![traits::OutboundAttachment::from_path("/ws/chart.png")];
+
+        let plain = with_outbound_attachments(
+            &RecordingChannel::default(),
+            SendMessage::new("Here you go", "chat-1"),
+            files.clone(),
+        );
+        assert!(plain.attachments.is_empty());
+        assert_eq!(plain.content, "Here you go\n\nFiles:\n- /ws/chart.png");
+
+        let uploaded = with_outbound_attachments(
+            &TelegramRecordingChannel::default(),
+            SendMessage::new("Here you go", "chat-1"),
+            files,
+        );
+        assert_eq!(uploaded.content, "Here you go");
+        assert_eq!(uploaded.attachments.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn inbound_attachments_become_image_markers_and_notes() {
+        let tmp = TempDir::new().unwrap();
+        let msg = traits::ChannelMessage {
+            id: "msg-1".into(),
+            sender: "alice".into(),
+            reply_target: "chat-1".into(),
+            content: "what is this?".into(),
+            channel: "discord".into(),
+            timestamp: 1,
+            thread_ts: None,
+            attachments: vec![
+                traits::ChannelAttachment::inline("image/png", b"\x89PNG".to_vec()),
+                traits::ChannelAttachment::inline("application/pdf", b"%PDF-1.4".to_vec())
+                    .with_file_name(Some("q3.pdf".into())),
+                traits::ChannelAttachment::inline("audio/ogg", b"OggS".to_vec()),
+            ],
+        };
+        let multimodal = crate::config::MultimodalConfig::default();
+
+        let content =
+            content_with_inbound_attachments(&msg, tmp.path(), &[], true, &multimodal, None).await;
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[0], "what is this?");
+        let image = lines[1]
+            .strip_prefix("[IMAGE:")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .unwrap();
+        assert_eq!(std::fs::read(image).unwrap(), b"\x89PNG");
+        assert!(lines[2].starts_with("[Document q3.pdf saved to attachments/inbound/"));
+        assert!(lines[3].starts_with("[Attachment audio/ogg saved to attachments/inbound/"));
+
+        let without_vision =
+            content_with_inbound_attachments(&msg, tmp.path(), &[], false, &multimodal, None).await;
+        assert!(!without_vision.contains("[IMAGE:"));
+    }
+
+    #[tokio::test]
+    async fn inbound_attachment_retention_drops_expired_files() {
+        let tmp = TempDir::new().unwrap();
+        let stale = tmp.path().join("stale.png");
+        let fresh = tmp.path().join("fresh.png");
+        std::fs::write(&stale, b"old").unwrap();
+        std::fs::write(&fresh, b"new").unwrap();
+        let now = SystemTime::now();
+        std::fs::File::options()
+            .write(true)
+            .open(&stale)
+            .unwrap()
+            .set_modified(now - INBOUND_ATTACHMENT_RETENTION - Duration::from_secs(60))
+            .unwrap();
+
+        prune_inbound_attachments(tmp.path(), now).await;
+
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+    }
+
+    #[tokio::test]
+    async fn voice_messages_are_transcribed_into_content() {
+        let tmp = TempDir::new().unwrap();
+        let msg = traits::ChannelMessage {
+            id: "msg-1".into(),
+            sender: "alice".into(),
+            reply_target: "chat-1".into(),
+            content: String::new(),
+            channel: "telegram".into(),
+            timestamp: 1,
+            thread_ts: None,
+            attachments: vec![traits::ChannelAttachment::inline(
+                "audio/ogg",
+                b"OggS".to_vec(),
+            )],
+        };
+        let multimodal = crate::config::MultimodalConfig::default();
+
+        let content = content_with_inbound_attachments(
+            &msg,
+            tmp.path(),
+            &[],
+            false,
+            &multimodal,
+            Some(&crate::transcription::StubTranscriber),
+        )
+        .await;
+        let lines: Vec<&str> = content.lines().collect();
+        assert!(lines[0].starts_with("stub transcript of "));
+        assert!(lines[0].ends_with(".ogg"));
+        assert!(lines[1].starts_with("[Attachment audio/ogg saved to attachments/inbound/"));
+    }
+
     #[test]
     fn channel_message_timeout_budget_scales_with_tool_iterations() {
         assert_eq!(channel_message_timeout_budget_secs(300, 1), 300);
@@ -3156,6 +3967,13 @@ mod tests {
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3201,6 +4019,7 @@ mod tests {
     #[derive(Default)]
     struct TelegramRecordingChannel {
         sent_messages: tokio::sync::Mutex<Vec<String>>,
+        sent_attachment_mimes: tokio::sync::Mutex<Vec<String>>,
     }
 
     #[async_trait::async_trait]
@@ -3209,11 +4028,19 @@ mod tests {
             "telegram"
         }
 
+        fn supports_attachments(&self) -> bool {
+            true
+        }
+
         async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
             self.sent_messages
                 .lock()
                 .await
                 .push(format!("{}:{}", message.recipient, message.content));
+            self.sent_attachment_mimes
+                .lock()
+                .await
+                .extend(message.attachments.iter().map(|a| a.mime_type.clone()));
             Ok(())
         }
 
@@ -3560,6 +4387,7 @@ BTC is currently around $65,000 based on latest tool output."#
                     success: false,
                     output: String::new(),
                     error: Some("unexpected symbol".to_string()),
+                    parts: Vec::new(),
                 });
             }
 
@@ -3567,6 +4395,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 success: true,
                 output: r#"{"symbol":"BTC","price_usd":65000}"#.to_string(),
                 error: None,
+                parts: Vec::new(),
             })
         }
     }
@@ -3603,6 +4432,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -3615,6 +4451,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -3660,6 +4497,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -3672,6 +4516,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -3717,6 +4562,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -3729,6 +4581,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -3783,6 +4636,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -3795,6 +4655,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -3819,6 +4680,96 @@ BTC is currently around $65,000 based on latest tool output."#
         assert_eq!(fallback_provider_impl.call_count.load(Ordering::SeqCst), 0);
     }
 
+    #[test]
+    fn parse_runtime_command_handles_voice_toggle_on_voice_channels() {
+        assert_eq!(
+            parse_runtime_command("telegram", "/voice on"),
+            Some(ChannelRuntimeCommand::SetVoice(true))
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/voice OFF"),
+            Some(ChannelRuntimeCommand::SetVoice(false))
+        );
+        assert_eq!(
+            parse_runtime_command("matrix", "/voice"),
+            Some(ChannelRuntimeCommand::ShowVoice)
+        );
+        assert_eq!(parse_runtime_command("slack", "/model gpt-4o"), None);
+        assert_eq!(parse_runtime_command("whatsapp", "/voice on"), None);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_sends_voice_reply_after_voice_on() {
+        let tmp = TempDir::new().unwrap();
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider: Arc<dyn Provider> = Arc::new(ModelCaptureProvider::default());
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(tmp.path().to_path_buf()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: Some(Arc::new(crate::tts::StubSpeech)),
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
+        });
+
+        for (id, content) in [("msg-1", "/voice on"), ("msg-2", "hello")] {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: "alice".to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: content.to_string(),
+                    channel: "telegram".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                    attachments: Vec::new(),
+                },
+                CancellationToken::new(),
+            )
+            .await;
+        }
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 3);
+        assert!(sent[0].contains("Voice replies turned on"));
+        assert_eq!(sent[1], "chat-1:ok");
+        assert_eq!(sent[2], "chat-1:");
+        assert_eq!(
+            *channel_impl.sent_attachment_mimes.lock().await,
+            vec!["audio/wav".to_string()]
+        );
+    }
+
     #[tokio::test]
     async fn process_channel_message_uses_route_override_provider_and_model() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -3870,6 +4821,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -3882,6 +4840,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -3939,6 +4898,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -3951,6 +4917,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4023,6 +4990,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -4035,6 +5009,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 4,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4092,6 +5067,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -4104,6 +5086,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4150,6 +5133,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -4162,6 +5152,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4319,6 +5310,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -4330,6 +5328,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         })
         .await
         .unwrap();
@@ -4341,6 +5340,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         })
         .await
         .unwrap();
@@ -4397,6 +5397,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: true,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -4409,6 +5416,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -4421,6 +5429,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -4487,6 +5496,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: true,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -4499,6 +5515,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -4511,6 +5528,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -4559,6 +5577,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -4571,6 +5596,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -4935,6 +5961,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
@@ -4950,6 +5977,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -4959,6 +5987,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         assert_ne!(
@@ -4980,6 +6009,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -4989,6 +6019,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         mem.store(
@@ -5061,6 +6092,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -5073,6 +6111,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5088,6 +6127,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5144,6 +6184,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -5156,6 +6203,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5184,6 +6232,89 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!turns[0].content.contains("[Memory context]"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_drops_evicted_sessions_from_memory() {
+        let workspace = TempDir::new().unwrap();
+        let store = ChannelSessionStore::open(
+            workspace.path(),
+            &crate::config::ChannelSessionsConfig {
+                persist: true,
+                ttl_hours: 0,
+                max_sessions: 1,
+            },
+        )
+        .unwrap();
+
+        let channel: Arc<dyn Channel> = Arc::new(RecordingChannel::default());
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(HistoryCaptureProvider::default()),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(workspace.path().to_path_buf()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: Some(Arc::new(store)),
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
+        });
+
+        for (id, sender) in [("msg-1", "alice"), ("msg-2", "bob"), ("msg-3", "alice")] {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: sender.to_string(),
+                    reply_target: "chat".to_string(),
+                    content: "hello".to_string(),
+                    channel: "test-channel".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                    attachments: Vec::new(),
+                },
+                CancellationToken::new(),
+            )
+            .await;
+        }
+
+        // Alice was evicted when Bob arrived, so her return starts a fresh
+        // session instead of writing the evicted turns back.
+        let store = runtime_ctx.session_store.as_ref().unwrap();
+        let sessions = store.list().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].key, "test-channel_alice");
+        assert_eq!(sessions[0].turns.len(), 2);
+        let histories = runtime_ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert!(!histories.contains_key("test-channel_bob"));
+        assert_eq!(histories["test-channel_alice"].len(), 2);
+    }
+
     #[tokio::test]
     async fn process_channel_message_telegram_keeps_system_instruction_at_top_only() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -5227,6 +6358,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
+            cost_tracker: None,
+            session_store: None,
+            approval_manager: None,
+            show_plan_in_drafts: true,
+            transcriber: None,
+            synthesizer: None,
+            voice_overrides: Arc::new(Mutex::new(HashMap::new())),
         });
 
         process_channel_message(
@@ -5239,6 +6377,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -0,0 +1,220 @@
+use super::session_store::{ChannelSession, ChannelSessionStore};
+use crate::config::Config;
+use anyhow::{Context, Result};
+use console::style;
+use std::path::PathBuf;
+
+/// Handle `zeroclaw sessions <subcommand>` CLI commands.
+pub fn handle_command(command: crate::SessionCommands, config: &Config) -> Result<()> {
+    let store = ChannelSessionStore::open(&config.workspace_dir, &config.channels_config.sessions)?;
+    match command {
+        crate::SessionCommands::List { channel, limit } => handle_list(&store, channel, limit),
+        crate::SessionCommands::Show { key } => handle_show(&store, &key),
+        crate::SessionCommands::Clear { key, yes } => handle_clear(&store, key, yes),
+        crate::SessionCommands::Export { key, output } => handle_export(&store, key, output),
+    }
+}
+
+fn handle_list(store: &ChannelSessionStore, channel: Option<String>, limit: usize) -> Result<()> {
+    store.prune()?;
+    let sessions: Vec<ChannelSession> = store
+        .list()?
+        .into_iter()
+        .filter(|s| channel.as_deref().is_none_or(|c| s.channel == c))
+        .collect();
+
+    if sessions.is_empty() {
+        println!("No channel sessions found.");
+        return Ok(());
+    }
+
+    println!(
+        "Channel sessions ({} total, showing {}):\n",
+        sessions.len(),
+        sessions.len().min(limit)
+    );
+    for session in sessions.iter().take(limit) {
+        println!(
+            "- {} [{} turns, updated {}]",
+            style(&session.key).white().bold(),
+            session.turns.len(),
+            session.updated_at,
+        );
+        if session.compaction_count > 0 {
+            println!("    compacted {}×", session.compaction_count);
+        }
+    }
+    Ok(())
+}
+
+fn handle_show(store: &ChannelSessionStore, key: &str) -> Result<()> {
+    let Some(session) = store.get(key)? else {
+        println!("No channel session found for key: {key}");
+        return Ok(());
+    };
+
+    println!("Session:    {}", style(&session.key).white().bold());
+    println!("Channel:    {}", session.channel);
+    println!("Sender:     {}", session.sender);
+    println!("Created:    {}", session.created_at);
+    println!("Updated:    {}", session.updated_at);
+    println!(
+        "Compacted:  {}{}",
+        session.compaction_count,
+        session
+            .last_compacted_at
+            .as_deref()
+            .map(|at| format!(" (last {at})"))
+            .unwrap_or_default()
+    );
+    println!();
+    for turn in &session.turns {
+        println!("[{}] {}", style(&turn.role).cyan(), turn.content);
+    }
+    Ok(())
+}
+
+fn handle_clear(store: &ChannelSessionStore, key: Option<String>, yes: bool) -> Result<()> {
+    if let Some(key) = key {
+        if store.delete(&key)? {
+            println!("{} Cleared session: {key}", style("✓").green().bold());
+        } else {
+            println!("No channel session found for key: {key}");
+        }
+        return Ok(());
+    }
+
+    let total = store.list()?.len();
+    if total == 0 {
+        println!("No channel sessions to clear.");
+        return Ok(());
+    }
+
+    if !yes {
+        let confirmed = dialoguer::Confirm::new()
+            .with_prompt(format!("  Delete all {total} channel sessions?"))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let deleted = store.clear_all()?;
+    println!(
+        "{} Cleared {deleted} channel sessions.",
+        style("✓").green().bold()
+    );
+    Ok(())
+}
+
+fn handle_export(
+    store: &ChannelSessionStore,
+    key: Option<String>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let sessions = match key {
+        Some(key) => vec![store
+            .get(&key)?
+            .with_context(|| format!("No channel session found for key: {key}"))?],
+        None => store.list()?,
+    };
+    let json = serde_json::to_string_pretty(&sessions)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, json)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "{} Exported {} session(s) to {}",
+                style("✓").green().bold(),
+                sessions.len(),
+                path.display()
+            );
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ChannelSessionsConfig;
+    use crate::providers::ChatMessage;
+    use tempfile::TempDir;
+
+    fn seeded_store(tmp: &TempDir) -> ChannelSessionStore {
+        let store = ChannelSessionStore::open(
+            tmp.path(),
+            &ChannelSessionsConfig {
+                persist: true,
+                ttl_hours: 0,
+                max_sessions: 0,
+            },
+        )
+        .unwrap();
+        store
+            .save(
+                "telegram_alice",
+                "telegram",
+                "alice",
+                &[ChatMessage::user("hi"), ChatMessage::assistant("hello")],
+            )
+            .unwrap();
+        store
+            .save("slack_bob", "slack", "bob", &[ChatMessage::user("yo")])
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn list_and_show_handle_present_and_missing_sessions() {
+        let tmp = TempDir::new().unwrap();
+        let store = seeded_store(&tmp);
+        handle_list(&store, Some("slack".into()), 10).unwrap();
+        handle_list(&store, Some("irc".into()), 10).unwrap();
+        handle_show(&store, "telegram_alice").unwrap();
+        handle_show(&store, "missing").unwrap();
+    }
+
+    #[test]
+    fn clear_by_key_removes_only_that_session() {
+        let tmp = TempDir::new().unwrap();
+        let store = seeded_store(&tmp);
+        handle_clear(&store, Some("slack_bob".into()), false).unwrap();
+        assert!(store.get("slack_bob").unwrap().is_none());
+        assert!(store.get("telegram_alice").unwrap().is_some());
+    }
+
+    #[test]
+    fn clear_all_with_yes_skips_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let store = seeded_store(&tmp);
+        handle_clear(&store, None, true).unwrap();
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn export_writes_selected_or_all_sessions_as_json() {
+        let tmp = TempDir::new().unwrap();
+        let store = seeded_store(&tmp);
+
+        let one = tmp.path().join("one.json");
+        handle_export(&store, Some("telegram_alice".into()), Some(one.clone())).unwrap();
+        let exported: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&one).unwrap()).unwrap();
+        assert_eq!(exported.as_array().unwrap().len(), 1);
+        assert_eq!(exported[0]["sender"], "alice");
+        assert_eq!(exported[0]["turns"][1]["content"], "hello");
+
+        let all = tmp.path().join("all.json");
+        handle_export(&store, None, Some(all.clone())).unwrap();
+        let exported: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&all).unwrap()).unwrap();
+        assert_eq!(exported.as_array().unwrap().len(), 2);
+
+        assert!(handle_export(&store, Some("missing".into()), None).is_err());
+    }
+}
@@ -0,0 +1,415 @@
+//! Persistent per-sender channel conversation sessions.
+//!
+//! Histories live in the workspace `memory/brain.db` next to the memory
+//! tables so a daemon restart or upgrade does not wipe every chat thread's
+//! context. Rows are keyed by the same `<channel>_<sender>` key used for the
+//! in-memory history map.
+
+use crate::config::ChannelSessionsConfig;
+use crate::providers::ChatMessage;
+use anyhow::Context;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration as StdDuration, Instant};
+
+/// Minimum gap between TTL sweeps triggered from the write path.
+const PRUNE_INTERVAL: StdDuration = StdDuration::from_secs(10 * 60);
+
+/// One persisted sender session.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelSession {
+    pub key: String,
+    pub channel: String,
+    pub sender: String,
+    pub turns: Vec<ChatMessage>,
+    /// How many times the history was compacted after a context overflow.
+    pub compaction_count: u32,
+    pub last_compacted_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// SQLite-backed store for channel conversation histories.
+pub struct ChannelSessionStore {
+    conn: Mutex<Connection>,
+    ttl_hours: u64,
+    max_sessions: usize,
+    last_prune: Mutex<Option<Instant>>,
+}
+
+impl ChannelSessionStore {
+    pub fn open(workspace_dir: &Path, config: &ChannelSessionsConfig) -> anyhow::Result<Self> {
+        let db_path = workspace_dir.join("memory").join("brain.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA busy_timeout = 5000;
+             CREATE TABLE IF NOT EXISTS channel_sessions (
+                session_key       TEXT PRIMARY KEY,
+                channel           TEXT NOT NULL,
+                sender            TEXT NOT NULL,
+                turns_json        TEXT NOT NULL,
+                turn_count        INTEGER NOT NULL DEFAULT 0,
+                compaction_count  INTEGER NOT NULL DEFAULT 0,
+                last_compacted_at TEXT,
+                created_at        TEXT NOT NULL,
+                updated_at        TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_channel_sessions_updated
+                ON channel_sessions(updated_at);",
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            ttl_hours: config.ttl_hours,
+            max_sessions: config.max_sessions,
+            last_prune: Mutex::new(None),
+        })
+    }
+
+    /// Write the full history for a sender. An empty history removes the row.
+    /// Returns the keys evicted to stay under the session cap so callers can
+    /// drop cached copies.
+    pub fn save(
+        &self,
+        key: &str,
+        channel: &str,
+        sender: &str,
+        turns: &[ChatMessage],
+    ) -> anyhow::Result<Vec<String>> {
+        if turns.is_empty() {
+            self.delete(key)?;
+            return Ok(Vec::new());
+        }
+
+        let turns_json = serde_json::to_string(turns)?;
+        let now = now_timestamp();
+        let inserted = {
+            let conn = self.conn.lock();
+            let existed: bool = conn
+                .query_row(
+                    "SELECT 1 FROM channel_sessions WHERE session_key = ?1",
+                    params![key],
+                    |_| Ok(()),
+                )
+                .optional()?
+                .is_some();
+            conn.execute(
+                "INSERT INTO channel_sessions
+                    (session_key, channel, sender, turns_json, turn_count, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+                 ON CONFLICT(session_key) DO UPDATE SET
+                    turns_json = excluded.turns_json,
+                    turn_count = excluded.turn_count,
+                    updated_at = excluded.updated_at",
+                params![key, channel, sender, turns_json, turns.len() as i64, now],
+            )?;
+            !existed
+        };
+
+        // Only a new session can push the table over the cap.
+        if inserted {
+            return self.evict_over_cap();
+        }
+        Ok(Vec::new())
+    }
+
+    /// Record that a sender's history was compacted after a context overflow.
+    pub fn record_compaction(&self, key: &str) -> anyhow::Result<()> {
+        self.conn.lock().execute(
+            "UPDATE channel_sessions
+             SET compaction_count = compaction_count + 1, last_compacted_at = ?2
+             WHERE session_key = ?1",
+            params![key, now_timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> anyhow::Result<Option<ChannelSession>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT session_key, channel, sender, turns_json, compaction_count,
+                    last_compacted_at, created_at, updated_at
+             FROM channel_sessions WHERE session_key = ?1",
+        )?;
+        let row = stmt.query_row(params![key], row_to_session).optional()?;
+        Ok(row)
+    }
+
+    /// All sessions, most recently active first.
+    pub fn list(&self) -> anyhow::Result<Vec<ChannelSession>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT session_key, channel, sender, turns_json, compaction_count,
+                    last_compacted_at, created_at, updated_at
+             FROM channel_sessions ORDER BY updated_at DESC",
+        )?;
+        let rows = stmt.query_map([], row_to_session)?;
+        let mut sessions = Vec::new();
+        for row in rows {
+            sessions.push(row?);
+        }
+        Ok(sessions)
+    }
+
+    pub fn delete(&self, key: &str) -> anyhow::Result<bool> {
+        let removed = self.conn.lock().execute(
+            "DELETE FROM channel_sessions WHERE session_key = ?1",
+            params![key],
+        )?;
+        Ok(removed > 0)
+    }
+
+    pub fn clear_all(&self) -> anyhow::Result<usize> {
+        Ok(self
+            .conn
+            .lock()
+            .execute("DELETE FROM channel_sessions", [])?)
+    }
+
+    /// Drop expired sessions and enforce the session cap. Returns rows removed.
+    pub fn prune(&self) -> anyhow::Result<usize> {
+        Ok(self.prune_keys()?.len())
+    }
+
+    /// [`Self::prune`] at most once per sweep interval, for long-running
+    /// daemons. Returns the keys removed so callers can drop cached copies.
+    pub fn prune_if_due(&self) -> anyhow::Result<Vec<String>> {
+        {
+            let mut last_prune = self.last_prune.lock();
+            if last_prune.is_some_and(|at| at.elapsed() < PRUNE_INTERVAL) {
+                return Ok(Vec::new());
+            }
+            *last_prune = Some(Instant::now());
+        }
+        self.prune_keys()
+    }
+
+    fn prune_keys(&self) -> anyhow::Result<Vec<String>> {
+        let mut removed = Vec::new();
+        if self.ttl_hours > 0 {
+            let hours = i64::try_from(self.ttl_hours).unwrap_or(i64::MAX);
+            let cutoff = Utc::now()
+                .checked_sub_signed(Duration::try_hours(hours).unwrap_or(Duration::MAX))
+                .unwrap_or(DateTime::<Utc>::MIN_UTC);
+            let conn = self.conn.lock();
+            let mut stmt = conn.prepare(
+                "DELETE FROM channel_sessions WHERE updated_at < ?1 RETURNING session_key",
+            )?;
+            let keys = stmt.query_map(params![format_timestamp(cutoff)], |row| row.get(0))?;
+            for key in keys {
+                removed.push(key?);
+            }
+        }
+        removed.extend(self.evict_over_cap()?);
+        *self.last_prune.lock() = Some(Instant::now());
+        Ok(removed)
+    }
+
+    /// Prune, then return every remaining history keyed for the in-memory map.
+    pub fn load_active(&self) -> anyhow::Result<HashMap<String, Vec<ChatMessage>>> {
+        let pruned = self.prune()?;
+        if pruned > 0 {
+            tracing::info!("Pruned {pruned} expired channel session(s)");
+        }
+        Ok(self
+            .list()?
+            .into_iter()
+            .map(|session| (session.key, session.turns))
+            .collect())
+    }
+
+    fn evict_over_cap(&self) -> anyhow::Result<Vec<String>> {
+        if self.max_sessions == 0 {
+            return Ok(Vec::new());
+        }
+        let limit = i64::try_from(self.max_sessions).unwrap_or(i64::MAX);
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "DELETE FROM channel_sessions WHERE session_key NOT IN (
+                SELECT session_key FROM channel_sessions
+                ORDER BY updated_at DESC LIMIT ?1
+             ) RETURNING session_key",
+        )?;
+        let keys = stmt.query_map(params![limit], |row| row.get(0))?;
+        let mut evicted = Vec::new();
+        for key in keys {
+            evicted.push(key?);
+        }
+        Ok(evicted)
+    }
+}
+
+fn row_to_session(row: &rusqlite::Row<'_>) -> rusqlite::Result<ChannelSession> {
+    let turns_json: String = row.get(3)?;
+    let turns = serde_json::from_str(&turns_json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    Ok(ChannelSession {
+        key: row.get(0)?,
+        channel: row.get(1)?,
+        sender: row.get(2)?,
+        turns,
+        compaction_count: row.get(4)?,
+        last_compacted_at: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+// Fixed-width UTC timestamps so `updated_at` compares correctly as text.
+fn format_timestamp(ts: DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn now_timestamp() -> String {
+    format_timestamp(Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn store(tmp: &TempDir, ttl_hours: u64, max_sessions: usize) -> ChannelSessionStore {
+        ChannelSessionStore::open(
+            tmp.path(),
+            &ChannelSessionsConfig {
+                persist: true,
+                ttl_hours,
+                max_sessions,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn save_and_reload_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let turns = vec![ChatMessage::user("hi"), ChatMessage::assistant("hello")];
+        {
+            let store = store(&tmp, 0, 0);
+            store
+                .save("telegram_alice", "telegram", "alice", &turns)
+                .unwrap();
+            store.record_compaction("telegram_alice").unwrap();
+        }
+
+        let reopened = store(&tmp, 0, 0);
+        let session = reopened.get("telegram_alice").unwrap().unwrap();
+        assert_eq!(session.channel, "telegram");
+        assert_eq!(session.sender, "alice");
+        assert_eq!(session.turns.len(), 2);
+        assert_eq!(session.turns[1].content, "hello");
+        assert_eq!(session.compaction_count, 1);
+        assert!(session.last_compacted_at.is_some());
+
+        let active = reopened.load_active().unwrap();
+        assert_eq!(active["telegram_alice"].len(), 2);
+    }
+
+    #[test]
+    fn empty_history_deletes_session() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp, 0, 0);
+        store
+            .save("discord_bob", "discord", "bob", &[ChatMessage::user("x")])
+            .unwrap();
+        store.save("discord_bob", "discord", "bob", &[]).unwrap();
+        assert!(store.get("discord_bob").unwrap().is_none());
+    }
+
+    #[test]
+    fn max_sessions_evicts_least_recently_updated() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp, 0, 2);
+        let mut evicted = Vec::new();
+        for sender in ["a", "b", "c"] {
+            evicted = store
+                .save(
+                    &format!("slack_{sender}"),
+                    "slack",
+                    sender,
+                    &[ChatMessage::user(sender)],
+                )
+                .unwrap();
+        }
+        assert_eq!(evicted, vec!["slack_a".to_string()]);
+        let keys: Vec<String> = store.list().unwrap().into_iter().map(|s| s.key).collect();
+        assert_eq!(keys, vec!["slack_c", "slack_b"]);
+
+        // Updating an existing session never evicts.
+        assert!(store
+            .save("slack_b", "slack", "b", &[ChatMessage::user("again")])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn prune_drops_expired_sessions() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp, 1, 0);
+        store
+            .save("irc_old", "irc", "old", &[ChatMessage::user("x")])
+            .unwrap();
+        store
+            .save("irc_new", "irc", "new", &[ChatMessage::user("y")])
+            .unwrap();
+        store
+            .conn
+            .lock()
+            .execute(
+                "UPDATE channel_sessions SET updated_at = ?1 WHERE session_key = 'irc_old'",
+                params![format_timestamp(Utc::now() - Duration::hours(2))],
+            )
+            .unwrap();
+
+        assert_eq!(store.prune().unwrap(), 1);
+        assert!(store.get("irc_old").unwrap().is_none());
+        assert!(store.get("irc_new").unwrap().is_some());
+    }
+
+    #[test]
+    fn prune_if_due_reports_removed_keys_and_is_throttled() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp, 1, 0);
+        let expire = |key: &str| {
+            store
+                .conn
+                .lock()
+                .execute(
+                    "UPDATE channel_sessions SET updated_at = ?1 WHERE session_key = ?2",
+                    params![format_timestamp(Utc::now() - Duration::hours(2)), key],
+                )
+                .unwrap();
+        };
+        for sender in ["old", "stale"] {
+            store
+                .save(
+                    &format!("irc_{sender}"),
+                    "irc",
+                    sender,
+                    &[ChatMessage::user("x")],
+                )
+                .unwrap();
+        }
+
+        expire("irc_old");
+        assert_eq!(store.prune_if_due().unwrap(), vec!["irc_old".to_string()]);
+
+        // A second sweep inside the interval is skipped.
+        expire("irc_stale");
+        assert!(store.prune_if_due().unwrap().is_empty());
+        assert!(store.get("irc_stale").unwrap().is_some());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    AttachmentFetcher, AttachmentSource, Channel, ChannelAttachment, ChannelMessage, SendMessage,
+};
 use async_trait::async_trait;
 use futures_util::StreamExt;
 use reqwest::Client;
@@ -70,6 +72,30 @@ struct GroupInfo {
     group_id: Option<String>,
 }
 
+/// Pulls an attachment out of signal-cli's store via the `getAttachment` RPC.
+struct SignalAttachmentFetcher {
+    channel: SignalChannel,
+    params: serde_json::Value,
+}
+
+#[async_trait]
+impl AttachmentFetcher for SignalAttachmentFetcher {
+    async fn fetch(&self) -> anyhow::Result<Vec<u8>> {
+        use base64::Engine as _;
+
+        let result = self
+            .channel
+            .rpc_request("getAttachment", self.params.clone())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("getAttachment returned no result"))?;
+        let data = result
+            .get("data")
+            .and_then(|d| d.as_str())
+            .ok_or_else(|| anyhow::anyhow!("getAttachment: no data in response"))?;
+        Ok(base64::engine::general_purpose::STANDARD.decode(data)?)
+    }
+}
+
 impl SignalChannel {
     pub fn new(
         http_url: String,
@@ -167,6 +193,43 @@ impl SignalChannel {
         }
     }
 
+    /// Attachments of a data message, fetched lazily through the daemon.
+    fn inbound_attachments(&self, data_msg: &DataMessage, target: &str) -> Vec<ChannelAttachment> {
+        let Some(items) = data_msg.attachments.as_ref() else {
+            return Vec::new();
+        };
+        items
+            .iter()
+            .filter_map(|item| {
+                let id = item.get("id").and_then(|i| i.as_str())?;
+                let mut params = match Self::parse_recipient_target(target) {
+                    RecipientTarget::Direct(number) => serde_json::json!({ "recipient": number }),
+                    RecipientTarget::Group(group_id) => serde_json::json!({ "groupId": group_id }),
+                };
+                params["id"] = serde_json::json!(id);
+                params["account"] = serde_json::json!(&self.account);
+                let fetcher = SignalAttachmentFetcher {
+                    channel: self.clone(),
+                    params,
+                };
+                Some(
+                    ChannelAttachment::new(
+                        item.get("contentType")
+                            .and_then(|c| c.as_str())
+                            .unwrap_or_default(),
+                        AttachmentSource::Fetcher(std::sync::Arc::new(fetcher)),
+                    )
+                    .with_file_name(
+                        item.get("filename")
+                            .and_then(|f| f.as_str())
+                            .map(String::from),
+                    )
+                    .with_size(item.get("size").and_then(serde_json::Value::as_u64)),
+                )
+            })
+            .collect()
+    }
+
     /// Send a JSON-RPC request to signal-cli daemon.
     async fn rpc_request(
         &self,
@@ -232,7 +295,7 @@ impl SignalChannel {
             }
         }
 
-        let text = data_msg.message.as_deref().filter(|t| !t.is_empty())?;
+        let text = data_msg.message.as_deref().unwrap_or_default();
         let sender = Self::sender(envelope)?;
 
         if !self.is_sender_allowed(&sender) {
@@ -244,6 +307,14 @@ impl SignalChannel {
         }
 
         let target = self.reply_target(data_msg, &sender);
+        let attachments = if self.ignore_attachments {
+            Vec::new()
+        } else {
+            self.inbound_attachments(data_msg, &target)
+        };
+        if text.is_empty() && attachments.is_empty() {
+            return None;
+        }
 
         let timestamp = data_msg
             .timestamp
@@ -266,6 +337,7 @@ impl SignalChannel {
             channel: "signal".to_string(),
             timestamp: timestamp / 1000, // millis → secs
             thread_ts: None,
+            attachments,
         })
     }
 }
@@ -855,6 +927,34 @@ mod tests {
         assert!(ch.process_envelope(&env).is_none());
     }
 
+    #[test]
+    fn process_envelope_keeps_attachment_only_messages() {
+        let ch = make_channel();
+        let env = Envelope {
+            source: Some("+1111111111".to_string()),
+            source_number: Some("+1111111111".to_string()),
+            data_message: Some(DataMessage {
+                message: None,
+                timestamp: Some(1_700_000_000_000),
+                group_info: None,
+                attachments: Some(vec![serde_json::json!({
+                    "id": "abc123.jpg",
+                    "contentType": "image/jpeg",
+                    "filename": "photo.jpg",
+                    "size": 4096
+                })]),
+            }),
+            story_message: None,
+            timestamp: Some(1_700_000_000_000),
+        };
+        let msg = ch.process_envelope(&env).unwrap();
+        assert!(msg.content.is_empty());
+        assert_eq!(msg.attachments.len(), 1);
+        assert!(msg.attachments[0].is_image());
+        assert_eq!(msg.attachments[0].file_name.as_deref(), Some("photo.jpg"));
+        assert_eq!(msg.attachments[0].size_bytes, Some(4096));
+    }
+
     #[test]
     fn sse_envelope_deserializes() {
         let json = r#"{
@@ -1,4 +1,5 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelAttachment, ChannelMessage, OutboundAttachment, SendMessage};
+use crate::approval::PendingApproval;
 use async_trait::async_trait;
 
 /// Slack channel — polls conversations.history via Web API
@@ -6,6 +7,9 @@ pub struct SlackChannel {
     bot_token: String,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    /// Button presses reach the gateway's `/slack/interactions`, so only
+    /// render buttons when a signing secret is configured to verify them.
+    interactive_approvals: bool,
 }
 
 impl SlackChannel {
@@ -14,9 +18,43 @@ impl SlackChannel {
             bot_token,
             channel_id,
             allowed_users,
+            interactive_approvals: false,
         }
     }
 
+    /// Render approval prompts with Block Kit buttons.
+    pub fn with_interactive_approvals(mut self, enabled: bool) -> Self {
+        self.interactive_approvals = enabled;
+        self
+    }
+
+    /// Block Kit layout for an approval prompt: the text plus three buttons.
+    fn approval_blocks(text: &str, approval: &PendingApproval) -> serde_json::Value {
+        let button = |label: &str, verb: &str, style: Option<&str>| {
+            let mut button = serde_json::json!({
+                "type": "button",
+                "text": { "type": "plain_text", "text": label },
+                "action_id": format!("approval:{verb}:{}", approval.id),
+                "value": approval.id,
+            });
+            if let Some(style) = style {
+                button["style"] = serde_json::json!(style);
+            }
+            button
+        };
+        serde_json::json!([
+            { "type": "section", "text": { "type": "mrkdwn", "text": text } },
+            {
+                "type": "actions",
+                "elements": [
+                    button("Approve", "approve", Some("primary")),
+                    button("Deny", "deny", Some("danger")),
+                    button("Always", "always", None),
+                ]
+            }
+        ])
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.slack")
     }
@@ -54,15 +92,46 @@ impl SlackChannel {
             .or(if ts.is_empty() { None } else { Some(ts) })
             .map(str::to_string)
     }
-}
 
-#[async_trait]
-impl Channel for SlackChannel {
-    fn name(&self) -> &str {
-        "slack"
+    /// Files shared with a message. `url_private_download` requires the bot token.
+    fn inbound_attachments(&self, msg: &serde_json::Value) -> Vec<ChannelAttachment> {
+        msg.get("files")
+            .and_then(|f| f.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|file| {
+                let url = file
+                    .get("url_private_download")
+                    .or_else(|| file.get("url_private"))
+                    .and_then(|u| u.as_str())?;
+                let mime_type = file
+                    .get("mimetype")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("application/octet-stream");
+                Some(
+                    ChannelAttachment::url(
+                        mime_type,
+                        url,
+                        Some(self.bot_token.clone()),
+                        "channel.slack",
+                    )
+                    .with_file_name(
+                        file.get("name")
+                            .and_then(|n| n.as_str())
+                            .map(str::to_string),
+                    )
+                    .with_size(file.get("size").and_then(serde_json::Value::as_u64)),
+                )
+            })
+            .collect()
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    /// Post a message via chat.postMessage, optionally with Block Kit blocks.
+    async fn post_message(
+        &self,
+        message: &SendMessage,
+        blocks: Option<serde_json::Value>,
+    ) -> anyhow::Result<()> {
         let mut body = serde_json::json!({
             "channel": message.recipient,
             "text": message.content
@@ -71,6 +140,9 @@ impl Channel for SlackChannel {
         if let Some(ref ts) = message.thread_ts {
             body["thread_ts"] = serde_json::json!(ts);
         }
+        if let Some(blocks) = blocks {
+            body["blocks"] = blocks;
+        }
 
         let resp = self
             .http_client()
@@ -103,6 +175,102 @@ impl Channel for SlackChannel {
         Ok(())
     }
 
+    /// Upload files with the external upload flow: reserve an upload URL per
+    /// file, POST the bytes there, then share them all in one
+    /// `files.completeUploadExternal` call.
+    async fn upload_files(
+        &self,
+        channel_id: &str,
+        thread_ts: Option<&str>,
+        attachments: &[OutboundAttachment],
+    ) -> anyhow::Result<()> {
+        let client = self.http_client();
+        let mut files = Vec::with_capacity(attachments.len());
+        for attachment in attachments {
+            let bytes = attachment.read().await?;
+            let reserved: serde_json::Value = client
+                .post("https://slack.com/api/files.getUploadURLExternal")
+                .bearer_auth(&self.bot_token)
+                .form(&[
+                    ("filename", attachment.file_name.clone()),
+                    ("length", bytes.len().to_string()),
+                ])
+                .send()
+                .await?
+                .json()
+                .await?;
+            Self::ensure_ok(&reserved, "files.getUploadURLExternal")?;
+            let (Some(upload_url), Some(file_id)) = (
+                reserved.get("upload_url").and_then(|u| u.as_str()),
+                reserved.get("file_id").and_then(|f| f.as_str()),
+            ) else {
+                anyhow::bail!("Slack files.getUploadURLExternal returned no upload_url");
+            };
+
+            let resp = client
+                .post(upload_url)
+                .header("Content-Type", attachment.mime_type.as_str())
+                .body(bytes)
+                .send()
+                .await?;
+            if !resp.status().is_success() {
+                anyhow::bail!("Slack file upload failed ({})", resp.status());
+            }
+            files.push(serde_json::json!({ "id": file_id, "title": attachment.file_name }));
+        }
+
+        let mut body = serde_json::json!({ "files": files, "channel_id": channel_id });
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+        let completed: serde_json::Value = client
+            .post("https://slack.com/api/files.completeUploadExternal")
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await?
+            .json()
+            .await?;
+        Self::ensure_ok(&completed, "files.completeUploadExternal")
+    }
+
+    fn ensure_ok(response: &serde_json::Value, method: &str) -> anyhow::Result<()> {
+        if response.get("ok") == Some(&serde_json::Value::Bool(true)) {
+            return Ok(());
+        }
+        let err = response
+            .get("error")
+            .and_then(|e| e.as_str())
+            .unwrap_or("unknown");
+        anyhow::bail!("Slack {method} failed: {err}")
+    }
+}
+
+#[async_trait]
+impl Channel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        if !message.content.is_empty() || message.attachments.is_empty() {
+            self.post_message(message, None).await?;
+        }
+        if !message.attachments.is_empty() {
+            self.upload_files(
+                &message.recipient,
+                message.thread_ts.as_deref(),
+                &message.attachments,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let channel_id = self
             .channel_id
@@ -154,6 +322,7 @@ impl Channel for SlackChannel {
                         .and_then(|u| u.as_str())
                         .unwrap_or("unknown");
                     let text = msg.get("text").and_then(|t| t.as_str()).unwrap_or("");
+                    let attachments = self.inbound_attachments(msg);
 
                     // Skip bot's own messages
                     if user == bot_user_id {
@@ -167,7 +336,7 @@ impl Channel for SlackChannel {
                     }
 
                     // Skip empty or already-seen
-                    if text.is_empty() || ts <= last_ts.as_str() {
+                    if (text.is_empty() && attachments.is_empty()) || ts <= last_ts.as_str() {
                         continue;
                     }
 
@@ -184,6 +353,7 @@ impl Channel for SlackChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: Self::inbound_thread_ts(msg, ts),
+                        attachments,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -203,6 +373,18 @@ impl Channel for SlackChannel {
             .map(|r| r.status().is_success())
             .unwrap_or(false)
     }
+
+    async fn send_approval_prompt(
+        &self,
+        message: &SendMessage,
+        approval: &PendingApproval,
+    ) -> anyhow::Result<()> {
+        if !self.interactive_approvals {
+            return self.send(message).await;
+        }
+        let blocks = Self::approval_blocks(&message.content, approval);
+        self.post_message(message, Some(blocks)).await
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +397,35 @@ mod tests {
         assert_eq!(ch.name(), "slack");
     }
 
+    #[test]
+    fn approval_blocks_carry_action_ids_for_each_decision() {
+        let approval = PendingApproval {
+            id: "deadbeef".into(),
+            tool_name: "shell".into(),
+            arguments_summary: "command: ls".into(),
+            channel: "slack".into(),
+            requester: None,
+            requested_at: String::new(),
+            expires_at: String::new(),
+        };
+        let blocks = SlackChannel::approval_blocks("Approval needed", &approval);
+        let ids: Vec<&str> = blocks[1]["elements"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|b| b["action_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "approval:approve:deadbeef",
+                "approval:deny:deadbeef",
+                "approval:always:deadbeef"
+            ]
+        );
+        assert_eq!(blocks[0]["text"]["text"], "Approval needed");
+    }
+
     #[test]
     fn slack_channel_with_channel_id() {
         let ch = SlackChannel::new("xoxb-fake".into(), Some("C12345".into()), vec![]);
@@ -339,6 +550,35 @@ mod tests {
         assert_eq!(thread_ts.as_deref(), Some("123.001"));
     }
 
+    #[test]
+    fn inbound_attachments_use_private_download_url() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
+        let msg = serde_json::json!({
+            "files": [{
+                "name": "report.pdf",
+                "mimetype": "application/pdf",
+                "size": 1234,
+                "url_private_download": "https://files.slack.com/report.pdf"
+            }]
+        });
+        let attachments = ch.inbound_attachments(&msg);
+        assert_eq!(attachments.len(), 1);
+        assert!(attachments[0].is_pdf());
+        assert_eq!(attachments[0].size_bytes, Some(1234));
+        assert!(ch.inbound_attachments(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn ensure_ok_surfaces_slack_error_codes() {
+        let ok = serde_json::json!({ "ok": true });
+        assert!(SlackChannel::ensure_ok(&ok, "files.completeUploadExternal").is_ok());
+
+        let denied = serde_json::json!({ "ok": false, "error": "not_in_channel" });
+        let err = SlackChannel::ensure_ok(&denied, "files.completeUploadExternal").unwrap_err();
+        assert!(err.to_string().contains("not_in_channel"));
+        assert!(SlackChannel::ensure_ok(&serde_json::json!({}), "x").is_err());
+    }
+
     #[test]
     fn inbound_thread_ts_none_when_ts_missing() {
         let msg = serde_json::json!({});
@@ -1,4 +1,7 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    AttachmentFetcher, AttachmentSource, Channel, ChannelAttachment, ChannelMessage, SendMessage,
+};
+use crate::approval::PendingApproval;
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
@@ -17,6 +20,8 @@ const TELEGRAM_MAX_MESSAGE_LENGTH: usize = 4096;
 /// worst case is "(continued)\n\n" + chunk + "\n\n(continues...)" = 30 extra chars
 const TELEGRAM_CONTINUATION_OVERHEAD: usize = 30;
 const TELEGRAM_BIND_COMMAND: &str = "/bind";
+/// `callback_data` prefix for approval buttons: `approval:<verb>:<id>`.
+const APPROVAL_CALLBACK_PREFIX: &str = "approval";
 
 /// Split a message into chunks that respect Telegram's 4096 character limit.
 /// Tries to split at word boundaries when possible, and handles continuation.
@@ -100,6 +105,16 @@ impl TelegramAttachmentKind {
             _ => None,
         }
     }
+
+    fn from_mime(mime_type: &str) -> Self {
+        match mime_type {
+            "image/png" | "image/jpeg" | "image/jpg" | "image/webp" => Self::Image,
+            "audio/ogg" => Self::Voice,
+            m if m.starts_with("audio/") => Self::Audio,
+            m if m.starts_with("video/") => Self::Video,
+            _ => Self::Document,
+        }
+    }
 }
 
 fn is_http_url(target: &str) -> bool {
@@ -314,6 +329,43 @@ fn parse_attachment_markers(message: &str) -> (String, Vec<TelegramAttachment>)
     (cleaned.trim().to_string(), attachments)
 }
 
+/// Downloads a Telegram file: `getFile` resolves the path, then the file
+/// endpoint serves the bytes.
+struct TelegramFileFetcher {
+    bot_token: String,
+    file_id: String,
+}
+
+#[async_trait]
+impl AttachmentFetcher for TelegramFileFetcher {
+    async fn fetch(&self) -> anyhow::Result<Vec<u8>> {
+        let client = crate::config::build_runtime_proxy_client("channel.telegram");
+        let get_file_url = format!(
+            "https://api.telegram.org/bot{}/getFile?file_id={}",
+            self.bot_token, self.file_id
+        );
+        let json: serde_json::Value = client.get(&get_file_url).send().await?.json().await?;
+        let file_path = json
+            .get("result")
+            .and_then(|r| r.get("file_path"))
+            .and_then(|p| p.as_str())
+            .ok_or_else(|| anyhow::anyhow!("getFile: no file_path in response"))?;
+
+        let download_url = format!(
+            "https://api.telegram.org/file/bot{}/{}",
+            self.bot_token, file_path
+        );
+        let bytes = client
+            .get(&download_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        Ok(bytes.to_vec())
+    }
+}
+
 /// Telegram channel — long-polls the Bot API for updates
 pub struct TelegramChannel {
     bot_token: String,
@@ -756,32 +808,20 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         }
     }
 
-    fn parse_update_message(
-        &self,
-        update: &serde_json::Value,
-    ) -> Option<(ChannelMessage, Option<String>)> {
+    fn parse_update_message(&self, update: &serde_json::Value) -> Option<ChannelMessage> {
         let message = update.get("message")?;
 
-        // Support both text messages and photo messages (with optional caption)
+        // Support text messages and media messages (with optional caption)
         let text_opt = message.get("text").and_then(serde_json::Value::as_str);
         let caption_opt = message.get("caption").and_then(serde_json::Value::as_str);
-
-        // Extract file_id from photo (highest resolution = last element)
-        let photo_file_id = message
-            .get("photo")
-            .and_then(serde_json::Value::as_array)
-            .and_then(|photos| photos.last())
-            .and_then(|p| p.get("file_id"))
-            .and_then(serde_json::Value::as_str)
-            .map(|s| s.to_string());
-
-        // Require at least text, caption, or photo
-        let text = match (text_opt, caption_opt, &photo_file_id) {
-            (Some(t), _, _) => t.to_string(),
-            (None, Some(c), Some(_)) => c.to_string(),
-            (None, Some(c), None) => c.to_string(),
-            (None, None, Some(_)) => String::new(), // will be filled with image marker later
-            (None, None, None) => return None,
+        let attachments = self.inbound_attachments(message);
+
+        // Require at least text, caption, or an attachment
+        let text = match (text_opt, caption_opt) {
+            (Some(t), _) => t.to_string(),
+            (None, Some(c)) => c.to_string(),
+            (None, None) if !attachments.is_empty() => String::new(),
+            (None, None) => return None,
         };
 
         let username = message
@@ -856,69 +896,134 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             text.to_string()
         };
 
+        Some(ChannelMessage {
+            id: format!("telegram_{chat_id}_{message_id}"),
+            sender: sender_identity,
+            reply_target,
+            content,
+            channel: "telegram".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+            attachments,
+        })
+    }
+
+    /// Photo (largest size), document, voice and audio payloads of a message.
+    fn inbound_attachments(&self, message: &serde_json::Value) -> Vec<ChannelAttachment> {
+        let photo = message
+            .get("photo")
+            .and_then(serde_json::Value::as_array)
+            .and_then(|photos| photos.last())
+            .map(|p| (p, "image/jpeg"));
+        let others = [
+            ("document", "application/octet-stream"),
+            ("voice", "audio/ogg"),
+            ("audio", "audio/mpeg"),
+        ]
+        .into_iter()
+        .filter_map(|(key, default_mime)| message.get(key).map(|m| (m, default_mime)));
+
+        photo
+            .into_iter()
+            .chain(others)
+            .filter_map(|(media, default_mime)| {
+                let file_id = media.get("file_id").and_then(serde_json::Value::as_str)?;
+                let mime_type = media
+                    .get("mime_type")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or(default_mime);
+                let fetcher = TelegramFileFetcher {
+                    bot_token: self.bot_token.clone(),
+                    file_id: file_id.to_string(),
+                };
+                Some(
+                    ChannelAttachment::new(mime_type, AttachmentSource::Fetcher(Arc::new(fetcher)))
+                        .with_file_name(
+                            media
+                                .get("file_name")
+                                .and_then(serde_json::Value::as_str)
+                                .map(str::to_string),
+                        )
+                        .with_size(media.get("file_size").and_then(serde_json::Value::as_u64)),
+                )
+            })
+            .collect()
+    }
+
+    /// Turn an approval button press (`callback_query`) into the equivalent
+    /// `/approve <id>` message. Returns the message and the callback query ID.
+    fn parse_approval_callback(
+        &self,
+        update: &serde_json::Value,
+    ) -> Option<(ChannelMessage, String)> {
+        let callback = update.get("callback_query")?;
+        let callback_id = callback.get("id")?.as_str()?.to_string();
+        let data = callback.get("data")?.as_str()?;
+        let mut parts = data.splitn(3, ':');
+        if parts.next()? != APPROVAL_CALLBACK_PREFIX {
+            return None;
+        }
+        let verb = parts.next()?;
+        let approval_id = parts.next()?;
+
+        let from = callback.get("from")?;
+        let username = from
+            .get("username")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("unknown")
+            .to_string();
+        let sender_id = from
+            .get("id")
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string());
+        let mut identities = vec![username.as_str()];
+        if let Some(id) = sender_id.as_deref() {
+            identities.push(id);
+        }
+        if !self.is_any_user_allowed(identities.iter().copied()) {
+            return None;
+        }
+        let sender_identity = if username == "unknown" {
+            sender_id.clone().unwrap_or_else(|| "unknown".to_string())
+        } else {
+            username.clone()
+        };
+
+        let message = callback.get("message")?;
+        let chat_id = message
+            .get("chat")
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)?
+            .to_string();
+        let reply_target = match message
+            .get("message_thread_id")
+            .and_then(serde_json::Value::as_i64)
+        {
+            Some(tid) => format!("{chat_id}:{tid}"),
+            None => chat_id.clone(),
+        };
+
         Some((
             ChannelMessage {
-                id: format!("telegram_{chat_id}_{message_id}"),
+                id: format!("telegram_callback_{callback_id}"),
                 sender: sender_identity,
                 reply_target,
-                content,
+                content: format!("/{verb} {approval_id}"),
                 channel: "telegram".to_string(),
                 timestamp: std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap_or_default()
                     .as_secs(),
                 thread_ts: None,
+                attachments: Vec::new(),
             },
-            photo_file_id,
+            callback_id,
         ))
     }
 
-    /// Download a Telegram photo by file_id, resize to fit within 1024px, and return as base64 data URI.
-    async fn resolve_photo_data_uri(&self, file_id: &str) -> anyhow::Result<String> {
-        use base64::Engine as _;
-
-        // Step 1: call getFile to get file_path
-        let get_file_url = self.api_url(&format!("getFile?file_id={}", file_id));
-        let resp = self.http_client().get(&get_file_url).send().await?;
-        let json: serde_json::Value = resp.json().await?;
-        let file_path = json
-            .get("result")
-            .and_then(|r| r.get("file_path"))
-            .and_then(|p| p.as_str())
-            .ok_or_else(|| anyhow::anyhow!("getFile: no file_path in response"))?
-            .to_string();
-
-        // Step 2: download the actual file
-        let download_url = format!(
-            "https://api.telegram.org/file/bot{}/{}",
-            self.bot_token, file_path
-        );
-        let img_resp = self.http_client().get(&download_url).send().await?;
-        let bytes = img_resp.bytes().await?;
-
-        // Step 3: resize to max 1024px on longest side to fit within model context
-        let resized_bytes = tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<u8>> {
-            let img = image::load_from_memory(&bytes)?;
-            let (w, h) = (img.width(), img.height());
-            let max_dim = 512u32;
-            let resized = if w > max_dim || h > max_dim {
-                img.thumbnail(max_dim, max_dim)
-            } else {
-                img
-            };
-            let mut buf = Vec::new();
-            resized.write_to(
-                &mut std::io::Cursor::new(&mut buf),
-                image::ImageFormat::Jpeg,
-            )?;
-            Ok(buf)
-        })
-        .await??;
-
-        let b64 = base64::engine::general_purpose::STANDARD.encode(&resized_bytes);
-        Ok(format!("data:image/jpeg;base64,{}", b64))
-    }
-
     async fn send_text_chunks(
         &self,
         message: &str,
@@ -1753,6 +1858,10 @@ impl Channel for TelegramChannel {
         Ok(())
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         // Strip tool_call tags before processing to prevent Markdown parsing failures
         let content = strip_tool_call_tags(&message.content);
@@ -1763,6 +1872,21 @@ impl Channel for TelegramChannel {
             None => (message.recipient.as_str(), None),
         };
 
+        if !message.attachments.is_empty() {
+            if !content.trim().is_empty() {
+                self.send_text_chunks(&content, chat_id, thread_id).await?;
+            }
+            for attachment in &message.attachments {
+                let attachment = TelegramAttachment {
+                    kind: TelegramAttachmentKind::from_mime(&attachment.mime_type),
+                    target: attachment.path.display().to_string(),
+                };
+                self.send_attachment(chat_id, thread_id, &attachment)
+                    .await?;
+            }
+            return Ok(());
+        }
+
         let (text_without_markers, attachments) = parse_attachment_markers(&content);
 
         if !attachments.is_empty() {
@@ -1808,7 +1932,7 @@ impl Channel for TelegramChannel {
             let body = serde_json::json!({
                 "offset": offset,
                 "timeout": 30,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "callback_query"]
             });
 
             let resp = match self.http_client().post(&url).json(&body).send().await {
@@ -1866,23 +1990,25 @@ Ensure only one `zeroclaw` process is using this bot token."
                         offset = uid + 1;
                     }
 
-                    let Some((mut msg, photo_file_id)) = self.parse_update_message(update) else {
+                    if let Some((msg, callback_id)) = self.parse_approval_callback(update) {
+                        // Stop the button's loading spinner; errors are cosmetic.
+                        let _ = self
+                            .http_client()
+                            .post(self.api_url("answerCallbackQuery"))
+                            .json(&serde_json::json!({ "callback_query_id": callback_id }))
+                            .send()
+                            .await;
+                        if tx.send(msg).await.is_err() {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+
+                    let Some(msg) = self.parse_update_message(update) else {
                         self.handle_unauthorized_message(update).await;
                         continue;
                     };
 
-                    // Resolve photo file_id to data URI and inject as IMAGE marker
-                    if let Some(file_id) = photo_file_id {
-                        if let Ok(data_uri) = self.resolve_photo_data_uri(&file_id).await {
-                            let image_marker = format!("[IMAGE:{}]", data_uri);
-                            if msg.content.is_empty() {
-                                msg.content = image_marker;
-                            } else {
-                                msg.content = format!("{}\n{}", msg.content, image_marker);
-                            }
-                        }
-                    }
-
                     // Send "typing" indicator immediately when we receive a message
                     let typing_body = serde_json::json!({
                         "chat_id": &msg.reply_target,
@@ -1956,6 +2082,50 @@ Ensure only one `zeroclaw` process is using this bot token."
         }
         Ok(())
     }
+
+    async fn send_approval_prompt(
+        &self,
+        message: &SendMessage,
+        approval: &PendingApproval,
+    ) -> anyhow::Result<()> {
+        let (chat_id, thread_id) = match message.recipient.split_once(':') {
+            Some((chat, thread)) => (chat, Some(thread)),
+            None => (message.recipient.as_str(), None),
+        };
+        let button = |label: &str, verb: &str| {
+            serde_json::json!({
+                "text": label,
+                "callback_data": format!("{APPROVAL_CALLBACK_PREFIX}:{verb}:{}", approval.id),
+            })
+        };
+        let mut body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": message.content,
+            "reply_markup": {
+                "inline_keyboard": [[
+                    button("✅ Approve", "approve"),
+                    button("🚫 Deny", "deny"),
+                    button("♾️ Always", "always"),
+                ]]
+            }
+        });
+        if let Some(thread_id) = thread_id {
+            body["message_thread_id"] = serde_json::Value::String(thread_id.to_string());
+        }
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("sendMessage"))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Telegram sendMessage (approval) failed ({status}): {err}");
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -2230,6 +2400,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn attachment_kind_from_mime_picks_upload_method() {
+        assert_eq!(
+            TelegramAttachmentKind::from_mime("image/png"),
+            TelegramAttachmentKind::Image
+        );
+        assert_eq!(
+            TelegramAttachmentKind::from_mime("image/svg+xml"),
+            TelegramAttachmentKind::Document
+        );
+        assert_eq!(
+            TelegramAttachmentKind::from_mime("audio/ogg"),
+            TelegramAttachmentKind::Voice
+        );
+        assert_eq!(
+            TelegramAttachmentKind::from_mime("audio/mpeg"),
+            TelegramAttachmentKind::Audio
+        );
+        assert_eq!(
+            TelegramAttachmentKind::from_mime("text/plain"),
+            TelegramAttachmentKind::Document
+        );
+    }
+
     #[test]
     fn parse_update_message_uses_chat_id_as_reply_target() {
         let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
@@ -2250,7 +2444,6 @@ mod tests {
 
         let msg = ch
             .parse_update_message(&update)
-            .map(|(m, _)| m)
             .expect("message should parse");
 
         assert_eq!(msg.sender, "alice");
@@ -2259,6 +2452,31 @@ mod tests {
         assert_eq!(msg.id, "telegram_-100200300_33");
     }
 
+    #[test]
+    fn parse_approval_callback_maps_button_to_reply_keyword() {
+        let ch = TelegramChannel::new("token".into(), vec!["alice".into()], false);
+        let update = serde_json::json!({
+            "update_id": 2,
+            "callback_query": {
+                "id": "cb-1",
+                "data": "approval:always:ab12cd34",
+                "from": { "id": 555, "username": "alice" },
+                "message": { "message_id": 9, "chat": { "id": 42 }, "message_thread_id": 7 }
+            }
+        });
+
+        let (msg, callback_id) = ch
+            .parse_approval_callback(&update)
+            .expect("callback should parse");
+        assert_eq!(callback_id, "cb-1");
+        assert_eq!(msg.content, "/always ab12cd34");
+        assert_eq!(msg.sender, "alice");
+        assert_eq!(msg.reply_target, "42:7");
+
+        let stranger = TelegramChannel::new("token".into(), vec!["bob".into()], false);
+        assert!(stranger.parse_approval_callback(&update).is_none());
+    }
+
     #[test]
     fn parse_update_message_allows_numeric_id_without_username() {
         let ch = TelegramChannel::new("token".into(), vec!["555".into()], false);
@@ -2278,7 +2496,6 @@ mod tests {
 
         let msg = ch
             .parse_update_message(&update)
-            .map(|(m, _)| m)
             .expect("numeric allowlist should pass");
 
         assert_eq!(msg.sender, "555");
@@ -2306,7 +2523,6 @@ mod tests {
 
         let msg = ch
             .parse_update_message(&update)
-            .map(|(m, _)| m)
             .expect("message with thread_id should parse");
 
         assert_eq!(msg.sender, "alice");
@@ -2315,6 +2531,51 @@ mod tests {
         assert_eq!(msg.id, "telegram_-100200300_42");
     }
 
+    #[test]
+    fn parse_update_message_collects_photo_and_document_attachments() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        let photo = serde_json::json!({
+            "update_id": 4,
+            "message": {
+                "message_id": 50,
+                "photo": [
+                    { "file_id": "small", "file_size": 100 },
+                    { "file_id": "large", "file_size": 9000 }
+                ],
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": 12345 }
+            }
+        });
+
+        let msg = ch.parse_update_message(&photo).expect("photo should parse");
+        assert!(msg.content.is_empty());
+        assert_eq!(msg.attachments.len(), 1);
+        assert!(msg.attachments[0].is_image());
+        assert_eq!(msg.attachments[0].size_bytes, Some(9000));
+
+        let document = serde_json::json!({
+            "update_id": 5,
+            "message": {
+                "message_id": 51,
+                "caption": "summarize this",
+                "document": {
+                    "file_id": "doc",
+                    "file_name": "q3.pdf",
+                    "mime_type": "application/pdf"
+                },
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": 12345 }
+            }
+        });
+
+        let msg = ch
+            .parse_update_message(&document)
+            .expect("document should parse");
+        assert_eq!(msg.content, "summarize this");
+        assert!(msg.attachments[0].is_pdf());
+        assert_eq!(msg.attachments[0].display_name(), "q3.pdf");
+    }
+
     // ── File sending API URL tests ──────────────────────────────────
 
     #[test]
@@ -2920,7 +3181,6 @@ mod tests {
 
         let parsed = ch
             .parse_update_message(&update)
-            .map(|(m, _)| m)
             .expect("mention should parse");
         assert_eq!(parsed.content, "Hi status please");
 
@@ -1,4 +1,12 @@
+use crate::approval::PendingApproval;
 use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Largest inbound attachment that will be downloaded (25 MiB).
+pub const MAX_INBOUND_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
+/// Largest file uploaded with an outbound message (25 MiB).
+pub const MAX_OUTBOUND_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
 
 /// A message received from or sent to a channel
 #[derive(Debug, Clone)]
@@ -12,6 +20,260 @@ pub struct ChannelMessage {
     /// Platform thread identifier (e.g. Slack `ts`, Discord thread ID).
     /// When set, replies should be posted as threaded responses.
     pub thread_ts: Option<String>,
+    /// Files the sender attached (images, voice notes, documents).
+    pub attachments: Vec<ChannelAttachment>,
+}
+
+/// Platform-specific download for attachments that need more than a GET
+/// (e.g. Telegram `getFile`, signal-cli `getAttachment`).
+#[async_trait]
+pub trait AttachmentFetcher: Send + Sync {
+    async fn fetch(&self) -> anyhow::Result<Vec<u8>>;
+}
+
+/// Where an inbound attachment's bytes come from.
+#[derive(Clone)]
+pub enum AttachmentSource {
+    /// Plain download, optionally authenticated with a bearer token.
+    Url {
+        url: String,
+        bearer_token: Option<String>,
+        /// Proxy service key used to build the HTTP client (e.g. `channel.slack`).
+        service_key: &'static str,
+    },
+    /// Bytes delivered with the message itself (e.g. email MIME parts).
+    Inline(Arc<Vec<u8>>),
+    /// Custom platform download.
+    Fetcher(Arc<dyn AttachmentFetcher>),
+}
+
+impl std::fmt::Debug for AttachmentSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Url { url, .. } => f.debug_struct("Url").field("url", url).finish(),
+            Self::Inline(bytes) => f.debug_tuple("Inline").field(&bytes.len()).finish(),
+            Self::Fetcher(_) => f.write_str("Fetcher"),
+        }
+    }
+}
+
+/// A file attached to an inbound message.
+///
+/// The bytes are not downloaded until [`ChannelAttachment::local_path`] is
+/// called; clones share the downloaded copy.
+#[derive(Debug, Clone)]
+pub struct ChannelAttachment {
+    pub mime_type: String,
+    pub file_name: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub source: AttachmentSource,
+    local: Arc<tokio::sync::OnceCell<PathBuf>>,
+}
+
+impl ChannelAttachment {
+    pub fn new(mime_type: impl Into<String>, source: AttachmentSource) -> Self {
+        let mime_type = mime_type.into();
+        Self {
+            mime_type: if mime_type.trim().is_empty() {
+                "application/octet-stream".to_string()
+            } else {
+                mime_type.trim().to_ascii_lowercase()
+            },
+            file_name: None,
+            size_bytes: None,
+            source,
+            local: Arc::new(tokio::sync::OnceCell::new()),
+        }
+    }
+
+    /// Attachment whose bytes arrived with the message.
+    pub fn inline(mime_type: impl Into<String>, bytes: Vec<u8>) -> Self {
+        let size = bytes.len() as u64;
+        Self::new(mime_type, AttachmentSource::Inline(Arc::new(bytes))).with_size(Some(size))
+    }
+
+    /// Attachment downloaded with a plain GET.
+    pub fn url(
+        mime_type: impl Into<String>,
+        url: impl Into<String>,
+        bearer_token: Option<String>,
+        service_key: &'static str,
+    ) -> Self {
+        Self::new(
+            mime_type,
+            AttachmentSource::Url {
+                url: url.into(),
+                bearer_token,
+                service_key,
+            },
+        )
+    }
+
+    #[must_use]
+    pub fn with_file_name(mut self, file_name: Option<String>) -> Self {
+        self.file_name = file_name.filter(|name| !name.trim().is_empty());
+        self
+    }
+
+    #[must_use]
+    pub fn with_size(mut self, size_bytes: Option<u64>) -> Self {
+        self.size_bytes = size_bytes;
+        self
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+
+    pub fn is_audio(&self) -> bool {
+        self.mime_type.starts_with("audio/")
+    }
+
+    pub fn is_pdf(&self) -> bool {
+        self.mime_type == "application/pdf"
+            || self
+                .file_name
+                .as_deref()
+                .is_some_and(|name| name.to_ascii_lowercase().ends_with(".pdf"))
+    }
+
+    /// Name shown to the model: the original file name or the MIME type.
+    pub fn display_name(&self) -> &str {
+        self.file_name.as_deref().unwrap_or(&self.mime_type)
+    }
+
+    /// Download the attachment into `dir` (once) and return the file path.
+    pub async fn local_path(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        self.local
+            .get_or_try_init(|| async {
+                if let Some(size) = self.size_bytes {
+                    if size > MAX_INBOUND_ATTACHMENT_BYTES as u64 {
+                        anyhow::bail!(
+                            "attachment is {size} bytes (limit {MAX_INBOUND_ATTACHMENT_BYTES})"
+                        );
+                    }
+                }
+                let bytes = self.fetch_bytes().await?;
+                if bytes.len() > MAX_INBOUND_ATTACHMENT_BYTES {
+                    anyhow::bail!(
+                        "attachment is {} bytes (limit {MAX_INBOUND_ATTACHMENT_BYTES})",
+                        bytes.len()
+                    );
+                }
+                tokio::fs::create_dir_all(dir).await?;
+                let path = dir.join(self.local_file_name());
+                tokio::fs::write(&path, &bytes).await?;
+                Ok(path)
+            })
+            .await
+            .cloned()
+    }
+
+    async fn fetch_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        match &self.source {
+            AttachmentSource::Inline(bytes) => Ok(bytes.as_ref().clone()),
+            AttachmentSource::Fetcher(fetcher) => fetcher.fetch().await,
+            AttachmentSource::Url {
+                url,
+                bearer_token,
+                service_key,
+            } => {
+                let mut request = crate::config::build_runtime_proxy_client(service_key).get(url);
+                if let Some(token) = bearer_token {
+                    request = request.bearer_auth(token);
+                }
+                let mut response = request.send().await?.error_for_status()?;
+                if response
+                    .content_length()
+                    .is_some_and(|len| len > MAX_INBOUND_ATTACHMENT_BYTES as u64)
+                {
+                    anyhow::bail!("attachment exceeds {MAX_INBOUND_ATTACHMENT_BYTES} bytes");
+                }
+                // Content-Length may be missing or wrong; cap the body as it streams in.
+                let mut bytes = Vec::new();
+                while let Some(chunk) = response.chunk().await? {
+                    if bytes.len() + chunk.len() > MAX_INBOUND_ATTACHMENT_BYTES {
+                        anyhow::bail!("attachment exceeds {MAX_INBOUND_ATTACHMENT_BYTES} bytes");
+                    }
+                    bytes.extend_from_slice(&chunk);
+                }
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Unique, filesystem-safe name that keeps a useful extension.
+    fn local_file_name(&self) -> String {
+        let stem = uuid::Uuid::new_v4().to_string();
+        let extension = self
+            .file_name
+            .as_deref()
+            .and_then(|name| Path::new(name).extension())
+            .and_then(|ext| ext.to_str())
+            .filter(|ext| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+            .map(str::to_ascii_lowercase)
+            .or_else(|| extension_for_mime(&self.mime_type).map(str::to_string));
+        match extension {
+            Some(ext) => format!("{stem}.{ext}"),
+            None => stem,
+        }
+    }
+}
+
+/// Known `(MIME type, extension)` pairs; the first match wins in either direction.
+const MIME_EXTENSIONS: &[(&str, &str)] = &[
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/jpeg", "jpeg"),
+    ("image/jpg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+    ("image/bmp", "bmp"),
+    ("image/svg+xml", "svg"),
+    ("application/pdf", "pdf"),
+    ("application/json", "json"),
+    ("application/zip", "zip"),
+    (
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "docx",
+    ),
+    (
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "xlsx",
+    ),
+    (
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "pptx",
+    ),
+    ("audio/ogg", "ogg"),
+    ("audio/mpeg", "mp3"),
+    ("audio/mp4", "m4a"),
+    ("audio/m4a", "m4a"),
+    ("audio/wav", "wav"),
+    ("audio/x-wav", "wav"),
+    ("video/mp4", "mp4"),
+    ("text/plain", "txt"),
+    ("text/plain", "log"),
+    ("text/markdown", "md"),
+    ("text/csv", "csv"),
+    ("text/html", "html"),
+];
+
+/// File extension for a MIME type, ignoring parameters such as `charset`.
+pub fn extension_for_mime(mime: &str) -> Option<&'static str> {
+    let mime = mime.split(';').next().unwrap_or_default().trim();
+    MIME_EXTENSIONS
+        .iter()
+        .find(|(known, _)| *known == mime)
+        .map(|(_, ext)| *ext)
+}
+
+/// MIME type for a file extension (case-insensitive).
+pub fn mime_for_extension(extension: &str) -> Option<&'static str> {
+    MIME_EXTENSIONS
+        .iter()
+        .find(|(_, ext)| ext.eq_ignore_ascii_case(extension))
+        .map(|(mime, _)| *mime)
 }
 
 /// Message to send through a channel
@@ -22,6 +284,63 @@ pub struct SendMessage {
     pub subject: Option<String>,
     /// Platform thread identifier for threaded replies (e.g. Slack `thread_ts`).
     pub thread_ts: Option<String>,
+    /// Local files to upload with the message. Only honoured by channels
+    /// whose [`Channel::supports_attachments`] returns `true`.
+    pub attachments: Vec<OutboundAttachment>,
+}
+
+/// A local file uploaded alongside an outbound message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboundAttachment {
+    pub path: PathBuf,
+    pub mime_type: String,
+    /// Name shown to recipients.
+    pub file_name: String,
+}
+
+impl OutboundAttachment {
+    /// Attachment for `path`, with the MIME type guessed from its extension.
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("file")
+            .to_string();
+        let mime_type = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(mime_for_extension)
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        Self {
+            path,
+            mime_type,
+            file_name,
+        }
+    }
+
+    #[must_use]
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = mime_type.into();
+        self
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+
+    /// Read the file, refusing anything above [`MAX_OUTBOUND_ATTACHMENT_BYTES`].
+    pub async fn read(&self) -> anyhow::Result<Vec<u8>> {
+        let size = tokio::fs::metadata(&self.path).await?.len();
+        if size > MAX_OUTBOUND_ATTACHMENT_BYTES as u64 {
+            anyhow::bail!(
+                "{} is {size} bytes (limit {MAX_OUTBOUND_ATTACHMENT_BYTES})",
+                self.path.display()
+            );
+        }
+        Ok(tokio::fs::read(&self.path).await?)
+    }
 }
 
 impl SendMessage {
@@ -32,6 +351,7 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: None,
             thread_ts: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -46,6 +366,7 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: Some(subject.into()),
             thread_ts: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -54,6 +375,12 @@ impl SendMessage {
         self.thread_ts = thread_ts;
         self
     }
+
+    /// Attach local files to upload with the message.
+    pub fn with_attachments(mut self, attachments: Vec<OutboundAttachment>) -> Self {
+        self.attachments = attachments;
+        self
+    }
 }
 
 /// Core channel trait — implement for any messaging platform
@@ -84,6 +411,12 @@ pub trait Channel: Send + Sync {
         Ok(())
     }
 
+    /// Whether `send` uploads [`SendMessage::attachments`]. Callers fall back
+    /// to mentioning file paths in the text when this is `false`.
+    fn supports_attachments(&self) -> bool {
+        false
+    }
+
     /// Whether this channel supports progressive message updates via draft edits.
     fn supports_draft_updates(&self) -> bool {
         false
@@ -118,6 +451,19 @@ pub trait Channel: Send + Sync {
     async fn cancel_draft(&self, _recipient: &str, _message_id: &str) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Ask the user to approve a gated tool call.
+    ///
+    /// `message` carries a plain-text prompt with `/approve <id>` reply keywords.
+    /// Channels with native buttons override this and deliver button presses
+    /// back through `listen` as the equivalent keyword message.
+    async fn send_approval_prompt(
+        &self,
+        message: &SendMessage,
+        _approval: &PendingApproval,
+    ) -> anyhow::Result<()> {
+        self.send(message).await
+    }
 }
 
 #[cfg(test)]
@@ -148,6 +494,7 @@ mod tests {
                 channel: "dummy".into(),
                 timestamp: 123,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -164,6 +511,7 @@ mod tests {
             channel: "dummy".into(),
             timestamp: 999,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         let cloned = message.clone();
@@ -218,4 +566,86 @@ mod tests {
         assert_eq!(received.content, "hello");
         assert_eq!(received.channel, "dummy");
     }
+
+    #[tokio::test]
+    async fn inline_attachment_is_written_once_with_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let attachment = ChannelAttachment::inline("image/PNG", b"png-bytes".to_vec())
+            .with_file_name(Some(String::new()));
+        assert!(attachment.is_image());
+        assert_eq!(attachment.display_name(), "image/png");
+
+        let first = attachment.local_path(dir.path()).await.unwrap();
+        let second = attachment.clone().local_path(dir.path()).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.extension().and_then(|e| e.to_str()), Some("png"));
+        assert_eq!(std::fs::read(&first).unwrap(), b"png-bytes");
+    }
+
+    #[tokio::test]
+    async fn outbound_attachment_guesses_mime_and_reads_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chart.PNG");
+        std::fs::write(&path, b"png").unwrap();
+
+        let attachment = OutboundAttachment::from_path(&path);
+        assert_eq!(attachment.mime_type, "image/png");
+        assert_eq!(attachment.file_name, "chart.PNG");
+        assert!(attachment.is_image());
+        assert_eq!(attachment.read().await.unwrap(), b"png");
+
+        let unknown = OutboundAttachment::from_path(dir.path().join("blob.xyz"));
+        assert_eq!(unknown.mime_type, "application/octet-stream");
+        assert!(unknown.read().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn oversized_attachment_is_not_downloaded() {
+        let attachment = ChannelAttachment::url(
+            "application/pdf",
+            "http://127.0.0.1:9/report.pdf",
+            None,
+            "channel.test",
+        )
+        .with_size(Some(MAX_INBOUND_ATTACHMENT_BYTES as u64 + 1));
+        assert!(attachment.is_pdf());
+        let err = attachment
+            .local_path(&std::env::temp_dir())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("limit"));
+    }
+
+    #[tokio::test]
+    async fn attachment_without_content_length_is_capped_while_streaming() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let _ = socket
+                .write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n")
+                .await;
+            let chunk = vec![0u8; 64 * 1024];
+            let mut sent = 0;
+            while sent <= MAX_INBOUND_ATTACHMENT_BYTES {
+                if socket.write_all(&chunk).await.is_err() {
+                    return;
+                }
+                sent += chunk.len();
+            }
+        });
+
+        let attachment = ChannelAttachment::url(
+            "application/pdf",
+            format!("http://{addr}/report.pdf"),
+            None,
+            "channel.test",
+        );
+        let err = attachment.fetch_bytes().await.unwrap_err();
+        assert!(err.to_string().contains("exceeds"));
+    }
 }
@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    AttachmentFetcher, AttachmentSource, Channel, ChannelAttachment, ChannelMessage, SendMessage,
+};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -29,6 +31,46 @@ pub struct WhatsAppChannel {
     allowed_numbers: Vec<String>,
 }
 
+/// Media kinds forwarded as attachments; the rest (video, stickers, ...) are skipped.
+const WHATSAPP_MEDIA_TYPES: [&str; 3] = ["image", "audio", "document"];
+
+/// Resolves a Cloud API media ID to its short-lived URL, then downloads it.
+/// Both requests need the access token.
+struct WhatsAppMediaFetcher {
+    access_token: String,
+    media_id: String,
+}
+
+#[async_trait]
+impl AttachmentFetcher for WhatsAppMediaFetcher {
+    async fn fetch(&self) -> anyhow::Result<Vec<u8>> {
+        let client = crate::config::build_runtime_proxy_client("channel.whatsapp");
+        let lookup_url = format!("https://graph.facebook.com/v18.0/{}", self.media_id);
+        let info: serde_json::Value = client
+            .get(&lookup_url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let url = info
+            .get("url")
+            .and_then(|u| u.as_str())
+            .ok_or_else(|| anyhow::anyhow!("WhatsApp media lookup returned no url"))?;
+        ensure_https(url)?;
+        let bytes = client
+            .get(url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        Ok(bytes.to_vec())
+    }
+}
+
 impl WhatsAppChannel {
     pub fn new(
         access_token: String,
@@ -58,6 +100,39 @@ impl WhatsAppChannel {
         &self.verify_token
     }
 
+    /// Caption and attachment for an image, audio or document message.
+    fn media_attachment(&self, msg: &serde_json::Value) -> Option<(String, ChannelAttachment)> {
+        let kind = msg.get("type").and_then(|t| t.as_str())?;
+        if !WHATSAPP_MEDIA_TYPES.contains(&kind) {
+            return None;
+        }
+        let media = msg.get(kind)?;
+        let media_id = media.get("id").and_then(|i| i.as_str())?;
+        let caption = media
+            .get("caption")
+            .and_then(|c| c.as_str())
+            .unwrap_or("")
+            .to_string();
+        let fetcher = WhatsAppMediaFetcher {
+            access_token: self.access_token.clone(),
+            media_id: media_id.to_string(),
+        };
+        let attachment = ChannelAttachment::new(
+            media
+                .get("mime_type")
+                .and_then(|m| m.as_str())
+                .unwrap_or_default(),
+            AttachmentSource::Fetcher(std::sync::Arc::new(fetcher)),
+        )
+        .with_file_name(
+            media
+                .get("filename")
+                .and_then(|f| f.as_str())
+                .map(str::to_string),
+        );
+        Some((caption, attachment))
+    }
+
     /// Parse an incoming webhook payload from Meta and extract messages
     pub fn parse_webhook_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
         let mut messages = Vec::new();
@@ -105,20 +180,22 @@ impl WhatsAppChannel {
                         continue;
                     }
 
-                    // Extract text content (support text messages only for now)
+                    let mut attachments = Vec::new();
                     let content = if let Some(text_obj) = msg.get("text") {
                         text_obj
                             .get("body")
                             .and_then(|b| b.as_str())
                             .unwrap_or("")
                             .to_string()
+                    } else if let Some((caption, attachment)) = self.media_attachment(msg) {
+                        attachments.push(attachment);
+                        caption
                     } else {
-                        // Could be image, audio, etc. — skip for now
-                        tracing::debug!("WhatsApp: skipping non-text message from {from}");
+                        tracing::debug!("WhatsApp: skipping unsupported message from {from}");
                         continue;
                     };
 
-                    if content.is_empty() {
+                    if content.is_empty() && attachments.is_empty() {
                         continue;
                     }
 
@@ -142,6 +219,7 @@ impl WhatsAppChannel {
                         channel: "whatsapp".to_string(),
                         timestamp,
                         thread_ts: None,
+                        attachments,
                     });
                 }
             }
@@ -349,7 +427,7 @@ mod tests {
     }
 
     #[test]
-    fn whatsapp_parse_non_text_message_skipped() {
+    fn whatsapp_parse_image_message_becomes_attachment() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
         let payload = serde_json::json!({
             "entry": [{
@@ -359,7 +437,7 @@ mod tests {
                             "from": "1234567890",
                             "timestamp": "1699999999",
                             "type": "image",
-                            "image": { "id": "img123" }
+                            "image": { "id": "img123", "mime_type": "image/jpeg", "caption": "look" }
                         }]
                     }
                 }]
@@ -367,7 +445,10 @@ mod tests {
         });
 
         let msgs = ch.parse_webhook_payload(&payload);
-        assert!(msgs.is_empty(), "Non-text messages should be skipped");
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].content, "look");
+        assert_eq!(msgs[0].attachments.len(), 1);
+        assert!(msgs[0].attachments[0].is_image());
     }
 
     #[test]
@@ -736,7 +817,7 @@ mod tests {
     }
 
     #[test]
-    fn whatsapp_parse_audio_message_skipped() {
+    fn whatsapp_parse_audio_message_becomes_attachment() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
         let payload = serde_json::json!({
             "entry": [{
@@ -753,7 +834,9 @@ mod tests {
             }]
         });
         let msgs = ch.parse_webhook_payload(&payload);
-        assert!(msgs.is_empty());
+        assert_eq!(msgs.len(), 1);
+        assert!(msgs[0].content.is_empty());
+        assert!(msgs[0].attachments[0].is_audio());
     }
 
     #[test]
@@ -778,7 +861,7 @@ mod tests {
     }
 
     #[test]
-    fn whatsapp_parse_document_message_skipped() {
+    fn whatsapp_parse_document_message_becomes_attachment() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
         let payload = serde_json::json!({
             "entry": [{
@@ -795,7 +878,9 @@ mod tests {
             }]
         });
         let msgs = ch.parse_webhook_payload(&payload);
-        assert!(msgs.is_empty());
+        assert_eq!(msgs.len(), 1);
+        assert!(msgs[0].attachments[0].is_pdf());
+        assert_eq!(msgs[0].attachments[0].display_name(), "file.pdf");
     }
 
     #[test]
@@ -287,6 +287,7 @@ impl Channel for WhatsAppWebChannel {
                                         content: trimmed.to_string(),
                                         timestamp: chrono::Utc::now().timestamp() as u64,
                                         thread_ts: None,
+                                        attachments: Vec::new(),
                                     })
                                     .await
                                 {
@@ -21,6 +21,8 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "provider.ollama",
     "provider.openai",
     "provider.openrouter",
+    "provider.transcription",
+    "provider.tts",
     "channel.dingtalk",
     "channel.discord",
     "channel.lark",
@@ -35,6 +37,7 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "tool.browser",
     "tool.composio",
     "tool.http_request",
+    "tool.mcp",
     "tool.pushover",
     "memory.embeddings",
     "tunnel.custom",
@@ -147,6 +150,10 @@ pub struct Config {
     #[serde(default)]
     pub secrets: SecretsConfig,
 
+    /// Sandbox, resource limit, and audit logging configuration (`[security]`).
+    #[serde(default)]
+    pub security: SecurityConfig,
+
     /// Browser automation configuration (`[browser]`).
     #[serde(default)]
     pub browser: BrowserConfig,
@@ -155,6 +162,10 @@ pub struct Config {
     #[serde(default)]
     pub http_request: HttpRequestConfig,
 
+    /// External Model Context Protocol tool servers (`[mcp]`).
+    #[serde(default)]
+    pub mcp: McpConfig,
+
     /// Multimodal (image) handling configuration (`[multimodal]`).
     #[serde(default)]
     pub multimodal: MultimodalConfig,
@@ -217,6 +228,8 @@ pub struct DelegateAgentConfig {
     #[serde(default)]
     pub agentic: bool,
     /// Allowlist of tool names available to the sub-agent in agentic mode.
+    /// `@read_only`, `@offline` and `@idempotent` admit every tool whose
+    /// declared metadata matches.
     #[serde(default)]
     pub allowed_tools: Vec<String>,
     /// Maximum tool-call iterations in agentic mode.
@@ -304,6 +317,7 @@ impl Default for HardwareConfig {
 
 /// Agent orchestration configuration (`[agent]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct AgentConfig {
     /// When true: bootstrap_max_chars=6000, rag_chunk_limit=2. Use for 13B or smaller models.
     #[serde(default)]
@@ -315,6 +329,18 @@ pub struct AgentConfig {
     /// Maximum conversation history messages retained per session. Default: `50`.
     #[serde(default = "default_agent_max_history_messages")]
     pub max_history_messages: usize,
+    /// Roll messages beyond `max_history_messages` into a running summary
+    /// instead of dropping them (interactive `Agent` sessions). Default: `true`.
+    #[serde(default = "default_agent_summarize_history")]
+    pub summarize_history: bool,
+    /// Store the running history summary in memory as a `conversation` entry.
+    /// Default: `false`.
+    #[serde(default)]
+    pub persist_history_summaries: bool,
+    /// Show the agent's task plan as a live checklist in channel drafts.
+    /// Default: `true`.
+    #[serde(default = "default_agent_show_plan_in_drafts")]
+    pub show_plan_in_drafts: bool,
     /// Enable parallel tool execution within a single iteration. Default: `false`.
     #[serde(default)]
     pub parallel_tools: bool,
@@ -350,6 +376,14 @@ fn default_agent_max_history_messages() -> usize {
     50
 }
 
+fn default_agent_summarize_history() -> bool {
+    true
+}
+
+fn default_agent_show_plan_in_drafts() -> bool {
+    true
+}
+
 fn default_agent_tool_dispatcher() -> String {
     "auto".into()
 }
@@ -384,6 +418,9 @@ impl Default for AgentConfig {
             compact_context: false,
             max_tool_iterations: default_agent_max_tool_iterations(),
             max_history_messages: default_agent_max_history_messages(),
+            summarize_history: default_agent_summarize_history(),
+            persist_history_summaries: false,
+            show_plan_in_drafts: default_agent_show_plan_in_drafts(),
             parallel_tools: false,
             tool_dispatcher: default_agent_tool_dispatcher(),
             trajectory_compression_enabled: default_agent_trajectory_compression_enabled(),
@@ -455,6 +492,12 @@ pub struct MultimodalConfig {
     /// Allow fetching remote image URLs (http/https). Disabled by default.
     #[serde(default)]
     pub allow_remote_fetch: bool,
+    /// Speech-to-text for inbound voice messages (`[multimodal.transcription]`).
+    #[serde(default)]
+    pub transcription: TranscriptionConfig,
+    /// Spoken replies on voice-first channels (`[multimodal.tts]`).
+    #[serde(default)]
+    pub tts: TtsConfig,
 }
 
 fn default_multimodal_max_images() -> usize {
@@ -480,6 +523,159 @@ impl Default for MultimodalConfig {
             max_images: default_multimodal_max_images(),
             max_image_size_mb: default_multimodal_max_image_size_mb(),
             allow_remote_fetch: false,
+            transcription: TranscriptionConfig::default(),
+            tts: TtsConfig::default(),
+        }
+    }
+}
+
+/// Voice message transcription configuration (`[multimodal.transcription]`).
+///
+/// When enabled, inbound audio attachments are transcribed and the text is
+/// used as the message content; the audio stays available as an attachment.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TranscriptionConfig {
+    /// Transcribe inbound voice messages (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Speech-to-text backend: "openai" | "command" | "stub"
+    #[serde(default = "default_transcription_backend")]
+    pub backend: String,
+    /// Base URL of an OpenAI-compatible API (`/audio/transcriptions` is appended)
+    #[serde(default = "default_audio_api_url")]
+    pub api_url: String,
+    /// API key for the transcription endpoint (falls back to `OPENAI_API_KEY`)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model name sent to the transcription endpoint
+    #[serde(default = "default_transcription_model")]
+    pub model: String,
+    /// Optional ISO-639-1 language hint (e.g. "en")
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Executable for the "command" backend (e.g. a whisper.cpp wrapper script)
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments for the "command" backend; `{file}` is replaced with the audio path
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Per-message transcription timeout in seconds
+    #[serde(default = "default_audio_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_transcription_backend() -> String {
+    "openai".into()
+}
+
+fn default_audio_api_url() -> String {
+    "https://api.openai.com/v1".into()
+}
+
+fn default_transcription_model() -> String {
+    "whisper-1".into()
+}
+
+fn default_audio_timeout_secs() -> u64 {
+    120
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_transcription_backend(),
+            api_url: default_audio_api_url(),
+            api_key: None,
+            model: default_transcription_model(),
+            language: None,
+            command: None,
+            args: Vec::new(),
+            timeout_secs: default_audio_timeout_secs(),
+        }
+    }
+}
+
+/// Text-to-speech reply configuration (`[multimodal.tts]`).
+///
+/// When enabled, final channel replies are also rendered to audio and sent as
+/// a voice note. Senders can toggle this with `/voice on|off`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TtsConfig {
+    /// Enable the text-to-speech stage (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Text-to-speech backend: "openai" | "command" | "stub"
+    #[serde(default = "default_tts_backend")]
+    pub backend: String,
+    /// Base URL of an OpenAI-compatible API (`/audio/speech` is appended)
+    #[serde(default = "default_audio_api_url")]
+    pub api_url: String,
+    /// API key for the speech endpoint (falls back to `OPENAI_API_KEY`)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model name sent to the speech endpoint
+    #[serde(default = "default_tts_model")]
+    pub model: String,
+    /// Voice name sent to the speech endpoint
+    #[serde(default = "default_tts_voice")]
+    pub voice: String,
+    /// Audio format: "opus" (Ogg voice notes) | "mp3" | "wav" | "aac" | "flac"
+    #[serde(default = "default_tts_format")]
+    pub format: String,
+    /// Executable for the "command" backend (e.g. piper); reply text is written to stdin
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments for the "command" backend; `{output}` is replaced with the audio
+    /// file path, otherwise audio is read from stdout
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Channels that send voice replies unless a sender turns them off
+    #[serde(default)]
+    pub channels: Vec<String>,
+    /// Replies longer than this many characters are sent as text only
+    #[serde(default = "default_tts_max_chars")]
+    pub max_chars: usize,
+    /// Per-reply synthesis timeout in seconds
+    #[serde(default = "default_audio_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_tts_backend() -> String {
+    "openai".into()
+}
+
+fn default_tts_model() -> String {
+    "tts-1".into()
+}
+
+fn default_tts_voice() -> String {
+    "alloy".into()
+}
+
+fn default_tts_format() -> String {
+    "opus".into()
+}
+
+fn default_tts_max_chars() -> usize {
+    1500
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_tts_backend(),
+            api_url: default_audio_api_url(),
+            api_key: None,
+            model: default_tts_model(),
+            voice: default_tts_voice(),
+            format: default_tts_format(),
+            command: None,
+            args: Vec::new(),
+            channels: Vec::new(),
+            max_chars: default_tts_max_chars(),
+            timeout_secs: default_audio_timeout_secs(),
         }
     }
 }
@@ -547,7 +743,7 @@ pub struct CostConfig {
 }
 
 /// Per-model pricing entry (USD per 1M tokens).
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ModelPricing {
     /// Input price per 1M tokens
     #[serde(default)]
@@ -556,6 +752,16 @@ pub struct ModelPricing {
     /// Output price per 1M tokens
     #[serde(default)]
     pub output: f64,
+
+    /// Price per 1M prompt tokens served from a provider prompt cache
+    /// (defaults to `input` when unset)
+    #[serde(default)]
+    pub cache_read: Option<f64>,
+
+    /// Price per 1M prompt tokens written to a provider prompt cache
+    /// (defaults to `input` when unset)
+    #[serde(default)]
+    pub cache_write: Option<f64>,
 }
 
 fn default_daily_limit() -> f64 {
@@ -593,6 +799,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 3.0,
             output: 15.0,
+            cache_read: Some(0.3),
+            cache_write: Some(3.75),
         },
     );
     prices.insert(
@@ -600,6 +808,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 15.0,
             output: 75.0,
+            cache_read: Some(1.5),
+            cache_write: Some(18.75),
         },
     );
     prices.insert(
@@ -607,6 +817,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 3.0,
             output: 15.0,
+            cache_read: Some(0.3),
+            cache_write: Some(3.75),
         },
     );
     prices.insert(
@@ -614,6 +826,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 0.25,
             output: 1.25,
+            cache_read: Some(0.025),
+            cache_write: Some(0.3125),
         },
     );
 
@@ -623,6 +837,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 5.0,
             output: 15.0,
+            cache_read: None,
+            cache_write: None,
         },
     );
     prices.insert(
@@ -630,6 +846,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 0.15,
             output: 0.60,
+            cache_read: None,
+            cache_write: None,
         },
     );
     prices.insert(
@@ -637,6 +855,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 15.0,
             output: 60.0,
+            cache_read: None,
+            cache_write: None,
         },
     );
 
@@ -646,6 +866,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 0.10,
             output: 0.40,
+            cache_read: None,
+            cache_write: None,
         },
     );
     prices.insert(
@@ -653,6 +875,8 @@ fn get_default_pricing() -> std::collections::HashMap<String, ModelPricing> {
         ModelPricing {
             input: 1.25,
             output: 5.0,
+            cache_read: None,
+            cache_write: None,
         },
     );
 
@@ -995,6 +1219,72 @@ fn default_http_timeout_secs() -> u64 {
     30
 }
 
+// ── MCP client ──────────────────────────────────────────────────
+
+/// Model Context Protocol client configuration (`[mcp]` section).
+///
+/// Each configured server is connected at tool-registry construction time and
+/// every tool it advertises is registered as `<server>__<tool>`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct McpConfig {
+    /// Enable MCP tool servers
+    #[serde(default)]
+    pub enabled: bool,
+    /// MCP servers to connect to (`[[mcp.servers]]`)
+    #[serde(default)]
+    pub servers: Vec<McpServerConfig>,
+}
+
+/// Transport used to reach an MCP server.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum McpTransport {
+    /// Spawn `command` and exchange newline-delimited JSON-RPC over stdin/stdout
+    #[default]
+    Stdio,
+    /// POST JSON-RPC to `url` (MCP streamable HTTP transport)
+    Http,
+}
+
+/// A single MCP server entry (`[[mcp.servers]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct McpServerConfig {
+    /// Server name; used as the tool name prefix
+    pub name: String,
+    /// Transport: "stdio" (default) or "http"
+    #[serde(default)]
+    pub transport: McpTransport,
+    /// Executable to spawn (stdio transport)
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments for `command` (stdio transport)
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment variables for the spawned server (stdio transport)
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Endpoint URL (http transport)
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Extra HTTP headers, e.g. `Authorization` (http transport)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Per-request timeout in seconds (default: 60)
+    #[serde(default = "default_mcp_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Only register these remote tools (empty = all)
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    /// Honour the server's `readOnlyHint` annotations (default: false, so
+    /// every remote tool is treated as mutating)
+    #[serde(default)]
+    pub trust_annotations: bool,
+}
+
+fn default_mcp_timeout_secs() -> u64 {
+    60
+}
+
 // ── Web search ───────────────────────────────────────────────────
 
 /// Web search tool configuration (`[web_search]` section).
@@ -1651,7 +1941,7 @@ pub struct MemoryConfig {
     /// For sqlite backend: prune conversation rows older than this many days
     #[serde(default = "default_conversation_retention_days")]
     pub conversation_retention_days: u32,
-    /// Embedding provider: "none" | "openai" | "custom:URL"
+    /// Embedding provider: "none" | "openai" | "openrouter" | "ollama" | "ollama:URL" | "custom:URL"
     #[serde(default = "default_embedding_provider")]
     pub embedding_provider: String,
     /// Embedding model name (e.g. "text-embedding-3-small")
@@ -1688,6 +1978,13 @@ pub struct MemoryConfig {
     /// Max number of cached responses before LRU eviction (default: 5000)
     #[serde(default = "default_response_cache_max")]
     pub response_cache_max_entries: usize,
+    /// Also match near-identical prompts by embedding similarity (needs an
+    /// `embedding_provider`)
+    #[serde(default)]
+    pub response_cache_semantic: bool,
+    /// Minimum cosine similarity for a semantic cache hit (default: 0.95)
+    #[serde(default = "default_response_cache_similarity")]
+    pub response_cache_similarity_threshold: f64,
 
     // ── Memory Snapshot (soul backup to Markdown) ─────────────
     /// Enable periodic export of core memories to MEMORY_SNAPSHOT.md
@@ -1705,6 +2002,10 @@ pub struct MemoryConfig {
     /// None = wait indefinitely (default). Recommended max: 300.
     #[serde(default)]
     pub sqlite_open_timeout_secs: Option<u64>,
+    /// For sqlite backend: build an IVF approximate nearest-neighbour index once
+    /// this many memories have embeddings. 0 = always use exact linear search.
+    #[serde(default = "default_sqlite_ann_min_rows")]
+    pub sqlite_ann_min_rows: usize,
 }
 
 fn default_embedding_provider() -> String {
@@ -1749,6 +2050,12 @@ fn default_response_cache_ttl() -> u32 {
 fn default_response_cache_max() -> usize {
     5_000
 }
+fn default_response_cache_similarity() -> f64 {
+    0.95
+}
+fn default_sqlite_ann_min_rows() -> usize {
+    2_048
+}
 
 impl Default for MemoryConfig {
     fn default() -> Self {
@@ -1770,10 +2077,13 @@ impl Default for MemoryConfig {
             response_cache_enabled: false,
             response_cache_ttl_minutes: default_response_cache_ttl(),
             response_cache_max_entries: default_response_cache_max(),
+            response_cache_semantic: false,
+            response_cache_similarity_threshold: default_response_cache_similarity(),
             snapshot_enabled: false,
             snapshot_on_hygiene: false,
             auto_hydrate: true,
             sqlite_open_timeout_secs: None,
+            sqlite_ann_min_rows: default_sqlite_ann_min_rows(),
         }
     }
 }
@@ -1841,6 +2151,30 @@ pub struct AutonomyConfig {
     /// Tools that always require interactive approval, even after "Always".
     #[serde(default = "default_always_ask")]
     pub always_ask: Vec<String>,
+
+    /// Where supervised tool approvals are requested: `cli` (default) or
+    /// `channel`, which asks in the originating chat and the gateway `/approvals` API.
+    #[serde(default)]
+    pub approval_transport: ApprovalTransport,
+
+    /// Seconds a channel/gateway approval may stay pending before it is denied. Default: `300`.
+    #[serde(default = "default_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+}
+
+/// How supervised tool approvals reach a human.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalTransport {
+    /// Prompt in the interactive CLI only; channel turns are not prompted.
+    #[default]
+    Cli,
+    /// Post pending approvals to the originating chat channel and the gateway.
+    Channel,
+}
+
+fn default_approval_timeout_secs() -> u64 {
+    300
 }
 
 fn default_auto_approve() -> Vec<String> {
@@ -1896,6 +2230,8 @@ impl Default for AutonomyConfig {
             block_high_risk_commands: true,
             auto_approve: default_auto_approve(),
             always_ask: default_always_ask(),
+            approval_transport: ApprovalTransport::Cli,
+            approval_timeout_secs: default_approval_timeout_secs(),
         }
     }
 }
@@ -2377,12 +2713,50 @@ pub struct ChannelsConfig {
     /// Default: 300s for on-device LLMs (Ollama) which are slower than cloud APIs.
     #[serde(default = "default_channel_message_timeout_secs")]
     pub message_timeout_secs: u64,
+    /// Persistent per-sender conversation sessions (`[channels_config.sessions]`).
+    #[serde(default)]
+    pub sessions: ChannelSessionsConfig,
 }
 
 fn default_channel_message_timeout_secs() -> u64 {
     300
 }
 
+/// Persistent channel conversation sessions (`[channels_config.sessions]`).
+///
+/// Per-sender history is stored in the workspace SQLite database
+/// (`memory/brain.db`) and restored when channels start.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelSessionsConfig {
+    /// Persist channel conversation history across restarts. Default: `true`.
+    #[serde(default = "default_true")]
+    pub persist: bool,
+    /// Drop sessions idle for longer than this many hours (0 = never). Default: 168 (7 days).
+    #[serde(default = "default_channel_session_ttl_hours")]
+    pub ttl_hours: u64,
+    /// Maximum persisted sessions (0 = unlimited); the least recently active are evicted first. Default: 1000.
+    #[serde(default = "default_channel_session_max_sessions")]
+    pub max_sessions: usize,
+}
+
+fn default_channel_session_ttl_hours() -> u64 {
+    168
+}
+
+fn default_channel_session_max_sessions() -> usize {
+    1000
+}
+
+impl Default for ChannelSessionsConfig {
+    fn default() -> Self {
+        Self {
+            persist: true,
+            ttl_hours: default_channel_session_ttl_hours(),
+            max_sessions: default_channel_session_max_sessions(),
+        }
+    }
+}
+
 impl Default for ChannelsConfig {
     fn default() -> Self {
         Self {
@@ -2404,6 +2778,7 @@ impl Default for ChannelsConfig {
             dingtalk: None,
             qq: None,
             message_timeout_secs: default_channel_message_timeout_secs(),
+            sessions: ChannelSessionsConfig::default(),
         }
     }
 }
@@ -2478,6 +2853,10 @@ pub struct SlackConfig {
     /// Allowed Slack user IDs. Empty = deny all.
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// Slack app signing secret. Enables approval buttons, delivered to the
+    /// gateway at `POST /slack/interactions` (set as the app's Interactivity URL).
+    #[serde(default)]
+    pub signing_secret: Option<String>,
 }
 
 /// Mattermost bot channel configuration.
@@ -2945,8 +3324,10 @@ impl Default for Config {
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
+            security: SecurityConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
+            mcp: McpConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_search: WebSearchConfig::default(),
             email_tool: EmailToolConfig::default(),
@@ -3338,6 +3719,18 @@ impl Config {
                 "config.web_search.brave_api_key",
             )?;
 
+            decrypt_optional_secret(
+                &store,
+                &mut config.multimodal.transcription.api_key,
+                "config.multimodal.transcription.api_key",
+            )?;
+
+            decrypt_optional_secret(
+                &store,
+                &mut config.multimodal.tts.api_key,
+                "config.multimodal.tts.api_key",
+            )?;
+
             decrypt_optional_secret(
                 &store,
                 &mut config.storage.provider.config.db_url,
@@ -3478,6 +3871,48 @@ impl Config {
             }
         }
 
+        // MCP servers
+        let mut mcp_names = std::collections::HashSet::new();
+        for (i, server) in self.mcp.servers.iter().enumerate() {
+            let name = server.name.trim();
+            if name.is_empty() {
+                anyhow::bail!("mcp.servers[{i}].name must not be empty");
+            }
+            if !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                anyhow::bail!(
+                    "mcp.servers[{i}].name may only contain ASCII letters, digits, '_' and '-'"
+                );
+            }
+            if !mcp_names.insert(name) {
+                anyhow::bail!("mcp.servers[{i}].name '{name}' is duplicated");
+            }
+            if server.timeout_secs == 0 {
+                anyhow::bail!("mcp.servers[{i}].timeout_secs must be greater than 0");
+            }
+            match server.transport {
+                McpTransport::Stdio => {
+                    if server
+                        .command
+                        .as_deref()
+                        .map_or(true, |c| c.trim().is_empty())
+                    {
+                        anyhow::bail!("mcp.servers[{i}].command is required for stdio transport");
+                    }
+                }
+                McpTransport::Http => {
+                    let url = server.url.as_deref().unwrap_or("").trim();
+                    if !(url.starts_with("http://") || url.starts_with("https://")) {
+                        anyhow::bail!(
+                            "mcp.servers[{i}].url must be an http(s) URL for http transport"
+                        );
+                    }
+                }
+            }
+        }
+
         // Proxy (delegate to existing validation)
         self.proxy.validate()?;
 
@@ -3793,6 +4228,18 @@ impl Config {
             "config.web_search.brave_api_key",
         )?;
 
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.multimodal.transcription.api_key,
+            "config.multimodal.transcription.api_key",
+        )?;
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.multimodal.tts.api_key,
+            "config.multimodal.tts.api_key",
+        )?;
+
         encrypt_optional_secret(
             &store,
             &mut config_to_save.storage.provider.config.db_url,
@@ -3986,6 +4433,17 @@ mod tests {
         assert_eq!(a.max_cost_per_day_cents, 500);
         assert!(a.require_approval_for_medium_risk);
         assert!(a.block_high_risk_commands);
+        assert_eq!(a.approval_transport, ApprovalTransport::Cli);
+    }
+
+    #[test]
+    async fn autonomy_approval_transport_parses_from_toml() {
+        let mut a = AutonomyConfig::default();
+        a.approval_transport = ApprovalTransport::Channel;
+        let toml_str = toml::to_string(&a).unwrap();
+        assert!(toml_str.contains(r#"approval_transport = "channel""#));
+        let parsed: AutonomyConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.approval_transport, ApprovalTransport::Channel);
     }
 
     #[test]
@@ -4096,6 +4554,8 @@ default_temperature = 0.7
                 block_high_risk_commands: true,
                 auto_approve: vec!["file_read".into()],
                 always_ask: vec![],
+                approval_transport: ApprovalTransport::Cli,
+                approval_timeout_secs: 300,
             },
             runtime: RuntimeConfig {
                 kind: "docker".into(),
@@ -4138,6 +4598,7 @@ default_temperature = 0.7
                 dingtalk: None,
                 qq: None,
                 message_timeout_secs: 300,
+                sessions: ChannelSessionsConfig::default(),
             },
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -4145,8 +4606,10 @@ default_temperature = 0.7
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
+            security: SecurityConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
+            mcp: McpConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_search: WebSearchConfig::default(),
             email_tool: EmailToolConfig::default(),
@@ -4321,8 +4784,10 @@ trajectory_tool_call_dedup_window = 7
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
             secrets: SecretsConfig::default(),
+            security: SecurityConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
+            mcp: McpConfig::default(),
             multimodal: MultimodalConfig::default(),
             web_search: WebSearchConfig::default(),
             email_tool: EmailToolConfig::default(),
@@ -4690,6 +5155,7 @@ allowed_users = ["@ops:matrix.org"]
             dingtalk: None,
             qq: None,
             message_timeout_secs: 300,
+            sessions: ChannelSessionsConfig::default(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -4900,6 +5366,7 @@ channel_id = "C123"
             dingtalk: None,
             qq: None,
             message_timeout_secs: 300,
+            sessions: ChannelSessionsConfig::default(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -5457,6 +5924,78 @@ default_temperature = 0.7
         assert!(result.is_ok(), "expected validation to pass: {result:?}");
     }
 
+    #[test]
+    async fn mcp_servers_parse_from_toml() {
+        let toml_str = r#"
+default_temperature = 0.7
+
+[mcp]
+enabled = true
+
+[[mcp.servers]]
+name = "internal"
+command = "internal-mcp"
+args = ["--stdio"]
+
+[[mcp.servers]]
+name = "remote"
+transport = "http"
+url = "https://mcp.example.com/mcp"
+headers = { Authorization = "Bearer token" }
+"#;
+        let parsed: Config = toml::from_str(toml_str).unwrap();
+        assert!(parsed.mcp.enabled);
+        assert_eq!(parsed.mcp.servers.len(), 2);
+        assert_eq!(parsed.mcp.servers[0].transport, McpTransport::Stdio);
+        assert_eq!(parsed.mcp.servers[0].timeout_secs, 60);
+        assert_eq!(parsed.mcp.servers[1].transport, McpTransport::Http);
+        assert_eq!(
+            parsed.mcp.servers[1]
+                .headers
+                .get("Authorization")
+                .map(String::as_str),
+            Some("Bearer token")
+        );
+        assert!(parsed.validate().is_ok());
+    }
+
+    #[test]
+    async fn validate_rejects_incomplete_mcp_servers() {
+        let server = McpServerConfig {
+            name: "internal".into(),
+            transport: McpTransport::Stdio,
+            command: None,
+            args: Vec::new(),
+            env: HashMap::new(),
+            url: None,
+            headers: HashMap::new(),
+            timeout_secs: 60,
+            allowed_tools: Vec::new(),
+            trust_annotations: false,
+        };
+
+        let mut config = Config::default();
+        config.mcp.servers = vec![server.clone()];
+        let error = config.validate().expect_err("stdio server without command");
+        assert!(error.to_string().contains("mcp.servers[0].command"));
+
+        config.mcp.servers = vec![McpServerConfig {
+            transport: McpTransport::Http,
+            url: Some("ftp://example.com".into()),
+            ..server.clone()
+        }];
+        let error = config.validate().expect_err("http server with bad url");
+        assert!(error.to_string().contains("mcp.servers[0].url"));
+
+        let valid = McpServerConfig {
+            command: Some("internal-mcp".into()),
+            ..server
+        };
+        config.mcp.servers = vec![valid.clone(), valid];
+        let error = config.validate().expect_err("duplicate server names");
+        assert!(error.to_string().contains("duplicated"));
+    }
+
     #[test]
     async fn env_override_model_fallback() {
         let _env_guard = env_override_lock().await;
@@ -1,5 +1,5 @@
 use super::types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
-use crate::config::schema::CostConfig;
+use crate::config::schema::{CostConfig, ModelPricing};
 use anyhow::{anyhow, Context, Result};
 use chrono::{Datelike, NaiveDate, Utc};
 use parking_lot::{Mutex, MutexGuard};
@@ -133,6 +133,81 @@ impl CostTracker {
         Ok(())
     }
 
+    /// Resolve configured pricing for a model.
+    ///
+    /// Price keys may be bare model IDs (`gpt-4o`) or `provider/model`
+    /// (`openai/gpt-4o`); the bare ID, the provider-qualified ID, and finally
+    /// any `*/model` entry are tried in that order. When several `*/model`
+    /// entries disagree on price the match is ambiguous and `None` is returned.
+    pub fn pricing_for(&self, provider: &str, model: &str) -> Option<&ModelPricing> {
+        let prices = &self.config.prices;
+        prices
+            .get(model)
+            .or_else(|| prices.get(&format!("{provider}/{model}")))
+            .or_else(|| {
+                let mut matches = prices
+                    .iter()
+                    .filter(|(key, _)| key.rsplit_once('/').is_some_and(|(_, id)| id == model))
+                    .map(|(_, pricing)| pricing);
+                let first = matches.next()?;
+                if matches.all(|pricing| pricing == first) {
+                    Some(first)
+                } else {
+                    tracing::debug!(
+                        "Ambiguous pricing for model '{model}'; configure '{provider}/{model}'"
+                    );
+                    None
+                }
+            })
+    }
+
+    /// Build a priced usage record for a provider call.
+    ///
+    /// Models without a configured price are recorded with zero cost so their
+    /// token counts still show up in summaries.
+    pub fn usage_for(
+        &self,
+        provider: &str,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) -> TokenUsage {
+        let (input_price, output_price) = self
+            .pricing_for(provider, model)
+            .map_or((0.0, 0.0), |pricing| (pricing.input, pricing.output));
+        TokenUsage::new(
+            model,
+            input_tokens,
+            output_tokens,
+            input_price,
+            output_price,
+        )
+    }
+
+    /// Like [`Self::usage_for`], pricing prompt-cache reads and writes with the
+    /// model's `cache_read` / `cache_write` prices (falling back to `input`).
+    pub fn cached_usage_for(
+        &self,
+        provider: &str,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_write_tokens: u64,
+    ) -> TokenUsage {
+        let usage = self.usage_for(provider, model, input_tokens, output_tokens);
+        let Some(pricing) = self.pricing_for(provider, model) else {
+            return usage.with_prompt_cache(cache_read_tokens, cache_write_tokens, 0.0, 0.0, 0.0);
+        };
+        usage.with_prompt_cache(
+            cache_read_tokens,
+            cache_write_tokens,
+            pricing.input,
+            pricing.cache_read.unwrap_or(pricing.input),
+            pricing.cache_write.unwrap_or(pricing.input),
+        )
+    }
+
     /// Get the current cost summary.
     pub fn get_summary(&self) -> Result<CostSummary> {
         let (daily_cost, monthly_cost) = {
@@ -420,6 +495,83 @@ mod tests {
         assert!(!tracker.session_id().is_empty());
     }
 
+    #[test]
+    fn pricing_lookup_accepts_bare_and_provider_qualified_models() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        let qualified = tracker.pricing_for("openrouter", "openai/gpt-4o").unwrap();
+        assert!((qualified.input - 5.0).abs() < f64::EPSILON);
+
+        let by_provider = tracker.pricing_for("openai", "gpt-4o-mini").unwrap();
+        assert!((by_provider.output - 0.60).abs() < f64::EPSILON);
+
+        let by_suffix = tracker
+            .pricing_for("custom", "claude-sonnet-4-20250514")
+            .unwrap();
+        assert!((by_suffix.input - 3.0).abs() < f64::EPSILON);
+
+        assert!(tracker.pricing_for("openai", "unknown-model").is_none());
+    }
+
+    #[test]
+    fn pricing_lookup_rejects_ambiguous_suffix_matches() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = enabled_config();
+        config.prices.clear();
+        for (key, input) in [("alpha/shared-model", 1.0), ("beta/shared-model", 2.0)] {
+            config.prices.insert(
+                key.to_string(),
+                ModelPricing {
+                    input,
+                    output: 1.0,
+                    cache_read: None,
+                    cache_write: None,
+                },
+            );
+        }
+        let tracker = CostTracker::new(config, tmp.path()).unwrap();
+
+        assert!(tracker.pricing_for("custom", "shared-model").is_none());
+        let exact = tracker.pricing_for("beta", "shared-model").unwrap();
+        assert!((exact.input - 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn usage_for_applies_configured_prices() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        let usage = tracker.usage_for("openai", "gpt-4o", 1_000_000, 1_000_000);
+        assert!((usage.cost_usd - 20.0).abs() < 1e-9);
+
+        let unpriced = tracker.usage_for("openai", "unknown-model", 1_000, 1_000);
+        assert_eq!(unpriced.total_tokens, 2_000);
+        assert!(unpriced.cost_usd.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn cached_usage_for_uses_cache_prices_with_input_fallback() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        // Sonnet: input 3.0, cache_read 0.3, output 15.0.
+        let usage = tracker.cached_usage_for(
+            "anthropic",
+            "claude-sonnet-4-20250514",
+            1_000_000,
+            0,
+            1_000_000,
+            0,
+        );
+        assert!((usage.cost_usd - 0.3).abs() < 1e-9);
+        assert_eq!(usage.cache_read_tokens, 1_000_000);
+
+        // gpt-4o has no cache prices configured, so cached input costs the same.
+        let usage = tracker.cached_usage_for("openai", "gpt-4o", 1_000_000, 0, 500_000, 0);
+        assert!((usage.cost_usd - 5.0).abs() < 1e-9);
+    }
+
     #[test]
     fn budget_check_when_disabled() {
         let tmp = TempDir::new().unwrap();
@@ -11,6 +11,12 @@ pub struct TokenUsage {
     pub output_tokens: u64,
     /// Total tokens
     pub total_tokens: u64,
+    /// Portion of the input served from a provider prompt cache
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    /// Portion of the input written to a provider prompt cache
+    #[serde(default)]
+    pub cache_write_tokens: u64,
     /// Calculated cost in USD
     pub cost_usd: f64,
     /// Timestamp of the request
@@ -49,11 +55,40 @@ impl TokenUsage {
             input_tokens,
             output_tokens,
             total_tokens,
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
             cost_usd,
             timestamp: chrono::Utc::now(),
         }
     }
 
+    /// Re-price the cached portion of the input.
+    ///
+    /// `input_tokens` includes cache reads and writes; those tokens are moved
+    /// from the regular input price to the cache read/write prices.
+    pub fn with_prompt_cache(
+        mut self,
+        cache_read_tokens: u64,
+        cache_write_tokens: u64,
+        input_price_per_million: f64,
+        cache_read_price_per_million: f64,
+        cache_write_price_per_million: f64,
+    ) -> Self {
+        let input_price = Self::sanitize_price(input_price_per_million);
+        let read_price = Self::sanitize_price(cache_read_price_per_million);
+        let write_price = Self::sanitize_price(cache_write_price_per_million);
+        let read = cache_read_tokens.min(self.input_tokens);
+        let write = cache_write_tokens.min(self.input_tokens - read);
+
+        let per_million = |tokens: u64, price: f64| (tokens as f64 / 1_000_000.0) * price;
+        self.cost_usd += per_million(read, read_price - input_price)
+            + per_million(write, write_price - input_price);
+        self.cost_usd = self.cost_usd.max(0.0);
+        self.cache_read_tokens = read;
+        self.cache_write_tokens = write;
+        self
+    }
+
     /// Get the total cost.
     pub fn cost(&self) -> f64 {
         self.cost_usd
@@ -181,6 +216,19 @@ mod tests {
         assert_eq!(usage.total_tokens, 2000);
     }
 
+    #[test]
+    fn prompt_cache_reprices_cached_input() {
+        // 1M input of which 600k cache reads and 200k cache writes.
+        let usage = TokenUsage::new("test/model", 1_000_000, 0, 3.0, 15.0)
+            .with_prompt_cache(600_000, 200_000, 3.0, 0.3, 3.75);
+
+        // 200k uncached * 3 + 600k * 0.3 + 200k * 3.75 = 0.6 + 0.18 + 0.75
+        assert!((usage.cost_usd - 1.53).abs() < 1e-9);
+        assert_eq!(usage.cache_read_tokens, 600_000);
+        assert_eq!(usage.cache_write_tokens, 200_000);
+        assert_eq!(usage.total_tokens, 1_000_000);
+    }
+
     #[test]
     fn cost_record_creation() {
         let usage = TokenUsage::new("test/model", 100, 50, 1.0, 2.0);
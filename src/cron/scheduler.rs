@@ -1,3 +1,4 @@
+use crate::agent::loop_::TOOL_LOOP_RESPONSE_FORMAT;
 use crate::channels::{
     Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
 };
@@ -165,17 +166,28 @@ async fn run_agent_job(
     let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
     let model_override = job.model.clone();
 
+    // Each run is its own session, so its plan is persisted and inspectable
+    // without leaking into the job's next run.
+    let session_id = format!("cron-{}-{}", job.id, Utc::now().format("%Y%m%dT%H%M%S"));
+    let response_format = job.response_format.clone().map(Arc::new);
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
-            crate::agent::run(
-                config.clone(),
-                Some(prefixed_prompt),
-                None,
-                model_override,
-                config.default_temperature,
-                vec![],
-            )
-            .await
+            crate::gateway::CURRENT_SESSION_ID
+                .scope(
+                    session_id,
+                    TOOL_LOOP_RESPONSE_FORMAT.scope(
+                        response_format,
+                        crate::agent::run(
+                            config.clone(),
+                            Some(prefixed_prompt),
+                            None,
+                            model_override,
+                            config.default_temperature,
+                            vec![],
+                        ),
+                    ),
+                )
+                .await
         }
     };
 
@@ -544,6 +556,7 @@ mod tests {
             job_type: JobType::Shell,
             session_target: SessionTarget::Isolated,
             model: None,
+            response_format: None,
             enabled: true,
             delivery: DeliveryConfig::default(),
             delete_after_run: false,
@@ -808,6 +821,7 @@ mod tests {
             SessionTarget::Isolated,
             None,
             None,
+            None,
             true,
         )
         .unwrap();
@@ -833,6 +847,7 @@ mod tests {
             SessionTarget::Isolated,
             None,
             None,
+            None,
             true,
         )
         .unwrap();
@@ -3,6 +3,7 @@ use crate::cron::{
     next_run_for_schedule, schedule_cron_expression, validate_schedule, CronJob, CronJobPatch,
     CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
 };
+use crate::providers::ResponseFormat;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::types::{FromSqlResult, ValueRef};
@@ -72,6 +73,7 @@ pub fn add_agent_job(
     prompt: &str,
     session_target: SessionTarget,
     model: Option<String>,
+    response_format: Option<ResponseFormat>,
     delivery: Option<DeliveryConfig>,
     delete_after_run: bool,
 ) -> Result<CronJob> {
@@ -82,13 +84,14 @@ pub fn add_agent_job(
     let expression = schedule_cron_expression(&schedule).unwrap_or_default();
     let schedule_json = serde_json::to_string(&schedule)?;
     let delivery = delivery.unwrap_or_default();
+    let response_format = encode_response_format(response_format.as_ref())?;
 
     with_connection(config, |conn| {
         conn.execute(
             "INSERT INTO cron_jobs (
                 id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                enabled, delivery, delete_after_run, created_at, next_run
-             ) VALUES (?1, ?2, '', ?3, 'agent', ?4, ?5, ?6, ?7, 1, ?8, ?9, ?10, ?11)",
+                enabled, delivery, delete_after_run, created_at, next_run, response_format
+             ) VALUES (?1, ?2, '', ?3, 'agent', ?4, ?5, ?6, ?7, 1, ?8, ?9, ?10, ?11, ?12)",
             params![
                 id,
                 expression,
@@ -101,6 +104,7 @@ pub fn add_agent_job(
                 if delete_after_run { 1 } else { 0 },
                 now.to_rfc3339(),
                 next_run.to_rfc3339(),
+                response_format,
             ],
         )
         .context("Failed to insert cron agent job")?;
@@ -114,7 +118,8 @@ pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    response_format
              FROM cron_jobs ORDER BY next_run ASC",
         )?;
 
@@ -132,7 +137,8 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    response_format
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -165,7 +171,8 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    response_format
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
              ORDER BY next_run ASC
@@ -210,6 +217,9 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     if let Some(model) = patch.model {
         job.model = Some(model);
     }
+    if let Some(response_format) = patch.response_format {
+        job.response_format = Some(response_format);
+    }
     if let Some(target) = patch.session_target {
         job.session_target = target;
     }
@@ -226,8 +236,8 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
             "UPDATE cron_jobs
              SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
                  session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
-                 next_run = ?12
-             WHERE id = ?13",
+                 next_run = ?12, response_format = ?13
+             WHERE id = ?14",
             params![
                 job.expression,
                 job.command,
@@ -241,6 +251,7 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
                 serde_json::to_string(&job.delivery)?,
                 if job.delete_after_run { 1 } else { 0 },
                 job.next_run.to_rfc3339(),
+                encode_response_format(job.response_format.as_ref())?,
                 job.id,
             ],
         )
@@ -425,6 +436,9 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
     let next_run_raw: String = row.get(13)?;
     let last_run_raw: Option<String> = row.get(14)?;
     let created_at_raw: String = row.get(12)?;
+    let response_format_raw: Option<String> = row.get(17)?;
+    let response_format =
+        decode_response_format(response_format_raw.as_deref()).map_err(sql_conversion_error)?;
 
     Ok(CronJob {
         id: row.get(0)?,
@@ -436,6 +450,7 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
         name: row.get(6)?,
         session_target: SessionTarget::parse(&row.get::<_, String>(7)?),
         model: row.get(8)?,
+        response_format,
         enabled: row.get::<_, i64>(9)? != 0,
         delivery,
         delete_after_run: row.get::<_, i64>(11)? != 0,
@@ -480,6 +495,22 @@ fn decode_delivery(delivery_raw: Option<&str>) -> Result<DeliveryConfig> {
     Ok(DeliveryConfig::default())
 }
 
+fn encode_response_format(response_format: Option<&ResponseFormat>) -> Result<Option<String>> {
+    response_format
+        .map(serde_json::to_string)
+        .transpose()
+        .context("Failed to encode cron response_format")
+}
+
+fn decode_response_format(raw: Option<&str>) -> Result<Option<ResponseFormat>> {
+    match raw.map(str::trim) {
+        Some(trimmed) if !trimmed.is_empty() => serde_json::from_str(trimmed)
+            .map(Some)
+            .with_context(|| format!("Failed to parse cron response_format JSON: {trimmed}")),
+        _ => Ok(None),
+    }
+}
+
 fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Result<()> {
     let mut stmt = conn.prepare("PRAGMA table_info(cron_jobs)")?;
     let mut rows = stmt.query([])?;
@@ -532,6 +563,7 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
             name             TEXT,
             session_target   TEXT NOT NULL DEFAULT 'isolated',
             model            TEXT,
+            response_format  TEXT,
             enabled          INTEGER NOT NULL DEFAULT 1,
             delivery         TEXT,
             delete_after_run INTEGER NOT NULL DEFAULT 0,
@@ -568,6 +600,7 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     add_column_if_missing(&conn, "enabled", "INTEGER NOT NULL DEFAULT 1")?;
     add_column_if_missing(&conn, "delivery", "TEXT")?;
     add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "response_format", "TEXT")?;
 
     f(&conn)
 }
@@ -600,6 +633,48 @@ mod tests {
         assert!(matches!(job.schedule, Schedule::Cron { .. }));
     }
 
+    #[test]
+    fn agent_job_persists_response_format() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let format = ResponseFormat::json_schema(
+            "report",
+            serde_json::json!({"type": "object", "required": ["status"]}),
+        );
+
+        let job = add_agent_job(
+            &config,
+            None,
+            Schedule::Cron {
+                expr: "0 9 * * *".into(),
+                tz: None,
+            },
+            "Summarize",
+            SessionTarget::Isolated,
+            None,
+            Some(format.clone()),
+            None,
+            false,
+        )
+        .unwrap();
+        assert_eq!(job.response_format.as_ref(), Some(&format));
+
+        let updated = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                enabled: Some(false),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.response_format, Some(format));
+        assert!(add_job(&config, "*/5 * * * *", "echo ok")
+            .unwrap()
+            .response_format
+            .is_none());
+    }
+
     #[test]
     fn add_list_remove_roundtrip() {
         let tmp = TempDir::new().unwrap();
@@ -1,3 +1,4 @@
+use crate::providers::ResponseFormat;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -112,6 +113,9 @@ pub struct CronJob {
     pub job_type: JobType,
     pub session_target: SessionTarget,
     pub model: Option<String>,
+    /// JSON schema an agent job's final reply must satisfy.
+    #[serde(default)]
+    pub response_format: Option<ResponseFormat>,
     pub enabled: bool,
     pub delivery: DeliveryConfig,
     pub delete_after_run: bool,
@@ -142,6 +146,7 @@ pub struct CronJobPatch {
     pub enabled: Option<bool>,
     pub delivery: Option<DeliveryConfig>,
     pub model: Option<String>,
+    pub response_format: Option<ResponseFormat>,
     pub session_target: Option<SessionTarget>,
     pub delete_after_run: Option<bool>,
 }
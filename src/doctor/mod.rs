@@ -451,26 +451,41 @@ fn provider_validation_error(name: &str) -> Option<String> {
 
 fn embedding_provider_validation_error(name: &str) -> Option<String> {
     let normalized = name.trim();
-    if normalized.eq_ignore_ascii_case("none") || normalized.eq_ignore_ascii_case("openai") {
+    if ["none", "openai", "openrouter", "ollama"]
+        .iter()
+        .any(|known| normalized.eq_ignore_ascii_case(known))
+    {
         return None;
     }
 
-    let Some(url) = normalized.strip_prefix("custom:") else {
-        return Some("supported values: none, openai, custom:<url>".into());
+    let Some((kind, url)) = normalized
+        .strip_prefix("custom:")
+        .map(|url| ("custom", url))
+        .or_else(|| {
+            normalized
+                .strip_prefix("ollama:")
+                .map(|url| ("ollama", url))
+        })
+    else {
+        return Some(
+            "supported values: none, openai, openrouter, ollama, ollama:<url>, custom:<url>".into(),
+        );
     };
 
     let url = url.trim();
     if url.is_empty() {
-        return Some("custom provider requires a non-empty URL after 'custom:'".into());
+        return Some(format!(
+            "{kind} provider requires a non-empty URL after '{kind}:'"
+        ));
     }
 
     match reqwest::Url::parse(url) {
         Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => None,
         Ok(parsed) => Some(format!(
-            "custom provider URL must use http/https, got '{}'",
+            "{kind} provider URL must use http/https, got '{}'",
             parsed.scheme()
         )),
-        Err(err) => Some(format!("invalid custom provider URL: {err}")),
+        Err(err) => Some(format!("invalid {kind} provider URL: {err}")),
     }
 }
 
@@ -1010,6 +1025,15 @@ mod tests {
         assert_eq!(route_item.unwrap().severity, Severity::Warn);
     }
 
+    #[test]
+    fn embedding_provider_validation_accepts_ollama() {
+        assert!(embedding_provider_validation_error("ollama").is_none());
+        assert!(embedding_provider_validation_error("ollama:http://10.0.0.5:11434").is_none());
+        assert!(embedding_provider_validation_error("ollama:")
+            .unwrap()
+            .contains("non-empty URL after 'ollama:'"));
+    }
+
     #[test]
     fn config_validation_warns_missing_embedding_hint_target() {
         let mut config = Config::default();
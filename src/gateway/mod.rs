@@ -324,18 +324,24 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let actual_port = listener.local_addr()?.port();
     let display_addr = format!("{host}:{actual_port}");
 
-    let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider_with_options(
-        config.default_provider.as_deref().unwrap_or("openrouter"),
-        config.api_key.as_deref(),
-        config.api_url.as_deref(),
-        &config.reliability,
-        &providers::ProviderRuntimeOptions {
-            auth_profile_override: None,
-            zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
-            secrets_encrypt: config.secrets.encrypt,
-            reasoning_enabled: config.runtime.reasoning_enabled,
-        },
-    )?);
+    let observer: Arc<dyn crate::observability::Observer> =
+        Arc::from(crate::observability::create_observer(&config.observability));
+    let provider: Arc<dyn Provider> = Arc::from(providers::with_response_cache(
+        providers::create_resilient_provider_with_options(
+            config.default_provider.as_deref().unwrap_or("openrouter"),
+            config.api_key.as_deref(),
+            config.api_url.as_deref(),
+            &config.reliability,
+            &providers::ProviderRuntimeOptions {
+                auth_profile_override: None,
+                zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+                secrets_encrypt: config.secrets.encrypt,
+                reasoning_enabled: config.runtime.reasoning_enabled,
+            },
+        )?,
+        &config,
+        Some(observer.clone()),
+    ));
     let model = config
         .default_model
         .clone()
@@ -574,9 +580,6 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     crate::health::mark_component_ok("gateway");
 
     // Build shared state
-    let observer: Arc<dyn crate::observability::Observer> =
-        Arc::from(crate::observability::create_observer(&config.observability));
-
     let state = AppState {
         config: config_state,
         provider,
@@ -611,6 +614,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/whatsapp", post(handle_whatsapp_message))
         .route("/linq", post(handle_linq_webhook))
         .route("/nextcloud-talk", post(handle_nextcloud_talk_webhook))
+        .route("/slack/interactions", post(handle_slack_interactions))
         // Management API (bearer token required)
         .route("/status", get(handle_status))
         .route("/dashboard/metrics", get(handle_dashboard_metrics))
@@ -649,6 +653,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/channels", get(handle_channels_list))
         // Traces API (bearer token required)
         .route("/traces/{session_id}", get(handle_trace_get))
+        // Approvals API (bearer token required)
+        .route("/approvals", get(handle_approvals_list))
+        .route("/approvals/{id}", post(handle_approval_resolve))
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -1494,6 +1501,15 @@ async fn handle_whatsapp_message(
         .default_provider
         .clone()
         .unwrap_or_else(|| "unknown".to_string());
+    let (workspace_dir, multimodal) = {
+        let config = state.config.lock();
+        (config.workspace_dir.clone(), config.multimodal.clone())
+    };
+    let transcriber = crate::transcription::create_transcriber(&multimodal.transcription)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Voice transcription disabled: {e:#}");
+            None
+        });
     for msg in &messages {
         tracing::info!(
             "WhatsApp message from {}: {}",
@@ -1501,16 +1517,26 @@ async fn handle_whatsapp_message(
             truncate_with_ellipsis(&msg.content, 50)
         );
 
+        let content = crate::channels::content_with_inbound_attachments(
+            msg,
+            &workspace_dir,
+            state.tools_registry.as_slice(),
+            state.provider.supports_vision(),
+            &multimodal,
+            transcriber.as_deref(),
+        )
+        .await;
+
         // Auto-save to memory
         if state.auto_save {
             let key = whatsapp_memory_key(msg);
             let _ = state
                 .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                .store(&key, &content, MemoryCategory::Conversation, None)
                 .await;
         }
 
-        match run_gateway_chat_with_multimodal(&state, &provider_label, &msg.content).await {
+        match run_gateway_chat_with_multimodal(&state, &provider_label, &content).await {
             Ok(response) => {
                 // Send reply via WhatsApp
                 if let Err(e) = wa
@@ -2373,6 +2399,7 @@ async fn handle_tools_list(State(state): State<AppState>, headers: HeaderMap) ->
             serde_json::json!({
                 "name": t.name(),
                 "description": t.description(),
+                "metadata": t.metadata(),
             })
         })
         .collect();
@@ -2403,6 +2430,7 @@ async fn handle_tools_get(
                 "name": spec.name,
                 "description": spec.description,
                 "parameters": spec.parameters,
+                "metadata": tool.metadata(),
             });
             (StatusCode::OK, Json(body))
         }
@@ -2454,6 +2482,7 @@ struct CronCreateBody {
     #[serde(default)]
     session_target: cron::SessionTarget,
     model: Option<String>,
+    response_format: Option<crate::providers::ResponseFormat>,
     delivery: Option<cron::DeliveryConfig>,
     #[serde(default)]
     delete_after_run: bool,
@@ -2500,6 +2529,7 @@ async fn handle_cron_create(
                 prompt,
                 data.session_target.clone(),
                 data.model.clone(),
+                data.response_format.clone(),
                 data.delivery.clone(),
                 data.delete_after_run,
             )
@@ -2905,6 +2935,206 @@ async fn handle_trace_get(
     }
 }
 
+// ══════════════════════════════════════════════════════════════════════════════
+// APPROVALS API HANDLERS
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// GET /approvals — list tool calls waiting for human approval
+async fn handle_approvals_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Some(resp) = require_pairing_auth(&state, &headers) {
+        return resp;
+    }
+
+    let approvals = crate::approval::pending_approvals();
+    let body = serde_json::json!({
+        "count": approvals.len(),
+        "approvals": approvals,
+    });
+    (StatusCode::OK, Json(body))
+}
+
+#[derive(serde::Deserialize)]
+struct ApprovalResolveBody {
+    decision: crate::approval::ApprovalResponse,
+}
+
+/// POST /approvals/{id} — resolve a pending approval (`{"decision": "yes"|"no"|"always"}`)
+async fn handle_approval_resolve(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    axum::extract::Path(id): axum::extract::Path<String>,
+    body: Result<Json<ApprovalResolveBody>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if let Some(resp) = require_pairing_auth(&state, &headers) {
+        return resp;
+    }
+
+    let Ok(Json(body)) = body else {
+        let err = serde_json::json!({
+            "error": "Invalid JSON body. Expected: {\"decision\": \"yes\" | \"no\" | \"always\"}"
+        });
+        return (StatusCode::BAD_REQUEST, Json(err));
+    };
+
+    match crate::approval::resolve_pending_approval(&id, body.decision, "gateway", None) {
+        Ok(pending) => {
+            let body = serde_json::json!({
+                "resolved": true,
+                "id": pending.id,
+                "tool_name": pending.tool_name,
+                "decision": body.decision,
+            });
+            (StatusCode::OK, Json(body))
+        }
+        Err(e) => {
+            let err = serde_json::json!({"error": e.to_string(), "id": id});
+            (StatusCode::NOT_FOUND, Json(err))
+        }
+    }
+}
+
+/// Maximum age of a Slack interaction request before it is treated as a replay.
+const SLACK_SIGNATURE_MAX_AGE_SECS: i64 = 300;
+
+/// Verify a Slack request signature (`X-Slack-Signature: v0=<hex>`).
+/// See: <https://api.slack.com/authentication/verifying-requests-from-slack>
+pub fn verify_slack_signature(
+    signing_secret: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature_header: &str,
+) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    let Some(hex_sig) = signature_header.strip_prefix("v0=") else {
+        return false;
+    };
+    let Ok(expected) = hex::decode(hex_sig) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(signing_secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(b"v0:");
+    mac.update(timestamp.as_bytes());
+    mac.update(b":");
+    mac.update(body);
+
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// POST /slack/interactions — approval button presses from Slack Block Kit messages
+async fn handle_slack_interactions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let (signing_secret, allowed_users) = {
+        let config = state.config.lock();
+        let slack = config.channels_config.slack.as_ref();
+        (
+            slack
+                .and_then(|sl| sl.signing_secret.as_deref())
+                .map(str::trim)
+                .filter(|secret| !secret.is_empty())
+                .map(ToOwned::to_owned),
+            slack.map(|sl| sl.allowed_users.clone()).unwrap_or_default(),
+        )
+    };
+    let Some(signing_secret) = signing_secret else {
+        return (StatusCode::NOT_FOUND, "Slack interactivity not configured");
+    };
+
+    let header_str = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string()
+    };
+    let timestamp = header_str("X-Slack-Request-Timestamp");
+    let fresh = timestamp.parse::<i64>().is_ok_and(|ts| {
+        (chrono::Utc::now().timestamp() - ts).abs() <= SLACK_SIGNATURE_MAX_AGE_SECS
+    });
+    if !fresh
+        || !verify_slack_signature(
+            &signing_secret,
+            &timestamp,
+            &body,
+            &header_str("X-Slack-Signature"),
+        )
+    {
+        tracing::warn!("Slack interaction rejected — invalid or stale signature");
+        return (StatusCode::UNAUTHORIZED, "Invalid signature");
+    }
+
+    // Body is `application/x-www-form-urlencoded` with a single `payload` field.
+    let payload = std::str::from_utf8(&body)
+        .ok()
+        .and_then(|form| {
+            form.split('&')
+                .find_map(|pair| pair.strip_prefix("payload="))
+        })
+        .map(|raw| raw.replace('+', " "))
+        .and_then(|raw| urlencoding::decode(&raw).ok().map(|s| s.into_owned()))
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok());
+    let Some(payload) = payload else {
+        return (StatusCode::BAD_REQUEST, "Missing payload");
+    };
+
+    let user_id = payload
+        .pointer("/user/id")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default();
+    if !allowed_users.iter().any(|u| u == "*" || u == user_id) {
+        tracing::warn!("Slack interaction from unauthorized user: {user_id}");
+        return (StatusCode::OK, "");
+    }
+
+    let Some((id, decision)) = payload
+        .pointer("/actions/0/action_id")
+        .and_then(serde_json::Value::as_str)
+        .and_then(|action| action.strip_prefix("approval:"))
+        .and_then(|rest| rest.split_once(':'))
+        .and_then(|(verb, id)| crate::approval::parse_approval_reply(&format!("/{verb} {id}")))
+    else {
+        return (StatusCode::OK, "");
+    };
+
+    let outcome = crate::approval::resolve_pending_approval(
+        &id,
+        decision,
+        &format!("slack:{user_id}"),
+        Some(&format!("slack_{user_id}")),
+    );
+    let text = match (&outcome, decision) {
+        (Ok(p), crate::approval::ApprovalResponse::No) => format!("🚫 Denied `{}`.", p.tool_name),
+        (Ok(p), _) => format!("✅ Approved `{}`.", p.tool_name),
+        (Err(e), _) => format!("⚠️ {e}"),
+    };
+
+    // Replace the button message so it cannot be pressed twice.
+    if let Some(response_url) = payload
+        .get("response_url")
+        .and_then(serde_json::Value::as_str)
+        .map(ToOwned::to_owned)
+    {
+        tokio::spawn(async move {
+            let _ = crate::config::build_runtime_proxy_client("channel.slack")
+                .post(&response_url)
+                .json(&serde_json::json!({ "replace_original": true, "text": text }))
+                .send()
+                .await;
+        });
+    }
+
+    (StatusCode::OK, "")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3230,6 +3460,7 @@ mod tests {
             channel: "whatsapp".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         let key = whatsapp_memory_key(&msg);
@@ -3773,6 +4004,39 @@ mod tests {
         format!("sha256={}", compute_whatsapp_signature_hex(secret, body))
     }
 
+    #[test]
+    fn slack_signature_covers_timestamp_and_body() {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let secret = generate_test_secret();
+        let body = b"payload=%7B%7D";
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(b"v0:1700000000:");
+        mac.update(body);
+        let header = format!("v0={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert!(verify_slack_signature(&secret, "1700000000", body, &header));
+        assert!(!verify_slack_signature(
+            &secret,
+            "1700000001",
+            body,
+            &header
+        ));
+        assert!(!verify_slack_signature(
+            &secret,
+            "1700000000",
+            b"payload=x",
+            &header
+        ));
+        assert!(!verify_slack_signature(
+            &secret,
+            "1700000000",
+            body,
+            "sha256=00"
+        ));
+    }
+
     #[test]
     fn whatsapp_signature_valid() {
         let app_secret = generate_test_secret();
@@ -53,6 +53,7 @@ pub(crate) mod health;
 pub(crate) mod heartbeat;
 pub(crate) mod identity;
 pub(crate) mod integrations;
+pub(crate) mod mcp;
 pub mod memory;
 pub(crate) mod migration;
 pub(crate) mod multimodal;
@@ -66,6 +67,8 @@ pub(crate) mod security;
 pub(crate) mod service;
 pub(crate) mod skills;
 pub mod tools;
+pub(crate) mod transcription;
+pub(crate) mod tts;
 pub(crate) mod tunnel;
 pub(crate) mod util;
 
@@ -321,6 +324,80 @@ pub enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Inspect or clear the LLM response cache
+    Cache {
+        #[command(subcommand)]
+        cache_command: ResponseCacheCommands,
+    },
+    /// Ingest documents (Markdown, text, code, HTML, PDF) as memory chunks
+    Ingest {
+        /// File or directory to ingest
+        path: std::path::PathBuf,
+        /// Category to store chunks under
+        #[arg(long, default_value = "docs")]
+        category: String,
+        /// Maximum chunk size in approximate tokens
+        #[arg(long, default_value = "512")]
+        max_tokens: usize,
+        /// Re-ingest files even if their content is unchanged
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Response cache subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ResponseCacheCommands {
+    /// Show cached entry count, hits and tokens saved
+    Stats,
+    /// Remove all cached responses
+    Clear {
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+/// Channel session subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionCommands {
+    /// List persisted channel sessions, most recently active first
+    List {
+        /// Only show sessions from this channel (e.g. telegram)
+        #[arg(long)]
+        channel: Option<String>,
+        /// Maximum number of sessions to display
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// Show the stored history of one session
+    Show {
+        /// Session key (`<channel>_<sender>`)
+        key: String,
+    },
+    /// Clear one session, or all sessions when no key is given
+    Clear {
+        /// Session key (`<channel>_<sender>`)
+        key: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Export sessions as JSON
+    Export {
+        /// Session key to export (default: all sessions)
+        key: Option<String>,
+        /// Write JSON to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// MCP subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum McpCommands {
+    /// Serve zeroclaw tools to an MCP host over stdio
+    Serve,
 }
 
 /// Integration subcommands
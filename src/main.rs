@@ -56,6 +56,7 @@ mod rag {
     pub use zeroclaw::rag::*;
 }
 mod config;
+mod cost;
 mod cron;
 mod daemon;
 mod doctor;
@@ -65,6 +66,7 @@ mod health;
 mod heartbeat;
 mod identity;
 mod integrations;
+mod mcp;
 mod memory;
 mod migration;
 mod multimodal;
@@ -78,6 +80,8 @@ mod service;
 mod skillforge;
 mod skills;
 mod tools;
+mod transcription;
+mod tts;
 mod tunnel;
 mod util;
 
@@ -376,25 +380,73 @@ Examples:
         peripheral_command: zeroclaw::PeripheralCommands,
     },
 
-    /// Manage agent memory (list, get, stats, clear)
+    /// Manage agent memory (list, get, stats, clear, ingest)
     #[command(long_about = "\
 Manage agent memory entries.
 
 List, inspect, and clear memory entries stored by the agent. \
 Supports filtering by category and session, pagination, and \
-batch clearing with confirmation.
+batch clearing with confirmation. `ingest` loads local documents \
+(Markdown, text, source code, HTML, and PDF with the rag-pdf feature) \
+as chunks, re-ingesting only files whose content changed.
 
 Examples:
   zeroclaw memory stats
   zeroclaw memory list
   zeroclaw memory list --category core --limit 10
   zeroclaw memory get <key>
-  zeroclaw memory clear --category conversation --yes")]
+  zeroclaw memory clear --category conversation --yes
+  zeroclaw memory cache stats
+  zeroclaw memory cache clear --yes
+  zeroclaw memory ingest ./runbooks
+  zeroclaw memory ingest ./docs --category runbooks --force")]
     Memory {
         #[command(subcommand)]
         memory_command: MemoryCommands,
     },
 
+    /// Manage persisted channel conversation sessions
+    #[command(long_about = "\
+Manage persisted channel conversation sessions.
+
+Per-sender channel history is stored in the workspace SQLite database \
+so conversations survive daemon restarts. Sessions are keyed as \
+<channel>_<sender>. Expiry and caps are configured under \
+[channels_config.sessions]. A running daemon keeps its in-memory \
+history until it restarts.
+
+Examples:
+  zeroclaw sessions list
+  zeroclaw sessions list --channel telegram
+  zeroclaw sessions show telegram_alice
+  zeroclaw sessions clear telegram_alice
+  zeroclaw sessions clear --yes
+  zeroclaw sessions export --output sessions.json")]
+    Sessions {
+        #[command(subcommand)]
+        session_command: SessionCommands,
+    },
+
+    /// Model Context Protocol integration (serve tools to MCP hosts)
+    #[command(long_about = "\
+Model Context Protocol integration.
+
+'serve' publishes the registered tool set (file, shell, memory, cron, \
+git, ...) over MCP stdio so editors and other agent hosts can call them. \
+Calls are subject to the same security policy as the agent and are \
+recorded in the audit log. Logs go to stderr; stdout carries protocol \
+messages only.
+
+External MCP servers consumed by zeroclaw are configured under [mcp] \
+in config.toml.
+
+Examples:
+  zeroclaw mcp serve")]
+    Mcp {
+        #[command(subcommand)]
+        mcp_command: McpCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
@@ -699,6 +751,67 @@ enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Inspect or clear the LLM response cache
+    Cache {
+        #[command(subcommand)]
+        cache_command: ResponseCacheCommands,
+    },
+    /// Ingest documents (Markdown, text, code, HTML, PDF) as memory chunks
+    Ingest {
+        path: std::path::PathBuf,
+        #[arg(long, default_value = "docs")]
+        category: String,
+        #[arg(long, default_value = "512")]
+        max_tokens: usize,
+        /// Re-ingest files even if their content is unchanged
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ResponseCacheCommands {
+    /// Show cached entry count, hits and tokens saved
+    Stats,
+    /// Remove all cached responses
+    Clear {
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SessionCommands {
+    /// List persisted channel sessions, most recently active first
+    List {
+        #[arg(long)]
+        channel: Option<String>,
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+    /// Show the stored history of one session
+    Show { key: String },
+    /// Clear one session, or all sessions when no key is given
+    Clear {
+        key: Option<String>,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Export sessions as JSON
+    Export {
+        key: Option<String>,
+        /// Write JSON to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum McpCommands {
+    /// Serve zeroclaw tools to an MCP host over stdio
+    Serve,
 }
 
 #[derive(Subcommand, Debug)]
@@ -737,11 +850,18 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    // Initialize logging - respects RUST_LOG env var, defaults to INFO
+    // Initialize logging - respects RUST_LOG env var, defaults to INFO.
+    // MCP stdio reserves stdout for protocol messages, so log to stderr there.
+    let log_writer = if matches!(cli.command, Commands::Mcp { .. }) {
+        fmt::writer::BoxMakeWriter::new(std::io::stderr)
+    } else {
+        fmt::writer::BoxMakeWriter::new(std::io::stdout)
+    };
     let subscriber = fmt::Subscriber::builder()
         .with_env_filter(
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
         )
+        .with_writer(log_writer)
         .finish();
 
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
@@ -1015,6 +1135,12 @@ async fn main() -> Result<()> {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Sessions { session_command } => {
+            channels::session_cli::handle_command(session_command, &config)
+        }
+
+        Commands::Mcp { mcp_command } => mcp::handle_command(mcp_command, &config).await,
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
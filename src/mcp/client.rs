@@ -0,0 +1,699 @@
+//! MCP client: connects to one configured server and forwards tool calls.
+//!
+//! Each server connection lives on a dedicated thread with its own
+//! current-thread Tokio runtime, so it outlives whichever runtime performed
+//! the handshake. The connection thread owns the transport; [`McpClient`] is
+//! a cheap handle that sends requests over a channel and awaits the reply.
+//! Synchronous tool registries connect through [`connect_all_blocking`].
+
+use super::protocol::{
+    CallToolResult, InitializeResult, JsonRpcMessage, ListToolsResult, McpToolDefinition,
+    METHOD_NOT_FOUND, PROTOCOL_VERSION,
+};
+use crate::config::{McpServerConfig, McpTransport};
+use crate::tools::shell::SAFE_ENV_VARS;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::sync::{mpsc, oneshot};
+
+/// Upper bound on `tools/list` pagination to guard against cursor loops.
+const MAX_TOOL_LIST_PAGES: usize = 100;
+
+/// Maximum bytes of an HTTP error body echoed into error messages.
+const MAX_ERROR_BODY_CHARS: usize = 512;
+
+struct ClientRequest {
+    method: String,
+    params: Option<Value>,
+    reply: oneshot::Sender<Result<Value>>,
+}
+
+/// Handle to a connected MCP server.
+pub struct McpClient {
+    server_name: String,
+    server_info: InitializeResult,
+    tools: Vec<McpToolDefinition>,
+    requests: mpsc::UnboundedSender<ClientRequest>,
+}
+
+impl McpClient {
+    /// Spawn the connection, perform `initialize` + `tools/list`, and return
+    /// once the server is ready (or the configured timeout elapses).
+    pub async fn connect(config: &McpServerConfig) -> Result<Self> {
+        let (requests, request_rx) = mpsc::unbounded_channel();
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let timeout = Duration::from_secs(config.timeout_secs.max(1));
+        let thread_config = config.clone();
+
+        std::thread::Builder::new()
+            .name(format!("mcp-{}", config.name))
+            .spawn(move || run_connection(thread_config, request_rx, ready_tx))
+            .context("Failed to spawn MCP connection thread")?;
+
+        // Small grace period so the in-thread timeout reports first.
+        let (server_info, tools) = tokio::time::timeout(timeout + Duration::from_secs(1), ready_rx)
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "MCP server '{}' did not complete the handshake within {}s",
+                    config.name,
+                    timeout.as_secs()
+                )
+            })?
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "MCP server '{}' connection thread exited during the handshake",
+                    config.name
+                )
+            })??;
+
+        Ok(Self {
+            server_name: config.name.clone(),
+            server_info,
+            tools,
+            requests,
+        })
+    }
+
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    pub fn server_info(&self) -> &InitializeResult {
+        &self.server_info
+    }
+
+    /// Tools advertised by the server during the handshake.
+    pub fn tools(&self) -> &[McpToolDefinition] {
+        &self.tools
+    }
+
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<CallToolResult> {
+        let result = self
+            .request(
+                "tools/call",
+                Some(json!({ "name": name, "arguments": arguments })),
+            )
+            .await?;
+        serde_json::from_value(result).with_context(|| {
+            format!(
+                "MCP server '{}' returned a malformed tools/call result",
+                self.server_name
+            )
+        })
+    }
+
+    async fn request(&self, method: &str, params: Option<Value>) -> Result<Value> {
+        let (reply, response) = oneshot::channel();
+        self.requests
+            .send(ClientRequest {
+                method: method.to_string(),
+                params,
+                reply,
+            })
+            .map_err(|_| self.closed_error())?;
+        response.await.map_err(|_| self.closed_error())?
+    }
+
+    fn closed_error(&self) -> anyhow::Error {
+        anyhow::anyhow!("MCP server '{}' connection is closed", self.server_name)
+    }
+}
+
+/// Connect every server concurrently from synchronous code.
+///
+/// Tool registries are built synchronously from both multi-thread and
+/// current-thread runtimes. The handshakes run on a helper thread with its
+/// own runtime; on a multi-thread runtime the wait happens inside
+/// [`tokio::task::block_in_place`] so the calling worker's other tasks are
+/// moved elsewhere instead of stalling behind the handshake timeout.
+pub fn connect_all_blocking(servers: &[McpServerConfig]) -> Vec<Result<McpClient>> {
+    let connect_all = || {
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()?;
+                    Ok::<_, anyhow::Error>(runtime.block_on(futures_util::future::join_all(
+                        servers.iter().map(McpClient::connect),
+                    )))
+                })
+                .join()
+                .map_err(|_| anyhow::anyhow!("MCP connection helper thread panicked"))?
+        })
+    };
+
+    let on_multi_thread_runtime = tokio::runtime::Handle::try_current()
+        .is_ok_and(|handle| handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread);
+    let connected = if on_multi_thread_runtime {
+        tokio::task::block_in_place(connect_all)
+    } else {
+        connect_all()
+    };
+
+    match connected {
+        Ok(clients) => clients,
+        Err(error) => servers
+            .iter()
+            .map(|_| Err(anyhow::anyhow!("{error:#}")))
+            .collect(),
+    }
+}
+
+type Handshake = (InitializeResult, Vec<McpToolDefinition>);
+
+fn run_connection(
+    config: McpServerConfig,
+    mut requests: mpsc::UnboundedReceiver<ClientRequest>,
+    ready: oneshot::Sender<Result<Handshake>>,
+) {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            let _ = ready.send(Err(error.into()));
+            return;
+        }
+    };
+
+    runtime.block_on(async move {
+        let timeout = Duration::from_secs(config.timeout_secs.max(1));
+        let connected = tokio::time::timeout(timeout, async {
+            let mut transport = open_transport(&config)?;
+            let handshake = handshake(transport.as_mut()).await?;
+            Ok::<_, anyhow::Error>((transport, handshake))
+        })
+        .await;
+
+        let mut transport = match connected {
+            Ok(Ok((transport, handshake))) => {
+                if ready.send(Ok(handshake)).is_err() {
+                    return;
+                }
+                transport
+            }
+            Ok(Err(error)) => {
+                let _ = ready.send(Err(
+                    error.context(format!("MCP server '{}' handshake failed", config.name))
+                ));
+                return;
+            }
+            Err(_) => {
+                let _ = ready.send(Err(anyhow::anyhow!(
+                    "MCP server '{}' did not complete the handshake within {}s",
+                    config.name,
+                    timeout.as_secs()
+                )));
+                return;
+            }
+        };
+
+        // Requests are served one at a time; the loop ends when every
+        // `McpClient` handle has been dropped.
+        while let Some(request) = requests.recv().await {
+            let result = match tokio::time::timeout(
+                timeout,
+                transport.request(&request.method, request.params),
+            )
+            .await
+            {
+                Ok(result) => result,
+                Err(_) => Err(anyhow::anyhow!(
+                    "MCP server '{}' did not answer '{}' within {}s",
+                    config.name,
+                    request.method,
+                    timeout.as_secs()
+                )),
+            };
+            let _ = request.reply.send(result);
+        }
+    });
+}
+
+async fn handshake(transport: &mut dyn Transport) -> Result<Handshake> {
+    let init = transport
+        .request(
+            "initialize",
+            Some(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {
+                    "name": "zeroclaw",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+        )
+        .await?;
+    let server_info: InitializeResult =
+        serde_json::from_value(init).context("Malformed initialize result")?;
+    transport.notify("notifications/initialized", None).await?;
+
+    let mut tools = Vec::new();
+    let mut cursor: Option<String> = None;
+    for _ in 0..MAX_TOOL_LIST_PAGES {
+        let params = cursor.as_ref().map(|cursor| json!({ "cursor": cursor }));
+        let page: ListToolsResult =
+            serde_json::from_value(transport.request("tools/list", params).await?)
+                .context("Malformed tools/list result")?;
+        tools.extend(page.tools);
+        match page.next_cursor {
+            Some(next) if !next.is_empty() => cursor = Some(next),
+            _ => break,
+        }
+    }
+
+    Ok((server_info, tools))
+}
+
+fn open_transport(config: &McpServerConfig) -> Result<Box<dyn Transport>> {
+    match config.transport {
+        McpTransport::Stdio => Ok(Box::new(StdioTransport::spawn(config)?)),
+        McpTransport::Http => Ok(Box::new(HttpTransport::new(config)?)),
+    }
+}
+
+#[async_trait]
+trait Transport: Send {
+    async fn request(&mut self, method: &str, params: Option<Value>) -> Result<Value>;
+    async fn notify(&mut self, method: &str, params: Option<Value>) -> Result<()>;
+}
+
+// ── stdio ────────────────────────────────────────────────────────
+
+struct StdioTransport {
+    server_name: String,
+    // Held so the child is killed when the transport drops.
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    next_id: u64,
+}
+
+impl StdioTransport {
+    fn spawn(config: &McpServerConfig) -> Result<Self> {
+        let command = config
+            .command
+            .as_deref()
+            .map(str::trim)
+            .filter(|command| !command.is_empty())
+            .with_context(|| format!("MCP server '{}' has no command", config.name))?;
+
+        let mut cmd = tokio::process::Command::new(command);
+        cmd.args(&config.args)
+            .env_clear()
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+        // Same allowlist as the shell tool, plus the server's configured `env`.
+        for var in SAFE_ENV_VARS {
+            if let Ok(value) = std::env::var(var) {
+                cmd.env(var, value);
+            }
+        }
+        cmd.envs(&config.env);
+
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("Failed to spawn MCP server '{}' ({command})", config.name))?;
+        let stdin = child.stdin.take().context("MCP server stdin unavailable")?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("MCP server stdout unavailable")?;
+
+        if let Some(stderr) = child.stderr.take() {
+            let server_name = config.name.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::debug!(server = %server_name, "mcp stderr: {line}");
+                }
+            });
+        }
+
+        Ok(Self {
+            server_name: config.name.clone(),
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+            next_id: 1,
+        })
+    }
+
+    async fn send(&mut self, message: &JsonRpcMessage) -> Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        // A server that exits early surfaces as a broken pipe here, racing the
+        // EOF on stdout; report both the same way.
+        let written = match self.stdin.write_all(line.as_bytes()).await {
+            Ok(()) => self.stdin.flush().await,
+            Err(error) => Err(error),
+        };
+        written
+            .map_err(|error| anyhow::anyhow!("MCP server '{}' exited: {error}", self.server_name))
+    }
+}
+
+#[async_trait]
+impl Transport for StdioTransport {
+    async fn request(&mut self, method: &str, params: Option<Value>) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&JsonRpcMessage::request(id, method, params))
+            .await?;
+
+        loop {
+            let Some(line) = self.stdout.next_line().await? else {
+                anyhow::bail!(
+                    "MCP server '{}' exited (closed its stdout)",
+                    self.server_name
+                );
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Ok(message) = serde_json::from_str::<JsonRpcMessage>(line) else {
+                tracing::debug!(server = %self.server_name, "ignoring non JSON-RPC line: {line}");
+                continue;
+            };
+
+            if message.is_response_to(id) {
+                return message.into_result();
+            }
+            if let Some(reply) = reply_to_server_request(&message) {
+                self.send(&reply).await?;
+            }
+            // Notifications and stale responses (from timed-out calls) are dropped.
+        }
+    }
+
+    async fn notify(&mut self, method: &str, params: Option<Value>) -> Result<()> {
+        self.send(&JsonRpcMessage::notification(method, params))
+            .await
+    }
+}
+
+/// Answer server-initiated requests. zeroclaw advertises no client
+/// capabilities, so only `ping` is supported.
+fn reply_to_server_request(message: &JsonRpcMessage) -> Option<JsonRpcMessage> {
+    if !message.is_request() {
+        return None;
+    }
+    let id = message.id.clone()?;
+    Some(match message.method.as_deref() {
+        Some("ping") => JsonRpcMessage::response(id, json!({})),
+        Some(method) => JsonRpcMessage::error_response(
+            id,
+            METHOD_NOT_FOUND,
+            format!("Unsupported method: {method}"),
+        ),
+        None => return None,
+    })
+}
+
+// ── streamable HTTP ──────────────────────────────────────────────
+
+struct HttpTransport {
+    server_name: String,
+    client: reqwest::Client,
+    url: String,
+    headers: reqwest::header::HeaderMap,
+    session_id: Option<String>,
+    next_id: u64,
+}
+
+impl HttpTransport {
+    fn new(config: &McpServerConfig) -> Result<Self> {
+        let url = config
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .with_context(|| format!("MCP server '{}' has no url", config.name))?;
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &config.headers {
+            let name =
+                reqwest::header::HeaderName::from_bytes(name.as_bytes()).with_context(|| {
+                    format!(
+                        "Invalid header name '{name}' for MCP server '{}'",
+                        config.name
+                    )
+                })?;
+            let value = reqwest::header::HeaderValue::from_str(value).with_context(|| {
+                format!("Invalid header value for MCP server '{}'", config.name)
+            })?;
+            headers.insert(name, value);
+        }
+
+        Ok(Self {
+            server_name: config.name.clone(),
+            client: crate::config::build_runtime_proxy_client_with_timeouts(
+                "tool.mcp",
+                config.timeout_secs,
+                10,
+            ),
+            url: url.to_string(),
+            headers,
+            session_id: None,
+            next_id: 1,
+        })
+    }
+
+    async fn post(&mut self, message: &JsonRpcMessage) -> Result<reqwest::Response> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .headers(self.headers.clone())
+            .header(
+                reqwest::header::ACCEPT,
+                "application/json, text/event-stream",
+            )
+            .header("MCP-Protocol-Version", PROTOCOL_VERSION)
+            .json(message);
+        if let Some(session_id) = &self.session_id {
+            request = request.header("Mcp-Session-Id", session_id);
+        }
+
+        let response = request.send().await?;
+        if let Some(session_id) = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|value| value.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let body: String = body.chars().take(MAX_ERROR_BODY_CHARS).collect();
+            anyhow::bail!(
+                "MCP server '{}' returned HTTP {status}: {body}",
+                self.server_name
+            );
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn request(&mut self, method: &str, params: Option<Value>) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let response = self
+            .post(&JsonRpcMessage::request(id, method, params))
+            .await?;
+
+        let is_event_stream = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+
+        if !is_event_stream {
+            let body: Value = response.json().await?;
+            return find_response(body, id)
+                .with_context(|| {
+                    format!(
+                        "MCP server '{}' sent no response for '{method}'",
+                        self.server_name
+                    )
+                })?
+                .into_result();
+        }
+
+        // The server may interleave notifications before the response and
+        // should close the stream once the response has been sent.
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        while let Some(chunk) = stream.next().await {
+            buffer.push_str(&String::from_utf8_lossy(&chunk?));
+            while let Some((event, rest)) = split_sse_event(&buffer) {
+                buffer = rest;
+                let Some(data) = sse_event_data(&event) else {
+                    continue;
+                };
+                if let Ok(body) = serde_json::from_str::<Value>(&data) {
+                    if let Some(message) = find_response(body, id) {
+                        return message.into_result();
+                    }
+                }
+            }
+        }
+
+        anyhow::bail!(
+            "MCP server '{}' closed the event stream without answering '{method}'",
+            self.server_name
+        )
+    }
+
+    async fn notify(&mut self, method: &str, params: Option<Value>) -> Result<()> {
+        self.post(&JsonRpcMessage::notification(method, params))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Pick the response with `id` out of a single message or a batch.
+fn find_response(body: Value, id: u64) -> Option<JsonRpcMessage> {
+    let candidates = match body {
+        Value::Array(items) => items,
+        single => vec![single],
+    };
+    candidates
+        .into_iter()
+        .filter_map(|item| serde_json::from_value::<JsonRpcMessage>(item).ok())
+        .find(|message| message.is_response_to(id))
+}
+
+/// Split the first complete SSE event (terminated by a blank line) off `buffer`.
+fn split_sse_event(buffer: &str) -> Option<(String, String)> {
+    let normalized = buffer.replace("\r\n", "\n");
+    let end = normalized.find("\n\n")?;
+    Some((
+        normalized[..end].to_string(),
+        normalized[end + 2..].to_string(),
+    ))
+}
+
+/// Join the `data:` lines of one SSE event.
+fn sse_event_data(event: &str) -> Option<String> {
+    let data: Vec<&str> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    (!data.is_empty()).then(|| data.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn stdio_config(script: &str) -> McpServerConfig {
+        McpServerConfig {
+            name: "fake".into(),
+            transport: McpTransport::Stdio,
+            command: Some("sh".into()),
+            args: vec!["-c".into(), script.into()],
+            env: HashMap::new(),
+            url: None,
+            headers: HashMap::new(),
+            timeout_secs: 5,
+            allowed_tools: Vec::new(),
+            trust_annotations: false,
+        }
+    }
+
+    /// A tiny line-oriented MCP server: answers initialize, tools/list and
+    /// tools/call in order, ignoring the initialized notification.
+    const FAKE_SERVER: &str = r#"
+read init
+echo '{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info"}}'
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{"tools":{}},"serverInfo":{"name":"fake","version":"0.1"}}}'
+read initialized
+read list
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo","description":"Echo text","inputSchema":{"type":"object","properties":{"text":{"type":"string"}}}}]}}'
+read call
+echo '{"jsonrpc":"2.0","id":3,"result":{"content":[{"type":"text","text":"pong"}]}}'
+"#;
+
+    #[test]
+    fn split_sse_event_handles_crlf_and_partial_input() {
+        assert!(split_sse_event("data: {\"a\":1}").is_none());
+        let (event, rest) = split_sse_event("event: message\r\ndata: {}\r\n\r\ndata: x").unwrap();
+        assert_eq!(sse_event_data(&event).as_deref(), Some("{}"));
+        assert_eq!(rest, "data: x");
+    }
+
+    #[test]
+    fn find_response_searches_batches() {
+        let body = json!([
+            { "jsonrpc": "2.0", "method": "notifications/progress" },
+            { "jsonrpc": "2.0", "id": 9, "result": { "ok": true } }
+        ]);
+        let message = find_response(body, 9).unwrap();
+        assert_eq!(message.into_result().unwrap()["ok"], true);
+        assert!(find_response(json!({ "jsonrpc": "2.0", "id": 1, "result": {} }), 2).is_none());
+    }
+
+    #[test]
+    fn server_ping_is_answered_and_other_requests_rejected() {
+        let ping = JsonRpcMessage::request(5, "ping", None);
+        let reply = reply_to_server_request(&ping).unwrap();
+        assert_eq!(reply.result, Some(json!({})));
+
+        let sampling = JsonRpcMessage::request(6, "sampling/createMessage", None);
+        let reply = reply_to_server_request(&sampling).unwrap();
+        assert_eq!(reply.error.unwrap().code, METHOD_NOT_FOUND);
+
+        let notification = JsonRpcMessage::notification("notifications/message", None);
+        assert!(reply_to_server_request(&notification).is_none());
+    }
+
+    #[tokio::test]
+    async fn stdio_client_handshakes_and_calls_tools() {
+        let client = McpClient::connect(&stdio_config(FAKE_SERVER))
+            .await
+            .unwrap();
+        assert_eq!(client.server_name(), "fake");
+        assert_eq!(
+            client
+                .server_info()
+                .server_info
+                .as_ref()
+                .map(|i| i.name.as_str()),
+            Some("fake")
+        );
+        assert_eq!(client.tools().len(), 1);
+        assert_eq!(client.tools()[0].name, "echo");
+
+        let result = client
+            .call_tool("echo", json!({ "text": "ping" }))
+            .await
+            .unwrap();
+        assert_eq!(result.to_text(), "pong");
+    }
+
+    #[tokio::test]
+    async fn stdio_client_reports_server_exit_during_handshake() {
+        let error = McpClient::connect(&stdio_config("exit 0"))
+            .await
+            .err()
+            .unwrap();
+        assert!(format!("{error:#}").contains("MCP server 'fake' exited"));
+    }
+}
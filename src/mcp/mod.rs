@@ -0,0 +1,29 @@
+//! Model Context Protocol (MCP) support.
+//!
+//! - [`client`] connects to external MCP servers configured under `[mcp]`
+//!   (stdio or streamable HTTP) and lists/calls their tools.
+//! - [`server`] implements `zeroclaw mcp serve`, publishing the agent tool
+//!   registry to other MCP hosts over stdio.
+//! - [`protocol`] holds the JSON-RPC envelope and MCP message types.
+//!
+//! Remote tools are exposed to the agent through
+//! [`McpTool`](crate::tools::McpTool), so they pass through the same
+//! `SecurityPolicy` and `ApprovalManager` gates as built-in tools.
+
+pub mod client;
+pub mod protocol;
+pub mod server;
+
+pub use client::{connect_all_blocking, McpClient};
+#[allow(unused_imports)]
+pub use server::McpServer;
+
+use crate::config::Config;
+use anyhow::Result;
+
+/// Handle `zeroclaw mcp <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::McpCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::McpCommands::Serve => server::serve_stdio(config).await,
+    }
+}
@@ -0,0 +1,372 @@
+//! JSON-RPC 2.0 envelope and MCP message types shared by client and server.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// MCP protocol revision spoken by zeroclaw.
+pub const PROTOCOL_VERSION: &str = "2025-03-26";
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// Any JSON-RPC message: request, notification, or response.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcMessage {
+    #[serde(default)]
+    pub jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl JsonRpcMessage {
+    pub fn request(id: u64, method: &str, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.into(),
+            id: Some(Value::from(id)),
+            method: Some(method.into()),
+            params,
+            ..Self::default()
+        }
+    }
+
+    pub fn notification(method: &str, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.into(),
+            method: Some(method.into()),
+            params,
+            ..Self::default()
+        }
+    }
+
+    pub fn response(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.into(),
+            id: Some(id),
+            result: Some(result),
+            ..Self::default()
+        }
+    }
+
+    pub fn error_response(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.into(),
+            id: Some(id),
+            error: Some(JsonRpcError {
+                code,
+                message: message.into(),
+                data: None,
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// A request expects a response; a notification does not.
+    pub fn is_request(&self) -> bool {
+        self.method.is_some() && self.id.is_some()
+    }
+
+    pub fn is_notification(&self) -> bool {
+        self.method.is_some() && self.id.is_none()
+    }
+
+    pub fn is_response_to(&self, id: u64) -> bool {
+        self.method.is_none() && self.id.as_ref().and_then(Value::as_u64) == Some(id)
+    }
+
+    /// Convert a response into its `result`, surfacing JSON-RPC errors.
+    pub fn into_result(self) -> Result<Value> {
+        if let Some(error) = self.error {
+            anyhow::bail!("MCP error {}: {}", error.code, error.message);
+        }
+        Ok(self.result.unwrap_or(Value::Null))
+    }
+}
+
+/// `serverInfo` / `clientInfo` in the initialize exchange.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Implementation {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+}
+
+/// Result of the `initialize` request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeResult {
+    #[serde(default)]
+    pub protocol_version: String,
+    #[serde(default)]
+    pub capabilities: Value,
+    #[serde(default)]
+    pub server_info: Option<Implementation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+}
+
+/// Behavioural hints a server may attach to a tool definition.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only_hint: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive_hint: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotent_hint: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_world_hint: Option<bool>,
+}
+
+/// One entry of a `tools/list` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpToolDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default = "empty_object_schema")]
+    pub input_schema: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+fn empty_object_schema() -> Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListToolsResult {
+    #[serde(default)]
+    pub tools: Vec<McpToolDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// A single content block of a `tools/call` result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolContent {
+    Text {
+        text: String,
+    },
+    Image {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    Audio {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    Resource {
+        resource: Value,
+    },
+    ResourceLink {
+        uri: String,
+        #[serde(default)]
+        name: Option<String>,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// Result of a `tools/call` request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallToolResult {
+    #[serde(default)]
+    pub content: Vec<ToolContent>,
+    #[serde(default)]
+    pub is_error: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_content: Option<Value>,
+}
+
+impl CallToolResult {
+    /// Flatten content blocks into plain text for the agent loop.
+    ///
+    /// Binary blocks are summarized rather than inlined so base64 payloads do
+    /// not flood the model context.
+    pub fn to_text(&self) -> String {
+        let mut parts = Vec::new();
+        for block in &self.content {
+            match block {
+                ToolContent::Text { text } => parts.push(text.clone()),
+                ToolContent::Image { data, mime_type } => {
+                    parts.push(format!("[image: {mime_type}, {} base64 bytes]", data.len()));
+                }
+                ToolContent::Audio { data, mime_type } => {
+                    parts.push(format!("[audio: {mime_type}, {} base64 bytes]", data.len()));
+                }
+                ToolContent::Resource { resource } => {
+                    if let Some(text) = resource.get("text").and_then(Value::as_str) {
+                        parts.push(text.to_string());
+                    } else if let Some(uri) = resource.get("uri").and_then(Value::as_str) {
+                        parts.push(format!("[resource: {uri}]"));
+                    }
+                }
+                ToolContent::ResourceLink { uri, name } => {
+                    parts.push(match name {
+                        Some(name) => format!("[resource link: {name} <{uri}>]"),
+                        None => format!("[resource link: {uri}]"),
+                    });
+                }
+                ToolContent::Unknown => {}
+            }
+        }
+
+        if parts.is_empty() {
+            if let Some(structured) = &self.structured_content {
+                return serde_json::to_string_pretty(structured).unwrap_or_default();
+            }
+        }
+
+        parts.join("\n")
+    }
+
+    /// Typed parts the agent loop can hand to vision providers and channels.
+    pub fn to_parts(&self) -> Vec<crate::tools::ToolContent> {
+        let mut parts: Vec<crate::tools::ToolContent> = self
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ToolContent::Image { data, mime_type } => Some(crate::tools::ToolContent::Image {
+                    mime_type: mime_type.clone(),
+                    data: data.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+        if let Some(structured) = &self.structured_content {
+            parts.push(crate::tools::ToolContent::Json {
+                value: structured.clone(),
+            });
+        }
+        parts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn request_serializes_without_empty_fields() {
+        let message = JsonRpcMessage::request(7, "tools/list", None);
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(
+            value,
+            json!({ "jsonrpc": "2.0", "id": 7, "method": "tools/list" })
+        );
+    }
+
+    #[test]
+    fn response_matching_and_error_surface() {
+        let ok: JsonRpcMessage =
+            serde_json::from_value(json!({ "jsonrpc": "2.0", "id": 3, "result": { "x": 1 } }))
+                .unwrap();
+        assert!(ok.is_response_to(3));
+        assert!(!ok.is_response_to(4));
+        assert_eq!(ok.into_result().unwrap()["x"], 1);
+
+        let err: JsonRpcMessage = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "error": { "code": -32601, "message": "Method not found" }
+        }))
+        .unwrap();
+        let message = err.into_result().unwrap_err().to_string();
+        assert!(message.contains("-32601"));
+        assert!(message.contains("Method not found"));
+    }
+
+    #[test]
+    fn tool_definition_defaults_missing_schema() {
+        let tool: McpToolDefinition = serde_json::from_value(json!({
+            "name": "lookup",
+            "annotations": { "readOnlyHint": true }
+        }))
+        .unwrap();
+        assert_eq!(tool.input_schema["type"], "object");
+        assert_eq!(tool.annotations.and_then(|a| a.read_only_hint), Some(true));
+    }
+
+    #[test]
+    fn call_result_flattens_content_blocks() {
+        let result: CallToolResult = serde_json::from_value(json!({
+            "content": [
+                { "type": "text", "text": "first" },
+                { "type": "image", "data": "AAAA", "mimeType": "image/png" },
+                { "type": "resource", "resource": { "uri": "file:///a", "text": "inline" } },
+                { "type": "something_new" }
+            ],
+            "isError": false
+        }))
+        .unwrap();
+        assert_eq!(
+            result.to_text(),
+            "first\n[image: image/png, 4 base64 bytes]\ninline"
+        );
+    }
+
+    #[test]
+    fn call_result_falls_back_to_structured_content() {
+        let result: CallToolResult = serde_json::from_value(json!({
+            "content": [],
+            "structuredContent": { "count": 2 }
+        }))
+        .unwrap();
+        assert!(result.to_text().contains("\"count\": 2"));
+    }
+
+    #[test]
+    fn call_result_exposes_images_and_structured_content_as_parts() {
+        let result: CallToolResult = serde_json::from_value(json!({
+            "content": [
+                { "type": "text", "text": "chart" },
+                { "type": "image", "data": "aGk=", "mimeType": "image/png" }
+            ],
+            "structuredContent": { "points": 3 }
+        }))
+        .unwrap();
+
+        let parts = result.to_parts();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(
+            parts[0].image_data_uri().as_deref(),
+            Some("data:image/png;base64,aGk=")
+        );
+        assert!(matches!(
+            &parts[1],
+            crate::tools::ToolContent::Json { value } if value["points"] == 3
+        ));
+    }
+}
@@ -0,0 +1,485 @@
+//! `zeroclaw mcp serve`: publish the agent tool registry over MCP stdio.
+//!
+//! Tools are built exactly as for the agent loop, so each one still enforces
+//! its own `SecurityPolicy` checks (workspace scoping, read-only autonomy,
+//! rate limits). MCP has no approval prompt, so calls that would need one
+//! under supervised autonomy are refused. Every `tools/call` is recorded
+//! through [`AuditLogger`].
+
+use super::protocol::{
+    CallToolResult, JsonRpcMessage, ToolContent, INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST,
+    METHOD_NOT_FOUND, PARSE_ERROR, PROTOCOL_VERSION,
+};
+use crate::approval::ApprovalManager;
+use crate::config::Config;
+use crate::security::audit::ToolExecutionLog;
+use crate::security::{AuditLogger, SecurityPolicy};
+use crate::tools::Tool;
+use crate::{memory, runtime, tools};
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Protocol revisions a client may negotiate; anything else gets ours.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", PROTOCOL_VERSION, "2025-06-18"];
+
+/// Audit actor channel for calls arriving over MCP.
+const AUDIT_CHANNEL: &str = "mcp";
+
+pub struct McpServer {
+    tools: Vec<Box<dyn Tool>>,
+    audit: Option<AuditLogger>,
+    /// Decides which calls would need a prompt; those are refused.
+    approval: ApprovalManager,
+}
+
+impl McpServer {
+    pub fn new(
+        tools: Vec<Box<dyn Tool>>,
+        audit: Option<AuditLogger>,
+        approval: ApprovalManager,
+    ) -> Self {
+        Self {
+            tools,
+            audit,
+            approval,
+        }
+    }
+
+    /// Build the server from config using the same registry as the agent.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        // Do not re-export tools proxied from other MCP servers.
+        let mut config = config.clone();
+        config.mcp.enabled = false;
+
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runtime: Arc<dyn runtime::RuntimeAdapter> =
+            Arc::from(runtime::create_runtime(&config.runtime)?);
+        let mem: Arc<dyn memory::Memory> = Arc::from(memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?);
+        let (composio_key, composio_entity_id) = if config.composio.enabled {
+            (
+                config.composio.api_key.as_deref(),
+                Some(config.composio.entity_id.as_str()),
+            )
+        } else {
+            (None, None)
+        };
+
+        let tools = tools::all_tools_with_runtime(
+            Arc::new(config.clone()),
+            &security,
+            runtime,
+            mem,
+            composio_key,
+            composio_entity_id,
+            &config.browser,
+            &config.http_request,
+            &config.workspace_dir,
+            &config.agents,
+            config.api_key.as_deref(),
+            &config,
+        );
+
+        let audit = config
+            .config_path
+            .parent()
+            .map(|dir| AuditLogger::new(config.security.audit.clone(), dir.to_path_buf()))
+            .transpose()?;
+
+        let approval = ApprovalManager::from_config(&config.autonomy);
+
+        Ok(Self::new(tools, audit, approval))
+    }
+
+    /// Serve newline-delimited JSON-RPC until the reader reaches EOF.
+    pub async fn serve<R, W>(&self, reader: R, mut writer: W) -> Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let reply = match serde_json::from_str::<Value>(line) {
+                Ok(Value::Array(batch)) => {
+                    let mut replies = Vec::new();
+                    for item in batch {
+                        if let Some(reply) = self.handle_value(item).await {
+                            replies.push(reply);
+                        }
+                    }
+                    (!replies.is_empty())
+                        .then(|| serde_json::to_value(replies))
+                        .transpose()?
+                }
+                Ok(value) => self
+                    .handle_value(value)
+                    .await
+                    .map(serde_json::to_value)
+                    .transpose()?,
+                Err(error) => Some(serde_json::to_value(JsonRpcMessage::error_response(
+                    Value::Null,
+                    PARSE_ERROR,
+                    format!("Parse error: {error}"),
+                ))?),
+            };
+
+            if let Some(reply) = reply {
+                let mut out = serde_json::to_string(&reply)?;
+                out.push('\n');
+                writer.write_all(out.as_bytes()).await?;
+                writer.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_value(&self, value: Value) -> Option<JsonRpcMessage> {
+        match serde_json::from_value::<JsonRpcMessage>(value) {
+            Ok(message) => self.handle_message(message).await,
+            Err(error) => Some(JsonRpcMessage::error_response(
+                Value::Null,
+                INVALID_REQUEST,
+                format!("Invalid request: {error}"),
+            )),
+        }
+    }
+
+    /// Handle one message; returns the response for requests, `None` otherwise.
+    pub async fn handle_message(&self, message: JsonRpcMessage) -> Option<JsonRpcMessage> {
+        if !message.is_request() {
+            // Notifications (e.g. notifications/initialized) and stray responses.
+            return None;
+        }
+        let id = message.id.clone().unwrap_or(Value::Null);
+        let params = message.params.unwrap_or(Value::Null);
+
+        Some(match message.method.as_deref().unwrap_or_default() {
+            "initialize" => JsonRpcMessage::response(id, Self::initialize(&params)),
+            "ping" => JsonRpcMessage::response(id, json!({})),
+            "tools/list" => JsonRpcMessage::response(id, self.list_tools()),
+            "tools/call" => match self.call_tool(&params).await {
+                Ok(result) => match serde_json::to_value(result) {
+                    Ok(result) => JsonRpcMessage::response(id, result),
+                    Err(error) => {
+                        JsonRpcMessage::error_response(id, INTERNAL_ERROR, error.to_string())
+                    }
+                },
+                Err(message) => JsonRpcMessage::error_response(id, INVALID_PARAMS, message),
+            },
+            method => JsonRpcMessage::error_response(
+                id,
+                METHOD_NOT_FOUND,
+                format!("Method not found: {method}"),
+            ),
+        })
+    }
+
+    fn initialize(params: &Value) -> Value {
+        let requested = params
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .unwrap_or(PROTOCOL_VERSION);
+        let version = if SUPPORTED_PROTOCOL_VERSIONS.contains(&requested) {
+            requested
+        } else {
+            PROTOCOL_VERSION
+        };
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": { "name": "zeroclaw", "version": env!("CARGO_PKG_VERSION") },
+        })
+    }
+
+    fn list_tools(&self) -> Value {
+        let tools: Vec<Value> = self
+            .tools
+            .iter()
+            .map(|tool| {
+                json!({
+                    "name": tool.name(),
+                    "description": tool.description(),
+                    "inputSchema": tool.parameters_schema(),
+                })
+            })
+            .collect();
+        json!({ "tools": tools })
+    }
+
+    /// Run a tool. `Err` is a protocol error (bad params / unknown tool);
+    /// tool failures are reported in-band with `isError`.
+    async fn call_tool(&self, params: &Value) -> Result<CallToolResult, String> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| "Missing tool name".to_string())?;
+        let tool = self
+            .tools
+            .iter()
+            .find(|tool| tool.name() == name)
+            .ok_or_else(|| format!("Unknown tool: {name}"))?;
+        let arguments = params
+            .get("arguments")
+            .cloned()
+            .filter(|args| !args.is_null())
+            .unwrap_or_else(|| json!({}));
+        let arguments_summary = crate::approval::summarize_args(&arguments);
+
+        if self.approval.needs_approval_for(name, &tool.metadata()) {
+            let error = format!(
+                "Tool '{name}' requires interactive approval and cannot run over MCP; \
+                 add it to autonomy.auto_approve or use full autonomy to allow it"
+            );
+            self.audit(ToolExecutionLog {
+                channel: AUDIT_CHANNEL,
+                tool: name,
+                arguments: &arguments_summary,
+                approved: false,
+                allowed: false,
+                success: false,
+                duration_ms: 0,
+                error: Some(&error),
+            });
+            return Ok(text_result(error, true));
+        }
+
+        let started = Instant::now();
+        let (success, text, parts) = match tool.execute(arguments).await {
+            Ok(result) if result.success => (true, result.output, result.parts),
+            Ok(result) => (
+                false,
+                result
+                    .error
+                    .filter(|error| !error.is_empty())
+                    .unwrap_or(result.output),
+                Vec::new(),
+            ),
+            Err(error) => (false, format!("{error:#}"), Vec::new()),
+        };
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        self.audit(ToolExecutionLog {
+            channel: AUDIT_CHANNEL,
+            tool: name,
+            arguments: &arguments_summary,
+            approved: true,
+            allowed: true,
+            success,
+            duration_ms,
+            error: (!success).then_some(text.as_str()),
+        });
+
+        let mut result = text_result(text, !success);
+        for part in parts {
+            match part {
+                tools::ToolContent::Image { mime_type, data } => {
+                    result.content.push(ToolContent::Image { data, mime_type });
+                }
+                tools::ToolContent::Json { value } if result.structured_content.is_none() => {
+                    result.structured_content = Some(value);
+                }
+                _ => {}
+            }
+        }
+        Ok(result)
+    }
+
+    fn audit(&self, entry: ToolExecutionLog<'_>) {
+        if let Some(audit) = &self.audit {
+            if let Err(error) = audit.log_tool_event(entry) {
+                tracing::warn!("Failed to write MCP audit event: {error}");
+            }
+        }
+    }
+}
+
+fn text_result(text: String, is_error: bool) -> CallToolResult {
+    CallToolResult {
+        content: vec![ToolContent::Text { text }],
+        is_error,
+        structured_content: None,
+    }
+}
+
+/// Entry point for `zeroclaw mcp serve`.
+pub async fn serve_stdio(config: &Config) -> Result<()> {
+    let server = McpServer::from_config(config)?;
+    tracing::info!(
+        tools = server.tools.len(),
+        "Serving zeroclaw tools over MCP stdio"
+    );
+    let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+    server.serve(stdin, tokio::io::stdout()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AuditConfig, AutonomyConfig};
+    use crate::security::AutonomyLevel;
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+    use tempfile::TempDir;
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the text argument"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({ "type": "object", "properties": { "text": { "type": "string" } } })
+        }
+
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            match args.get("text").and_then(Value::as_str) {
+                Some(text) => Ok(ToolResult::ok(text.to_string())),
+                None => Ok(ToolResult::err("Missing 'text'")),
+            }
+        }
+    }
+
+    fn server(audit: Option<AuditLogger>, autonomy: &AutonomyConfig) -> McpServer {
+        McpServer::new(
+            vec![Box::new(EchoTool)],
+            audit,
+            ApprovalManager::from_config(autonomy),
+        )
+    }
+
+    fn autonomy(level: AutonomyLevel) -> AutonomyConfig {
+        AutonomyConfig {
+            level,
+            ..AutonomyConfig::default()
+        }
+    }
+
+    async fn exchange(server: &McpServer, input: &str) -> Vec<Value> {
+        let mut output = Vec::new();
+        server.serve(input.as_bytes(), &mut output).await.unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn handshake_and_tool_listing() {
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2024-11-05","capabilities":{},"clientInfo":{"name":"test"}}}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+            "\n",
+        );
+        let replies = exchange(&server(None, &autonomy(AutonomyLevel::Full)), input).await;
+
+        assert_eq!(replies.len(), 2, "notifications must not be answered");
+        assert_eq!(replies[0]["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(replies[0]["result"]["serverInfo"]["name"], "zeroclaw");
+        assert_eq!(replies[1]["id"], 2);
+        assert_eq!(replies[1]["result"]["tools"][0]["name"], "echo");
+        assert_eq!(
+            replies[1]["result"]["tools"][0]["inputSchema"]["type"],
+            "object"
+        );
+    }
+
+    #[tokio::test]
+    async fn tool_calls_report_success_and_failure_in_band() {
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"echo","arguments":{"text":"hi"}}}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"echo","arguments":{}}}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"nope"}}"#,
+            "\n",
+        );
+        let replies = exchange(&server(None, &autonomy(AutonomyLevel::Full)), input).await;
+
+        assert_eq!(replies[0]["result"]["content"][0]["text"], "hi");
+        assert_eq!(replies[0]["result"]["isError"], false);
+        assert_eq!(replies[1]["result"]["content"][0]["text"], "Missing 'text'");
+        assert_eq!(replies[1]["result"]["isError"], true);
+        assert_eq!(replies[2]["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn malformed_and_unknown_requests_get_errors() {
+        let input = "not json\n{\"jsonrpc\":\"2.0\",\"id\":5,\"method\":\"resources/list\"}\n";
+        let replies = exchange(&server(None, &autonomy(AutonomyLevel::Full)), input).await;
+
+        assert_eq!(replies[0]["error"]["code"], PARSE_ERROR);
+        assert_eq!(replies[1]["id"], 5);
+        assert_eq!(replies[1]["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn supervised_calls_needing_approval_are_refused_and_audited() {
+        let tmp = TempDir::new().unwrap();
+        let audit = AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf()).unwrap();
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"echo","arguments":{"text":"hi"}}}"#,
+            "\n",
+        );
+        let supervised = autonomy(AutonomyLevel::Supervised);
+        let replies = exchange(&server(Some(audit), &supervised), input).await;
+
+        assert_eq!(replies[0]["result"]["isError"], true);
+        assert!(replies[0]["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("requires interactive approval"));
+
+        let log = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        let event: Value = serde_json::from_str(log.lines().next().unwrap()).unwrap();
+        assert_eq!(event["event_type"], "tool_execution");
+        assert_eq!(event["actor"]["channel"], "mcp");
+        assert_eq!(event["action"]["approved"], false);
+        assert_eq!(event["action"]["allowed"], false);
+    }
+
+    #[tokio::test]
+    async fn auto_approved_calls_run_and_are_audited_as_approved() {
+        let tmp = TempDir::new().unwrap();
+        let audit = AuditLogger::new(AuditConfig::default(), tmp.path().to_path_buf()).unwrap();
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"echo","arguments":{"text":"hi"}}}"#,
+            "\n",
+        );
+        let config = AutonomyConfig {
+            auto_approve: vec!["echo".into()],
+            ..autonomy(AutonomyLevel::Supervised)
+        };
+        let replies = exchange(&server(Some(audit), &config), input).await;
+
+        assert_eq!(replies[0]["result"]["isError"], false);
+        let log = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        let event: Value = serde_json::from_str(log.lines().next().unwrap()).unwrap();
+        assert_eq!(event["action"]["approved"], true);
+        assert_eq!(event["action"]["allowed"], true);
+    }
+}
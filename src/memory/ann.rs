@@ -0,0 +1,226 @@
+// Approximate nearest-neighbour search — inverted-file (IVF) index.
+//
+// Embeddings are partitioned into lists around spherical k-means centroids.
+// A query is compared against the centroids first and only the closest lists
+// are scanned, so search cost grows with √N instead of N.
+
+/// Lloyd iterations when training centroids.
+const KMEANS_ITERATIONS: usize = 10;
+/// Training uses an evenly strided sample of at most this many vectors.
+const MAX_TRAINING_VECTORS: usize = 25_000;
+const MIN_LISTS: usize = 8;
+const MAX_LISTS: usize = 1024;
+/// Lower bound on the number of lists scanned per query.
+const MIN_PROBES: usize = 4;
+
+/// IVF centroids for one embedding space.
+#[derive(Debug, Clone)]
+pub struct IvfIndex {
+    dims: usize,
+    centroids: Vec<Vec<f32>>,
+}
+
+impl IvfIndex {
+    /// Number of lists for a collection of `rows` vectors (≈ √rows).
+    pub fn list_count_for(rows: usize) -> usize {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let lists = (rows as f64).sqrt().round() as usize;
+        lists.clamp(MIN_LISTS, MAX_LISTS)
+    }
+
+    /// Train centroids with spherical k-means.
+    ///
+    /// Vectors whose length differs from the first one are ignored. Returns
+    /// `None` when there is nothing to train on. Seeding is deterministic so
+    /// rebuilding the same data yields the same index.
+    pub fn train(vectors: &[Vec<f32>]) -> Option<Self> {
+        let dims = vectors.first()?.len();
+        if dims == 0 {
+            return None;
+        }
+
+        let candidates: Vec<&Vec<f32>> = vectors.iter().filter(|v| v.len() == dims).collect();
+        let step = candidates.len().div_ceil(MAX_TRAINING_VECTORS).max(1);
+        let sample: Vec<Vec<f32>> = candidates
+            .iter()
+            .step_by(step)
+            .filter_map(|v| normalized(v))
+            .collect();
+        let lists = Self::list_count_for(candidates.len()).min(sample.len());
+        if lists == 0 {
+            return None;
+        }
+
+        let mut centroids: Vec<Vec<f32>> = (0..lists)
+            .map(|i| sample[i * sample.len() / lists].clone())
+            .collect();
+        let mut assignments = vec![usize::MAX; sample.len()];
+
+        for _ in 0..KMEANS_ITERATIONS {
+            let mut changed = false;
+            for (vector, assignment) in sample.iter().zip(assignments.iter_mut()) {
+                let nearest = nearest_centroid(&centroids, vector);
+                if *assignment != nearest {
+                    *assignment = nearest;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+
+            let mut sums = vec![vec![0.0_f32; dims]; lists];
+            for (vector, &list) in sample.iter().zip(&assignments) {
+                for (sum, x) in sums[list].iter_mut().zip(vector) {
+                    *sum += x;
+                }
+            }
+            for (centroid, sum) in centroids.iter_mut().zip(sums) {
+                // Empty lists keep their previous centroid.
+                if let Some(updated) = normalized(&sum) {
+                    *centroid = updated;
+                }
+            }
+        }
+
+        Some(Self { dims, centroids })
+    }
+
+    /// Rebuild an index from persisted centroids; all must share one length.
+    pub fn from_centroids(centroids: Vec<Vec<f32>>) -> Option<Self> {
+        let dims = centroids.first()?.len();
+        if dims == 0 || centroids.iter().any(|c| c.len() != dims) {
+            return None;
+        }
+        Some(Self { dims, centroids })
+    }
+
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    pub fn centroids(&self) -> &[Vec<f32>] {
+        &self.centroids
+    }
+
+    /// List a vector belongs to, or `None` for a different embedding size.
+    pub fn assign(&self, vector: &[f32]) -> Option<usize> {
+        (vector.len() == self.dims).then(|| nearest_centroid(&self.centroids, vector))
+    }
+
+    /// The `nprobe` lists closest to `query`, best first.
+    pub fn probe(&self, query: &[f32], nprobe: usize) -> Vec<usize> {
+        if query.len() != self.dims {
+            return Vec::new();
+        }
+        let mut scored: Vec<(usize, f32)> = self
+            .centroids
+            .iter()
+            .enumerate()
+            .map(|(list, centroid)| (list, dot(centroid, query)))
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored
+            .into_iter()
+            .take(nprobe.max(1))
+            .map(|(list, _)| list)
+            .collect()
+    }
+
+    /// Lists scanned per query: about one in six, never fewer than four.
+    pub fn default_probes(&self) -> usize {
+        (self.centroids.len() / 6)
+            .max(MIN_PROBES)
+            .min(self.centroids.len())
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalized(v: &[f32]) -> Option<Vec<f32>> {
+    let norm = dot(v, v).sqrt();
+    (norm.is_finite() && norm > f32::EPSILON).then(|| v.iter().map(|x| x / norm).collect())
+}
+
+/// Centroids are unit length, so the largest dot product is the closest by cosine.
+/// Ties go to the lowest list, matching the order `probe` returns.
+fn nearest_centroid(centroids: &[Vec<f32>], vector: &[f32]) -> usize {
+    let mut best = (0, f32::NEG_INFINITY);
+    for (list, centroid) in centroids.iter().enumerate() {
+        let score = dot(centroid, vector);
+        if score > best.1 {
+            best = (list, score);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four well-separated clusters along the axes of a 4-d space.
+    fn clustered(per_cluster: usize) -> Vec<Vec<f32>> {
+        let mut vectors = Vec::new();
+        for axis in 0..4 {
+            for i in 0..per_cluster {
+                let mut v = vec![0.05_f32; 4];
+                #[allow(clippy::cast_precision_loss)]
+                let jitter = i as f32 * 0.001;
+                v[axis] = 1.0 + jitter;
+                vectors.push(v);
+            }
+        }
+        vectors
+    }
+
+    #[test]
+    fn list_count_tracks_square_root() {
+        assert_eq!(IvfIndex::list_count_for(10), MIN_LISTS);
+        assert_eq!(IvfIndex::list_count_for(10_000), 100);
+        assert_eq!(IvfIndex::list_count_for(10_000_000), MAX_LISTS);
+    }
+
+    #[test]
+    fn train_keeps_clusters_in_the_same_list() {
+        let vectors = clustered(20);
+        let index = IvfIndex::train(&vectors).unwrap();
+        assert_eq!(index.dims(), 4);
+        assert_eq!(index.centroids().len(), IvfIndex::list_count_for(80));
+
+        let mut seen = std::collections::HashSet::new();
+        for cluster in vectors.chunks(20) {
+            let lists: std::collections::HashSet<usize> =
+                cluster.iter().filter_map(|v| index.assign(v)).collect();
+            assert!(
+                lists.is_disjoint(&seen),
+                "clusters share lists: {lists:?} vs {seen:?}"
+            );
+            assert!(lists.contains(&index.probe(&cluster[0], 1)[0]));
+            seen.extend(lists);
+        }
+    }
+
+    #[test]
+    fn mismatched_dimensions_are_rejected() {
+        let index = IvfIndex::train(&clustered(5)).unwrap();
+        assert_eq!(index.assign(&[1.0, 0.0]), None);
+        assert!(index.probe(&[1.0, 0.0], 3).is_empty());
+        assert!(IvfIndex::from_centroids(vec![vec![1.0, 0.0], vec![1.0]]).is_none());
+        assert!(IvfIndex::train(&[]).is_none());
+    }
+
+    #[test]
+    fn probes_scale_with_list_count() {
+        let small = IvfIndex::from_centroids(vec![vec![1.0, 0.0]; 3]).unwrap();
+        assert_eq!(small.default_probes(), 3);
+        let large = IvfIndex::from_centroids(vec![vec![1.0, 0.0]; 120]).unwrap();
+        assert_eq!(large.default_probes(), 20);
+    }
+}
@@ -11,6 +11,19 @@ pub struct Chunk {
     pub index: usize,
     pub content: String,
     pub heading: Option<Rc<str>>,
+    /// First source line (1-based) this chunk was taken from.
+    pub start_line: usize,
+    /// Last source line (1-based, inclusive) this chunk was taken from.
+    pub end_line: usize,
+}
+
+/// A source line paired with its 1-based line number.
+type Line<'a> = (usize, &'a str);
+
+/// A heading and the lines beneath it, up to the next heading.
+struct Section<'a> {
+    heading: Option<Line<'a>>,
+    body: Vec<Line<'a>>,
 }
 
 /// Split markdown text into chunks, each under `max_tokens` approximate tokens.
@@ -26,12 +39,35 @@ pub fn chunk_markdown(text: &str, max_tokens: usize) -> Vec<Chunk> {
         return Vec::new();
     }
 
-    let max_chars = max_tokens * 4;
-    let sections = split_on_headings(text);
+    chunk_sections(split_on_headings(text), max_tokens * 4)
+}
+
+/// Split plain text or source code into chunks under `max_tokens`.
+///
+/// Same paragraph and line splitting as [`chunk_markdown`], but no line is
+/// treated as a heading (so `# comment` lines in code stay in place).
+pub fn chunk_text(text: &str, max_tokens: usize) -> Vec<Chunk> {
+    if text.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let body = text.lines().enumerate().map(|(i, l)| (i + 1, l)).collect();
+    chunk_sections(
+        vec![Section {
+            heading: None,
+            body,
+        }],
+        max_tokens * 4,
+    )
+}
+
+fn chunk_sections(sections: Vec<Section<'_>>, max_chars: usize) -> Vec<Chunk> {
     let mut chunks = Vec::with_capacity(sections.len());
 
-    for (heading, body) in sections {
-        let heading: Option<Rc<str>> = heading.map(Rc::from);
+    for section in sections {
+        let heading: Option<Rc<str>> = section.heading.map(|(_, h)| Rc::from(h));
+        let heading_line = section.heading.map(|(n, _)| n);
+        let body = render(&section.body);
         let full = if let Some(ref h) = heading {
             format!("{h}\n{body}")
         } else {
@@ -39,60 +75,80 @@ pub fn chunk_markdown(text: &str, max_tokens: usize) -> Vec<Chunk> {
         };
 
         if full.len() <= max_chars {
+            let (start_line, end_line) = line_span(heading_line, &section.body, true);
             chunks.push(Chunk {
                 index: chunks.len(),
                 content: full.trim().to_string(),
                 heading: heading.clone(),
+                start_line,
+                end_line,
             });
         } else {
             // Split on paragraphs (blank lines)
-            let paragraphs = split_on_blank_lines(&body);
+            let paragraphs = split_on_blank_lines(&section.body);
             let mut current = heading
                 .as_deref()
                 .map_or_else(String::new, |h| format!("{h}\n"));
+            let mut current_lines: Vec<Line> = Vec::new();
 
             for para in paragraphs {
-                if current.len() + para.len() > max_chars && !current.trim().is_empty() {
+                let para_text = render(&para);
+                if current.len() + para_text.len() > max_chars && !current.trim().is_empty() {
+                    let (start_line, end_line) = line_span(heading_line, &current_lines, false);
                     chunks.push(Chunk {
                         index: chunks.len(),
                         content: current.trim().to_string(),
                         heading: heading.clone(),
+                        start_line,
+                        end_line,
                     });
                     current = heading
                         .as_deref()
                         .map_or_else(String::new, |h| format!("{h}\n"));
+                    current_lines.clear();
                 }
 
-                if para.len() > max_chars {
+                if para_text.len() > max_chars {
                     // Paragraph too big — split on lines
                     if !current.trim().is_empty() {
+                        let (start_line, end_line) = line_span(heading_line, &current_lines, false);
                         chunks.push(Chunk {
                             index: chunks.len(),
                             content: current.trim().to_string(),
                             heading: heading.clone(),
+                            start_line,
+                            end_line,
                         });
                         current = heading
                             .as_deref()
                             .map_or_else(String::new, |h| format!("{h}\n"));
+                        current_lines.clear();
                     }
-                    for line_chunk in split_on_lines(&para, max_chars) {
+                    for piece in split_on_lines(&para, max_chars) {
+                        let (start_line, end_line) = line_span(heading_line, &piece, false);
                         chunks.push(Chunk {
                             index: chunks.len(),
-                            content: line_chunk.trim().to_string(),
+                            content: render(&piece).trim().to_string(),
                             heading: heading.clone(),
+                            start_line,
+                            end_line,
                         });
                     }
                 } else {
-                    current.push_str(&para);
+                    current.push_str(&para_text);
                     current.push('\n');
+                    current_lines.extend(para);
                 }
             }
 
             if !current.trim().is_empty() {
+                let (start_line, end_line) = line_span(heading_line, &current_lines, false);
                 chunks.push(Chunk {
                     index: chunks.len(),
                     content: current.trim().to_string(),
                     heading: heading.clone(),
+                    start_line,
+                    end_line,
                 });
             }
         }
@@ -109,65 +165,96 @@ pub fn chunk_markdown(text: &str, max_tokens: usize) -> Vec<Chunk> {
     chunks
 }
 
-/// Split text into `(heading, body)` sections.
-fn split_on_headings(text: &str) -> Vec<(Option<String>, String)> {
+/// Join lines back into text, one trailing newline per line.
+fn render(lines: &[Line]) -> String {
+    lines.iter().fold(String::new(), |mut out, (_, line)| {
+        out.push_str(line);
+        out.push('\n');
+        out
+    })
+}
+
+/// Range of the non-blank `lines`. The heading line is included when
+/// `with_heading` is set or when there are no such lines.
+fn line_span(heading_line: Option<usize>, lines: &[Line], with_heading: bool) -> (usize, usize) {
+    let body = lines
+        .iter()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, _)| *n);
+    let has_body = body.clone().next().is_some();
+    let heading = heading_line.filter(|_| with_heading || !has_body);
+    let numbers = body.chain(heading);
+    (
+        numbers.clone().min().unwrap_or(1),
+        numbers.max().unwrap_or(1),
+    )
+}
+
+/// Split text into heading sections.
+fn split_on_headings(text: &str) -> Vec<Section<'_>> {
     let mut sections = Vec::new();
-    let mut current_heading: Option<String> = None;
-    let mut current_body = String::new();
+    let mut current_heading: Option<Line> = None;
+    let mut current_body: Vec<Line> = Vec::new();
 
-    for line in text.lines() {
+    for (i, line) in text.lines().enumerate() {
         if line.starts_with("# ") || line.starts_with("## ") || line.starts_with("### ") {
-            if !current_body.trim().is_empty() || current_heading.is_some() {
-                sections.push((current_heading.take(), std::mem::take(&mut current_body)));
+            if current_body.iter().any(|(_, l)| !l.trim().is_empty()) || current_heading.is_some() {
+                sections.push(Section {
+                    heading: current_heading.take(),
+                    body: std::mem::take(&mut current_body),
+                });
             }
-            current_heading = Some(line.to_string());
+            current_heading = Some((i + 1, line));
         } else {
-            current_body.push_str(line);
-            current_body.push('\n');
+            current_body.push((i + 1, line));
         }
     }
 
-    if !current_body.trim().is_empty() || current_heading.is_some() {
-        sections.push((current_heading, current_body));
+    if current_body.iter().any(|(_, l)| !l.trim().is_empty()) || current_heading.is_some() {
+        sections.push(Section {
+            heading: current_heading,
+            body: current_body,
+        });
     }
 
     sections
 }
 
-/// Split text on blank lines (paragraph boundaries)
-fn split_on_blank_lines(text: &str) -> Vec<String> {
+/// Split lines on blank lines (paragraph boundaries)
+fn split_on_blank_lines<'a>(lines: &[Line<'a>]) -> Vec<Vec<Line<'a>>> {
     let mut paragraphs = Vec::new();
-    let mut current = String::new();
+    let mut current = Vec::new();
 
-    for line in text.lines() {
+    for &(n, line) in lines {
         if line.trim().is_empty() {
-            if !current.trim().is_empty() {
+            if !current.is_empty() {
                 paragraphs.push(std::mem::take(&mut current));
             }
         } else {
-            current.push_str(line);
-            current.push('\n');
+            current.push((n, line));
         }
     }
 
-    if !current.trim().is_empty() {
+    if !current.is_empty() {
         paragraphs.push(current);
     }
 
     paragraphs
 }
 
-/// Split text on line boundaries to fit within `max_chars`
-fn split_on_lines(text: &str, max_chars: usize) -> Vec<String> {
-    let mut chunks = Vec::with_capacity(text.len() / max_chars.max(1) + 1);
-    let mut current = String::new();
+/// Split lines into groups whose rendered text fits within `max_chars`
+fn split_on_lines<'a>(lines: &[Line<'a>], max_chars: usize) -> Vec<Vec<Line<'a>>> {
+    let mut chunks = Vec::new();
+    let mut current: Vec<Line<'a>> = Vec::new();
+    let mut current_len = 0;
 
-    for line in text.lines() {
-        if current.len() + line.len() + 1 > max_chars && !current.is_empty() {
+    for &(n, line) in lines {
+        if current_len + line.len() + 1 > max_chars && !current.is_empty() {
             chunks.push(std::mem::take(&mut current));
+            current_len = 0;
         }
-        current.push_str(line);
-        current.push('\n');
+        current.push((n, line));
+        current_len += line.len() + 1;
     }
 
     if !current.is_empty() {
@@ -357,6 +444,40 @@ mod tests {
         assert_eq!(chunks[0].heading.as_deref(), Some("# Just a heading"));
     }
 
+    #[test]
+    fn chunks_record_source_lines() {
+        let text = "# Title\nIntro line.\n\n## Setup\nStep one.\nStep two.\n\n## Deploy\nShip it.";
+        let chunks = chunk_markdown(text, 512);
+        let spans: Vec<(usize, usize)> =
+            chunks.iter().map(|c| (c.start_line, c.end_line)).collect();
+        assert_eq!(spans, vec![(1, 2), (4, 6), (8, 9)]);
+    }
+
+    #[test]
+    fn split_sections_record_body_lines_only() {
+        let mut text = String::from("## Big Section\n");
+        for i in 0..40 {
+            use std::fmt::Write;
+            let _ = write!(text, "Line {i} with some content here.\n\n");
+        }
+        let chunks = chunk_markdown(&text, 50);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks[0].start_line, 2);
+        for pair in chunks.windows(2) {
+            assert!(pair[0].end_line < pair[1].start_line);
+        }
+        assert_eq!(chunks.last().unwrap().end_line, 80);
+    }
+
+    #[test]
+    fn chunk_text_ignores_hash_comment_headings() {
+        let text = "# comment\nx = 1\n\n# another\ny = 2";
+        let chunks = chunk_text(text, 512);
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].heading.is_none());
+        assert_eq!((chunks[0].start_line, chunks[0].end_line), (1, 5));
+    }
+
     #[test]
     fn no_content_loss() {
         let text = "# A\nContent A line 1\nContent A line 2\n\n## B\nContent B\n\n## C\nContent C";
@@ -1,7 +1,8 @@
+use super::ingest::{ingest_path, IngestOptions};
 use super::traits::{Memory, MemoryCategory};
 use super::{
     classify_memory_backend, create_memory_for_migration, effective_memory_backend_name,
-    MemoryBackendKind,
+    MemoryBackendKind, ResponseCache,
 };
 use crate::config::Config;
 #[cfg(feature = "memory-postgres")]
@@ -23,6 +24,16 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
         crate::MemoryCommands::Clear { key, category, yes } => {
             handle_clear(config, key, category, yes).await
         }
+        crate::MemoryCommands::Cache { cache_command } => match cache_command {
+            crate::ResponseCacheCommands::Stats => handle_cache_stats(config),
+            crate::ResponseCacheCommands::Clear { yes } => handle_cache_clear(config, yes),
+        },
+        crate::MemoryCommands::Ingest {
+            path,
+            category,
+            max_tokens,
+            force,
+        } => handle_ingest(config, &path, &category, max_tokens, force).await,
     }
 }
 
@@ -286,6 +297,115 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
     Ok(())
 }
 
+async fn handle_ingest(
+    config: &Config,
+    path: &std::path::Path,
+    category: &str,
+    max_tokens: usize,
+    force: bool,
+) -> Result<()> {
+    // Unlike the other subcommands, ingestion needs the embedding provider
+    // so stored chunks are available to vector recall.
+    let mem = super::create_memory_with_storage_and_routes(
+        &config.memory,
+        &config.embedding_routes,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    if mem.name() == "none" {
+        bail!("Memory backend is 'none' (disabled). Nothing to ingest into.");
+    }
+
+    let options = IngestOptions {
+        category: parse_category(category),
+        max_tokens: max_tokens.max(1),
+        force,
+    };
+    println!("Ingesting {} ...", path.display());
+    let report = ingest_path(&*mem, &config.workspace_dir, path, &options).await?;
+
+    println!(
+        "{} Ingested {} files ({} chunks) into '{}'.",
+        style("✓").green().bold(),
+        report.ingested_files,
+        report.stored_chunks,
+        options.category,
+    );
+    println!("  Unchanged: {}", report.unchanged_files);
+    println!("  Skipped:   {}", report.skipped_files);
+    println!("  Removed:   {}", report.removed_files);
+
+    Ok(())
+}
+
+/// Open the response cache directly, regardless of `response_cache_enabled`,
+/// so entries left behind by an earlier configuration can still be managed.
+fn open_response_cache(config: &Config) -> Result<ResponseCache> {
+    ResponseCache::new(
+        &config.workspace_dir,
+        config.memory.response_cache_ttl_minutes,
+        config.memory.response_cache_max_entries,
+    )
+}
+
+fn handle_cache_stats(config: &Config) -> Result<()> {
+    let cache = open_response_cache(config)?;
+    let (entries, hits, tokens_saved) = cache.stats()?;
+
+    println!("Response Cache:\n");
+    println!(
+        "  Enabled:      {}",
+        if config.memory.response_cache_enabled {
+            style("yes").green().bold().to_string()
+        } else {
+            style("no").yellow().bold().to_string()
+        }
+    );
+    println!(
+        "  Mode:         {}",
+        if config.memory.response_cache_semantic {
+            "semantic"
+        } else {
+            "exact"
+        }
+    );
+    println!("  Entries:      {entries}");
+    println!("  Hits:         {hits}");
+    println!("  Tokens saved: {tokens_saved}");
+
+    Ok(())
+}
+
+fn handle_cache_clear(config: &Config, yes: bool) -> Result<()> {
+    let cache = open_response_cache(config)?;
+    let (entries, _, _) = cache.stats()?;
+
+    if entries == 0 {
+        println!("Response cache is already empty.");
+        return Ok(());
+    }
+
+    if !yes {
+        let confirmed = dialoguer::Confirm::new()
+            .with_prompt(format!("  Delete {entries} cached responses?"))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let cleared = cache.clear()?;
+    println!(
+        "{} Cleared {cleared} cached responses.",
+        style("✓").green().bold()
+    );
+
+    Ok(())
+}
+
 fn parse_category(s: &str) -> MemoryCategory {
     match s.trim().to_ascii_lowercase().as_str() {
         "core" => MemoryCategory::Core,
@@ -1,4 +1,8 @@
 use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Texts sent per `/api/embed` request by [`OllamaEmbedding`].
+const OLLAMA_EMBED_BATCH_SIZE: usize = 32;
 
 /// Trait for embedding providers — convert text to vectors
 #[async_trait]
@@ -6,6 +10,11 @@ pub trait EmbeddingProvider: Send + Sync {
     /// Provider name
     fn name(&self) -> &str;
 
+    /// Embedding model identifier, used to detect model changes in stored vectors
+    fn model(&self) -> &str {
+        self.name()
+    }
+
     /// Embedding dimensions
     fn dimensions(&self) -> usize;
 
@@ -99,6 +108,10 @@ impl EmbeddingProvider for OpenAiEmbedding {
         "openai"
     }
 
+    fn model(&self) -> &str {
+        &self.model
+    }
+
     fn dimensions(&self) -> usize {
         self.dims
     }
@@ -113,14 +126,15 @@ impl EmbeddingProvider for OpenAiEmbedding {
             "input": texts,
         });
 
-        let resp = self
+        let mut request = self
             .http_client()
             .post(self.embeddings_url())
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+            .header("Content-Type", "application/json");
+        // Local OpenAI-compatible servers (LM Studio, llama.cpp, vLLM) often run without auth.
+        if !self.api_key.is_empty() {
+            request = request.header("Authorization", format!("Bearer {}", self.api_key));
+        }
+        let resp = request.json(&body).send().await?;
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -154,6 +168,117 @@ impl EmbeddingProvider for OpenAiEmbedding {
     }
 }
 
+// ── Ollama native embedding provider ─────────────────────────
+
+/// Native Ollama `/api/embed` provider.
+///
+/// Inputs are sent in batches of [`OLLAMA_EMBED_BATCH_SIZE`]. The configured
+/// dimensions are only a starting hint: once Ollama returns a vector, its length
+/// becomes the reported dimension count.
+pub struct OllamaEmbedding {
+    base_url: String,
+    model: String,
+    dims: AtomicUsize,
+}
+
+impl OllamaEmbedding {
+    pub fn new(base_url: &str, model: &str, dims: usize) -> Self {
+        let trimmed = base_url.trim().trim_end_matches('/');
+        let base_url = trimmed.strip_suffix("/api").unwrap_or(trimmed);
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            dims: AtomicUsize::new(dims),
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("memory.embeddings")
+    }
+
+    fn embed_url(&self) -> String {
+        format!("{}/api/embed", self.base_url)
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "input": texts,
+        });
+
+        let resp = self
+            .http_client()
+            .post(self.embed_url())
+            .json(&body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Ollama embedding error {status}: {text}");
+        }
+
+        let json: serde_json::Value = resp.json().await?;
+        let data = json
+            .get("embeddings")
+            .and_then(|d| d.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Invalid Ollama response: missing 'embeddings'"))?;
+        if data.len() != texts.len() {
+            anyhow::bail!(
+                "Ollama returned {} embeddings for {} inputs",
+                data.len(),
+                texts.len()
+            );
+        }
+
+        let mut embeddings = Vec::with_capacity(data.len());
+        for item in data {
+            let embedding = item
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("Invalid Ollama embedding item"))?;
+
+            #[allow(clippy::cast_possible_truncation)]
+            let vec: Vec<f32> = embedding
+                .iter()
+                .filter_map(|v| v.as_f64().map(|f| f as f32))
+                .collect();
+
+            embeddings.push(vec);
+        }
+
+        Ok(embeddings)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OllamaEmbedding {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims.load(Ordering::Relaxed)
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(OLLAMA_EMBED_BATCH_SIZE) {
+            embeddings.extend(self.embed_batch(batch).await?);
+        }
+
+        if let Some(detected) = embeddings.first().map(Vec::len).filter(|len| *len > 0) {
+            self.dims.store(detected, Ordering::Relaxed);
+        }
+
+        Ok(embeddings)
+    }
+}
+
 // ── Factory ──────────────────────────────────────────────────
 
 pub fn create_embedding_provider(
@@ -181,6 +306,11 @@ pub fn create_embedding_provider(
                 dims,
             ))
         }
+        "ollama" => Box::new(OllamaEmbedding::new("http://localhost:11434", model, dims)),
+        name if name.starts_with("ollama:") => {
+            let base_url = name.strip_prefix("ollama:").unwrap_or("");
+            Box::new(OllamaEmbedding::new(base_url, model, dims))
+        }
         name if name.starts_with("custom:") => {
             let base_url = name.strip_prefix("custom:").unwrap_or("");
             let key = api_key.unwrap_or("");
@@ -240,6 +370,57 @@ mod tests {
         assert_eq!(p.dimensions(), 768);
     }
 
+    #[test]
+    fn factory_ollama() {
+        let p = create_embedding_provider("ollama", None, "nomic-embed-text", 768);
+        assert_eq!(p.name(), "ollama");
+        assert_eq!(p.model(), "nomic-embed-text");
+        assert_eq!(p.dimensions(), 768);
+    }
+
+    #[test]
+    fn ollama_embed_url_normalizes_api_suffix() {
+        let p = OllamaEmbedding::new("http://10.0.0.5:11434/api/", "m", 768);
+        assert_eq!(p.embed_url(), "http://10.0.0.5:11434/api/embed");
+
+        let p = OllamaEmbedding::new("http://localhost:11434", "m", 768);
+        assert_eq!(p.embed_url(), "http://localhost:11434/api/embed");
+    }
+
+    #[tokio::test]
+    async fn ollama_batches_requests_and_detects_dimensions() {
+        use axum::{routing::post, Json, Router};
+        use std::sync::Arc;
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let seen = requests.clone();
+        let app = Router::new().route(
+            "/api/embed",
+            post(move |Json(body): Json<serde_json::Value>| {
+                let seen = seen.clone();
+                async move {
+                    seen.fetch_add(1, Ordering::SeqCst);
+                    let count = body["input"].as_array().map_or(0, Vec::len);
+                    Json(serde_json::json!({ "embeddings": vec![vec![0.5_f32; 3]; count] }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let p = create_embedding_provider(&format!("ollama:http://{addr}"), None, "m", 1536);
+        let texts: Vec<String> = (0..OLLAMA_EMBED_BATCH_SIZE + 5)
+            .map(|i| format!("text {i}"))
+            .collect();
+        let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+        let embeddings = p.embed(&refs).await.unwrap();
+        assert_eq!(embeddings.len(), refs.len());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert_eq!(p.dimensions(), 3);
+    }
+
     // ── Edge cases ───────────────────────────────────────────────
 
     #[tokio::test]
@@ -0,0 +1,444 @@
+// Document ingestion — load local files into memory as searchable chunks.
+//
+// Walks a file or directory, extracts text from Markdown, plain text,
+// source code, HTML and (with `rag-pdf`) PDF files, chunks it with
+// source/line provenance and stores each chunk as a memory entry.
+// A content-hash manifest under `state/` lets re-runs skip unchanged files.
+
+use super::chunker::{self, Chunk};
+use super::traits::{Memory, MemoryCategory};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE: &str = "memory_ingest_state.json";
+
+/// Default category for ingested document chunks.
+pub const DEFAULT_INGEST_CATEGORY: &str = "docs";
+
+/// Default chunk size in approximate tokens.
+pub const DEFAULT_MAX_TOKENS: usize = 512;
+
+/// Files larger than this are skipped.
+const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Directories never descended into.
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "vendor", "__pycache__"];
+
+const TEXT_EXTENSIONS: &[&str] = &["txt", "text", "rst", "adoc", "log", "csv"];
+
+const CODE_EXTENSIONS: &[&str] = &[
+    "rs", "py", "js", "jsx", "ts", "tsx", "go", "java", "kt", "c", "h", "cc", "cpp", "hpp", "cs",
+    "rb", "php", "swift", "scala", "sh", "bash", "zsh", "ps1", "sql", "lua", "toml", "yaml", "yml",
+    "json", "ini", "cfg", "conf", "tf",
+];
+
+/// How a file's text is extracted and chunked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocKind {
+    Markdown,
+    Text,
+    Html,
+    Pdf,
+}
+
+impl DocKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "md" | "markdown" | "mdx" => Some(Self::Markdown),
+            "html" | "htm" | "xhtml" => Some(Self::Html),
+            "pdf" => Some(Self::Pdf),
+            e if TEXT_EXTENSIONS.contains(&e) || CODE_EXTENSIONS.contains(&e) => Some(Self::Text),
+            _ => None,
+        }
+    }
+}
+
+/// Options for a single `memory ingest` run.
+#[derive(Debug, Clone)]
+pub struct IngestOptions {
+    /// Category the chunks are stored under.
+    pub category: MemoryCategory,
+    /// Maximum chunk size in approximate tokens.
+    pub max_tokens: usize,
+    /// Re-ingest files even when their content hash is unchanged.
+    pub force: bool,
+}
+
+impl Default for IngestOptions {
+    fn default() -> Self {
+        Self {
+            category: MemoryCategory::Custom(DEFAULT_INGEST_CATEGORY.to_string()),
+            max_tokens: DEFAULT_MAX_TOKENS,
+            force: false,
+        }
+    }
+}
+
+/// Outcome of an ingest run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestReport {
+    pub ingested_files: usize,
+    pub unchanged_files: usize,
+    pub skipped_files: usize,
+    pub removed_files: usize,
+    pub stored_chunks: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IngestedFile {
+    hash: String,
+    category: String,
+    chunks: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct IngestState {
+    files: BTreeMap<String, IngestedFile>,
+}
+
+/// Ingest a file or directory tree into `mem`.
+///
+/// Files whose content hash matches the previous run are skipped. Changed
+/// files have their old chunks removed before the new ones are stored, and
+/// files under `root` that have disappeared since the last run are dropped.
+pub async fn ingest_path(
+    mem: &dyn Memory,
+    workspace_dir: &Path,
+    root: &Path,
+    options: &IngestOptions,
+) -> Result<IngestReport> {
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("cannot access {}", root.display()))?;
+
+    let mut paths = Vec::new();
+    if root.is_dir() {
+        collect_paths(&root, &mut paths);
+        paths.sort();
+    } else {
+        paths.push(root.clone());
+    }
+
+    let mut state = read_state(workspace_dir);
+    let mut report = IngestReport::default();
+    let mut seen = HashSet::new();
+    let category = options.category.to_string();
+
+    for path in paths {
+        let source = path.display().to_string();
+        let Some(kind) = DocKind::from_path(&path) else {
+            report.skipped_files += 1;
+            continue;
+        };
+        if fs::metadata(&path).map_or(true, |m| m.len() > MAX_FILE_BYTES) {
+            tracing::debug!("memory ingest: skipping {source} (too large or unreadable)");
+            report.skipped_files += 1;
+            continue;
+        }
+        let Ok(bytes) = fs::read(&path) else {
+            report.skipped_files += 1;
+            continue;
+        };
+        seen.insert(source.clone());
+
+        let hash = hex::encode(Sha256::digest(&bytes));
+        let previous = state.files.get(&source);
+        if !options.force && previous.is_some_and(|f| f.hash == hash && f.category == category) {
+            report.unchanged_files += 1;
+            continue;
+        }
+
+        if let Some(prev) = state.files.remove(&source) {
+            forget_chunks(mem, &source, prev.chunks).await?;
+        }
+
+        let Some(text) = extract_text(&path, kind, &bytes) else {
+            tracing::debug!("memory ingest: no text extracted from {source}");
+            report.skipped_files += 1;
+            continue;
+        };
+        let chunks = match kind {
+            DocKind::Markdown => chunker::chunk_markdown(&text, options.max_tokens),
+            _ => chunker::chunk_text(&text, options.max_tokens),
+        };
+
+        for chunk in &chunks {
+            mem.store(
+                &chunk_key(&source, chunk.index),
+                &render_chunk(&source, kind, chunk),
+                options.category.clone(),
+                None,
+            )
+            .await?;
+        }
+
+        report.ingested_files += 1;
+        report.stored_chunks += chunks.len();
+        state.files.insert(
+            source,
+            IngestedFile {
+                hash,
+                category: category.clone(),
+                chunks: chunks.len(),
+            },
+        );
+    }
+
+    let removed: Vec<String> = state
+        .files
+        .keys()
+        .filter(|source| Path::new(source.as_str()).starts_with(&root) && !seen.contains(*source))
+        .cloned()
+        .collect();
+    for source in removed {
+        if let Some(prev) = state.files.remove(&source) {
+            forget_chunks(mem, &source, prev.chunks).await?;
+            report.removed_files += 1;
+        }
+    }
+
+    write_state(workspace_dir, &state)?;
+    Ok(report)
+}
+
+fn chunk_key(source: &str, index: usize) -> String {
+    format!("doc:{source}#{index}")
+}
+
+async fn forget_chunks(mem: &dyn Memory, source: &str, count: usize) -> Result<()> {
+    for index in 0..count {
+        mem.forget(&chunk_key(source, index)).await?;
+    }
+    Ok(())
+}
+
+/// Prefix chunk content with its provenance so recalled entries can be cited.
+fn render_chunk(source: &str, kind: DocKind, chunk: &Chunk) -> String {
+    // PDF text is extracted, so its line numbers don't map back to the file.
+    let location = if kind == DocKind::Pdf {
+        String::new()
+    } else if chunk.start_line == chunk.end_line {
+        format!(" (line {})", chunk.start_line)
+    } else {
+        format!(" (lines {}-{})", chunk.start_line, chunk.end_line)
+    };
+    format!("[source: {source}{location}]\n{}", chunk.content)
+}
+
+fn collect_paths(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if !SKIP_DIRS.contains(&name.as_ref()) {
+                collect_paths(&path, out);
+            }
+        } else if file_type.is_file() {
+            out.push(path);
+        }
+    }
+}
+
+fn extract_text(path: &Path, kind: DocKind, bytes: &[u8]) -> Option<String> {
+    let text = match kind {
+        DocKind::Markdown | DocKind::Text => std::str::from_utf8(bytes).ok()?.to_string(),
+        DocKind::Html => html_to_text(std::str::from_utf8(bytes).ok()?),
+        DocKind::Pdf => extract_pdf_text(path, bytes)?,
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+#[cfg(feature = "rag-pdf")]
+fn extract_pdf_text(_path: &Path, bytes: &[u8]) -> Option<String> {
+    pdf_extract::extract_text_from_mem(bytes).ok()
+}
+
+#[cfg(not(feature = "rag-pdf"))]
+fn extract_pdf_text(path: &Path, _bytes: &[u8]) -> Option<String> {
+    tracing::warn!(
+        "memory ingest: skipping {} (PDF support requires the 'rag-pdf' feature)",
+        path.display()
+    );
+    None
+}
+
+/// Strip tags from HTML while keeping its line structure, so chunk line
+/// numbers still point into the original file. `<script>` and `<style>`
+/// bodies are dropped.
+fn html_to_text(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let Some(end) = tail.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &tail[1..end];
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase();
+        out.extend(tag.chars().filter(|&c| c == '\n'));
+        rest = &tail[end + 1..];
+
+        if !tag.starts_with('/') && (name == "script" || name == "style") {
+            let close = format!("</{name}");
+            let body_end = rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+            out.extend(rest[..body_end].chars().filter(|&c| c == '\n'));
+            rest = &rest[body_end..];
+        }
+    }
+    out.push_str(rest);
+
+    out.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn read_state(workspace_dir: &Path) -> IngestState {
+    fs::read_to_string(state_path(workspace_dir))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_state(workspace_dir: &Path, state: &IngestState) -> Result<()> {
+    let path = state_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(state)?)?;
+    Ok(())
+}
+
+fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(STATE_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    async fn ingest(mem: &SqliteMemory, ws: &Path, root: &Path) -> IngestReport {
+        ingest_path(mem, ws, root, &IngestOptions::default())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn ingests_supported_files_with_provenance() {
+        let ws = TempDir::new().unwrap();
+        let docs = TempDir::new().unwrap();
+        fs::write(
+            docs.path().join("deploy.md"),
+            "# Deploy\nRun the pipeline.\n\n## Rollback\nRevert the tag.",
+        )
+        .unwrap();
+        fs::create_dir(docs.path().join("scripts")).unwrap();
+        fs::write(
+            docs.path().join("scripts/check.sh"),
+            "# health\ncurl -f /health\n",
+        )
+        .unwrap();
+        fs::write(docs.path().join("logo.png"), [0u8, 1, 2]).unwrap();
+
+        let mem = SqliteMemory::new(ws.path()).unwrap();
+        let report = ingest(&mem, ws.path(), docs.path()).await;
+        assert_eq!(report.ingested_files, 2);
+        assert_eq!(report.skipped_files, 1);
+        assert_eq!(report.stored_chunks, 3);
+
+        let entries = mem
+            .list(Some(&MemoryCategory::Custom("docs".into())), None)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 3);
+        let rollback = entries
+            .iter()
+            .find(|e| e.content.contains("Revert the tag"))
+            .unwrap();
+        assert!(rollback.content.starts_with("[source: "));
+        assert!(rollback.content.contains("deploy.md (lines 4-5)]"));
+    }
+
+    #[tokio::test]
+    async fn reingest_only_touches_changed_and_removed_files() {
+        let ws = TempDir::new().unwrap();
+        let docs = TempDir::new().unwrap();
+        let a = docs.path().join("a.txt");
+        let b = docs.path().join("b.txt");
+        fs::write(&a, "alpha runbook").unwrap();
+        fs::write(&b, "beta runbook").unwrap();
+
+        let mem = SqliteMemory::new(ws.path()).unwrap();
+        assert_eq!(ingest(&mem, ws.path(), docs.path()).await.ingested_files, 2);
+
+        let report = ingest(&mem, ws.path(), docs.path()).await;
+        assert_eq!(report.ingested_files, 0);
+        assert_eq!(report.unchanged_files, 2);
+
+        fs::write(&a, "alpha runbook, revised").unwrap();
+        fs::remove_file(&b).unwrap();
+        let report = ingest(&mem, ws.path(), docs.path()).await;
+        assert_eq!(report.ingested_files, 1);
+        assert_eq!(report.removed_files, 1);
+
+        let entries = mem.list(None, None).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].content.ends_with("alpha runbook, revised"));
+    }
+
+    #[test]
+    fn html_to_text_keeps_lines_and_drops_scripts() {
+        let html = "<html>\n<head><script>\nvar x = 1;\n</script></head>\n<body>\n<p>Tom &amp; Jerry</p>\n</body>";
+        let text = html_to_text(html);
+        assert_eq!(text.split('\n').count(), html.split('\n').count());
+        assert!(text.contains("Tom & Jerry"));
+        assert!(!text.contains("var x"));
+        assert_eq!(text.lines().nth(5), Some("Tom & Jerry"));
+    }
+
+    #[test]
+    fn doc_kind_from_extension() {
+        assert_eq!(
+            DocKind::from_path(Path::new("a/README.md")),
+            Some(DocKind::Markdown)
+        );
+        assert_eq!(
+            DocKind::from_path(Path::new("main.RS")),
+            Some(DocKind::Text)
+        );
+        assert_eq!(DocKind::from_path(Path::new("x.htm")), Some(DocKind::Html));
+        assert_eq!(
+            DocKind::from_path(Path::new("spec.pdf")),
+            Some(DocKind::Pdf)
+        );
+        assert_eq!(DocKind::from_path(Path::new("image.png")), None);
+        assert_eq!(DocKind::from_path(Path::new("Makefile")), None);
+    }
+}
@@ -1,8 +1,10 @@
+pub mod ann;
 pub mod backend;
 pub mod chunker;
 pub mod cli;
 pub mod embeddings;
 pub mod hygiene;
+pub mod ingest;
 pub mod lucid;
 pub mod markdown;
 pub mod none;
@@ -238,34 +240,40 @@ pub fn create_memory_with_storage_and_routes(
         }
     }
 
+    fn build_embedder(
+        resolved_embedding: &ResolvedEmbeddingConfig,
+    ) -> Arc<dyn embeddings::EmbeddingProvider> {
+        Arc::from(embeddings::create_embedding_provider(
+            &resolved_embedding.provider,
+            resolved_embedding.api_key.as_deref(),
+            &resolved_embedding.model,
+            resolved_embedding.dimensions,
+        ))
+    }
+
     fn build_sqlite_memory(
         config: &MemoryConfig,
         workspace_dir: &Path,
         resolved_embedding: &ResolvedEmbeddingConfig,
     ) -> anyhow::Result<SqliteMemory> {
-        let embedder: Arc<dyn embeddings::EmbeddingProvider> =
-            Arc::from(embeddings::create_embedding_provider(
-                &resolved_embedding.provider,
-                resolved_embedding.api_key.as_deref(),
-                &resolved_embedding.model,
-                resolved_embedding.dimensions,
-            ));
-
         #[allow(clippy::cast_possible_truncation)]
         let mem = SqliteMemory::with_embedder(
             workspace_dir,
-            embedder,
+            build_embedder(resolved_embedding),
             config.vector_weight as f32,
             config.keyword_weight as f32,
             config.embedding_cache_size,
             config.sqlite_open_timeout_secs,
-        )?;
+        )?
+        .with_ann_min_rows(config.sqlite_ann_min_rows);
         Ok(mem)
     }
 
     #[cfg(feature = "memory-postgres")]
     fn build_postgres_memory(
+        config: &MemoryConfig,
         storage_provider: Option<&StorageProviderConfig>,
+        resolved_embedding: &ResolvedEmbeddingConfig,
     ) -> anyhow::Result<Box<dyn Memory>> {
         let storage_provider = storage_provider
             .context("memory backend 'postgres' requires [storage.provider.config] settings")?;
@@ -278,18 +286,24 @@ pub fn create_memory_with_storage_and_routes(
                 "memory backend 'postgres' requires [storage.provider.config].db_url (or dbURL)",
             )?;
 
-        let memory = PostgresMemory::new(
+        #[allow(clippy::cast_possible_truncation)]
+        let memory = PostgresMemory::with_embedder(
             db_url,
             &storage_provider.schema,
             &storage_provider.table,
             storage_provider.connect_timeout_secs,
+            build_embedder(resolved_embedding),
+            config.vector_weight as f32,
+            config.keyword_weight as f32,
         )?;
         Ok(Box::new(memory))
     }
 
     #[cfg(not(feature = "memory-postgres"))]
     fn build_postgres_memory(
+        _config: &MemoryConfig,
         _storage_provider: Option<&StorageProviderConfig>,
+        _resolved_embedding: &ResolvedEmbeddingConfig,
     ) -> anyhow::Result<Box<dyn Memory>> {
         anyhow::bail!(
             "memory backend 'postgres' requested but this build was compiled without `memory-postgres`; rebuild with `--features memory-postgres`"
@@ -300,7 +314,7 @@ pub fn create_memory_with_storage_and_routes(
         &backend_name,
         workspace_dir,
         || build_sqlite_memory(config, workspace_dir, &resolved_embedding),
-        || build_postgres_memory(storage_provider),
+        || build_postgres_memory(config, storage_provider, &resolved_embedding),
         "",
     )
 }
@@ -359,6 +373,21 @@ pub fn create_response_cache(config: &MemoryConfig, workspace_dir: &Path) -> Opt
     }
 }
 
+/// Factory: create the embedding provider resolved from `[memory]` and embedding routes.
+pub fn create_embedder(
+    config: &MemoryConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    api_key: Option<&str>,
+) -> Arc<dyn embeddings::EmbeddingProvider> {
+    let resolved = resolve_embedding_config(config, embedding_routes, api_key);
+    Arc::from(embeddings::create_embedding_provider(
+        &resolved.provider,
+        resolved.api_key.as_deref(),
+        &resolved.model,
+        resolved.dimensions,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,12 @@
+use super::embeddings::{EmbeddingProvider, NoopEmbedding};
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::vector;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use postgres::{Client, NoTls, Row};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use uuid::Uuid;
@@ -11,13 +14,36 @@ use uuid::Uuid;
 /// Maximum allowed connect timeout (seconds) to avoid unreasonable waits.
 const POSTGRES_CONNECT_TIMEOUT_CAP_SECS: u64 = 300;
 
+/// Search features detected while migrating the schema on startup.
+#[derive(Debug, Clone, Copy, Default)]
+struct SearchSupport {
+    /// `content_tsv` generated column is present.
+    full_text: bool,
+    /// pgvector `embedding` column is present with the embedder's dimensions.
+    vector: bool,
+    /// Rows without an embedding exist (new column or dimension change).
+    backfill: bool,
+}
+
 /// PostgreSQL-backed persistent memory.
 ///
-/// This backend focuses on reliable CRUD and keyword recall using SQL, without
-/// requiring extension setup (for example pgvector).
+/// Search mirrors `SqliteMemory`:
+/// - **Keyword**: a generated `tsvector` column with a GIN index, ranked by `ts_rank_cd`
+/// - **Vector**: optional pgvector column (HNSW cosine index where supported)
+/// - **Hybrid**: `vector::hybrid_merge` with the configured weights
+///
+/// Both columns are added on startup. When pgvector is not installed (or no
+/// embedding provider is configured) recall uses keyword search only, and
+/// `ILIKE` substring matching remains the last fallback.
 pub struct PostgresMemory {
     client: Arc<Mutex<Client>>,
     qualified_table: String,
+    embedder: Arc<dyn EmbeddingProvider>,
+    vector_weight: f32,
+    keyword_weight: f32,
+    search: SearchSupport,
+    /// Set while rows still need embeddings; cleared by a successful reindex.
+    reindex_pending: AtomicBool,
 }
 
 impl PostgresMemory {
@@ -26,6 +52,26 @@ impl PostgresMemory {
         schema: &str,
         table: &str,
         connect_timeout_secs: Option<u64>,
+    ) -> Result<Self> {
+        Self::with_embedder(
+            db_url,
+            schema,
+            table,
+            connect_timeout_secs,
+            Arc::new(NoopEmbedding),
+            0.7,
+            0.3,
+        )
+    }
+
+    pub fn with_embedder(
+        db_url: &str,
+        schema: &str,
+        table: &str,
+        connect_timeout_secs: Option<u64>,
+        embedder: Arc<dyn EmbeddingProvider>,
+        vector_weight: f32,
+        keyword_weight: f32,
     ) -> Result<Self> {
         validate_identifier(schema, "storage schema")?;
         validate_identifier(table, "storage table")?;
@@ -34,16 +80,22 @@ impl PostgresMemory {
         let table_ident = quote_identifier(table);
         let qualified_table = format!("{schema_ident}.{table_ident}");
 
-        let client = Self::initialize_client(
+        let (client, search) = Self::initialize_client(
             db_url.to_string(),
             connect_timeout_secs,
             schema_ident.clone(),
             qualified_table.clone(),
+            embedder.dimensions(),
         )?;
 
         Ok(Self {
             client: Arc::new(Mutex::new(client)),
             qualified_table,
+            embedder,
+            vector_weight,
+            keyword_weight,
+            search,
+            reindex_pending: AtomicBool::new(search.backfill),
         })
     }
 
@@ -52,10 +104,11 @@ impl PostgresMemory {
         connect_timeout_secs: Option<u64>,
         schema_ident: String,
         qualified_table: String,
-    ) -> Result<Client> {
+        dimensions: usize,
+    ) -> Result<(Client, SearchSupport)> {
         let init_handle = std::thread::Builder::new()
             .name("postgres-memory-init".to_string())
-            .spawn(move || -> Result<Client> {
+            .spawn(move || -> Result<(Client, SearchSupport)> {
                 let mut config: postgres::Config = db_url
                     .parse()
                     .context("invalid PostgreSQL connection URL")?;
@@ -70,7 +123,15 @@ impl PostgresMemory {
                     .context("failed to connect to PostgreSQL memory backend")?;
 
                 Self::init_schema(&mut client, &schema_ident, &qualified_table)?;
-                Ok(client)
+                let full_text = Self::migrate_full_text(&mut client, &qualified_table);
+                let vector = Self::migrate_vector(&mut client, &qualified_table, dimensions);
+                let backfill = vector && Self::has_unembedded_rows(&mut client, &qualified_table);
+                let search = SearchSupport {
+                    full_text,
+                    vector,
+                    backfill,
+                };
+                Ok((client, search))
             })
             .context("failed to spawn PostgreSQL initializer thread")?;
 
@@ -105,6 +166,234 @@ impl PostgresMemory {
         Ok(())
     }
 
+    /// Add the generated `tsvector` column. Returns `false` when the server
+    /// cannot create it (generated columns need PostgreSQL 12+).
+    fn migrate_full_text(client: &mut Client, qualified_table: &str) -> bool {
+        let result = client.batch_execute(&format!(
+            "
+            ALTER TABLE {qualified_table} ADD COLUMN IF NOT EXISTS content_tsv TSVECTOR
+                GENERATED ALWAYS AS (to_tsvector('simple', key || ' ' || content)) STORED;
+
+            CREATE INDEX IF NOT EXISTS idx_memories_content_tsv ON {qualified_table} USING GIN (content_tsv);
+            "
+        ));
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("PostgreSQL memory full-text search unavailable: {e}");
+                false
+            }
+        }
+    }
+
+    /// Add the pgvector `embedding` column sized for the current embedder.
+    ///
+    /// Returns `false` without touching the table when embeddings are disabled
+    /// or the `vector` extension is missing. A column of a different size is
+    /// dropped and re-added; the first store or recall backfills it.
+    fn migrate_vector(client: &mut Client, qualified_table: &str, dimensions: usize) -> bool {
+        if dimensions == 0 {
+            return false;
+        }
+
+        if let Err(e) = client.batch_execute("CREATE EXTENSION IF NOT EXISTS vector") {
+            let installed = client
+                .query_opt("SELECT 1 FROM pg_extension WHERE extname = 'vector'", &[])
+                .is_ok_and(|row| row.is_some());
+            if !installed {
+                tracing::warn!(
+                    "pgvector extension unavailable ({e}); PostgreSQL memory recall uses keyword search only"
+                );
+                return false;
+            }
+        }
+
+        let expected_type = format!("vector({dimensions})");
+        let existing_type: Option<String> = match client.query_opt(
+            "
+            SELECT format_type(atttypid, atttypmod)
+            FROM pg_attribute
+            WHERE attrelid = $1::TEXT::REGCLASS AND attname = 'embedding' AND NOT attisdropped
+            ",
+            &[&qualified_table],
+        ) {
+            Ok(row) => row.map(|row| row.get(0)),
+            Err(e) => {
+                tracing::warn!("failed to inspect PostgreSQL memory embedding column: {e}");
+                return false;
+            }
+        };
+
+        let mut statements = Vec::new();
+        if existing_type.as_deref() != Some(expected_type.as_str()) {
+            if let Some(existing) = &existing_type {
+                tracing::info!(
+                    "PostgreSQL memory embedding column changes from {existing} to {expected_type}; existing memories are re-embedded on next use"
+                );
+                statements.push(format!(
+                    "ALTER TABLE {qualified_table} DROP COLUMN embedding"
+                ));
+            }
+            statements.push(format!(
+                "ALTER TABLE {qualified_table} ADD COLUMN embedding {expected_type}"
+            ));
+        }
+        if let Err(e) = client.batch_execute(&statements.join(";")) {
+            tracing::warn!("failed to add PostgreSQL memory embedding column: {e}");
+            return false;
+        }
+
+        // HNSW needs pgvector 0.5+ and at most 2000 dimensions; without it
+        // vector search is an exact scan.
+        if let Err(e) = client.batch_execute(&format!(
+            "CREATE INDEX IF NOT EXISTS idx_memories_embedding ON {qualified_table} USING hnsw (embedding vector_cosine_ops)"
+        )) {
+            tracing::warn!("PostgreSQL memory embedding index not created: {e}");
+        }
+        true
+    }
+
+    /// Whether any row is missing an embedding. Errors count as "no" so a
+    /// broken query never blocks startup.
+    fn has_unembedded_rows(client: &mut Client, qualified_table: &str) -> bool {
+        client
+            .query_one(
+                &format!("SELECT EXISTS (SELECT 1 FROM {qualified_table} WHERE embedding IS NULL)"),
+                &[],
+            )
+            .is_ok_and(|row| row.get(0))
+    }
+
+    /// Run a pending reindex after the embedding column was added or resized.
+    ///
+    /// Failures are logged and retried on the next call so keyword search
+    /// keeps working while the embedding provider is unavailable.
+    async fn reindex_if_pending(&self) {
+        if !self.reindex_pending.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        match self.reindex().await {
+            Ok(count) => tracing::info!(count, "Re-embedded PostgreSQL memories"),
+            Err(e) => {
+                tracing::warn!("memory reindex after embedding change failed: {e}");
+                self.reindex_pending.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Compute embeddings for rows stored before the vector column existed.
+    ///
+    /// Returns the number of rows embedded.
+    pub async fn reindex(&self) -> Result<usize> {
+        if !self.search.vector {
+            return Ok(0);
+        }
+
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let pending: Vec<(String, String)> = tokio::task::spawn_blocking(move || -> Result<_> {
+            let stmt = format!("SELECT id, content FROM {qualified_table} WHERE embedding IS NULL");
+            let rows = client.lock().query(&stmt, &[])?;
+            Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+        })
+        .await??;
+
+        let mut embedded = 0;
+        for (id, content) in pending {
+            let literal = vector_literal(&self.embedder.embed_one(&content).await?);
+            let client = self.client.clone();
+            let stmt = format!(
+                "UPDATE {} SET embedding = $1::TEXT::vector WHERE id = $2",
+                self.qualified_table
+            );
+            tokio::task::spawn_blocking(move || client.lock().execute(&stmt, &[&literal, &id]))
+                .await??;
+            embedded += 1;
+        }
+        Ok(embedded)
+    }
+
+    fn full_text_search(
+        client: &mut Client,
+        qualified_table: &str,
+        query: &str,
+        session_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<(String, f32)>> {
+        let terms = ts_query_terms(query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let stmt = format!(
+            "
+            SELECT id, ts_rank_cd(content_tsv, to_tsquery('simple', $1))::REAL AS score
+            FROM {qualified_table}
+            WHERE content_tsv @@ to_tsquery('simple', $1)
+              AND ($2::TEXT IS NULL OR session_id = $2)
+            ORDER BY score DESC
+            LIMIT $3
+            "
+        );
+        let rows = client.query(&stmt, &[&terms, &session_id, &limit])?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    fn vector_search(
+        client: &mut Client,
+        qualified_table: &str,
+        query_embedding: &[f32],
+        session_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<(String, f32)>> {
+        let stmt = format!(
+            "
+            SELECT id, (1 - (embedding <=> $1::TEXT::vector))::REAL AS score
+            FROM {qualified_table}
+            WHERE embedding IS NOT NULL
+              AND ($2::TEXT IS NULL OR session_id = $2)
+            ORDER BY embedding <=> $1::TEXT::vector
+            LIMIT $3
+            "
+        );
+        let literal = vector_literal(query_embedding);
+        let rows = client.query(&stmt, &[&literal, &session_id, &limit])?;
+        Ok(rows
+            .iter()
+            .map(|row| (row.get::<_, String>(0), row.get::<_, f32>(1)))
+            .filter(|(_, score)| *score > 0.0)
+            .collect())
+    }
+
+    /// Substring ranking used when full-text and vector search find nothing.
+    fn substring_search(
+        client: &mut Client,
+        qualified_table: &str,
+        query: &str,
+        session_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<MemoryEntry>> {
+        let stmt = format!(
+            "
+            SELECT id, key, content, category, created_at, session_id,
+                   (
+                     CASE WHEN key ILIKE '%' || $1 || '%' THEN 2.0 ELSE 0.0 END +
+                     CASE WHEN content ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.0 END
+                   )::DOUBLE PRECISION AS score
+            FROM {qualified_table}
+            WHERE ($2::TEXT IS NULL OR session_id = $2)
+              AND ($1 = '' OR key ILIKE '%' || $1 || '%' OR content ILIKE '%' || $1 || '%')
+            ORDER BY score DESC, updated_at DESC
+            LIMIT $3
+            "
+        );
+
+        let rows = client.query(&stmt, &[&query, &session_id, &limit])?;
+        rows.iter()
+            .map(Self::row_to_entry)
+            .collect::<Result<Vec<MemoryEntry>>>()
+    }
+
     fn category_to_str(category: &MemoryCategory) -> String {
         match category {
             MemoryCategory::Core => "core".to_string(),
@@ -165,6 +454,22 @@ fn quote_identifier(value: &str) -> String {
     format!("\"{value}\"")
 }
 
+/// OR-combined `to_tsquery` input; punctuation is dropped so user text cannot
+/// inject tsquery operators.
+fn ts_query_terms(query: &str) -> String {
+    query
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|term| !term.is_empty())
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// pgvector text representation, bound as `$n::TEXT::vector`.
+fn vector_literal(values: &[f32]) -> String {
+    let parts: Vec<String> = values.iter().map(f32::to_string).collect();
+    format!("[{}]", parts.join(","))
+}
+
 #[async_trait]
 impl Memory for PostgresMemory {
     fn name(&self) -> &str {
@@ -178,8 +483,18 @@ impl Memory for PostgresMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> Result<()> {
+        self.reindex_if_pending().await;
+
+        // Compute embedding (async, before blocking work)
+        let embedding = if self.search.vector {
+            Some(vector_literal(&self.embedder.embed_one(content).await?))
+        } else {
+            None
+        };
+
         let client = self.client.clone();
         let qualified_table = self.qualified_table.clone();
+        let with_vector = self.search.vector;
         let key = key.to_string();
         let content = content.to_string();
         let category = Self::category_to_str(&category);
@@ -188,6 +503,30 @@ impl Memory for PostgresMemory {
         tokio::task::spawn_blocking(move || -> Result<()> {
             let now = Utc::now();
             let mut client = client.lock();
+            let id = Uuid::new_v4().to_string();
+
+            if with_vector {
+                let stmt = format!(
+                    "
+                    INSERT INTO {qualified_table}
+                        (id, key, content, category, created_at, updated_at, session_id, embedding)
+                    VALUES
+                        ($1, $2, $3, $4, $5, $6, $7, $8::TEXT::vector)
+                    ON CONFLICT (key) DO UPDATE SET
+                        content = EXCLUDED.content,
+                        category = EXCLUDED.category,
+                        updated_at = EXCLUDED.updated_at,
+                        session_id = EXCLUDED.session_id,
+                        embedding = EXCLUDED.embedding
+                    "
+                );
+                client.execute(
+                    &stmt,
+                    &[&id, &key, &content, &category, &now, &now, &sid, &embedding],
+                )?;
+                return Ok(());
+            }
+
             let stmt = format!(
                 "
                 INSERT INTO {qualified_table}
@@ -201,8 +540,6 @@ impl Memory for PostgresMemory {
                     session_id = EXCLUDED.session_id
                 "
             );
-
-            let id = Uuid::new_v4().to_string();
             client.execute(&stmt, &[&id, &key, &content, &category, &now, &now, &sid])?;
             Ok(())
         })
@@ -215,35 +552,109 @@ impl Memory for PostgresMemory {
         limit: usize,
         session_id: Option<&str>,
     ) -> Result<Vec<MemoryEntry>> {
+        self.reindex_if_pending().await;
+        let query = query.trim().to_string();
+
+        // Compute query embedding (async, before blocking work)
+        let query_embedding = if self.search.vector && !query.is_empty() {
+            Some(self.embedder.embed_one(&query).await?)
+        } else {
+            None
+        };
+
         let client = self.client.clone();
         let qualified_table = self.qualified_table.clone();
-        let query = query.trim().to_string();
+        let full_text = self.search.full_text;
+        let vector_weight = self.vector_weight;
+        let keyword_weight = self.keyword_weight;
         let sid = session_id.map(str::to_string);
 
         tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
             let mut client = client.lock();
+            let session_ref = sid.as_deref();
+
+            #[allow(clippy::cast_possible_wrap)]
+            let limit_i64 = limit as i64;
+
+            let keyword_results = if full_text {
+                Self::full_text_search(
+                    &mut client,
+                    &qualified_table,
+                    &query,
+                    session_ref,
+                    limit_i64 * 2,
+                )
+                .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
+            let vector_results = if let Some(ref qe) = query_embedding {
+                Self::vector_search(
+                    &mut client,
+                    &qualified_table,
+                    qe,
+                    session_ref,
+                    limit_i64 * 2,
+                )
+                .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
+            let mut merged = if vector_results.is_empty() {
+                keyword_results
+                    .iter()
+                    .map(|(id, score)| vector::ScoredResult {
+                        id: id.clone(),
+                        vector_score: None,
+                        keyword_score: Some(*score),
+                        final_score: *score,
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                vector::hybrid_merge(
+                    &vector_results,
+                    &keyword_results,
+                    vector_weight,
+                    keyword_weight,
+                    limit,
+                )
+            };
+            merged.truncate(limit);
+
+            if merged.is_empty() {
+                return Self::substring_search(
+                    &mut client,
+                    &qualified_table,
+                    &query,
+                    session_ref,
+                    limit_i64,
+                );
+            }
+
+            let ids: Vec<String> = merged.iter().map(|scored| scored.id.clone()).collect();
             let stmt = format!(
                 "
-                SELECT id, key, content, category, created_at, session_id,
-                       (
-                         CASE WHEN key ILIKE '%' || $1 || '%' THEN 2.0 ELSE 0.0 END +
-                         CASE WHEN content ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.0 END
-                       ) AS score
+                SELECT id, key, content, category, created_at, session_id
                 FROM {qualified_table}
-                WHERE ($2::TEXT IS NULL OR session_id = $2)
-                  AND ($1 = '' OR key ILIKE '%' || $1 || '%' OR content ILIKE '%' || $1 || '%')
-                ORDER BY score DESC, updated_at DESC
-                LIMIT $3
+                WHERE id = ANY($1)
                 "
             );
-
-            #[allow(clippy::cast_possible_wrap)]
-            let limit_i64 = limit as i64;
-
-            let rows = client.query(&stmt, &[&query, &sid, &limit_i64])?;
-            rows.iter()
-                .map(Self::row_to_entry)
-                .collect::<Result<Vec<MemoryEntry>>>()
+            let mut entries: std::collections::HashMap<String, MemoryEntry> = client
+                .query(&stmt, &[&ids])?
+                .iter()
+                .map(|row| Self::row_to_entry(row).map(|entry| (entry.id.clone(), entry)))
+                .collect::<Result<_>>()?;
+
+            Ok(merged
+                .iter()
+                .filter_map(|scored| {
+                    let mut entry = entries.remove(&scored.id)?;
+                    entry.score = Some(f64::from(scored.final_score));
+                    Some(entry)
+                })
+                .collect())
         })
         .await?
     }
@@ -373,6 +784,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ts_query_terms_or_words_and_strip_operators() {
+        assert_eq!(ts_query_terms("rust  async"), "rust | async");
+        assert_eq!(ts_query_terms("it's a & (b|c):*"), "it | s | a | b | c");
+        assert_eq!(ts_query_terms("user_id café"), "user_id | café");
+        assert_eq!(ts_query_terms(" !? "), "");
+    }
+
+    #[test]
+    fn vector_literal_uses_pgvector_text_format() {
+        assert_eq!(vector_literal(&[0.5, -1.0, 0.25]), "[0.5,-1,0.25]");
+        assert_eq!(vector_literal(&[]), "[]");
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn new_does_not_panic_inside_tokio_runtime() {
         let outcome = std::panic::catch_unwind(|| {
@@ -4,7 +4,12 @@
 //! `(model, system_prompt_hash, user_prompt)`. Entries expire after a
 //! configurable TTL (default: 1 hour). The cache is optional and disabled by
 //! default — users opt in via `[memory] response_cache_enabled = true`.
+//!
+//! Semantic mode additionally stores an embedding of the final user prompt
+//! next to a hash of the preceding context, so a near-identical prompt in the
+//! same context can reuse a response.
 
+use super::vector;
 use anyhow::Result;
 use chrono::{Duration, Local};
 use parking_lot::Mutex;
@@ -53,6 +58,22 @@ impl ResponseCache {
             CREATE INDEX IF NOT EXISTS idx_rc_created ON response_cache(created_at);",
         )?;
 
+        // Migration: semantic lookup columns.
+        let schema_sql: String = conn.query_row(
+            "SELECT sql FROM sqlite_master WHERE type='table' AND name='response_cache'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !schema_sql.contains("context_hash") {
+            conn.execute_batch(
+                "ALTER TABLE response_cache ADD COLUMN context_hash TEXT;
+                 ALTER TABLE response_cache ADD COLUMN embedding BLOB;",
+            )?;
+        }
+        conn.execute_batch(
+            "CREATE INDEX IF NOT EXISTS idx_rc_context ON response_cache(context_hash);",
+        )?;
+
         Ok(Self {
             conn: Mutex::new(conn),
             db_path,
@@ -103,17 +124,114 @@ impl ResponseCache {
         Ok(result)
     }
 
+    /// Find the most similar unexpired response stored under `context_hash`.
+    ///
+    /// Only entries written by [`put_semantic`](Self::put_semantic) take part.
+    /// Returns `None` unless the best cosine similarity reaches `threshold`.
+    pub fn get_similar(
+        &self,
+        context_hash: &str,
+        embedding: &[f32],
+        threshold: f32,
+    ) -> Result<Option<String>> {
+        let conn = self.conn.lock();
+
+        let now = Local::now();
+        let cutoff = (now - Duration::minutes(self.ttl_minutes)).to_rfc3339();
+
+        let mut stmt = conn.prepare(
+            "SELECT prompt_hash, response, embedding FROM response_cache
+             WHERE context_hash = ?1 AND created_at > ?2 AND embedding IS NOT NULL",
+        )?;
+        let rows = stmt.query_map(params![context_hash, cutoff], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Vec<u8>>(2)?,
+            ))
+        })?;
+
+        let mut best: Option<(f32, String, String)> = None;
+        for row in rows {
+            let (key, response, bytes) = row?;
+            let similarity = vector::cosine_similarity(embedding, &vector::bytes_to_vec(&bytes));
+            if similarity >= threshold && best.as_ref().is_none_or(|(s, _, _)| similarity > *s) {
+                best = Some((similarity, key, response));
+            }
+        }
+
+        let Some((_, key, response)) = best else {
+            return Ok(None);
+        };
+        conn.execute(
+            "UPDATE response_cache
+             SET accessed_at = ?1, hit_count = hit_count + 1
+             WHERE prompt_hash = ?2",
+            params![now.to_rfc3339(), key],
+        )?;
+        Ok(Some(response))
+    }
+
     /// Store a response in the cache.
     pub fn put(&self, key: &str, model: &str, response: &str, token_count: u32) -> Result<()> {
+        self.insert(key, model, response, token_count, None)
+    }
+
+    /// Store a response that [`get_similar`](Self::get_similar) can also match.
+    ///
+    /// `context_hash` identifies everything except the final user prompt and
+    /// `embedding` is that prompt's embedding.
+    pub fn put_semantic(
+        &self,
+        key: &str,
+        model: &str,
+        response: &str,
+        token_count: u32,
+        context_hash: &str,
+        embedding: &[f32],
+    ) -> Result<()> {
+        self.insert(
+            key,
+            model,
+            response,
+            token_count,
+            Some((context_hash, embedding)),
+        )
+    }
+
+    fn insert(
+        &self,
+        key: &str,
+        model: &str,
+        response: &str,
+        token_count: u32,
+        semantic: Option<(&str, &[f32])>,
+    ) -> Result<()> {
         let conn = self.conn.lock();
 
         let now = Local::now().to_rfc3339();
+        let (context_hash, embedding) = match semantic {
+            Some((context_hash, embedding)) => {
+                (Some(context_hash), Some(vector::vec_to_bytes(embedding)))
+            }
+            None => (None, None),
+        };
 
         conn.execute(
             "INSERT OR REPLACE INTO response_cache
-             (prompt_hash, model, response, token_count, created_at, accessed_at, hit_count)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0)",
-            params![key, model, response, token_count, now, now],
+             (prompt_hash, model, response, token_count, created_at, accessed_at, hit_count,
+              context_hash, embedding)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?8)",
+            params![
+                key,
+                model,
+                response,
+                token_count,
+                now,
+                now,
+                context_hash,
+                embedding
+            ],
         )?;
 
         // Evict expired entries
@@ -161,7 +279,7 @@ impl ResponseCache {
         Ok((count as usize, hits as u64, tokens_saved as u64))
     }
 
-    /// Wipe the entire cache (useful for `zeroclaw cache clear`).
+    /// Wipe the entire cache (`zeroclaw memory cache clear`).
     pub fn clear(&self) -> Result<usize> {
         let conn = self.conn.lock();
 
@@ -349,6 +467,69 @@ mod tests {
         assert_eq!(result.as_deref(), Some("はい、Rustは素晴らしい"));
     }
 
+    #[test]
+    fn semantic_lookup_matches_similar_prompts_in_same_context() {
+        let (_tmp, cache) = temp_cache(60);
+        let key = ResponseCache::cache_key("gpt-4", None, "What is Rust?");
+        cache
+            .put_semantic(&key, "gpt-4", "A language.", 10, "ctx-a", &[1.0, 0.0, 0.0])
+            .unwrap();
+
+        let near = [0.99, 0.1, 0.0];
+        assert_eq!(
+            cache.get_similar("ctx-a", &near, 0.95).unwrap().as_deref(),
+            Some("A language.")
+        );
+        assert!(cache.get_similar("ctx-b", &near, 0.95).unwrap().is_none());
+        assert!(cache
+            .get_similar("ctx-a", &[0.0, 1.0, 0.0], 0.95)
+            .unwrap()
+            .is_none());
+
+        let (_, hits, _) = cache.stats().unwrap();
+        assert_eq!(hits, 1);
+    }
+
+    #[test]
+    fn semantic_lookup_ignores_exact_only_entries() {
+        let (_tmp, cache) = temp_cache(60);
+        let key = ResponseCache::cache_key("gpt-4", None, "hello");
+        cache.put(&key, "gpt-4", "Hi!", 5).unwrap();
+        assert!(cache.get_similar("", &[1.0], 0.0).unwrap().is_none());
+    }
+
+    #[test]
+    fn opening_legacy_database_adds_semantic_columns() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("memory")).unwrap();
+        let conn = Connection::open(tmp.path().join("memory/response_cache.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE response_cache (
+                prompt_hash TEXT PRIMARY KEY,
+                model       TEXT NOT NULL,
+                response    TEXT NOT NULL,
+                token_count INTEGER NOT NULL DEFAULT 0,
+                created_at  TEXT NOT NULL,
+                accessed_at TEXT NOT NULL,
+                hit_count   INTEGER NOT NULL DEFAULT 0
+            );",
+        )
+        .unwrap();
+        drop(conn);
+
+        let cache = ResponseCache::new(tmp.path(), 60, 100).unwrap();
+        cache
+            .put_semantic("k", "gpt-4", "r", 1, "ctx", &[1.0, 0.0])
+            .unwrap();
+        assert_eq!(
+            cache
+                .get_similar("ctx", &[1.0, 0.0], 0.9)
+                .unwrap()
+                .as_deref(),
+            Some("r")
+        );
+    }
+
     // ── §4.4 Cache eviction under pressure tests ─────────────
 
     #[test]
@@ -1,3 +1,4 @@
+use super::ann::IvfIndex;
 use super::embeddings::EmbeddingProvider;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use super::vector;
@@ -8,6 +9,7 @@ use parking_lot::Mutex;
 use rusqlite::{params, Connection};
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
@@ -17,13 +19,35 @@ use uuid::Uuid;
 /// Maximum allowed open timeout (seconds) to avoid unreasonable waits.
 const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 
+/// Default embedded-row count before the IVF index is trained.
+const DEFAULT_ANN_MIN_ROWS: usize = 2_048;
+
+/// Retrain the IVF index once the embedded rows grow this many times past
+/// the count it was trained on.
+const ANN_RETRAIN_GROWTH: i64 = 4;
+
+/// Memories embedded per provider call during `reindex`.
+const REINDEX_BATCH_SIZE: usize = 32;
+
+/// In-process copy of the on-disk IVF centroids.
+///
+/// Tagged with the `vector_index_meta` generation it was loaded at, so a
+/// rebuild by another connection to the same `brain.db` is picked up.
+struct AnnCache {
+    generation: i64,
+    index: Option<Arc<IvfIndex>>,
+}
+
 /// SQLite-backed persistent memory — the brain
 ///
 /// Full-stack search engine:
 /// - **Vector DB**: embeddings stored as BLOB, cosine similarity search
+/// - **ANN Index**: IVF centroids in `brain.db` narrow large vector scans
 /// - **Keyword Search**: FTS5 virtual table with BM25 scoring
 /// - **Hybrid Merge**: weighted fusion of vector + keyword results
 /// - **Embedding Cache**: LRU-evicted cache to avoid redundant API calls
+/// - **Model Guard**: stored vectors are dropped and re-embedded when the
+///   embedding model or its detected dimensions change
 /// - **Safe Reindex**: temp DB → seed → sync → atomic swap → rollback
 pub struct SqliteMemory {
     conn: Arc<Mutex<Connection>>,
@@ -32,6 +56,11 @@ pub struct SqliteMemory {
     vector_weight: f32,
     keyword_weight: f32,
     cache_max: usize,
+    ann_min_rows: usize,
+    ann: Arc<Mutex<Option<AnnCache>>>,
+    /// Set while a background IVF retrain runs, so stores do not start another.
+    ann_rebuilding: Arc<AtomicBool>,
+    reindex_pending: Arc<AtomicBool>,
 }
 
 impl SqliteMemory {
@@ -82,6 +111,8 @@ impl SqliteMemory {
         )?;
 
         Self::init_schema(&conn)?;
+        let reindex_pending =
+            embedder.dimensions() > 0 && Self::check_embedding_model(&conn, embedder.as_ref())?;
 
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
@@ -90,9 +121,20 @@ impl SqliteMemory {
             vector_weight,
             keyword_weight,
             cache_max,
+            ann_min_rows: DEFAULT_ANN_MIN_ROWS,
+            ann: Arc::new(Mutex::new(None)),
+            ann_rebuilding: Arc::new(AtomicBool::new(false)),
+            reindex_pending: Arc::new(AtomicBool::new(reindex_pending)),
         })
     }
 
+    /// Embedded-row count before the IVF index is trained (0 disables it).
+    #[must_use]
+    pub fn with_ann_min_rows(mut self, ann_min_rows: usize) -> Self {
+        self.ann_min_rows = ann_min_rows;
+        self
+    }
+
     /// Open SQLite connection, optionally with a timeout (for locked/slow storage).
     fn open_connection(
         db_path: &Path,
@@ -192,9 +234,133 @@ impl SqliteMemory {
             );",
         )?;
 
+        // Migration: IVF approximate nearest-neighbour index
+        let has_ivf_list: bool = conn
+            .prepare("SELECT sql FROM sqlite_master WHERE type='table' AND name='memories'")?
+            .query_row([], |row| row.get::<_, String>(0))?
+            .contains("ivf_list");
+        if !has_ivf_list {
+            conn.execute_batch(
+                "ALTER TABLE memories ADD COLUMN ivf_list INTEGER;
+                 CREATE INDEX IF NOT EXISTS idx_memories_ivf_list ON memories(ivf_list);",
+            )?;
+        }
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS vector_centroids (
+                list_id   INTEGER PRIMARY KEY,
+                centroid  BLOB NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS vector_index_meta (
+                key    TEXT PRIMARY KEY,
+                value  INTEGER NOT NULL
+            );",
+        )?;
+
+        // Migration: embedding model + detected dimensions of stored vectors
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS embedding_meta (
+                key    TEXT PRIMARY KEY,
+                value  TEXT NOT NULL
+            );",
+        )?;
+
+        Ok(())
+    }
+
+    fn embedding_meta(conn: &Connection, key: &str) -> Option<String> {
+        conn.query_row(
+            "SELECT value FROM embedding_meta WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .ok()
+    }
+
+    fn set_embedding_meta(conn: &Connection, key: &str, value: &str) -> anyhow::Result<()> {
+        conn.execute(
+            "INSERT INTO embedding_meta (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
         Ok(())
     }
 
+    /// Drop every stored vector so `reindex` re-embeds with the current model.
+    fn invalidate_embeddings(conn: &Connection) -> anyhow::Result<()> {
+        conn.execute_batch(
+            "UPDATE memories SET embedding = NULL WHERE embedding IS NOT NULL;
+             DELETE FROM embedding_cache;
+             DELETE FROM embedding_meta WHERE key = 'dimensions';",
+        )?;
+        Self::rebuild_ann_index(conn, 0)?;
+        Ok(())
+    }
+
+    /// Record the embedder's model, invalidating vectors written by another one.
+    ///
+    /// Returns `true` when existing embeddings were dropped and need a reindex.
+    fn check_embedding_model(
+        conn: &Connection,
+        embedder: &dyn EmbeddingProvider,
+    ) -> anyhow::Result<bool> {
+        let model = format!("{}:{}", embedder.name(), embedder.model());
+        let changed = match Self::embedding_meta(conn, "model") {
+            Some(stored) if stored == model => return Ok(false),
+            Some(stored) => {
+                tracing::warn!(
+                    from = %stored,
+                    to = %model,
+                    "Embedding model changed; re-embedding SQLite memories"
+                );
+                Self::invalidate_embeddings(conn)?;
+                true
+            }
+            None => false,
+        };
+        Self::set_embedding_meta(conn, "model", &model)?;
+        Ok(changed)
+    }
+
+    /// Record the dimension count of a freshly computed vector.
+    ///
+    /// Returns `true` when it differs from the stored vectors, which are then
+    /// dropped and need a reindex.
+    fn check_embedding_dims(conn: &Connection, dims: usize) -> anyhow::Result<bool> {
+        let stored = Self::embedding_meta(conn, "dimensions").and_then(|v| v.parse::<usize>().ok());
+        let changed = match stored {
+            Some(stored) if stored == dims => return Ok(false),
+            Some(stored) => {
+                tracing::warn!(
+                    from = stored,
+                    to = dims,
+                    "Embedding dimensions changed; re-embedding SQLite memories"
+                );
+                Self::invalidate_embeddings(conn)?;
+                true
+            }
+            None => false,
+        };
+        Self::set_embedding_meta(conn, "dimensions", &dims.to_string())?;
+        Ok(changed)
+    }
+
+    /// Run a pending reindex after an embedding model or dimension change.
+    ///
+    /// Failures are logged and retried on the next call so keyword search
+    /// keeps working while the embedding provider is unavailable.
+    async fn reindex_if_pending(&self) {
+        if !self.reindex_pending.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        match self.reindex().await {
+            Ok(count) => tracing::info!(count, "Re-embedded SQLite memories"),
+            Err(e) => {
+                tracing::warn!("memory reindex after embedding change failed: {e}");
+                self.reindex_pending.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
     fn category_to_str(cat: &MemoryCategory) -> String {
         match cat {
             MemoryCategory::Core => "core".into(),
@@ -269,10 +435,15 @@ impl SqliteMemory {
 
         // Store in cache + LRU eviction (offloaded to blocking thread)
         let conn = self.conn.clone();
+        let reindex_pending = self.reindex_pending.clone();
+        let dims = embedding.len();
         #[allow(clippy::cast_possible_wrap)]
         let cache_max = self.cache_max as i64;
         tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
             let conn = conn.lock();
+            if Self::check_embedding_dims(&conn, dims)? {
+                reindex_pending.store(true, Ordering::SeqCst);
+            }
             conn.execute(
                 "INSERT OR REPLACE INTO embedding_cache (content_hash, embedding, created_at, accessed_at)
                  VALUES (?1, ?2, ?3, ?4)",
@@ -339,18 +510,33 @@ impl SqliteMemory {
     /// Vector similarity search: scan embeddings and compute cosine similarity.
     ///
     /// Optional `category` and `session_id` filters reduce full-table scans
-    /// when the caller already knows the scope of relevant memories.
+    /// when the caller already knows the scope of relevant memories. With an
+    /// IVF index, only the lists closest to the query (plus rows not yet
+    /// assigned to a list) are scanned.
     fn vector_search(
         conn: &Connection,
         query_embedding: &[f32],
         limit: usize,
         category: Option<&str>,
         session_id: Option<&str>,
+        index: Option<&IvfIndex>,
     ) -> anyhow::Result<Vec<(String, f32)>> {
         let mut sql = "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL".to_string();
         let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
         let mut idx = 1;
 
+        if let Some(index) = index {
+            let lists = index.probe(query_embedding, index.default_probes());
+            if !lists.is_empty() {
+                let list_ids = lists
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let _ = write!(sql, " AND (ivf_list IS NULL OR ivf_list IN ({list_ids}))");
+            }
+        }
+
         if let Some(cat) = category {
             let _ = write!(sql, " AND category = ?{idx}");
             param_values.push(Box::new(cat.to_string()));
@@ -385,6 +571,193 @@ impl SqliteMemory {
         Ok(scored)
     }
 
+    fn ann_meta(conn: &Connection, key: &str) -> i64 {
+        conn.query_row(
+            "SELECT value FROM vector_index_meta WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+    }
+
+    fn set_ann_meta(conn: &Connection, key: &str, value: i64) -> anyhow::Result<()> {
+        conn.execute(
+            "INSERT INTO vector_index_meta (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Current IVF index, reloading centroids when another writer rebuilt it.
+    fn current_ann_index(
+        conn: &Connection,
+        cache: &Mutex<Option<AnnCache>>,
+    ) -> anyhow::Result<Option<Arc<IvfIndex>>> {
+        let generation = Self::ann_meta(conn, "generation");
+        let mut cache = cache.lock();
+        if let Some(cached) = cache.as_ref().filter(|c| c.generation == generation) {
+            return Ok(cached.index.clone());
+        }
+
+        let mut stmt = conn.prepare("SELECT centroid FROM vector_centroids ORDER BY list_id")?;
+        let centroids = stmt
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))?
+            .map(|blob| blob.map(|bytes| vector::bytes_to_vec(&bytes)))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let index = IvfIndex::from_centroids(centroids).map(Arc::new);
+        *cache = Some(AnnCache {
+            generation,
+            index: index.clone(),
+        });
+        Ok(index)
+    }
+
+    /// Retrain the IVF index from every stored embedding and reassign all rows.
+    ///
+    /// With fewer than `min_rows` embeddings (or `min_rows == 0`) the index is
+    /// dropped and searches fall back to the exact linear scan. Returns the
+    /// number of lists built.
+    fn rebuild_ann_index(conn: &Connection, min_rows: usize) -> anyhow::Result<usize> {
+        let rows = Self::embedded_rows(conn)?;
+        let index = Self::train_ann_index(&rows, min_rows);
+        Self::write_ann_index(conn, index.as_ref(), &rows)
+    }
+
+    /// Like [`Self::rebuild_ann_index`], but trains without holding the
+    /// connection lock. Stores and searches keep running on the previous
+    /// index (or the exact scan) until the new one is written.
+    fn retrain_ann_index(conn: &Mutex<Connection>, min_rows: usize) -> anyhow::Result<usize> {
+        let rows = Self::embedded_rows(&conn.lock())?;
+        let index = Self::train_ann_index(&rows, min_rows);
+        Self::write_ann_index(&conn.lock(), index.as_ref(), &rows)
+    }
+
+    /// `(id, updated_at, embedding)` for every row with an embedding.
+    fn embedded_rows(conn: &Connection) -> anyhow::Result<Vec<(String, String, Vec<f32>)>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, updated_at, embedding FROM memories WHERE embedding IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                vector::bytes_to_vec(&row.get::<_, Vec<u8>>(2)?),
+            ))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn train_ann_index(rows: &[(String, String, Vec<f32>)], min_rows: usize) -> Option<IvfIndex> {
+        if min_rows == 0 || rows.len() < min_rows {
+            return None;
+        }
+        let vectors: Vec<Vec<f32>> = rows.iter().map(|(_, _, v)| v.clone()).collect();
+        IvfIndex::train(&vectors)
+    }
+
+    /// Replace the stored centroids and list assignments with `index`.
+    ///
+    /// `rows` are the embeddings the index was trained on; rows changed since
+    /// that snapshot (and rows added meanwhile) are assigned from their
+    /// current embedding instead.
+    fn write_ann_index(
+        conn: &Connection,
+        index: Option<&IvfIndex>,
+        rows: &[(String, String, Vec<f32>)],
+    ) -> anyhow::Result<usize> {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM vector_centroids", [])?;
+        tx.execute(
+            "UPDATE memories SET ivf_list = NULL WHERE ivf_list IS NOT NULL",
+            [],
+        )?;
+        let mut trained_rows = 0_i64;
+        if let Some(index) = index {
+            let mut insert =
+                tx.prepare("INSERT INTO vector_centroids (list_id, centroid) VALUES (?1, ?2)")?;
+            for (list_id, centroid) in index.centroids().iter().enumerate() {
+                #[allow(clippy::cast_possible_wrap)]
+                insert.execute(params![list_id as i64, vector::vec_to_bytes(centroid)])?;
+            }
+            let mut assign =
+                tx.prepare("UPDATE memories SET ivf_list = ?1 WHERE id = ?2 AND updated_at = ?3")?;
+            for (id, updated_at, embedding) in rows {
+                if let Some(list) = index.assign(embedding) {
+                    #[allow(clippy::cast_possible_wrap)]
+                    if assign.execute(params![list as i64, id, updated_at])? > 0 {
+                        trained_rows += 1;
+                    }
+                }
+            }
+
+            let stale: Vec<(String, Vec<f32>)> = {
+                let mut stmt = tx.prepare(
+                    "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL AND ivf_list IS NULL",
+                )?;
+                let stale = stmt.query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        vector::bytes_to_vec(&row.get::<_, Vec<u8>>(1)?),
+                    ))
+                })?;
+                stale.collect::<rusqlite::Result<_>>()?
+            };
+            let mut assign = tx.prepare("UPDATE memories SET ivf_list = ?1 WHERE id = ?2")?;
+            for (id, embedding) in &stale {
+                if let Some(list) = index.assign(embedding) {
+                    #[allow(clippy::cast_possible_wrap)]
+                    assign.execute(params![list as i64, id])?;
+                }
+            }
+        }
+        Self::set_ann_meta(&tx, "trained_rows", trained_rows)?;
+        let generation = Self::ann_meta(&tx, "generation") + 1;
+        Self::set_ann_meta(&tx, "generation", generation)?;
+        tx.commit()?;
+
+        Ok(index.map_or(0, |index| index.centroids().len()))
+    }
+
+    /// Whether enough rows have embeddings to train the IVF index, or the
+    /// collection has outgrown the centroids.
+    fn ann_rebuild_due(conn: &Connection, min_rows: usize) -> anyhow::Result<bool> {
+        if min_rows == 0 {
+            return Ok(false);
+        }
+        let embedded: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM memories WHERE embedding IS NOT NULL",
+            [],
+            |row| row.get(0),
+        )?;
+        let trained = Self::ann_meta(conn, "trained_rows");
+        #[allow(clippy::cast_possible_wrap)]
+        let due = if trained == 0 {
+            embedded >= min_rows as i64
+        } else {
+            embedded >= trained * ANN_RETRAIN_GROWTH
+        };
+        Ok(due)
+    }
+
+    /// Retrain the IVF index on a blocking thread unless a retrain is already
+    /// running.
+    fn spawn_ann_rebuild(&self) {
+        if self.ann_rebuilding.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let conn = self.conn.clone();
+        let rebuilding = self.ann_rebuilding.clone();
+        let min_rows = self.ann_min_rows;
+        tokio::task::spawn_blocking(move || {
+            match Self::retrain_ann_index(&conn, min_rows) {
+                Ok(lists) => tracing::debug!(lists, "Rebuilt SQLite IVF memory index"),
+                Err(e) => tracing::warn!("SQLite IVF memory index rebuild failed: {e}"),
+            }
+            rebuilding.store(false, Ordering::SeqCst);
+        });
+    }
+
     /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure
     #[allow(dead_code)]
     pub async fn reindex(&self) -> anyhow::Result<usize> {
@@ -400,10 +773,21 @@ impl SqliteMemory {
         }
 
         // Step 2: Re-embed all memories that lack embeddings
-        if self.embedder.dimensions() == 0 {
-            return Ok(0);
-        }
+        let count = if self.embedder.dimensions() == 0 {
+            0
+        } else {
+            self.embed_missing().await?
+        };
 
+        // Step 3: Retrain the IVF index over the full set of embeddings
+        let conn = self.conn.clone();
+        let min_rows = self.ann_min_rows;
+        tokio::task::spawn_blocking(move || Self::retrain_ann_index(&conn, min_rows)).await??;
+
+        Ok(count)
+    }
+
+    async fn embed_missing(&self) -> anyhow::Result<usize> {
         let conn = self.conn.clone();
         let entries: Vec<(String, String)> = tokio::task::spawn_blocking(move || {
             let conn = conn.lock();
@@ -417,22 +801,50 @@ impl SqliteMemory {
         .await??;
 
         let mut count = 0;
-        for (id, content) in &entries {
-            if let Ok(Some(emb)) = self.get_or_compute_embedding(content).await {
-                let bytes = vector::vec_to_bytes(&emb);
-                let conn = self.conn.clone();
-                let id = id.clone();
-                tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
-                    let conn = conn.lock();
-                    conn.execute(
-                        "UPDATE memories SET embedding = ?1 WHERE id = ?2",
-                        params![bytes, id],
-                    )?;
-                    Ok(())
-                })
-                .await??;
-                count += 1;
-            }
+        for batch in entries.chunks(REINDEX_BATCH_SIZE) {
+            let texts: Vec<&str> = batch.iter().map(|(_, content)| content.as_str()).collect();
+            let embeddings = match self.embedder.embed(&texts).await {
+                Ok(embeddings) if embeddings.len() == batch.len() => embeddings,
+                Ok(embeddings) => {
+                    tracing::warn!(
+                        expected = batch.len(),
+                        got = embeddings.len(),
+                        "Embedding provider returned a short batch; skipping"
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("Embedding batch failed during reindex: {e}");
+                    continue;
+                }
+            };
+
+            let conn = self.conn.clone();
+            let reindex_pending = self.reindex_pending.clone();
+            let updates: Vec<(String, Vec<u8>)> = batch
+                .iter()
+                .zip(&embeddings)
+                .map(|((id, _), emb)| (id.clone(), vector::vec_to_bytes(emb)))
+                .collect();
+            let dims = embeddings[0].len();
+            tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+                let conn = conn.lock();
+                if Self::check_embedding_dims(&conn, dims)? {
+                    reindex_pending.store(true, Ordering::SeqCst);
+                }
+                let tx = conn.unchecked_transaction()?;
+                {
+                    let mut stmt =
+                        tx.prepare("UPDATE memories SET embedding = ?1 WHERE id = ?2")?;
+                    for (id, bytes) in &updates {
+                        stmt.execute(params![bytes, id])?;
+                    }
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await??;
+            count += batch.len();
         }
 
         Ok(count)
@@ -489,37 +901,56 @@ impl Memory for SqliteMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> anyhow::Result<()> {
+        self.reindex_if_pending().await;
+
         // Compute embedding (async, before blocking work)
-        let embedding_bytes = self
-            .get_or_compute_embedding(content)
-            .await?
-            .map(|emb| vector::vec_to_bytes(&emb));
+        let embedding = self.get_or_compute_embedding(content).await?;
 
         let conn = self.conn.clone();
+        let ann = self.ann.clone();
+        let ann_min_rows = self.ann_min_rows;
         let key = key.to_string();
         let content = content.to_string();
         let sid = session_id.map(String::from);
 
-        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        let rebuild_due = tokio::task::spawn_blocking(move || -> anyhow::Result<bool> {
             let conn = conn.lock();
             let now = Local::now().to_rfc3339();
             let cat = Self::category_to_str(&category);
             let id = Uuid::new_v4().to_string();
 
+            // Assign the new embedding to its IVF list so searches see it
+            // without a rebuild.
+            let ivf_list = match embedding.as_deref() {
+                Some(emb) if ann_min_rows > 0 => Self::current_ann_index(&conn, &ann)?
+                    .and_then(|index| index.assign(emb)),
+                _ => None,
+            };
+            #[allow(clippy::cast_possible_wrap)]
+            let ivf_list = ivf_list.map(|list| list as i64);
+            let embedding_bytes = embedding.as_deref().map(vector::vec_to_bytes);
+
             conn.execute(
-                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id, ivf_list)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
                  ON CONFLICT(key) DO UPDATE SET
                     content = excluded.content,
                     category = excluded.category,
                     embedding = excluded.embedding,
                     updated_at = excluded.updated_at,
-                    session_id = excluded.session_id",
-                params![id, key, content, cat, embedding_bytes, now, now, sid],
+                    session_id = excluded.session_id,
+                    ivf_list = excluded.ivf_list",
+                params![id, key, content, cat, embedding_bytes, now, now, sid, ivf_list],
             )?;
-            Ok(())
+
+            Ok(embedding.is_some() && Self::ann_rebuild_due(&conn, ann_min_rows)?)
         })
-        .await?
+        .await??;
+
+        if rebuild_due {
+            self.spawn_ann_rebuild();
+        }
+        Ok(())
     }
 
     async fn recall(
@@ -532,10 +963,14 @@ impl Memory for SqliteMemory {
             return Ok(Vec::new());
         }
 
+        self.reindex_if_pending().await;
+
         // Compute query embedding (async, before blocking work)
         let query_embedding = self.get_or_compute_embedding(query).await?;
 
         let conn = self.conn.clone();
+        let ann = self.ann.clone();
+        let use_ann = self.ann_min_rows > 0;
         let query = query.to_string();
         let sid = session_id.map(String::from);
         let vector_weight = self.vector_weight;
@@ -550,7 +985,13 @@ impl Memory for SqliteMemory {
 
             // Vector similarity search (if embeddings available)
             let vector_results = if let Some(ref qe) = query_embedding {
-                Self::vector_search(&conn, qe, limit * 2, None, session_ref).unwrap_or_default()
+                let index = if use_ann {
+                    Self::current_ann_index(&conn, &ann).unwrap_or(None)
+                } else {
+                    None
+                };
+                Self::vector_search(&conn, qe, limit * 2, None, session_ref, index.as_deref())
+                    .unwrap_or_default()
             } else {
                 Vec::new()
             };
@@ -1943,4 +2384,279 @@ mod tests {
 
         assert_eq!(mem.count().await.unwrap(), 1);
     }
+
+    // ── IVF approximate nearest-neighbour index ─────────────
+
+    /// Maps `note-<n>` to a distinct direction so nearest neighbours are known.
+    struct TopicEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for TopicEmbedding {
+        fn name(&self) -> &str {
+            "topic"
+        }
+
+        fn dimensions(&self) -> usize {
+            8
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let n: usize = text
+                        .rsplit('-')
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .unwrap_or(0);
+                    let mut v = vec![0.01_f32; 8];
+                    v[n % 8] = 1.0;
+                    v[(n / 8) % 8] += 0.3;
+                    v
+                })
+                .collect())
+        }
+    }
+
+    fn ann_sqlite(tmp: &TempDir, ann_min_rows: usize) -> SqliteMemory {
+        SqliteMemory::with_embedder(tmp.path(), Arc::new(TopicEmbedding), 0.7, 0.3, 1000, None)
+            .unwrap()
+            .with_ann_min_rows(ann_min_rows)
+    }
+
+    async fn wait_for_ann_rebuild(mem: &SqliteMemory) {
+        while mem.ann_rebuilding.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    fn unassigned_rows(mem: &SqliteMemory) -> i64 {
+        mem.conn
+            .lock()
+            .query_row(
+                "SELECT COUNT(*) FROM memories WHERE embedding IS NOT NULL AND ivf_list IS NULL",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn ann_index_trains_at_threshold_and_assigns_new_rows() {
+        let tmp = TempDir::new().unwrap();
+        let mem = ann_sqlite(&tmp, 32);
+        for i in 0..40 {
+            mem.store(
+                &format!("k{i}"),
+                &format!("note-{i}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+            if i == 31 {
+                wait_for_ann_rebuild(&mem).await;
+            }
+        }
+
+        let lists: i64 = mem
+            .conn
+            .lock()
+            .query_row("SELECT COUNT(*) FROM vector_centroids", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(lists, 8);
+        assert_eq!(unassigned_rows(&mem), 0);
+        assert_eq!(SqliteMemory::ann_meta(&mem.conn.lock(), "trained_rows"), 32);
+
+        mem.store("k41", "note-41", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(unassigned_rows(&mem), 0);
+
+        let results = mem.recall("note-41", 3, None).await.unwrap();
+        assert_eq!(results[0].key, "k41");
+
+        assert!(mem.forget("k41").await.unwrap());
+        let results = mem.recall("note-41", 3, None).await.unwrap();
+        assert!(results.iter().all(|entry| entry.key != "k41"));
+    }
+
+    #[tokio::test]
+    async fn ann_retrain_assigns_rows_stored_while_training() {
+        let tmp = TempDir::new().unwrap();
+        let mem = ann_sqlite(&tmp, 16);
+        for i in 0..16 {
+            mem.store(
+                &format!("k{i}"),
+                &format!("note-{i}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        wait_for_ann_rebuild(&mem).await;
+
+        // Snapshot, then change one row and add another before the new
+        // centroids are written, as a concurrent store would.
+        let rows = SqliteMemory::embedded_rows(&mem.conn.lock()).unwrap();
+        mem.store("k3", "note-3 revised", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("k16", "note-16", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let index = SqliteMemory::train_ann_index(&rows, 16).unwrap();
+        SqliteMemory::write_ann_index(&mem.conn.lock(), Some(&index), &rows).unwrap();
+
+        assert_eq!(SqliteMemory::ann_meta(&mem.conn.lock(), "trained_rows"), 15);
+        assert_eq!(unassigned_rows(&mem), 0);
+        let results = mem.recall("note-16", 1, None).await.unwrap();
+        assert_eq!(results[0].key, "k16");
+    }
+
+    #[tokio::test]
+    async fn reindex_rebuilds_or_drops_ann_index() {
+        let tmp = TempDir::new().unwrap();
+        let mem = ann_sqlite(&tmp, 16);
+        for i in 0..20 {
+            mem.store(
+                &format!("k{i}"),
+                &format!("note-{i}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        wait_for_ann_rebuild(&mem).await;
+        let generation = SqliteMemory::ann_meta(&mem.conn.lock(), "generation");
+
+        mem.reindex().await.unwrap();
+        assert_eq!(
+            SqliteMemory::ann_meta(&mem.conn.lock(), "generation"),
+            generation + 1
+        );
+        assert_eq!(SqliteMemory::ann_meta(&mem.conn.lock(), "trained_rows"), 20);
+
+        let exact = ann_sqlite(&tmp, 0);
+        exact.reindex().await.unwrap();
+        assert_eq!(
+            SqliteMemory::ann_meta(&exact.conn.lock(), "trained_rows"),
+            0
+        );
+        assert_eq!(unassigned_rows(&exact), 20);
+
+        // The first handle notices the rebuild and falls back to a full scan.
+        let results = mem.recall("note-7", 1, None).await.unwrap();
+        assert_eq!(results[0].key, "k7");
+    }
+
+    // ── Embedding model guard ───────────────────────────────
+
+    /// Constant vectors of a configurable size, tagged with a model name.
+    struct SizedEmbedding {
+        model: &'static str,
+        dims: usize,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for SizedEmbedding {
+        fn name(&self) -> &str {
+            "sized"
+        }
+
+        fn model(&self) -> &str {
+            self.model
+        }
+
+        fn dimensions(&self) -> usize {
+            self.dims
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![1.0; self.dims]).collect())
+        }
+    }
+
+    fn sized_sqlite(tmp: &TempDir, model: &'static str, dims: usize) -> SqliteMemory {
+        let embedder = Arc::new(SizedEmbedding { model, dims });
+        SqliteMemory::with_embedder(tmp.path(), embedder, 0.7, 0.3, 1000, None).unwrap()
+    }
+
+    fn stored_dims(mem: &SqliteMemory) -> Vec<Option<usize>> {
+        let conn = mem.conn.lock();
+        let mut stmt = conn
+            .prepare("SELECT embedding FROM memories ORDER BY key")
+            .unwrap();
+        stmt.query_map([], |row| row.get::<_, Option<Vec<u8>>>(0))
+            .unwrap()
+            .map(|blob| {
+                blob.unwrap()
+                    .map(|bytes| vector::bytes_to_vec(&bytes).len())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn embedding_model_change_forces_reindex() {
+        let tmp = TempDir::new().unwrap();
+        let mem = sized_sqlite(&tmp, "model-a", 4);
+        for i in 0..3 {
+            mem.store(
+                &format!("k{i}"),
+                &format!("note {i}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        drop(mem);
+
+        let mem = sized_sqlite(&tmp, "model-b", 4);
+        assert_eq!(stored_dims(&mem), vec![None; 3]);
+        assert_eq!(
+            SqliteMemory::embedding_meta(&mem.conn.lock(), "model").as_deref(),
+            Some("sized:model-b")
+        );
+
+        mem.recall("note", 5, None).await.unwrap();
+        assert_eq!(stored_dims(&mem), vec![Some(4); 3]);
+    }
+
+    #[tokio::test]
+    async fn detected_dimension_change_forces_reindex() {
+        let tmp = TempDir::new().unwrap();
+        let mem = sized_sqlite(&tmp, "model-a", 4);
+        for i in 0..3 {
+            mem.store(
+                &format!("k{i}"),
+                &format!("note {i}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        drop(mem);
+
+        // Same model name, but the server now returns wider vectors.
+        let mem = sized_sqlite(&tmp, "model-a", 6);
+        assert_eq!(stored_dims(&mem), vec![Some(4); 3]);
+
+        mem.store("k3", "note 3", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(stored_dims(&mem), vec![None, None, None, Some(6)]);
+
+        mem.recall("note", 5, None).await.unwrap();
+        assert_eq!(stored_dims(&mem), vec![Some(6); 4]);
+        assert_eq!(
+            SqliteMemory::embedding_meta(&mem.conn.lock(), "dimensions").as_deref(),
+            Some("6")
+        );
+    }
 }
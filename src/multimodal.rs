@@ -50,6 +50,11 @@ pub enum MultimodalError {
     LocalReadFailed { input: String, reason: String },
 }
 
+/// Whether an image with this MIME type can be forwarded to a provider.
+pub fn is_supported_image_mime(mime: &str) -> bool {
+    ALLOWED_IMAGE_MIME_TYPES.contains(&mime)
+}
+
 pub fn parse_image_markers(content: &str) -> (String, Vec<String>) {
     let mut refs = Vec::new();
     let mut cleaned = String::with_capacity(content.len());
@@ -506,6 +511,7 @@ mod tests {
             max_images: 1,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            ..MultimodalConfig::default()
         };
 
         let error = prepare_messages_for_provider(&messages, &config)
@@ -548,6 +554,7 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 1,
             allow_remote_fetch: false,
+            ..MultimodalConfig::default()
         };
 
         let error = prepare_messages_for_provider(&messages, &config)
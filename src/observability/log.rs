@@ -27,6 +27,22 @@ impl Observer for LogObserver {
                 let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
                 info!(provider = %provider, model = %model, duration_ms = ms, tokens = ?tokens_used, cost_usd = ?cost_usd, "agent.end");
             }
+            ObserverEvent::PromptCache {
+                provider,
+                model,
+                cache_read_tokens,
+                cache_write_tokens,
+                uncached_input_tokens,
+            } => {
+                info!(
+                    provider = %provider,
+                    model = %model,
+                    cache_read_tokens = cache_read_tokens,
+                    cache_write_tokens = cache_write_tokens,
+                    uncached_input_tokens = uncached_input_tokens,
+                    "llm.prompt_cache"
+                );
+            }
             ObserverEvent::ToolCallStart { tool } => {
                 info!(tool = %tool, "tool.start");
             }
@@ -97,6 +113,12 @@ impl Observer for LogObserver {
             ObserverMetric::QueueDepth(d) => {
                 info!(depth = d, "metric.queue_depth");
             }
+            ObserverMetric::ResponseCacheHit { semantic } => {
+                info!(semantic = semantic, "metric.response_cache_hit");
+            }
+            ObserverMetric::ResponseCacheMiss => {
+                info!("metric.response_cache_miss");
+            }
         }
     }
 
@@ -164,5 +186,7 @@ mod tests {
         obs.record_metric(&ObserverMetric::TokensUsed(u64::MAX));
         obs.record_metric(&ObserverMetric::ActiveSessions(1));
         obs.record_metric(&ObserverMetric::QueueDepth(999));
+        obs.record_metric(&ObserverMetric::ResponseCacheHit { semantic: true });
+        obs.record_metric(&ObserverMetric::ResponseCacheMiss);
     }
 }
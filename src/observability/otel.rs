@@ -25,6 +25,8 @@ pub struct OtelObserver {
     errors: Counter<u64>,
     request_latency: Histogram<f64>,
     tokens_used: Counter<u64>,
+    prompt_cache_tokens: Counter<u64>,
+    response_cache_requests: Counter<u64>,
     active_sessions: Gauge<u64>,
     queue_depth: Gauge<u64>,
 }
@@ -140,6 +142,16 @@ impl OtelObserver {
             .with_description("Total tokens consumed (monotonic)")
             .build();
 
+        let prompt_cache_tokens = meter
+            .u64_counter("zeroclaw.llm.prompt_cache.tokens")
+            .with_description("Prompt tokens by cache outcome (read, write, uncached)")
+            .build();
+
+        let response_cache_requests = meter
+            .u64_counter("zeroclaw.llm.response_cache.requests")
+            .with_description("Cacheable LLM requests by outcome (hit, semantic_hit, miss)")
+            .build();
+
         let active_sessions = meter
             .u64_gauge("zeroclaw.sessions.active")
             .with_description("Current number of active sessions")
@@ -164,6 +176,8 @@ impl OtelObserver {
             errors,
             request_latency,
             tokens_used,
+            prompt_cache_tokens,
+            response_cache_requests,
             active_sessions,
             queue_depth,
         })
@@ -187,6 +201,28 @@ impl Observer for OtelObserver {
             ObserverEvent::LlmRequest { .. }
             | ObserverEvent::ToolCallStart { .. }
             | ObserverEvent::TurnComplete => {}
+            ObserverEvent::PromptCache {
+                provider,
+                model,
+                cache_read_tokens,
+                cache_write_tokens,
+                uncached_input_tokens,
+            } => {
+                for (kind, tokens) in [
+                    ("read", cache_read_tokens),
+                    ("write", cache_write_tokens),
+                    ("uncached", uncached_input_tokens),
+                ] {
+                    self.prompt_cache_tokens.add(
+                        *tokens,
+                        &[
+                            KeyValue::new("provider", provider.clone()),
+                            KeyValue::new("model", model.clone()),
+                            KeyValue::new("kind", kind),
+                        ],
+                    );
+                }
+            }
             ObserverEvent::LlmResponse {
                 provider,
                 model,
@@ -348,6 +384,15 @@ impl Observer for OtelObserver {
             ObserverMetric::QueueDepth(d) => {
                 self.queue_depth.record(*d as u64, &[]);
             }
+            ObserverMetric::ResponseCacheHit { semantic } => {
+                let outcome = if *semantic { "semantic_hit" } else { "hit" };
+                self.response_cache_requests
+                    .add(1, &[KeyValue::new("outcome", outcome)]);
+            }
+            ObserverMetric::ResponseCacheMiss => {
+                self.response_cache_requests
+                    .add(1, &[KeyValue::new("outcome", "miss")]);
+            }
         }
     }
 
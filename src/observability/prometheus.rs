@@ -13,6 +13,8 @@ pub struct PrometheusObserver {
     channel_messages: IntCounterVec,
     heartbeat_ticks: prometheus::IntCounter,
     errors: IntCounterVec,
+    prompt_cache_tokens: IntCounterVec,
+    response_cache_requests: IntCounterVec,
 
     // Histograms
     agent_duration: HistogramVec,
@@ -57,6 +59,24 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let prompt_cache_tokens = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_prompt_cache_tokens_total",
+                "Prompt tokens by cache outcome (read, write, uncached)",
+            ),
+            &["provider", "model", "kind"],
+        )
+        .expect("valid metric");
+
+        let response_cache_requests = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_response_cache_requests_total",
+                "Cacheable LLM requests by outcome (hit, semantic_hit, miss)",
+            ),
+            &["outcome"],
+        )
+        .expect("valid metric");
+
         let agent_duration = HistogramVec::new(
             HistogramOpts::new(
                 "zeroclaw_agent_duration_seconds",
@@ -110,6 +130,12 @@ impl PrometheusObserver {
         registry.register(Box::new(channel_messages.clone())).ok();
         registry.register(Box::new(heartbeat_ticks.clone())).ok();
         registry.register(Box::new(errors.clone())).ok();
+        registry
+            .register(Box::new(prompt_cache_tokens.clone()))
+            .ok();
+        registry
+            .register(Box::new(response_cache_requests.clone()))
+            .ok();
         registry.register(Box::new(agent_duration.clone())).ok();
         registry.register(Box::new(tool_duration.clone())).ok();
         registry.register(Box::new(request_latency.clone())).ok();
@@ -124,6 +150,8 @@ impl PrometheusObserver {
             channel_messages,
             heartbeat_ticks,
             errors,
+            prompt_cache_tokens,
+            response_cache_requests,
             agent_duration,
             tool_duration,
             request_latency,
@@ -166,6 +194,23 @@ impl Observer for PrometheusObserver {
                     self.tokens_used.set(i64::try_from(*t).unwrap_or(i64::MAX));
                 }
             }
+            ObserverEvent::PromptCache {
+                provider,
+                model,
+                cache_read_tokens,
+                cache_write_tokens,
+                uncached_input_tokens,
+            } => {
+                for (kind, tokens) in [
+                    ("read", cache_read_tokens),
+                    ("write", cache_write_tokens),
+                    ("uncached", uncached_input_tokens),
+                ] {
+                    self.prompt_cache_tokens
+                        .with_label_values(&[provider.as_str(), model.as_str(), kind])
+                        .inc_by(*tokens);
+                }
+            }
             ObserverEvent::ToolCallStart { tool: _ }
             | ObserverEvent::TurnComplete
             | ObserverEvent::LlmRequest { .. }
@@ -218,6 +263,17 @@ impl Observer for PrometheusObserver {
                     .with_label_values(&[] as &[&str])
                     .set(*d as f64);
             }
+            ObserverMetric::ResponseCacheHit { semantic } => {
+                let outcome = if *semantic { "semantic_hit" } else { "hit" };
+                self.response_cache_requests
+                    .with_label_values(&[outcome])
+                    .inc();
+            }
+            ObserverMetric::ResponseCacheMiss => {
+                self.response_cache_requests
+                    .with_label_values(&["miss"])
+                    .inc();
+            }
         }
     }
 
@@ -381,4 +437,39 @@ mod tests {
         let output = obs.encode();
         assert!(output.contains("zeroclaw_tokens_used_last 200"));
     }
+
+    #[test]
+    fn prompt_cache_tokens_track_by_kind() {
+        let obs = PrometheusObserver::new();
+        obs.record_event(&ObserverEvent::PromptCache {
+            provider: "anthropic".into(),
+            model: "claude-sonnet".into(),
+            cache_read_tokens: 900,
+            cache_write_tokens: 0,
+            uncached_input_tokens: 100,
+        });
+
+        let output = obs.encode();
+        assert!(output.contains(
+            r#"zeroclaw_prompt_cache_tokens_total{kind="read",model="claude-sonnet",provider="anthropic"} 900"#
+        ));
+        assert!(output.contains(
+            r#"zeroclaw_prompt_cache_tokens_total{kind="uncached",model="claude-sonnet",provider="anthropic"} 100"#
+        ));
+    }
+    #[test]
+    fn response_cache_requests_track_by_outcome() {
+        let obs = PrometheusObserver::new();
+        obs.record_metric(&ObserverMetric::ResponseCacheHit { semantic: false });
+        obs.record_metric(&ObserverMetric::ResponseCacheHit { semantic: true });
+        obs.record_metric(&ObserverMetric::ResponseCacheMiss);
+        obs.record_metric(&ObserverMetric::ResponseCacheMiss);
+
+        let output = obs.encode();
+        assert!(output.contains(r#"zeroclaw_response_cache_requests_total{outcome="hit"} 1"#));
+        assert!(
+            output.contains(r#"zeroclaw_response_cache_requests_total{outcome="semantic_hit"} 1"#)
+        );
+        assert!(output.contains(r#"zeroclaw_response_cache_requests_total{outcome="miss"} 2"#));
+    }
 }
@@ -27,6 +27,20 @@ pub enum ObserverEvent {
         success: bool,
         error_message: Option<String>,
     },
+    /// Prompt-cache accounting for a single LLM call.
+    ///
+    /// Emitted after a successful response when the provider reports cache
+    /// reads or writes (Anthropic, Bedrock, OpenAI, Gemini).
+    PromptCache {
+        provider: String,
+        model: String,
+        /// Prompt tokens served from the cache (hits).
+        cache_read_tokens: u64,
+        /// Prompt tokens written to the cache by this call.
+        cache_write_tokens: u64,
+        /// Prompt tokens processed without the cache (misses).
+        uncached_input_tokens: u64,
+    },
     /// The agent session has finished.
     ///
     /// Carries aggregate usage data (tokens, cost) when the provider reports it.
@@ -68,7 +82,7 @@ pub enum ObserverEvent {
 /// Numeric metrics emitted by the agent runtime.
 ///
 /// Observers can aggregate these into dashboards, alerts, or structured logs.
-/// Each variant carries a single scalar value with implicit units.
+/// Each variant carries at most a single scalar value with implicit units.
 #[derive(Debug, Clone)]
 pub enum ObserverMetric {
     /// Time elapsed for a single LLM or tool request.
@@ -79,6 +93,11 @@ pub enum ObserverMetric {
     ActiveSessions(u64),
     /// Current depth of the inbound message queue.
     QueueDepth(u64),
+    /// A cacheable provider request was answered from the response cache;
+    /// `semantic` is true when it matched by embedding similarity.
+    ResponseCacheHit { semantic: bool },
+    /// A cacheable provider request missed the response cache.
+    ResponseCacheMiss,
 }
 
 /// Core observability trait for recording agent runtime telemetry.
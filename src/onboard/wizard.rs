@@ -174,8 +174,10 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         gateway: crate::config::GatewayConfig::default(),
         composio: composio_config,
         secrets: secrets_config,
+        security: crate::config::SecurityConfig::default(),
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
+        mcp: crate::config::McpConfig::default(),
         multimodal: crate::config::MultimodalConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
         email_tool: crate::config::EmailToolConfig::default(),
@@ -322,10 +324,13 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         response_cache_enabled: false,
         response_cache_ttl_minutes: 60,
         response_cache_max_entries: 5_000,
+        response_cache_semantic: false,
+        response_cache_similarity_threshold: 0.95,
         snapshot_enabled: false,
         snapshot_on_hygiene: false,
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
+        sqlite_ann_min_rows: 2_048,
     }
 }
 
@@ -418,8 +423,10 @@ async fn run_quick_setup_with_home(
         gateway: crate::config::GatewayConfig::default(),
         composio: ComposioConfig::default(),
         secrets: SecretsConfig::default(),
+        security: crate::config::SecurityConfig::default(),
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
+        mcp: crate::config::McpConfig::default(),
         multimodal: crate::config::MultimodalConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
         email_tool: crate::config::EmailToolConfig::default(),
@@ -3281,6 +3288,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(channel)
                     },
                     allowed_users,
+                    signing_secret: None,
                 });
             }
             ChannelMenuChoice::IMessage => {
@@ -4593,6 +4601,9 @@ fn scaffold_workspace(workspace_dir: &Path, ctx: &ProjectContext) -> Result<()>
          - **file_write** — Write file contents\n\
            - Use when: applying focused edits, scaffolding files, or updating docs/code.\n\
            - Don't use when: unsure about side effects or when the file should remain user-owned.\n\
+         - **file_edit** — Edit part of an existing file\n\
+           - Use when: changing a few lines via exact replacement or a unified diff; set dry_run to preview.\n\
+           - Don't use when: creating a new file or rewriting it wholesale (use file_write).\n\
          - **memory_store** — Save to memory\n\
            - Use when: preserving durable preferences, decisions, or key context.\n\
            - Don't use when: info is transient, noisy, or sensitive without explicit need.\n\
@@ -5431,6 +5442,7 @@ mod tests {
             "shell",
             "file_read",
             "file_write",
+            "file_edit",
             "memory_store",
             "memory_recall",
             "memory_forget",
@@ -1,7 +1,7 @@
 //! Hardware capabilities tool — Phase C: query device for reported GPIO pins.
 
 use super::serial::SerialTransport;
-use crate::tools::traits::{Tool, ToolResult};
+use crate::tools::traits::{Tool, ToolMetadata, ToolResult};
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
@@ -40,6 +40,10 @@ impl Tool for HardwareCapabilitiesTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let filter = args.get("board").and_then(|v| v.as_str());
         let mut outputs = Vec::new();
@@ -94,6 +98,7 @@ impl Tool for HardwareCapabilitiesTool {
             success: !outputs.is_empty(),
             output,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
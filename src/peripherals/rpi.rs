@@ -5,7 +5,7 @@
 
 use crate::config::PeripheralBoardConfig;
 use crate::peripherals::traits::Peripheral;
-use crate::tools::{Tool, ToolResult};
+use crate::tools::{Tool, ToolMetadata, ToolResult};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 
@@ -86,6 +86,10 @@ impl Tool for RpiGpioReadTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         let pin = args
             .get("pin")
@@ -107,6 +111,7 @@ impl Tool for RpiGpioReadTool {
             success: true,
             output: format!("pin {} = {}", pin, value),
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -141,6 +146,10 @@ impl Tool for RpiGpioWriteTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_idempotent(true)
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         let pin = args
             .get("pin")
@@ -168,6 +177,7 @@ impl Tool for RpiGpioWriteTool {
             success: true,
             output: format!("pin {} = {}", pin, value),
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -6,7 +6,7 @@
 
 use super::traits::Peripheral;
 use crate::config::PeripheralBoardConfig;
-use crate::tools::traits::{Tool, ToolResult};
+use crate::tools::traits::{Tool, ToolMetadata, ToolResult};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -94,6 +94,7 @@ impl SerialTransport {
             success: ok,
             output: result,
             error,
+            parts: Vec::new(),
         })
     }
 
@@ -216,6 +217,10 @@ impl Tool for GpioReadTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         let pin = args
             .get("pin")
@@ -259,6 +264,10 @@ impl Tool for GpioWriteTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_idempotent(true)
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         let pin = args
             .get("pin")
@@ -3,7 +3,7 @@
 //! When ZeroClaw runs on Uno Q, the Bridge app (Python + MCU) exposes
 //! digitalWrite/digitalRead over a local socket. These tools connect to it.
 
-use crate::tools::traits::{Tool, ToolResult};
+use crate::tools::traits::{Tool, ToolMetadata, ToolResult};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::time::Duration;
@@ -56,6 +56,10 @@ impl Tool for UnoQGpioReadTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         let pin = args
             .get("pin")
@@ -68,12 +72,14 @@ impl Tool for UnoQGpioReadTool {
                         success: false,
                         output: resp.clone(),
                         error: Some(resp),
+                        parts: Vec::new(),
                     })
                 } else {
                     Ok(ToolResult {
                         success: true,
                         output: resp,
                         error: None,
+                        parts: Vec::new(),
                     })
                 }
             }
@@ -81,6 +87,7 @@ impl Tool for UnoQGpioReadTool {
                 success: false,
                 output: format!("Bridge error: {}", e),
                 error: Some(e.to_string()),
+                parts: Vec::new(),
             }),
         }
     }
@@ -116,6 +123,10 @@ impl Tool for UnoQGpioWriteTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_idempotent(true)
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         let pin = args
             .get("pin")
@@ -132,12 +143,14 @@ impl Tool for UnoQGpioWriteTool {
                         success: false,
                         output: resp.clone(),
                         error: Some(resp),
+                        parts: Vec::new(),
                     })
                 } else {
                     Ok(ToolResult {
                         success: true,
                         output: "done".into(),
                         error: None,
+                        parts: Vec::new(),
                     })
                 }
             }
@@ -145,6 +158,7 @@ impl Tool for UnoQGpioWriteTool {
                 success: false,
                 output: format!("Bridge error: {}", e),
                 error: Some(e.to_string()),
+                parts: Vec::new(),
             }),
         }
     }
@@ -1,17 +1,23 @@
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ToolCall as ProviderToolCall,
+    Provider, ResponseFormat, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
+    ToolCall as ProviderToolCall, ToolCallDelta,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 pub struct AnthropicProvider {
     credential: Option<String>,
     base_url: String,
 }
 
+const STRUCTURED_OUTPUT_TOOL_DESCRIPTION: &str =
+    "Deliver the final answer. The tool input is the complete response.";
+
 #[derive(Debug, Serialize)]
 struct ChatRequest {
     model: String,
@@ -51,6 +57,10 @@ struct NativeChatRequest<'a> {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -128,6 +138,37 @@ struct SystemBlock {
 struct NativeChatResponse {
     #[serde(default)]
     content: Vec<NativeContentIn>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct AnthropicUsage {
+    #[serde(default)]
+    input_tokens: Option<u64>,
+    #[serde(default)]
+    output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+}
+
+impl AnthropicUsage {
+    /// Anthropic reports cache reads/writes separately from `input_tokens`;
+    /// fold them back in so `input_tokens` is the full prompt size.
+    fn into_token_usage(self) -> TokenUsage {
+        let cache_write = self.cache_creation_input_tokens.unwrap_or(0);
+        let cache_read = self.cache_read_input_tokens.unwrap_or(0);
+        TokenUsage {
+            input_tokens: self
+                .input_tokens
+                .map(|tokens| tokens + cache_write + cache_read),
+            output_tokens: self.output_tokens,
+            cached_input_tokens: self.cache_read_input_tokens,
+            cache_write_input_tokens: self.cache_creation_input_tokens,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -229,6 +270,40 @@ impl AnthropicProvider {
         Some(native_tools)
     }
 
+    /// Anthropic has no JSON mode, so structured output is a tool whose input
+    /// schema is the requested schema. With no other tools it is forced by
+    /// name; alongside agent tools any tool call is required and the model
+    /// finishes by calling this one.
+    fn add_structured_output_tool<'a>(
+        tools: &mut Option<Vec<NativeToolSpec<'a>>>,
+        format: &'a ResponseFormat,
+    ) -> serde_json::Value {
+        let has_other_tools = tools.is_some();
+        tools.get_or_insert_with(Vec::new).push(NativeToolSpec {
+            name: &format.name,
+            description: STRUCTURED_OUTPUT_TOOL_DESCRIPTION,
+            input_schema: &format.schema,
+            cache_control: None,
+        });
+        if has_other_tools {
+            serde_json::json!({"type": "any"})
+        } else {
+            serde_json::json!({"type": "tool", "name": format.name})
+        }
+    }
+
+    /// Move the structured-output tool call's input into the response text.
+    fn take_structured_output(response: &mut ProviderChatResponse, tool_name: &str) {
+        if let Some(index) = response
+            .tool_calls
+            .iter()
+            .position(|call| call.name == tool_name)
+        {
+            let call = response.tool_calls.remove(index);
+            response.text = Some(call.arguments);
+        }
+    }
+
     fn parse_assistant_tool_call_message(content: &str) -> Option<Vec<NativeContentOut>> {
         let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
         let tool_calls = value
@@ -396,6 +471,36 @@ impl AnthropicProvider {
                 Some(text_parts.join("\n"))
             },
             tool_calls,
+            usage: response.usage.map(AnthropicUsage::into_token_usage),
+        }
+    }
+
+    fn build_native_request<'a>(
+        request: &ProviderChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+    ) -> NativeChatRequest<'a> {
+        let (system_prompt, mut messages) = Self::convert_messages(request.messages);
+
+        // Auto-cache last message if conversation is long
+        if Self::should_cache_conversation(request.messages) {
+            Self::apply_cache_to_last_message(&mut messages);
+        }
+
+        let mut tools = Self::convert_tools(request.tools);
+        let tool_choice = request
+            .response_format
+            .map(|format| Self::add_structured_output_tool(&mut tools, format));
+
+        NativeChatRequest {
+            model: model.to_string(),
+            max_tokens: 4096,
+            system: system_prompt,
+            messages,
+            temperature,
+            tools,
+            tool_choice,
+            stream: None,
         }
     }
 
@@ -404,6 +509,129 @@ impl AnthropicProvider {
     }
 }
 
+/// One `data:` payload of the Messages streaming API.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    MessageStart {
+        message: StreamMessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: NativeContentIn,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: StreamBlockDelta,
+    },
+    MessageDelta {
+        #[serde(default)]
+        usage: Option<AnthropicUsage>,
+    },
+    MessageStop,
+    Error {
+        error: serde_json::Value,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamMessageStart {
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamBlockDelta {
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Turns Messages API stream events into [`StreamChunk`]s.
+///
+/// Tool calls keep the content block index as their delta index. The
+/// structured-output tool, when present, is streamed as plain text so callers
+/// see the JSON reply rather than a tool call.
+#[derive(Debug, Default)]
+struct StreamState {
+    usage: AnthropicUsage,
+    structured_tool: Option<String>,
+    structured_blocks: HashSet<usize>,
+}
+
+impl StreamState {
+    fn handle(&mut self, data: &str) -> StreamResult<Option<StreamChunk>> {
+        let event: StreamEvent = serde_json::from_str(data).map_err(StreamError::Json)?;
+        let chunk = match event {
+            StreamEvent::MessageStart { message } => {
+                if let Some(usage) = message.usage {
+                    self.usage = usage;
+                }
+                None
+            }
+            StreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } if content_block.kind == "tool_use" => {
+                let name = content_block.name.unwrap_or_default();
+                if self.structured_tool.as_deref() == Some(name.as_str()) {
+                    self.structured_blocks.insert(index);
+                    None
+                } else {
+                    Some(StreamChunk::tool_call(ToolCallDelta {
+                        index,
+                        id: content_block.id,
+                        name: Some(name),
+                        arguments: String::new(),
+                    }))
+                }
+            }
+            StreamEvent::ContentBlockDelta { index, delta } => match delta {
+                StreamBlockDelta::TextDelta { text } => Some(StreamChunk::delta(text)),
+                StreamBlockDelta::InputJsonDelta { partial_json }
+                    if self.structured_blocks.contains(&index) =>
+                {
+                    Some(StreamChunk::delta(partial_json))
+                }
+                StreamBlockDelta::InputJsonDelta { partial_json } => {
+                    Some(StreamChunk::tool_call(ToolCallDelta {
+                        index,
+                        arguments: partial_json,
+                        ..ToolCallDelta::default()
+                    }))
+                }
+                StreamBlockDelta::Other => None,
+            },
+            StreamEvent::MessageDelta { usage } => {
+                if let Some(output) = usage.and_then(|usage| usage.output_tokens) {
+                    self.usage.output_tokens = Some(output);
+                }
+                None
+            }
+            StreamEvent::MessageStop => Some(
+                StreamChunk::final_chunk().with_usage(Some(self.usage.clone().into_token_usage())),
+            ),
+            StreamEvent::Error { error } => {
+                let message = error
+                    .get("message")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("stream error");
+                return Err(StreamError::Provider(format!("Anthropic: {message}")));
+            }
+            StreamEvent::ContentBlockStart { .. } | StreamEvent::Other => None,
+        };
+        Ok(chunk)
+    }
+}
+
 #[async_trait]
 impl Provider for AnthropicProvider {
     async fn chat_with_system(
@@ -461,21 +689,7 @@ impl Provider for AnthropicProvider {
             )
         })?;
 
-        let (system_prompt, mut messages) = Self::convert_messages(request.messages);
-
-        // Auto-cache last message if conversation is long
-        if Self::should_cache_conversation(request.messages) {
-            Self::apply_cache_to_last_message(&mut messages);
-        }
-
-        let native_request = NativeChatRequest {
-            model: model.to_string(),
-            max_tokens: 4096,
-            system: system_prompt,
-            messages,
-            temperature,
-            tools: Self::convert_tools(request.tools),
-        };
+        let native_request = Self::build_native_request(&request, model, temperature);
 
         let req = self
             .http_client()
@@ -490,13 +704,121 @@ impl Provider for AnthropicProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
-        Ok(Self::parse_native_response(native_response))
+        let mut chat_response = Self::parse_native_response(native_response);
+        if let Some(format) = request.response_format {
+            Self::take_structured_output(&mut chat_response, &format.name);
+        }
+        Ok(chat_response)
     }
 
     fn supports_native_tools(&self) -> bool {
         true
     }
 
+    fn supports_streaming_tool_calls(&self) -> bool {
+        true
+    }
+
+    fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return stream::once(async {
+                Err(StreamError::Provider(
+                    "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token)."
+                        .to_string(),
+                ))
+            })
+            .boxed();
+        };
+
+        let mut native_request = Self::build_native_request(&request, model, temperature);
+        native_request.stream = Some(true);
+        let req = self
+            .http_client()
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .header("Accept", "text/event-stream")
+            .json(&native_request);
+        let req = self.apply_auth(req, credential);
+        let mut state = StreamState {
+            structured_tool: request.response_format.map(|format| format.name.clone()),
+            ..StreamState::default()
+        };
+        let count_tokens = options.count_tokens;
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+        tokio::spawn(async move {
+            let response = match req.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+            };
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let _ = tx
+                    .send(Err(StreamError::Provider(format!(
+                        "Anthropic API error ({status}): {}",
+                        super::sanitize_api_error(&body)
+                    ))))
+                    .await;
+                return;
+            }
+
+            let mut buffer: Vec<u8> = Vec::new();
+            let mut bytes_stream = response.bytes_stream();
+            while let Some(item) = bytes_stream.next().await {
+                let bytes = match item {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        let _ = tx.send(Err(StreamError::Http(e))).await;
+                        return;
+                    }
+                };
+                buffer.extend_from_slice(&bytes);
+                while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=pos).collect();
+                    let line = String::from_utf8_lossy(&line);
+                    let Some(data) = line.trim().strip_prefix("data:") else {
+                        continue;
+                    };
+                    let chunk = match state.handle(data.trim()) {
+                        Ok(Some(chunk)) if count_tokens => chunk.with_token_estimate(),
+                        Ok(Some(chunk)) => chunk,
+                        Ok(None) => continue,
+                        Err(e) => {
+                            let _ = tx.send(Err(e)).await;
+                            return;
+                        }
+                    };
+                    let is_final = chunk.is_final;
+                    if tx.send(Ok(chunk)).await.is_err() || is_final {
+                        return;
+                    }
+                }
+            }
+            // Connection closed without `message_stop`.
+            let _ = tx
+                .send(Ok(
+                    StreamChunk::final_chunk().with_usage(Some(state.usage.into_token_usage()))
+                ))
+                .await;
+        });
+
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -540,6 +862,7 @@ impl Provider for AnthropicProvider {
             } else {
                 Some(&tool_specs)
             },
+            response_format: None,
         };
         self.chat(request, model, temperature).await
     }
@@ -1139,6 +1462,8 @@ mod tests {
             }],
             temperature: 0.7,
             tools: None,
+            tool_choice: None,
+            stream: None,
         };
 
         let json = serde_json::to_string(&req).unwrap();
@@ -1146,6 +1471,74 @@ mod tests {
         assert!(json.contains(r#""system":"System""#));
     }
 
+    #[test]
+    fn stream_state_assembles_text_tool_calls_and_usage() {
+        let mut state = StreamState::default();
+        let events = [
+            r#"{"type":"message_start","message":{"usage":{"input_tokens":20,"cache_read_input_tokens":100,"output_tokens":1}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Checking"}}"#,
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"shell","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"command\":"}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"\"ls\"}"}}"#,
+            r#"{"type":"ping"}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":42}}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+
+        let chunks: Vec<StreamChunk> = events
+            .iter()
+            .filter_map(|event| state.handle(event).unwrap())
+            .collect();
+
+        assert_eq!(chunks[0].delta, "Checking");
+        let mut assembler = crate::providers::traits::ToolCallAssembler::default();
+        for chunk in &chunks {
+            for delta in &chunk.tool_calls {
+                assembler.push(delta);
+            }
+        }
+        let calls = assembler.finish();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, "toolu_1");
+        assert_eq!(calls[0].arguments, r#"{"command":"ls"}"#);
+
+        let last = chunks.last().unwrap();
+        assert!(last.is_final);
+        let usage = last.usage.as_ref().unwrap();
+        assert_eq!(usage.input_tokens, Some(120));
+        assert_eq!(usage.output_tokens, Some(42));
+    }
+
+    #[test]
+    fn stream_state_streams_structured_output_tool_as_text() {
+        let mut state = StreamState {
+            structured_tool: Some("report".into()),
+            ..StreamState::default()
+        };
+        assert!(state
+            .handle(r#"{"type":"content_block_start","index":0,"content_block":{"type":"tool_use","id":"t","name":"report"}}"#)
+            .unwrap()
+            .is_none());
+        let chunk = state
+            .handle(r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"ok\":true}"}}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(chunk.delta, r#"{"ok":true}"#);
+        assert!(chunk.tool_calls.is_empty());
+    }
+
+    #[test]
+    fn stream_state_surfaces_error_events() {
+        let mut state = StreamState::default();
+        let err = state
+            .handle(
+                r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("Overloaded"));
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = AnthropicProvider::new(None);
@@ -1153,6 +1546,64 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn structured_output_tool_is_forced_by_name_without_other_tools() {
+        let format = ResponseFormat::json_schema(
+            "report",
+            serde_json::json!({"type": "object", "properties": {"ok": {"type": "boolean"}}}),
+        );
+        let mut tools = None;
+        let choice = AnthropicProvider::add_structured_output_tool(&mut tools, &format);
+        assert_eq!(
+            choice,
+            serde_json::json!({"type": "tool", "name": "report"})
+        );
+        let tools = tools.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].input_schema, &format.schema);
+
+        let json = r#"{"content": [{"type": "tool_use", "id": "t1", "name": "report", "input": {"ok": true}}]}"#;
+        let response: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let mut parsed = AnthropicProvider::parse_native_response(response);
+        AnthropicProvider::take_structured_output(&mut parsed, "report");
+        assert!(parsed.tool_calls.is_empty());
+        assert_eq!(parsed.text.as_deref(), Some(r#"{"ok":true}"#));
+    }
+
+    #[test]
+    fn structured_output_tool_requires_any_tool_alongside_agent_tools() {
+        let format = ResponseFormat::json_schema("report", serde_json::json!({"type": "object"}));
+        let specs = vec![ToolSpec {
+            name: "shell".to_string(),
+            description: "Run commands".to_string(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let mut tools = AnthropicProvider::convert_tools(Some(&specs));
+        let choice = AnthropicProvider::add_structured_output_tool(&mut tools, &format);
+        assert_eq!(choice, serde_json::json!({"type": "any"}));
+        assert_eq!(tools.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn native_response_usage_includes_cache_tokens_in_input() {
+        let json = r#"{
+            "content": [{"type": "text", "text": "hi"}],
+            "usage": {
+                "input_tokens": 10,
+                "output_tokens": 5,
+                "cache_creation_input_tokens": 100,
+                "cache_read_input_tokens": 1000
+            }
+        }"#;
+        let response: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let parsed = AnthropicProvider::parse_native_response(response);
+        let usage = parsed.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(1110));
+        assert_eq!(usage.output_tokens, Some(5));
+        assert_eq!(usage.cached_input_tokens, Some(1000));
+        assert_eq!(usage.cache_write_input_tokens, Some(100));
+    }
+
     #[test]
     fn convert_messages_preserves_multi_turn_history() {
         let messages = vec![
@@ -1257,6 +1708,9 @@ mod tests {
             .chat_with_tools(&messages, &tools, "claude-opus-4-6", 0.7)
             .await;
         assert!(result.is_ok(), "chat_with_tools failed: {:?}", result.err());
+        let usage = result.unwrap().usage.expect("usage should be parsed");
+        assert_eq!(usage.input_tokens, Some(100));
+        assert_eq!(usage.output_tokens, Some(20));
 
         let body = captured
             .lock()
@@ -5,8 +5,9 @@
 //! using hmac/sha2 crates — no AWS SDK dependency.
 
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, ToolCall as ProviderToolCall, ToolsPayload,
+    messages_with_tool_instructions, ChatMessage, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, ProviderCapabilities, TokenUsage,
+    ToolCall as ProviderToolCall, ToolsPayload,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -286,7 +287,15 @@ struct InferenceConfig {
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ToolConfig {
-    tools: Vec<ToolDefinition>,
+    tools: Vec<ToolEntry>,
+}
+
+/// Tool list entries: either `{"toolSpec": {...}}` or `{"cachePoint": {...}}`.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum ToolEntry {
+    Tool(ToolDefinition),
+    CachePoint(CachePointWrapper),
 }
 
 #[derive(Debug, Serialize)]
@@ -318,6 +327,38 @@ struct ConverseResponse {
     #[serde(default)]
     #[allow(dead_code)]
     stop_reason: Option<String>,
+    #[serde(default)]
+    usage: Option<ConverseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseUsage {
+    #[serde(default)]
+    input_tokens: Option<u64>,
+    #[serde(default)]
+    output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_write_input_tokens: Option<u64>,
+}
+
+impl From<ConverseUsage> for TokenUsage {
+    /// Converse reports cache reads/writes separately from `inputTokens`;
+    /// fold them back in so `input_tokens` is the full prompt size.
+    fn from(usage: ConverseUsage) -> Self {
+        let cache_read = usage.cache_read_input_tokens.unwrap_or(0);
+        let cache_write = usage.cache_write_input_tokens.unwrap_or(0);
+        Self {
+            input_tokens: usage
+                .input_tokens
+                .map(|tokens| tokens + cache_read + cache_write),
+            output_tokens: usage.output_tokens,
+            cached_input_tokens: usage.cache_read_input_tokens,
+            cache_write_input_tokens: usage.cache_write_input_tokens,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -615,18 +656,24 @@ impl BedrockProvider {
         if items.is_empty() {
             return None;
         }
-        let tool_defs: Vec<ToolDefinition> = items
+        let mut tool_defs: Vec<ToolEntry> = items
             .iter()
-            .map(|tool| ToolDefinition {
-                tool_spec: ToolSpecDef {
-                    name: tool.name.clone(),
-                    description: tool.description.clone(),
-                    input_schema: InputSchema {
-                        json: tool.parameters.clone(),
+            .map(|tool| {
+                ToolEntry::Tool(ToolDefinition {
+                    tool_spec: ToolSpecDef {
+                        name: tool.name.clone(),
+                        description: tool.description.clone(),
+                        input_schema: InputSchema {
+                            json: tool.parameters.clone(),
+                        },
                     },
-                },
+                })
             })
             .collect();
+        // The tool list is identical across turns; cache everything up to it.
+        tool_defs.push(ToolEntry::CachePoint(CachePointWrapper {
+            cache_point: CachePoint::default_cache(),
+        }));
         Some(ToolConfig { tools: tool_defs })
     }
 
@@ -668,6 +715,7 @@ impl BedrockProvider {
                 Some(text_parts.join("\n"))
             },
             tool_calls,
+            usage: response.usage.map(TokenUsage::from),
         }
     }
 
@@ -841,7 +889,14 @@ impl Provider for BedrockProvider {
     ) -> anyhow::Result<ProviderChatResponse> {
         let credentials = self.require_credentials()?;
 
-        let (system_blocks, mut converse_messages) = Self::convert_messages(request.messages);
+        // Converse has no JSON mode; ask for the schema in the system prompt.
+        let messages = match request.response_format {
+            Some(format) => {
+                messages_with_tool_instructions(request.messages, &format.instructions())
+            }
+            None => request.messages.to_vec(),
+        };
+        let (system_blocks, mut converse_messages) = Self::convert_messages(&messages);
 
         // Apply cachePoint to system if large.
         let system = system_blocks.map(|mut blocks| {
@@ -1212,8 +1267,16 @@ mod tests {
         let config = BedrockProvider::convert_tools_to_converse(Some(&tools));
         assert!(config.is_some());
         let config = config.unwrap();
-        assert_eq!(config.tools.len(), 1);
-        assert_eq!(config.tools[0].tool_spec.name, "shell");
+        assert_eq!(config.tools.len(), 2);
+        assert!(matches!(
+            &config.tools[0],
+            ToolEntry::Tool(def) if def.tool_spec.name == "shell"
+        ));
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json["tools"][1],
+            serde_json::json!({"cachePoint": {"type": "default"}})
+        );
     }
 
     #[test]
@@ -1284,6 +1347,28 @@ mod tests {
         assert_eq!(parsed.tool_calls[0].id, "call_1");
     }
 
+    #[test]
+    fn converse_response_parses_usage_with_cache_tokens() {
+        let json = r#"{
+            "output": {"message": {"role": "assistant", "content": [{"text": "ok"}]}},
+            "stopReason": "end_turn",
+            "usage": {
+                "inputTokens": 20,
+                "outputTokens": 8,
+                "totalTokens": 1128,
+                "cacheReadInputTokens": 1000,
+                "cacheWriteInputTokens": 100
+            }
+        }"#;
+        let resp: ConverseResponse = serde_json::from_str(json).unwrap();
+        let parsed = BedrockProvider::parse_converse_response(resp);
+        let usage = parsed.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(1120));
+        assert_eq!(usage.output_tokens, Some(8));
+        assert_eq!(usage.cached_input_tokens, Some(1000));
+        assert_eq!(usage.cache_write_input_tokens, Some(100));
+    }
+
     #[test]
     fn converse_response_empty_output() {
         let json = r#"{"output": null, "stopReason": null}"#;
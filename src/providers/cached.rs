@@ -0,0 +1,558 @@
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities, StreamChunk, StreamOptions,
+    StreamResult, ToolsPayload,
+};
+use super::{Provider, ResponseFormat};
+use crate::memory::embeddings::EmbeddingProvider;
+use crate::memory::ResponseCache;
+use crate::observability::traits::ObserverMetric;
+use crate::observability::Observer;
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Response-cache decorator — serves repeated prompts from `ResponseCache`.
+///
+/// Only deterministic (temperature 0), tool-free requests that end with a user
+/// message are cached; everything else, including streaming, passes straight
+/// through. With an embedder attached, a miss on the exact key falls back to
+/// the most similar cached prompt that shares the same system prompt, history
+/// and model.
+pub struct CachedProvider {
+    inner: Box<dyn Provider>,
+    cache: Arc<ResponseCache>,
+    embedder: Option<Arc<dyn EmbeddingProvider>>,
+    similarity_threshold: f32,
+    observer: Option<Arc<dyn Observer>>,
+}
+
+/// Cache identity of one request.
+struct CacheKey {
+    /// Exact key: context plus the final user prompt.
+    exact: String,
+    /// Everything except the final user prompt; scopes semantic matches.
+    context: String,
+    /// The final user prompt, embedded in semantic mode.
+    prompt: String,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Box<dyn Provider>, cache: Arc<ResponseCache>) -> Self {
+        Self {
+            inner,
+            cache,
+            embedder: None,
+            similarity_threshold: 0.95,
+            observer: None,
+        }
+    }
+
+    /// Enable semantic matching with the given embedder and minimum cosine similarity.
+    pub fn with_semantic(
+        mut self,
+        embedder: Arc<dyn EmbeddingProvider>,
+        similarity_threshold: f32,
+    ) -> Self {
+        self.embedder = Some(embedder);
+        self.similarity_threshold = similarity_threshold;
+        self
+    }
+
+    /// Report hits and misses as [`ObserverMetric`]s.
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    fn cache_key(
+        model: &str,
+        messages: &[ChatMessage],
+        response_format: Option<&ResponseFormat>,
+        temperature: f64,
+    ) -> Option<CacheKey> {
+        if temperature > 0.0 {
+            return None;
+        }
+        let (last, earlier) = messages.split_last()?;
+        if last.role != "user" {
+            return None;
+        }
+
+        let system: Vec<&str> = earlier
+            .iter()
+            .filter(|m| m.role == "system")
+            .map(|m| m.content.as_str())
+            .collect();
+        let mut system = system.join("\n\n");
+        if let Some(format) = response_format {
+            system.push('\n');
+            system.push_str(&serde_json::to_string(format).ok()?);
+        }
+        let history: Vec<&ChatMessage> = earlier.iter().filter(|m| m.role != "system").collect();
+        let history = serde_json::to_string(&history).ok()?;
+
+        let context = ResponseCache::cache_key(model, Some(&system), &history);
+        Some(CacheKey {
+            exact: ResponseCache::cache_key(model, Some(&context), &last.content),
+            context,
+            prompt: last.content.clone(),
+        })
+    }
+
+    fn record(&self, metric: &ObserverMetric) {
+        if let Some(observer) = &self.observer {
+            observer.record_metric(metric);
+        }
+    }
+
+    /// Look the request up; on a miss also returns the prompt embedding so
+    /// `store` does not compute it twice.
+    async fn lookup(&self, key: &CacheKey) -> (Option<String>, Option<Vec<f32>>) {
+        let cache = Arc::clone(&self.cache);
+        let exact = key.exact.clone();
+        match tokio::task::spawn_blocking(move || cache.get(&exact)).await {
+            Ok(Ok(Some(response))) => {
+                self.record(&ObserverMetric::ResponseCacheHit { semantic: false });
+                return (Some(response), None);
+            }
+            Ok(Ok(None)) => {}
+            Ok(Err(e)) => tracing::warn!("Response cache lookup failed: {e}"),
+            Err(e) => tracing::warn!("Response cache lookup failed: {e}"),
+        }
+
+        let mut embedding = None;
+        if let Some(embedder) = &self.embedder {
+            match embedder.embed_one(&key.prompt).await {
+                Ok(vector) => {
+                    let cache = Arc::clone(&self.cache);
+                    let context = key.context.clone();
+                    let query = vector.clone();
+                    let threshold = self.similarity_threshold;
+                    let similar = tokio::task::spawn_blocking(move || {
+                        cache.get_similar(&context, &query, threshold)
+                    })
+                    .await;
+                    match similar {
+                        Ok(Ok(Some(response))) => {
+                            self.record(&ObserverMetric::ResponseCacheHit { semantic: true });
+                            return (Some(response), None);
+                        }
+                        Ok(Ok(None)) => {}
+                        Ok(Err(e)) => tracing::warn!("Semantic response cache lookup failed: {e}"),
+                        Err(e) => tracing::warn!("Semantic response cache lookup failed: {e}"),
+                    }
+                    embedding = Some(vector);
+                }
+                Err(e) => tracing::warn!("Response cache embedding failed: {e}"),
+            }
+        }
+
+        self.record(&ObserverMetric::ResponseCacheMiss);
+        (None, embedding)
+    }
+
+    async fn store(
+        &self,
+        key: CacheKey,
+        model: &str,
+        response: String,
+        tokens: u64,
+        embedding: Option<Vec<f32>>,
+    ) {
+        let cache = Arc::clone(&self.cache);
+        let model = model.to_string();
+        let token_count = u32::try_from(tokens).unwrap_or(u32::MAX);
+        let result = tokio::task::spawn_blocking(move || match embedding {
+            Some(embedding) => cache.put_semantic(
+                &key.exact,
+                &model,
+                &response,
+                token_count,
+                &key.context,
+                &embedding,
+            ),
+            None => cache.put(&key.exact, &model, &response, token_count),
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => tracing::warn!("Response cache store failed: {e}"),
+            Err(e) => tracing::warn!("Response cache store failed: {e}"),
+        }
+    }
+
+    /// Tokens a hit saves when the provider reported no usage.
+    fn estimated_tokens(messages: &[ChatMessage], response: &str) -> u64 {
+        let chars: usize = messages.iter().map(|m| m.content.len()).sum::<usize>() + response.len();
+        u64::try_from(chars / 4).unwrap_or(u64::MAX)
+    }
+
+    /// Serve a text request from the cache, or run `fetch` and store its result.
+    async fn cached_text(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        fetch: impl Future<Output = anyhow::Result<String>>,
+    ) -> anyhow::Result<String> {
+        let Some(key) = Self::cache_key(model, messages, None, temperature) else {
+            return fetch.await;
+        };
+        let (cached, embedding) = self.lookup(&key).await;
+        if let Some(response) = cached {
+            return Ok(response);
+        }
+
+        let response = fetch.await?;
+        let tokens = Self::estimated_tokens(messages, &response);
+        self.store(key, model, response.clone(), tokens, embedding)
+            .await;
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Provider for CachedProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(system) = system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(message));
+        let fetch = self
+            .inner
+            .chat_with_system(system_prompt, message, model, temperature);
+        self.cached_text(&messages, model, temperature, fetch).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let fetch = self.inner.chat_with_history(messages, model, temperature);
+        self.cached_text(messages, model, temperature, fetch).await
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let tool_free = request.tools.is_none_or(<[ToolSpec]>::is_empty);
+        let key = tool_free
+            .then(|| {
+                Self::cache_key(
+                    model,
+                    request.messages,
+                    request.response_format,
+                    temperature,
+                )
+            })
+            .flatten();
+        let Some(key) = key else {
+            return self.inner.chat(request, model, temperature).await;
+        };
+
+        let (cached, embedding) = self.lookup(&key).await;
+        if let Some(text) = cached {
+            return Ok(ChatResponse {
+                text: Some(text),
+                tool_calls: Vec::new(),
+                usage: None,
+            });
+        }
+
+        let messages = request.messages;
+        let response = self.inner.chat(request, model, temperature).await?;
+        if let (Some(text), false) = (&response.text, response.has_tool_calls()) {
+            let tokens = response
+                .usage
+                .as_ref()
+                .map(|usage| usage.input_tokens.unwrap_or(0) + usage.output_tokens.unwrap_or(0))
+                .filter(|tokens| *tokens > 0)
+                .unwrap_or_else(|| Self::estimated_tokens(messages, text));
+            self.store(key, model, text.clone(), tokens, embedding)
+                .await;
+        }
+        Ok(response)
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_system(system_prompt, message, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_history(messages, model, temperature, options)
+    }
+
+    fn supports_streaming_tool_calls(&self) -> bool {
+        self.inner.supports_streaming_tool_calls()
+    }
+
+    fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner.stream_chat(request, model, temperature, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Provider for CountingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("answer {n} to {message}"))
+        }
+    }
+
+    #[derive(Default)]
+    struct MetricLog(Mutex<Vec<String>>);
+
+    impl Observer for MetricLog {
+        fn record_event(&self, _event: &crate::observability::ObserverEvent) {}
+
+        fn record_metric(&self, metric: &ObserverMetric) {
+            self.0.lock().push(format!("{metric:?}"));
+        }
+
+        fn name(&self) -> &str {
+            "metric-log"
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    /// Maps texts mentioning "rust" to one direction and everything else to another.
+    struct TopicEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for TopicEmbedding {
+        fn name(&self) -> &str {
+            "topic"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    if text.to_lowercase().contains("rust") {
+                        vec![1.0, 0.0]
+                    } else {
+                        vec![0.0, 1.0]
+                    }
+                })
+                .collect())
+        }
+    }
+
+    fn cached_provider(tmp: &tempfile::TempDir) -> (CachedProvider, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = CountingProvider {
+            calls: Arc::clone(&calls),
+        };
+        let cache = Arc::new(ResponseCache::new(tmp.path(), 60, 100).unwrap());
+        (CachedProvider::new(Box::new(inner), cache), calls)
+    }
+
+    #[tokio::test]
+    async fn deterministic_prompts_are_served_from_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (provider, calls) = cached_provider(&tmp);
+        let metrics = Arc::new(MetricLog::default());
+        let provider = provider.with_observer(metrics.clone());
+
+        let first = provider
+            .chat_with_system(Some("sys"), "hello", "m", 0.0)
+            .await
+            .unwrap();
+        let second = provider
+            .chat_with_system(Some("sys"), "hello", "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Different system prompt, model or temperature bypasses the entry.
+        provider
+            .chat_with_system(Some("other"), "hello", "m", 0.0)
+            .await
+            .unwrap();
+        provider
+            .chat_with_system(Some("sys"), "hello", "m2", 0.0)
+            .await
+            .unwrap();
+        provider
+            .chat_with_system(Some("sys"), "hello", "m", 0.7)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        assert_eq!(
+            *metrics.0.lock(),
+            vec![
+                "ResponseCacheMiss",
+                "ResponseCacheHit { semantic: false }",
+                "ResponseCacheMiss",
+                "ResponseCacheMiss",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_with_tools_are_not_cached() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (provider, calls) = cached_provider(&tmp);
+        let messages = vec![ChatMessage::user("list files")];
+        let tools = vec![ToolSpec {
+            name: "shell".into(),
+            description: "run".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+
+        for _ in 0..2 {
+            let request = ChatRequest {
+                messages: &messages,
+                tools: Some(&tools),
+                response_format: None,
+            };
+            provider.chat(request, "m", 0.0).await.unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        for _ in 0..2 {
+            let request = ChatRequest {
+                messages: &messages,
+                tools: None,
+                response_format: None,
+            };
+            provider.chat(request, "m", 0.0).await.unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn semantic_mode_matches_similar_prompts_with_same_history() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (provider, calls) = cached_provider(&tmp);
+        let provider = provider.with_semantic(Arc::new(TopicEmbedding), 0.9);
+
+        let history = |prompt: &str| {
+            vec![
+                ChatMessage::system("sys"),
+                ChatMessage::user("hi"),
+                ChatMessage::assistant("hello"),
+                ChatMessage::user(prompt),
+            ]
+        };
+
+        let first = provider
+            .chat_with_history(&history("What is Rust?"), "m", 0.0)
+            .await
+            .unwrap();
+        let similar = provider
+            .chat_with_history(&history("what is rust"), "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(first, similar);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        provider
+            .chat_with_history(&history("What is Go?"), "m", 0.0)
+            .await
+            .unwrap();
+        let mut other_history = history("What is Rust?");
+        other_history[2] = ChatMessage::assistant("hey");
+        provider
+            .chat_with_history(&other_history, "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
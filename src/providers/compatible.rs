@@ -2,10 +2,13 @@
 //! Most LLM APIs follow the same `/v1/chat/completions` format.
 //! This module provides a single implementation that works for all of them.
 
+use crate::providers::structured::MAX_STRUCTURED_OUTPUT_RETRIES;
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, StreamChunk, StreamError, StreamOptions, StreamResult, ToolCall as ProviderToolCall,
+    messages_with_tool_instructions, ChatMessage, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, StreamChunk, StreamError, StreamOptions,
+    StreamResult, TokenUsage, ToolCall as ProviderToolCall, ToolCallDelta,
 };
+use crate::providers::OpenAiUsage;
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use reqwest::{
@@ -258,11 +261,19 @@ struct ApiChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamUsageOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
 }
 
+/// Asks the server to append a final `usage` chunk to streamed responses.
+#[derive(Debug, Serialize)]
+struct StreamUsageOptions {
+    include_usage: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct Message {
     role: String,
@@ -272,6 +283,8 @@ struct Message {
 #[derive(Debug, Deserialize)]
 struct ApiChatResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<OpenAiUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -421,9 +434,13 @@ struct NativeChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamUsageOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -481,7 +498,10 @@ struct ResponsesContent {
 /// Server-Sent Event stream chunk for OpenAI-compatible streaming.
 #[derive(Debug, Deserialize)]
 struct StreamChunkResponse {
+    #[serde(default)]
     choices: Vec<StreamChoice>,
+    #[serde(default)]
+    usage: Option<OpenAiUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -497,45 +517,106 @@ struct StreamDelta {
     /// Reasoning/thinking models may stream output via `reasoning_content`.
     #[serde(default)]
     reasoning_content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<StreamToolCall>>,
+}
+
+/// Tool-call fragment inside a streamed `delta`.
+#[derive(Debug, Deserialize)]
+struct StreamToolCall {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<StreamFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+/// A single parsed SSE `data:` event: an optional text delta, any tool-call
+/// fragments and, on the trailing usage chunk, the provider-reported token counts.
+#[derive(Debug, Default)]
+struct SseEvent {
+    content: Option<String>,
+    tool_calls: Vec<ToolCallDelta>,
+    usage: Option<TokenUsage>,
 }
 
 /// Parse SSE (Server-Sent Events) stream from OpenAI-compatible providers.
 /// Handles the `data: {...}` format and `[DONE]` sentinel.
 fn parse_sse_line(line: &str) -> StreamResult<Option<String>> {
+    parse_sse_event(line).map(|event| event.content)
+}
+
+fn parse_sse_event(line: &str) -> StreamResult<SseEvent> {
     let line = line.trim();
 
     // Skip empty lines and comments
     if line.is_empty() || line.starts_with(':') {
-        return Ok(None);
+        return Ok(SseEvent::default());
     }
 
     // SSE format: "data: {...}"
-    if let Some(data) = line.strip_prefix("data:") {
-        let data = data.trim();
+    let Some(data) = line.strip_prefix("data:") else {
+        return Ok(SseEvent::default());
+    };
+    let data = data.trim();
 
-        // Check for [DONE] sentinel
-        if data == "[DONE]" {
-            return Ok(None);
-        }
+    // Check for [DONE] sentinel
+    if data == "[DONE]" {
+        return Ok(SseEvent::default());
+    }
 
-        // Parse JSON delta
-        let chunk: StreamChunkResponse = serde_json::from_str(data).map_err(StreamError::Json)?;
+    // Parse JSON delta
+    let chunk: StreamChunkResponse = serde_json::from_str(data).map_err(StreamError::Json)?;
+    let usage = chunk.usage.map(TokenUsage::from);
 
-        // Extract content from delta
-        if let Some(choice) = chunk.choices.first() {
-            if let Some(content) = &choice.delta.content {
-                if !content.is_empty() {
-                    return Ok(Some(content.clone()));
-                }
-            }
-            // Fallback to reasoning_content for thinking models
-            if let Some(reasoning) = &choice.delta.reasoning_content {
-                return Ok(Some(reasoning.clone()));
+    let Some(choice) = chunk.choices.into_iter().next() else {
+        return Ok(SseEvent {
+            usage,
+            ..SseEvent::default()
+        });
+    };
+
+    // Extract content from delta
+    let content = match choice.delta.content {
+        Some(content) if !content.is_empty() => Some(content),
+        // Fallback to reasoning_content for thinking models
+        _ => choice.delta.reasoning_content,
+    };
+
+    let tool_calls = choice
+        .delta
+        .tool_calls
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(position, call)| {
+            let (name, arguments) = call
+                .function
+                .map(|function| (function.name, function.arguments.unwrap_or_default()))
+                .unwrap_or_default();
+            ToolCallDelta {
+                index: call.index.unwrap_or(position),
+                id: call.id,
+                name,
+                arguments,
             }
-        }
-    }
+        })
+        .collect();
 
-    Ok(None)
+    Ok(SseEvent {
+        content,
+        tool_calls,
+        usage,
+    })
 }
 
 /// Convert SSE byte stream to text chunks.
@@ -547,8 +628,10 @@ fn sse_bytes_to_chunks(
     let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
 
     tokio::spawn(async move {
-        // Buffer for incomplete lines
-        let mut buffer = String::new();
+        // Buffer for incomplete lines. Kept as bytes so multi-byte characters
+        // split across network chunks are decoded only once the line is whole.
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut usage: Option<TokenUsage> = None;
 
         // Get response body as bytes stream
         match response.error_for_status_ref() {
@@ -564,30 +647,35 @@ fn sse_bytes_to_chunks(
         while let Some(item) = bytes_stream.next().await {
             match item {
                 Ok(bytes) => {
-                    // Convert bytes to string and process line by line
-                    let text = match String::from_utf8(bytes.to_vec()) {
-                        Ok(t) => t,
-                        Err(e) => {
-                            let _ = tx
-                                .send(Err(StreamError::InvalidSse(format!(
-                                    "Invalid UTF-8: {}",
-                                    e
-                                ))))
-                                .await;
-                            break;
-                        }
-                    };
-
-                    buffer.push_str(&text);
+                    buffer.extend_from_slice(&bytes);
 
                     // Process complete lines
-                    while let Some(pos) = buffer.find('\n') {
-                        let line = buffer.drain(..=pos).collect::<String>();
-                        buffer = buffer[pos + 1..].to_string();
+                    while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                        let line_bytes: Vec<u8> = buffer.drain(..=pos).collect();
+                        let line = match String::from_utf8(line_bytes) {
+                            Ok(line) => line,
+                            Err(e) => {
+                                let _ = tx
+                                    .send(Err(StreamError::InvalidSse(format!(
+                                        "Invalid UTF-8: {}",
+                                        e
+                                    ))))
+                                    .await;
+                                return;
+                            }
+                        };
 
-                        match parse_sse_line(&line) {
-                            Ok(Some(content)) => {
-                                let mut chunk = StreamChunk::delta(content);
+                        match parse_sse_event(&line) {
+                            Ok(event) => {
+                                if event.usage.is_some() {
+                                    usage = event.usage;
+                                }
+                                if event.content.is_none() && event.tool_calls.is_empty() {
+                                    continue;
+                                }
+                                let mut chunk =
+                                    StreamChunk::delta(event.content.unwrap_or_default());
+                                chunk.tool_calls = event.tool_calls;
                                 if count_tokens {
                                     chunk = chunk.with_token_estimate();
                                 }
@@ -595,7 +683,6 @@ fn sse_bytes_to_chunks(
                                     return; // Receiver dropped
                                 }
                             }
-                            Ok(None) => {}
                             Err(e) => {
                                 let _ = tx.send(Err(e)).await;
                                 return;
@@ -610,8 +697,10 @@ fn sse_bytes_to_chunks(
             }
         }
 
-        // Send final chunk
-        let _ = tx.send(Ok(StreamChunk::final_chunk())).await;
+        // Send final chunk (carrying usage when the server reported it)
+        let _ = tx
+            .send(Ok(StreamChunk::final_chunk().with_usage(usage)))
+            .await;
     });
 
     // Convert channel receiver to stream
@@ -724,6 +813,78 @@ fn parse_responses_response_body(
 }
 
 impl OpenAiCompatibleProvider {
+    /// POST a streaming chat completions request and relay the parsed SSE chunks.
+    fn stream_completions(
+        &self,
+        credential: String,
+        request: &impl Serialize,
+        count_tokens: bool,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let body = match serde_json::to_value(request) {
+            Ok(body) => body,
+            Err(e) => return stream::once(async move { Err(StreamError::Json(e)) }).boxed(),
+        };
+        let url = self.chat_completions_url();
+        let client = self.http_client();
+        let auth_header = self.auth_header.clone();
+
+        // Use a channel to bridge the async HTTP response to the stream
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+
+        tokio::spawn(async move {
+            // Build request with auth
+            let mut req_builder = client.post(&url).json(&body);
+
+            // Apply auth header
+            req_builder = match &auth_header {
+                AuthStyle::Bearer => {
+                    req_builder.header("Authorization", format!("Bearer {}", credential))
+                }
+                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
+                AuthStyle::Custom(header) => req_builder.header(header, &credential),
+            };
+
+            // Set accept header for streaming
+            req_builder = req_builder.header("Accept", "text/event-stream");
+
+            // Send request
+            let response = match req_builder.send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+            };
+
+            // Check status
+            if !response.status().is_success() {
+                let status = response.status();
+                let error = match response.text().await {
+                    Ok(e) => e,
+                    Err(_) => format!("HTTP error: {}", status),
+                };
+                let _ = tx
+                    .send(Err(StreamError::Provider(format!("{}: {}", status, error))))
+                    .await;
+                return;
+            }
+
+            // Convert to chunk stream and forward to channel
+            let mut chunk_stream = sse_bytes_to_chunks(response, count_tokens);
+            while let Some(chunk) = chunk_stream.next().await {
+                if tx.send(chunk).await.is_err() {
+                    break; // Receiver dropped
+                }
+            }
+        });
+
+        // Convert channel receiver to stream
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
+    }
+
     fn apply_auth_header(
         &self,
         req: reqwest::RequestBuilder,
@@ -898,7 +1059,10 @@ impl OpenAiCompatibleProvider {
         modified_messages
     }
 
-    fn parse_native_response(message: ResponseMessage) -> ProviderChatResponse {
+    fn parse_native_response(
+        message: ResponseMessage,
+        usage: Option<OpenAiUsage>,
+    ) -> ProviderChatResponse {
         let text = message.effective_content_optional();
         let tool_calls = message
             .tool_calls
@@ -926,7 +1090,11 @@ impl OpenAiCompatibleProvider {
             })
             .collect::<Vec<_>>();
 
-        ProviderChatResponse { text, tool_calls }
+        ProviderChatResponse {
+            text,
+            tool_calls,
+            usage: usage.map(TokenUsage::from),
+        }
     }
 
     fn is_native_tool_schema_unsupported(status: reqwest::StatusCode, error: &str) -> bool {
@@ -949,6 +1117,157 @@ impl OpenAiCompatibleProvider {
         .iter()
         .any(|hint| lower.contains(hint))
     }
+
+    fn is_response_format_unsupported(status: reqwest::StatusCode, error: &str) -> bool {
+        matches!(
+            status,
+            reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::UNPROCESSABLE_ENTITY
+        ) && {
+            let lower = error.to_lowercase();
+            lower.contains("response_format") || lower.contains("json_schema")
+        }
+    }
+
+    /// Single native chat completion call. A `response_format` is sent as an
+    /// OpenAI `json_schema` and also spelled out in the system prompt for
+    /// backends that ignore the field.
+    async fn chat_native(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} API key not set. Run `zeroclaw onboard` or set the appropriate env var.",
+                self.name
+            )
+        })?;
+
+        let tools = Self::convert_tool_specs(request.tools);
+        let formatted_messages;
+        let request = match request.response_format {
+            Some(format) => {
+                formatted_messages =
+                    messages_with_tool_instructions(request.messages, &format.instructions());
+                ProviderChatRequest {
+                    messages: &formatted_messages,
+                    ..request
+                }
+            }
+            None => request,
+        };
+        let effective_messages = if self.merge_system_into_user {
+            Self::flatten_system_messages(request.messages)
+        } else {
+            request.messages.to_vec()
+        };
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages_for_native(&effective_messages),
+            temperature,
+            stream: Some(false),
+            stream_options: None,
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+            response_format: request
+                .response_format
+                .map(|format| format.openai_payload()),
+        };
+
+        let url = self.chat_completions_url();
+        let response = match self
+            .apply_auth_header(
+                self.http_client().post(&url).json(&native_request),
+                credential,
+            )
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(chat_error) => {
+                if self.supports_responses_fallback {
+                    let sanitized = super::sanitize_api_error(&chat_error.to_string());
+                    return self
+                        .chat_via_responses(credential, &effective_messages, model)
+                        .await
+                        .map(|text| ProviderChatResponse {
+                            text: Some(text),
+                            tool_calls: vec![],
+                            usage: None,
+                        })
+                        .map_err(|responses_err| {
+                            anyhow::anyhow!(
+                                "{} native chat transport error: {sanitized} (responses fallback failed: {responses_err})",
+                                self.name
+                            )
+                        });
+                }
+
+                return Err(chat_error.into());
+            }
+        };
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = response.text().await?;
+            let sanitized = super::sanitize_api_error(&error);
+
+            if request.response_format.is_some()
+                && Self::is_response_format_unsupported(status, &sanitized)
+            {
+                // The schema is already in the system prompt; retry without the field.
+                let retry = ProviderChatRequest {
+                    response_format: None,
+                    ..request
+                };
+                return Box::pin(self.chat_native(retry, model, temperature)).await;
+            }
+
+            if Self::is_native_tool_schema_unsupported(status, &sanitized) {
+                let fallback_messages =
+                    Self::with_prompt_guided_tool_instructions(request.messages, request.tools);
+                let text = self
+                    .chat_with_history(&fallback_messages, model, temperature)
+                    .await?;
+                return Ok(ProviderChatResponse {
+                    text: Some(text),
+                    tool_calls: vec![],
+                    usage: None,
+                });
+            }
+
+            if status == reqwest::StatusCode::NOT_FOUND && self.supports_responses_fallback {
+                return self
+                    .chat_via_responses(credential, &effective_messages, model)
+                    .await
+                    .map(|text| ProviderChatResponse {
+                        text: Some(text),
+                        tool_calls: vec![],
+                        usage: None,
+                    })
+                    .map_err(|responses_err| {
+                        anyhow::anyhow!(
+                            "{} API error ({status}): {sanitized} (chat completions unavailable; responses fallback failed: {responses_err})",
+                            self.name
+                        )
+                    });
+            }
+
+            anyhow::bail!("{} API error ({status}): {sanitized}", self.name);
+        }
+
+        let native_response: ApiChatResponse = response.json().await?;
+        let usage = native_response.usage;
+        let message = native_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message)
+            .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
+
+        Ok(Self::parse_native_response(message, usage))
+    }
 }
 
 #[async_trait]
@@ -1003,6 +1322,7 @@ impl Provider for OpenAiCompatibleProvider {
             messages,
             temperature,
             stream: Some(false),
+            stream_options: None,
             tools: None,
             tool_choice: None,
         };
@@ -1121,6 +1441,7 @@ impl Provider for OpenAiCompatibleProvider {
             messages: api_messages,
             temperature,
             stream: Some(false),
+            stream_options: None,
             tools: None,
             tool_choice: None,
         };
@@ -1227,6 +1548,7 @@ impl Provider for OpenAiCompatibleProvider {
             messages: api_messages,
             temperature,
             stream: Some(false),
+            stream_options: None,
             tools: if tools.is_empty() {
                 None
             } else {
@@ -1255,6 +1577,7 @@ impl Provider for OpenAiCompatibleProvider {
                 return Ok(ProviderChatResponse {
                     text: Some(text),
                     tool_calls: vec![],
+                    usage: None,
                 });
             }
         };
@@ -1265,6 +1588,7 @@ impl Provider for OpenAiCompatibleProvider {
 
         let body = response.text().await?;
         let chat_response = parse_chat_response_body(&self.name, &body)?;
+        let usage = chat_response.usage.map(TokenUsage::from);
         let choice = chat_response
             .choices
             .into_iter()
@@ -1289,7 +1613,11 @@ impl Provider for OpenAiCompatibleProvider {
             })
             .collect::<Vec<_>>();
 
-        Ok(ProviderChatResponse { text, tool_calls })
+        Ok(ProviderChatResponse {
+            text,
+            tool_calls,
+            usage,
+        })
     }
 
     async fn chat(
@@ -1298,105 +1626,64 @@ impl Provider for OpenAiCompatibleProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ProviderChatResponse> {
-        let credential = self.credential.as_ref().ok_or_else(|| {
-            anyhow::anyhow!(
-                "{} API key not set. Run `zeroclaw onboard` or set the appropriate env var.",
-                self.name
-            )
-        })?;
-
-        let tools = Self::convert_tool_specs(request.tools);
-        let effective_messages = if self.merge_system_into_user {
-            Self::flatten_system_messages(request.messages)
-        } else {
-            request.messages.to_vec()
+        let Some(format) = request.response_format else {
+            return self.chat_native(request, model, temperature).await;
         };
-        let native_request = NativeChatRequest {
-            model: model.to_string(),
-            messages: Self::convert_messages_for_native(&effective_messages),
-            temperature,
-            stream: Some(false),
-            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
-            tools,
-        };
-
-        let url = self.chat_completions_url();
-        let response = match self
-            .apply_auth_header(
-                self.http_client().post(&url).json(&native_request),
-                credential,
-            )
-            .send()
-            .await
-        {
-            Ok(response) => response,
-            Err(chat_error) => {
-                if self.supports_responses_fallback {
-                    let sanitized = super::sanitize_api_error(&chat_error.to_string());
-                    return self
-                        .chat_via_responses(credential, &effective_messages, model)
-                        .await
-                        .map(|text| ProviderChatResponse {
-                            text: Some(text),
-                            tool_calls: vec![],
-                        })
-                        .map_err(|responses_err| {
-                            anyhow::anyhow!(
-                                "{} native chat transport error: {sanitized} (responses fallback failed: {responses_err})",
-                                self.name
-                            )
-                        });
-                }
 
-                return Err(chat_error.into());
+        // Many compatible backends accept `response_format` but do not
+        // enforce it, so validate the reply and re-prompt on violations.
+        // Usage is summed across attempts so rejected replies are still billed.
+        let mut messages = request.messages.to_vec();
+        let mut attempt = 0;
+        let mut usage: Option<TokenUsage> = None;
+        loop {
+            let mut response = self
+                .chat_native(
+                    ProviderChatRequest {
+                        messages: &messages,
+                        ..request
+                    },
+                    model,
+                    temperature,
+                )
+                .await?;
+            if let Some(attempt_usage) = response.usage {
+                usage
+                    .get_or_insert_with(TokenUsage::default)
+                    .accumulate(&attempt_usage);
             }
-        };
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error = response.text().await?;
-            let sanitized = super::sanitize_api_error(&error);
-
-            if Self::is_native_tool_schema_unsupported(status, &sanitized) {
-                let fallback_messages =
-                    Self::with_prompt_guided_tool_instructions(request.messages, request.tools);
-                let text = self
-                    .chat_with_history(&fallback_messages, model, temperature)
-                    .await?;
-                return Ok(ProviderChatResponse {
-                    text: Some(text),
-                    tool_calls: vec![],
-                });
+            response.usage = usage;
+            if response.has_tool_calls() {
+                return Ok(response);
             }
 
-            if status == reqwest::StatusCode::NOT_FOUND && self.supports_responses_fallback {
-                return self
-                    .chat_via_responses(credential, &effective_messages, model)
-                    .await
-                    .map(|text| ProviderChatResponse {
-                        text: Some(text),
-                        tool_calls: vec![],
-                    })
-                    .map_err(|responses_err| {
-                        anyhow::anyhow!(
-                            "{} API error ({status}): {sanitized} (chat completions unavailable; responses fallback failed: {responses_err})",
-                            self.name
-                        )
-                    });
+            let text = response.text_or_empty().to_string();
+            match format.validate(&text) {
+                Ok(value) => {
+                    response.text = Some(value.to_string());
+                    return Ok(response);
+                }
+                Err(error) if attempt < MAX_STRUCTURED_OUTPUT_RETRIES => {
+                    attempt += 1;
+                    tracing::warn!(
+                        provider = %self.name,
+                        attempt,
+                        "Response did not match schema '{}': {error}",
+                        format.name
+                    );
+                    messages.push(ChatMessage::assistant(text));
+                    messages.push(ChatMessage::user(format.correction_prompt(&error)));
+                }
+                Err(error) => {
+                    anyhow::bail!(
+                        "{} response did not match schema '{}' after {} attempts: {error}",
+                        self.name,
+                        format.name,
+                        attempt + 1
+                    );
+                }
             }
-
-            anyhow::bail!("{} API error ({status}): {sanitized}", self.name);
         }
-
-        let native_response: ApiChatResponse = response.json().await?;
-        let message = native_response
-            .choices
-            .into_iter()
-            .next()
-            .map(|choice| choice.message)
-            .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
-
-        Ok(Self::parse_native_response(message))
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -1446,69 +1733,66 @@ impl Provider for OpenAiCompatibleProvider {
             messages,
             temperature,
             stream: Some(options.enabled),
+            stream_options: options.enabled.then_some(StreamUsageOptions {
+                include_usage: true,
+            }),
             tools: None,
             tool_choice: None,
         };
 
-        let url = self.chat_completions_url();
-        let client = self.http_client();
-        let auth_header = self.auth_header.clone();
-
-        // Use a channel to bridge the async HTTP response to the stream
-        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-        tokio::spawn(async move {
-            // Build request with auth
-            let mut req_builder = client.post(&url).json(&request);
-
-            // Apply auth header
-            req_builder = match &auth_header {
-                AuthStyle::Bearer => {
-                    req_builder.header("Authorization", format!("Bearer {}", credential))
-                }
-                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
-                AuthStyle::Custom(header) => req_builder.header(header, &credential),
-            };
-
-            // Set accept header for streaming
-            req_builder = req_builder.header("Accept", "text/event-stream");
+        self.stream_completions(credential, &request, options.count_tokens)
+    }
 
-            // Send request
-            let response = match req_builder.send().await {
-                Ok(r) => r,
-                Err(e) => {
-                    let _ = tx.send(Err(StreamError::Http(e))).await;
-                    return;
-                }
-            };
+    fn supports_streaming_tool_calls(&self) -> bool {
+        true
+    }
 
-            // Check status
-            if !response.status().is_success() {
-                let status = response.status();
-                let error = match response.text().await {
-                    Ok(e) => e,
-                    Err(_) => format!("HTTP error: {}", status),
-                };
-                let _ = tx
-                    .send(Err(StreamError::Provider(format!("{}: {}", status, error))))
-                    .await;
-                return;
-            }
+    fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(credential) = self.credential.clone() else {
+            let provider_name = self.name.clone();
+            return stream::once(async move {
+                Err(StreamError::Provider(format!(
+                    "{} API key not set",
+                    provider_name
+                )))
+            })
+            .boxed();
+        };
 
-            // Convert to chunk stream and forward to channel
-            let mut chunk_stream = sse_bytes_to_chunks(response, options.count_tokens);
-            while let Some(chunk) = chunk_stream.next().await {
-                if tx.send(chunk).await.is_err() {
-                    break; // Receiver dropped
-                }
+        let formatted_messages = match request.response_format {
+            Some(format) => {
+                messages_with_tool_instructions(request.messages, &format.instructions())
             }
-        });
+            None => request.messages.to_vec(),
+        };
+        let effective_messages = if self.merge_system_into_user {
+            Self::flatten_system_messages(&formatted_messages)
+        } else {
+            formatted_messages
+        };
+        let tools = Self::convert_tool_specs(request.tools);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages_for_native(&effective_messages),
+            temperature,
+            stream: Some(true),
+            stream_options: Some(StreamUsageOptions {
+                include_usage: true,
+            }),
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+            response_format: request
+                .response_format
+                .map(|format| format.openai_payload()),
+        };
 
-        // Convert channel receiver to stream
-        stream::unfold(rx, |mut rx| async move {
-            rx.recv().await.map(|chunk| (chunk, rx))
-        })
-        .boxed()
+        self.stream_completions(credential, &native_request, options.count_tokens)
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -1587,6 +1871,7 @@ mod tests {
             ],
             temperature: 0.4,
             stream: Some(false),
+            stream_options: None,
             tools: None,
             tool_choice: None,
         };
@@ -2007,7 +2292,7 @@ mod tests {
             reasoning_content: None,
         };
 
-        let parsed = OpenAiCompatibleProvider::parse_native_response(message);
+        let parsed = OpenAiCompatibleProvider::parse_native_response(message, None);
         assert_eq!(parsed.tool_calls.len(), 1);
         assert_eq!(parsed.tool_calls[0].id, "call_123");
         assert_eq!(parsed.tool_calls[0].name, "shell");
@@ -2082,6 +2367,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn response_format_unsupported_detection_requires_client_error() {
+        assert!(OpenAiCompatibleProvider::is_response_format_unsupported(
+            reqwest::StatusCode::BAD_REQUEST,
+            "Invalid parameter: 'response_format' of type 'json_schema' is not supported"
+        ));
+        assert!(!OpenAiCompatibleProvider::is_response_format_unsupported(
+            reqwest::StatusCode::BAD_REQUEST,
+            "model not found"
+        ));
+        assert!(!OpenAiCompatibleProvider::is_response_format_unsupported(
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            "response_format failed"
+        ));
+    }
+
     #[test]
     fn prompt_guided_tool_fallback_injects_system_instruction() {
         let input = vec![ChatMessage::user("check status")];
@@ -2167,6 +2468,7 @@ mod tests {
             }],
             temperature: 0.7,
             stream: Some(false),
+            stream_options: None,
             tools: Some(tools),
             tool_choice: Some("auto".to_string()),
         };
@@ -2448,4 +2750,73 @@ mod tests {
         let result = parse_sse_line(line).unwrap();
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn parse_sse_event_extracts_trailing_usage_chunk() {
+        let line = r#"data: {"choices":[],"usage":{"prompt_tokens":50,"completion_tokens":12}}"#;
+        let event = parse_sse_event(line).unwrap();
+        assert!(event.content.is_none());
+        let usage = event.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(50));
+        assert_eq!(usage.output_tokens, Some(12));
+    }
+
+    #[test]
+    fn parse_sse_event_extracts_tool_call_fragments() {
+        let first = parse_sse_event(
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"shell","arguments":""}}]}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            first.tool_calls,
+            vec![ToolCallDelta {
+                index: 0,
+                id: Some("call_1".into()),
+                name: Some("shell".into()),
+                arguments: String::new(),
+            }]
+        );
+
+        let next = parse_sse_event(
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"command\""}}]}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(next.tool_calls[0].arguments, r#"{"command""#);
+        assert!(next.tool_calls[0].name.is_none());
+        assert!(next.content.is_none());
+    }
+
+    #[tokio::test]
+    async fn sse_bytes_to_chunks_handles_lines_split_across_reads() {
+        let parts: Vec<Result<Vec<u8>, std::io::Error>> = vec![
+            Ok(br#"data: {"choices":[{"delta":{"content":"Hel"#.to_vec()),
+            Ok(b"lo\"}}]}\n\ndata: {\"choices\":[{\"delta\":{\"content\":\"\xC3".to_vec()),
+            Ok(b"\xA9\"}}]}\n\ndata: {\"choices\":[{\"delta\":{\"content\":\"!\"}}]}\n".to_vec()),
+            Ok(b"data: [DONE]\n".to_vec()),
+        ];
+        let body = reqwest::Body::wrap_stream(futures_util::stream::iter(parts));
+        let response = reqwest::Response::from(axum::http::Response::new(body));
+
+        let chunks: Vec<StreamChunk> = sse_bytes_to_chunks(response, false)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().map(|chunk| chunk.delta.as_str()).collect();
+        assert_eq!(text, "Hello\u{e9}!");
+        assert!(chunks.last().unwrap().is_final);
+    }
+
+    #[test]
+    fn chat_response_usage_is_surfaced() {
+        let json = r#"{"choices":[{"message":{"content":"ok"}}],"usage":{"prompt_tokens":9,"completion_tokens":3,"prompt_tokens_details":{"cached_tokens":4}}}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let usage = resp.usage;
+        let message = resp.choices.into_iter().next().unwrap().message;
+        let parsed = OpenAiCompatibleProvider::parse_native_response(message, usage);
+        let usage = parsed.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(9));
+        assert_eq!(usage.output_tokens, Some(3));
+        assert_eq!(usage.cached_input_tokens, Some(4));
+    }
 }
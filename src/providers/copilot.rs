@@ -12,9 +12,10 @@
 //! third-party integrations simultaneously.
 
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ToolCall as ProviderToolCall,
+    messages_with_tool_instructions, ChatMessage, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, TokenUsage, ToolCall as ProviderToolCall,
 };
+use crate::providers::OpenAiUsage;
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -134,6 +135,8 @@ struct NativeFunctionCall {
 #[derive(Debug, Deserialize)]
 struct ApiChatResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<OpenAiUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -340,6 +343,7 @@ impl CopilotProvider {
         }
 
         let api_response: ApiChatResponse = response.json().await?;
+        let usage = api_response.usage.map(TokenUsage::from);
         let choice = api_response
             .choices
             .into_iter()
@@ -363,6 +367,7 @@ impl CopilotProvider {
         Ok(ProviderChatResponse {
             text: choice.message.content,
             tool_calls,
+            usage,
         })
     }
 
@@ -629,8 +634,14 @@ impl Provider for CopilotProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ProviderChatResponse> {
+        let messages = match request.response_format {
+            Some(format) => {
+                messages_with_tool_instructions(request.messages, &format.instructions())
+            }
+            None => request.messages.to_vec(),
+        };
         self.send_chat_request(
-            Self::convert_messages(request.messages),
+            Self::convert_messages(&messages),
             request.tools,
             model,
             temperature,
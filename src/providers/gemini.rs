@@ -3,11 +3,19 @@
 //! - Gemini CLI OAuth tokens (reuse existing ~/.gemini/ authentication)
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
-use crate::providers::traits::{ChatMessage, Provider};
+use crate::providers::structured::to_openapi_subset;
+use crate::providers::traits::{
+    messages_with_tool_instructions, ChatMessage, ChatRequest, ChatResponse, Provider,
+    ProviderCapabilities, ResponseFormat, StreamChunk, StreamError, StreamOptions, StreamResult,
+    TokenUsage, ToolCall, ToolCallDelta, ToolsPayload,
+};
+use crate::tools::ToolSpec;
 use async_trait::async_trait;
 use directories::UserDirs;
+use futures_util::{stream, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Gemini provider supporting multiple authentication methods.
@@ -65,6 +73,8 @@ struct GenerateContentRequest {
     system_instruction: Option<Content>,
     #[serde(rename = "generationConfig")]
     generation_config: GenerationConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<GeminiTools>>,
 }
 
 /// Request envelope for the internal cloudcode-pa API.
@@ -87,6 +97,15 @@ struct InternalGenerateContentRequest {
     system_instruction: Option<Content>,
     #[serde(rename = "generationConfig")]
     generation_config: GenerationConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<GeminiTools>>,
+}
+
+/// Native function declarations for a request.
+#[derive(Debug, Serialize, Clone)]
+struct GeminiTools {
+    #[serde(rename = "functionDeclarations")]
+    function_declarations: Vec<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -96,9 +115,36 @@ struct Content {
     parts: Vec<Part>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, Default)]
 struct Part {
-    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(rename = "functionCall", skip_serializing_if = "Option::is_none")]
+    function_call: Option<FunctionCall>,
+    #[serde(rename = "functionResponse", skip_serializing_if = "Option::is_none")]
+    function_response: Option<FunctionResponse>,
+}
+
+impl Part {
+    fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct FunctionCall {
+    name: String,
+    #[serde(default)]
+    args: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct FunctionResponse {
+    name: String,
+    response: serde_json::Value,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -106,29 +152,61 @@ struct GenerationConfig {
     temperature: f64,
     #[serde(rename = "maxOutputTokens")]
     max_output_tokens: u32,
+    #[serde(rename = "responseMimeType", skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(rename = "responseSchema", skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 struct GenerateContentResponse {
     candidates: Option<Vec<Candidate>>,
     error: Option<ApiError>,
+    #[serde(default, rename = "usageMetadata")]
+    usage_metadata: Option<UsageMetadata>,
     #[serde(default)]
     response: Option<Box<GenerateContentResponse>>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: Option<u64>,
+    #[serde(default)]
+    candidates_token_count: Option<u64>,
+    #[serde(default)]
+    cached_content_token_count: Option<u64>,
+}
+
+impl From<UsageMetadata> for TokenUsage {
+    fn from(usage: UsageMetadata) -> Self {
+        Self {
+            input_tokens: usage.prompt_token_count,
+            output_tokens: usage.candidates_token_count,
+            cached_input_tokens: usage.cached_content_token_count,
+            cache_write_input_tokens: None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct Candidate {
+    #[serde(default)]
     content: CandidateContent,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct CandidateContent {
+    #[serde(default)]
     parts: Vec<ResponsePart>,
 }
 
 #[derive(Debug, Deserialize)]
 struct ResponsePart {
     text: Option<String>,
+    #[serde(default, rename = "functionCall")]
+    function_call: Option<FunctionCall>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -147,6 +225,24 @@ impl GenerateContentResponse {
             other => other,
         }
     }
+
+    /// Parts of the first candidate (empty when the response has none).
+    fn into_parts(self) -> Vec<ResponsePart> {
+        self.candidates
+            .and_then(|c| c.into_iter().next())
+            .map(|c| c.content.parts)
+            .unwrap_or_default()
+    }
+}
+
+/// Gemini function calls carry no id; generate one so tool results can be
+/// matched back to the call in history.
+fn tool_call_from_function_call(call: FunctionCall) -> ToolCall {
+    ToolCall {
+        id: format!("call_{}", uuid::Uuid::new_v4().simple()),
+        name: call.name,
+        arguments: call.args.to_string(),
+    }
 }
 
 // ══════════════════════════════════════════════════════════════════════════════
@@ -282,23 +378,41 @@ impl GeminiProvider {
     /// "400 Bad Request: API key not valid" errors.
     /// See: https://github.com/google-gemini/gemini-cli/issues/19200
     fn build_generate_content_url(model: &str, auth: &GeminiAuth) -> String {
-        match auth {
+        Self::build_method_url(model, auth, "generateContent", None)
+    }
+
+    /// Server-sent events variant of [`Self::build_generate_content_url`].
+    fn build_stream_generate_content_url(model: &str, auth: &GeminiAuth) -> String {
+        Self::build_method_url(model, auth, "streamGenerateContent", Some("alt=sse"))
+    }
+
+    fn build_method_url(
+        model: &str,
+        auth: &GeminiAuth,
+        method: &str,
+        query: Option<&str>,
+    ) -> String {
+        let mut params: Vec<String> = query.map(ToString::to_string).into_iter().collect();
+        let base_url = match auth {
             GeminiAuth::OAuthToken(_) => {
                 // OAuth tokens from Gemini CLI are scoped for the internal
                 // Code Assist API. The model is passed in the request body,
                 // not the URL path.
-                format!("{CLOUDCODE_PA_ENDPOINT}:generateContent")
+                format!("{CLOUDCODE_PA_ENDPOINT}:{method}")
             }
             _ => {
                 let model_name = Self::format_model_name(model);
-                let base_url = format!("{PUBLIC_API_ENDPOINT}/{model_name}:generateContent");
-
                 if auth.is_api_key() {
-                    format!("{base_url}?key={}", auth.credential())
-                } else {
-                    base_url
+                    params.push(format!("key={}", auth.credential()));
                 }
+                format!("{PUBLIC_API_ENDPOINT}/{model_name}:{method}")
             }
+        };
+
+        if params.is_empty() {
+            base_url
+        } else {
+            format!("{base_url}?{}", params.join("&"))
         }
     }
 
@@ -326,6 +440,7 @@ impl GeminiProvider {
                         contents: request.contents.clone(),
                         system_instruction: request.system_instruction.clone(),
                         generation_config: request.generation_config.clone(),
+                        tools: request.tools.clone(),
                     },
                 };
                 self.http_client()
@@ -355,14 +470,8 @@ impl GeminiProvider {
 }
 
 impl GeminiProvider {
-    async fn send_generate_content(
-        &self,
-        contents: Vec<Content>,
-        system_instruction: Option<Content>,
-        model: &str,
-        temperature: f64,
-    ) -> anyhow::Result<String> {
-        let auth = self.auth.as_ref().ok_or_else(|| {
+    fn require_auth(&self) -> anyhow::Result<&GeminiAuth> {
+        self.auth.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
                 "Gemini API key not found. Options:\n\
                  1. Set GEMINI_API_KEY env var\n\
@@ -370,21 +479,216 @@ impl GeminiProvider {
                  3. Get an API key from https://aistudio.google.com/app/apikey\n\
                  4. Run `zeroclaw onboard` to configure"
             )
-        })?;
+        })
+    }
 
-        let request = GenerateContentRequest {
+    fn function_declarations(tools: &[ToolSpec]) -> Vec<serde_json::Value> {
+        tools
+            .iter()
+            .map(|tool| {
+                serde_json::json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "parameters": to_openapi_subset(&tool.parameters),
+                })
+            })
+            .collect()
+    }
+
+    /// Map an assistant message carrying native tool calls (the JSON written
+    /// by the agent loop) to `functionCall` parts, remembering each call's
+    /// name so later tool results can reference it.
+    fn parse_assistant_tool_calls(
+        content: &str,
+        call_names: &mut HashMap<String, String>,
+    ) -> Option<Vec<Part>> {
+        let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
+        let calls = value.get("tool_calls")?.as_array()?;
+
+        let mut parts = Vec::new();
+        if let Some(text) = value
+            .get("content")
+            .and_then(serde_json::Value::as_str)
+            .filter(|text| !text.trim().is_empty())
+        {
+            parts.push(Part::text(text));
+        }
+        for call in calls {
+            let Some(name) = call.get("name").and_then(serde_json::Value::as_str) else {
+                continue;
+            };
+            if let Some(id) = call.get("id").and_then(serde_json::Value::as_str) {
+                call_names.insert(id.to_string(), name.to_string());
+            }
+            let args = call
+                .get("arguments")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|raw| serde_json::from_str(raw).ok())
+                .unwrap_or_else(|| serde_json::json!({}));
+            parts.push(Part {
+                function_call: Some(FunctionCall {
+                    name: name.to_string(),
+                    args,
+                }),
+                ..Part::default()
+            });
+        }
+        Some(parts)
+    }
+
+    fn parse_tool_result(content: &str, call_names: &HashMap<String, String>) -> Option<Part> {
+        let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
+        let call_id = value.get("tool_call_id")?.as_str()?;
+        let result = value
+            .get("content")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("");
+        Some(Part {
+            function_response: Some(FunctionResponse {
+                name: call_names
+                    .get(call_id)
+                    .cloned()
+                    .unwrap_or_else(|| "tool".to_string()),
+                response: serde_json::json!({ "content": result }),
+            }),
+            ..Part::default()
+        })
+    }
+
+    fn convert_messages(messages: &[ChatMessage]) -> (Option<Content>, Vec<Content>) {
+        let mut system_parts: Vec<&str> = Vec::new();
+        let mut contents: Vec<Content> = Vec::new();
+        let mut call_names: HashMap<String, String> = HashMap::new();
+
+        for msg in messages {
+            match msg.role.as_str() {
+                "system" => {
+                    system_parts.push(&msg.content);
+                }
+                "user" => {
+                    contents.push(Content {
+                        role: Some("user".to_string()),
+                        parts: vec![Part::text(msg.content.clone())],
+                    });
+                }
+                "assistant" => {
+                    // Gemini API uses "model" role instead of "assistant"
+                    let parts = Self::parse_assistant_tool_calls(&msg.content, &mut call_names)
+                        .unwrap_or_else(|| vec![Part::text(msg.content.clone())]);
+                    contents.push(Content {
+                        role: Some("model".to_string()),
+                        parts,
+                    });
+                }
+                "tool" => {
+                    let Some(part) = Self::parse_tool_result(&msg.content, &call_names) else {
+                        continue;
+                    };
+                    // All responses to one model turn go back in a single user turn.
+                    match contents.last_mut() {
+                        Some(last)
+                            if last.role.as_deref() == Some("user")
+                                && last.parts.iter().all(|p| p.function_response.is_some()) =>
+                        {
+                            last.parts.push(part);
+                        }
+                        _ => contents.push(Content {
+                            role: Some("user".to_string()),
+                            parts: vec![part],
+                        }),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let system_instruction = if system_parts.is_empty() {
+            None
+        } else {
+            Some(Content {
+                role: None,
+                parts: vec![Part::text(system_parts.join("\n\n"))],
+            })
+        };
+        (system_instruction, contents)
+    }
+
+    fn build_request(
+        messages: &[ChatMessage],
+        tools: Option<&[ToolSpec]>,
+        temperature: f64,
+        response_format: Option<&ResponseFormat>,
+    ) -> GenerateContentRequest {
+        let tools = tools.filter(|tools| !tools.is_empty());
+        // Gemini rejects JSON mode combined with function calling, so with
+        // tools the schema is only given as instructions.
+        let (messages, response_format) = match (tools, response_format) {
+            (Some(_), Some(format)) => (
+                messages_with_tool_instructions(messages, &format.instructions()),
+                None,
+            ),
+            _ => (messages.to_vec(), response_format),
+        };
+        let (system_instruction, contents) = Self::convert_messages(&messages);
+
+        GenerateContentRequest {
             contents,
             system_instruction,
             generation_config: GenerationConfig {
                 temperature,
                 max_output_tokens: 8192,
+                response_mime_type: response_format.map(|_| "application/json".to_string()),
+                response_schema: response_format.map(ResponseFormat::gemini_schema),
             },
-        };
+            tools: tools.map(|tools| {
+                vec![GeminiTools {
+                    function_declarations: Self::function_declarations(tools),
+                }]
+            }),
+        }
+    }
+
+    fn parse_response(result: GenerateContentResponse) -> anyhow::Result<ChatResponse> {
+        if let Some(err) = &result.error {
+            anyhow::bail!("Gemini API error: {}", err.message);
+        }
+        let mut result = result.into_effective_response();
+        if let Some(err) = result.error {
+            anyhow::bail!("Gemini API error: {}", err.message);
+        }
+
+        let usage = result.usage_metadata.take().map(TokenUsage::from);
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        for part in result.into_parts() {
+            if let Some(part_text) = part.text {
+                text.push_str(&part_text);
+            }
+            if let Some(call) = part.function_call {
+                tool_calls.push(tool_call_from_function_call(call));
+            }
+        }
+        if text.is_empty() && tool_calls.is_empty() {
+            anyhow::bail!("No response from Gemini");
+        }
+
+        Ok(ChatResponse {
+            text: (!text.is_empty()).then_some(text),
+            tool_calls,
+            usage,
+        })
+    }
 
+    async fn send_generate_content(
+        &self,
+        request: &GenerateContentRequest,
+        model: &str,
+    ) -> anyhow::Result<ChatResponse> {
+        let auth = self.require_auth()?;
         let url = Self::build_generate_content_url(model, auth);
 
         let response = self
-            .build_generate_content_request(auth, &url, &request, model)
+            .build_generate_content_request(auth, &url, request, model)
             .send()
             .await?;
 
@@ -394,26 +698,63 @@ impl GeminiProvider {
             anyhow::bail!("Gemini API error ({status}): {error_text}");
         }
 
-        let result: GenerateContentResponse = response.json().await?;
-        if let Some(err) = &result.error {
-            anyhow::bail!("Gemini API error: {}", err.message);
+        Self::parse_response(response.json().await?)
+    }
+}
+
+/// Converts one streamed `GenerateContentResponse` into a chunk. Gemini sends
+/// each function call whole, so every call becomes a single complete delta.
+fn stream_event_chunk(
+    data: &str,
+    next_tool_index: &mut usize,
+    usage: &mut Option<TokenUsage>,
+) -> StreamResult<Option<StreamChunk>> {
+    let event: GenerateContentResponse = serde_json::from_str(data).map_err(StreamError::Json)?;
+    let mut event = match event.error {
+        Some(err) => return Err(StreamError::Provider(format!("Gemini: {}", err.message))),
+        None => event.into_effective_response(),
+    };
+    if let Some(err) = event.error {
+        return Err(StreamError::Provider(format!("Gemini: {}", err.message)));
+    }
+    if let Some(metadata) = event.usage_metadata.take() {
+        *usage = Some(TokenUsage::from(metadata));
+    }
+
+    let mut chunk = StreamChunk::delta(String::new());
+    for part in event.into_parts() {
+        if let Some(text) = part.text {
+            chunk.delta.push_str(&text);
         }
-        let result = result.into_effective_response();
-        if let Some(err) = result.error {
-            anyhow::bail!("Gemini API error: {}", err.message);
+        if let Some(call) = part.function_call {
+            let call = tool_call_from_function_call(call);
+            chunk.tool_calls.push(ToolCallDelta {
+                index: *next_tool_index,
+                id: Some(call.id),
+                name: Some(call.name),
+                arguments: call.arguments,
+            });
+            *next_tool_index += 1;
         }
-
-        result
-            .candidates
-            .and_then(|c| c.into_iter().next())
-            .and_then(|c| c.content.parts.into_iter().next())
-            .and_then(|p| p.text)
-            .ok_or_else(|| anyhow::anyhow!("No response from Gemini"))
     }
+    Ok((!chunk.delta.is_empty() || !chunk.tool_calls.is_empty()).then_some(chunk))
 }
 
 #[async_trait]
 impl Provider for GeminiProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: false,
+        }
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        ToolsPayload::Gemini {
+            function_declarations: Self::function_declarations(tools),
+        }
+    }
+
     async fn chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -421,22 +762,16 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let system_instruction = system_prompt.map(|sys| Content {
-            role: None,
-            parts: vec![Part {
-                text: sys.to_string(),
-            }],
-        });
-
-        let contents = vec![Content {
-            role: Some("user".to_string()),
-            parts: vec![Part {
-                text: message.to_string(),
-            }],
-        }];
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
 
-        self.send_generate_content(contents, system_instruction, model, temperature)
+        let request = Self::build_request(&messages, None, temperature, None);
+        self.send_generate_content(&request, model)
             .await
+            .map(|response| response.text.unwrap_or_default())
     }
 
     async fn chat_with_history(
@@ -445,48 +780,122 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
+        let request = Self::build_request(messages, None, temperature, None);
+        self.send_generate_content(&request, model)
+            .await
+            .map(|response| response.text.unwrap_or_default())
+    }
 
-        for msg in messages {
-            match msg.role.as_str() {
-                "system" => {
-                    system_parts.push(&msg.content);
-                }
-                "user" => {
-                    contents.push(Content {
-                        role: Some("user".to_string()),
-                        parts: vec![Part {
-                            text: msg.content.clone(),
-                        }],
-                    });
-                }
-                "assistant" => {
-                    // Gemini API uses "model" role instead of "assistant"
-                    contents.push(Content {
-                        role: Some("model".to_string()),
-                        parts: vec![Part {
-                            text: msg.content.clone(),
-                        }],
-                    });
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let body = Self::build_request(
+            request.messages,
+            request.tools,
+            temperature,
+            request.response_format,
+        );
+        self.send_generate_content(&body, model).await
+    }
+
+    fn supports_streaming_tool_calls(&self) -> bool {
+        true
+    }
+
+    fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let auth = match self.require_auth() {
+            Ok(auth) => auth,
+            Err(e) => {
+                let message = e.to_string();
+                return stream::once(async move { Err(StreamError::Provider(message)) }).boxed();
+            }
+        };
+        let body = Self::build_request(
+            request.messages,
+            request.tools,
+            temperature,
+            request.response_format,
+        );
+        let url = Self::build_stream_generate_content_url(model, auth);
+        let req = self.build_generate_content_request(auth, &url, &body, model);
+        let count_tokens = options.count_tokens;
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+        tokio::spawn(async move {
+            let response = match req.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
                 }
-                _ => {}
+            };
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                let _ = tx
+                    .send(Err(StreamError::Provider(format!(
+                        "Gemini API error ({status}): {error_text}"
+                    ))))
+                    .await;
+                return;
             }
-        }
 
-        let system_instruction = if system_parts.is_empty() {
-            None
-        } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part {
-                    text: system_parts.join("\n\n"),
-                }],
-            })
-        };
+            let mut usage = None;
+            let mut next_tool_index = 0;
+            let mut buffer: Vec<u8> = Vec::new();
+            let mut bytes_stream = response.bytes_stream();
+            while let Some(item) = bytes_stream.next().await {
+                let bytes = match item {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        let _ = tx.send(Err(StreamError::Http(e))).await;
+                        return;
+                    }
+                };
+                buffer.extend_from_slice(&bytes);
+                while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=pos).collect();
+                    let line = String::from_utf8_lossy(&line);
+                    let Some(data) = line.trim().strip_prefix("data:") else {
+                        continue;
+                    };
+                    match stream_event_chunk(data.trim(), &mut next_tool_index, &mut usage) {
+                        Ok(Some(chunk)) => {
+                            let chunk = if count_tokens {
+                                chunk.with_token_estimate()
+                            } else {
+                                chunk
+                            };
+                            if tx.send(Ok(chunk)).await.is_err() {
+                                return;
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            let _ = tx.send(Err(e)).await;
+                            return;
+                        }
+                    }
+                }
+            }
+            let _ = tx
+                .send(Ok(StreamChunk::final_chunk().with_usage(usage)))
+                .await;
+        });
 
-        self.send_generate_content(contents, system_instruction, model, temperature)
-            .await
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -640,15 +1049,16 @@ mod tests {
         let body = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".into()),
-                parts: vec![Part {
-                    text: "hello".into(),
-                }],
+                parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
+            tools: None,
         };
 
         let request = provider
@@ -675,15 +1085,16 @@ mod tests {
         let body = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".into()),
-                parts: vec![Part {
-                    text: "hello".into(),
-                }],
+                parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
+            tools: None,
         };
 
         let request = provider
@@ -713,15 +1124,16 @@ mod tests {
         let body = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".into()),
-                parts: vec![Part {
-                    text: "hello".into(),
-                }],
+                parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
+            tools: None,
         };
 
         let request = provider
@@ -737,20 +1149,19 @@ mod tests {
         let request = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".to_string()),
-                parts: vec![Part {
-                    text: "Hello".to_string(),
-                }],
+                parts: vec![Part::text("Hello")],
             }],
             system_instruction: Some(Content {
                 role: None,
-                parts: vec![Part {
-                    text: "You are helpful".to_string(),
-                }],
+                parts: vec![Part::text("You are helpful")],
             }),
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
+            tools: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -771,15 +1182,16 @@ mod tests {
             request: InternalGenerateContentRequest {
                 contents: vec![Content {
                     role: Some("user".to_string()),
-                    parts: vec![Part {
-                        text: "Hello".to_string(),
-                    }],
+                    parts: vec![Part::text("Hello")],
                 }],
                 system_instruction: None,
                 generation_config: GenerationConfig {
                     temperature: 0.7,
                     max_output_tokens: 8192,
+                    response_mime_type: None,
+                    response_schema: None,
                 },
+                tools: None,
             },
         };
 
@@ -862,6 +1274,163 @@ mod tests {
         assert_eq!(text, Some("Hello from internal".to_string()));
     }
 
+    #[test]
+    fn usage_metadata_deserialization() {
+        let json = r#"{
+            "candidates": [{"content": {"parts": [{"text": "hi"}]}}],
+            "usageMetadata": {
+                "promptTokenCount": 200,
+                "candidatesTokenCount": 15,
+                "cachedContentTokenCount": 128,
+                "totalTokenCount": 215
+            }
+        }"#;
+
+        let response: GenerateContentResponse = serde_json::from_str(json).unwrap();
+        let usage = TokenUsage::from(response.usage_metadata.unwrap());
+        assert_eq!(usage.input_tokens, Some(200));
+        assert_eq!(usage.output_tokens, Some(15));
+        assert_eq!(usage.cached_input_tokens, Some(128));
+    }
+
+    #[test]
+    fn stream_url_requests_sse_before_api_key() {
+        let auth = GeminiAuth::ExplicitKey("api-key-123".into());
+        let url = GeminiProvider::build_stream_generate_content_url("gemini-2.0-flash", &auth);
+        assert_eq!(
+            url,
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:streamGenerateContent?alt=sse&key=api-key-123"
+        );
+
+        let oauth = GeminiAuth::OAuthToken("ya29.token".into());
+        let url = GeminiProvider::build_stream_generate_content_url("gemini-2.0-flash", &oauth);
+        assert_eq!(
+            url,
+            "https://cloudcode-pa.googleapis.com/v1internal:streamGenerateContent?alt=sse"
+        );
+    }
+
+    #[test]
+    fn build_request_declares_tools_with_openapi_schemas() {
+        let tools = vec![ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {"command": {"type": "string"}},
+                "required": ["command"]
+            }),
+        }];
+        let request =
+            GeminiProvider::build_request(&[ChatMessage::user("hi")], Some(&tools), 0.2, None);
+
+        let json = serde_json::to_value(&request).unwrap();
+        let declaration = &json["tools"][0]["functionDeclarations"][0];
+        assert_eq!(declaration["name"], "shell");
+        assert_eq!(declaration["parameters"]["required"][0], "command");
+        assert!(declaration["parameters"]
+            .get("additionalProperties")
+            .is_none());
+    }
+
+    #[test]
+    fn convert_messages_maps_native_tool_history() {
+        let messages = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("list files"),
+            ChatMessage::assistant(
+                serde_json::json!({
+                    "content": "Checking.",
+                    "tool_calls": [
+                        {"id": "call_a", "name": "shell", "arguments": "{\"command\":\"ls\"}"},
+                        {"id": "call_b", "name": "file_read", "arguments": "{}"}
+                    ]
+                })
+                .to_string(),
+            ),
+            ChatMessage::tool(
+                serde_json::json!({"tool_call_id": "call_a", "content": "a.txt"}).to_string(),
+            ),
+            ChatMessage::tool(
+                serde_json::json!({"tool_call_id": "call_b", "content": "data"}).to_string(),
+            ),
+        ];
+
+        let (system, contents) = GeminiProvider::convert_messages(&messages);
+        assert!(system.is_some());
+        assert_eq!(contents.len(), 3);
+
+        let model_turn = serde_json::to_value(&contents[1]).unwrap();
+        assert_eq!(model_turn["role"], "model");
+        assert_eq!(model_turn["parts"][0]["text"], "Checking.");
+        assert_eq!(model_turn["parts"][1]["functionCall"]["name"], "shell");
+        assert_eq!(
+            model_turn["parts"][1]["functionCall"]["args"]["command"],
+            "ls"
+        );
+
+        let results = serde_json::to_value(&contents[2]).unwrap();
+        assert_eq!(results["role"], "user");
+        assert_eq!(results["parts"][0]["functionResponse"]["name"], "shell");
+        assert_eq!(
+            results["parts"][1]["functionResponse"]["response"]["content"],
+            "data"
+        );
+    }
+
+    #[test]
+    fn parse_response_returns_function_calls() {
+        let json = r#"{
+            "candidates": [{"content": {"parts": [
+                {"functionCall": {"name": "shell", "args": {"command": "ls"}}}
+            ]}}]
+        }"#;
+        let response = GeminiProvider::parse_response(serde_json::from_str(json).unwrap()).unwrap();
+        assert!(response.text.is_none());
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].name, "shell");
+        assert_eq!(response.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+    }
+
+    #[test]
+    fn stream_event_chunk_emits_text_and_whole_tool_calls() {
+        let mut next_index = 0;
+        let mut usage = None;
+
+        let text = stream_event_chunk(
+            r#"{"candidates": [{"content": {"parts": [{"text": "Let me "}]}}]}"#,
+            &mut next_index,
+            &mut usage,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(text.delta, "Let me ");
+        assert!(text.tool_calls.is_empty());
+
+        let call = stream_event_chunk(
+            r#"{"candidates": [{"content": {"parts": [{"functionCall": {"name": "shell", "args": {"command": "ls"}}}]}}],
+                "usageMetadata": {"promptTokenCount": 10, "candidatesTokenCount": 4}}"#,
+            &mut next_index,
+            &mut usage,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(call.tool_calls.len(), 1);
+        assert_eq!(call.tool_calls[0].index, 0);
+        assert_eq!(call.tool_calls[0].name.as_deref(), Some("shell"));
+        assert_eq!(next_index, 1);
+        assert_eq!(usage.unwrap().output_tokens, Some(4));
+
+        let empty = stream_event_chunk(
+            r#"{"candidates": [{"finishReason": "STOP"}]}"#,
+            &mut next_index,
+            &mut None,
+        )
+        .unwrap();
+        assert!(empty.is_none());
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = GeminiProvider { auth: None };
@@ -18,6 +18,7 @@
 
 pub mod anthropic;
 pub mod bedrock;
+pub mod cached;
 pub mod compatible;
 pub mod copilot;
 pub mod gemini;
@@ -27,18 +28,21 @@ pub mod openai_codex;
 pub mod openrouter;
 pub mod reliable;
 pub mod router;
+pub mod structured;
 pub mod traits;
 
+pub use structured::ResponseFormat;
 #[allow(unused_imports)]
 pub use traits::{
     ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    TokenUsage, ToolCall, ToolResultMessage,
 };
 
 use compatible::{AuthStyle, OpenAiCompatibleProvider};
 use reliable::ReliableProvider;
 use serde::Deserialize;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 const MAX_API_ERROR_CHARS: usize = 200;
 const MINIMAX_INTL_BASE_URL: &str = "https://api.minimax.io/v1";
@@ -782,6 +786,37 @@ pub async fn api_error(provider: &str, response: reqwest::Response) -> anyhow::E
     anyhow::anyhow!("{provider} API error ({status}): {sanitized}")
 }
 
+/// OpenAI-style `usage` object returned by OpenAI, OpenRouter and most
+/// OpenAI-compatible chat completion APIs.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct OpenAiUsage {
+    #[serde(default)]
+    prompt_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens: Option<u64>,
+    #[serde(default)]
+    prompt_tokens_details: Option<OpenAiPromptTokensDetails>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct OpenAiPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+impl From<OpenAiUsage> for TokenUsage {
+    fn from(usage: OpenAiUsage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+            cached_input_tokens: usage
+                .prompt_tokens_details
+                .and_then(|details| details.cached_tokens),
+            cache_write_input_tokens: None,
+        }
+    }
+}
+
 /// Resolve API key for a provider from config and environment variables.
 ///
 /// Resolution order:
@@ -1342,6 +1377,43 @@ pub fn create_routed_provider_with_options(
     )))
 }
 
+/// Wrap a provider with the `[memory]` response cache when it is enabled.
+///
+/// Returns the provider unchanged if the cache is disabled or cannot be opened.
+pub fn with_response_cache(
+    provider: Box<dyn Provider>,
+    config: &crate::config::Config,
+    observer: Option<Arc<dyn crate::observability::Observer>>,
+) -> Box<dyn Provider> {
+    let Some(cache) = crate::memory::create_response_cache(&config.memory, &config.workspace_dir)
+    else {
+        return provider;
+    };
+
+    let mut cached = cached::CachedProvider::new(provider, Arc::new(cache));
+    if config.memory.response_cache_semantic {
+        let embedder = crate::memory::create_embedder(
+            &config.memory,
+            &config.embedding_routes,
+            config.api_key.as_deref(),
+        );
+        if embedder.dimensions() > 0 {
+            #[allow(clippy::cast_possible_truncation)]
+            let threshold = config.memory.response_cache_similarity_threshold as f32;
+            cached = cached.with_semantic(embedder, threshold);
+        } else {
+            tracing::warn!(
+                "response_cache_semantic requires an embedding provider; using exact matching only"
+            );
+        }
+    }
+    if let Some(observer) = observer {
+        cached = cached.with_observer(observer);
+    }
+
+    Box::new(cached)
+}
+
 /// Information about a supported provider for display purposes.
 pub struct ProviderInfo {
     /// Canonical name used in config (e.g. `"openrouter"`)
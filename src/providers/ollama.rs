@@ -1,6 +1,6 @@
 use crate::multimodal;
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, ToolCall,
+    ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
 };
 use async_trait::async_trait;
 use reqwest::Client;
@@ -25,6 +25,9 @@ struct ChatRequest {
     think: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
+    /// JSON schema constraining the reply (structured outputs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -63,6 +66,24 @@ struct Options {
 #[derive(Debug, Deserialize)]
 struct ApiChatResponse {
     message: ResponseMessage,
+    /// Number of prompt tokens evaluated (Ollama's input count).
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    /// Number of tokens generated in the response.
+    #[serde(default)]
+    eval_count: Option<u64>,
+}
+
+impl ApiChatResponse {
+    fn token_usage(&self) -> Option<TokenUsage> {
+        let usage = TokenUsage {
+            input_tokens: self.prompt_eval_count,
+            output_tokens: self.eval_count,
+            cached_input_tokens: None,
+            cache_write_input_tokens: None,
+        };
+        (!usage.is_empty()).then_some(usage)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -170,6 +191,7 @@ impl OllamaProvider {
         model: &str,
         temperature: f64,
         tools: Option<&[serde_json::Value]>,
+        format: Option<&serde_json::Value>,
     ) -> ChatRequest {
         ChatRequest {
             model: model.to_string(),
@@ -178,6 +200,7 @@ impl OllamaProvider {
             options: Options { temperature },
             think: self.reasoning_enabled,
             tools: tools.map(|t| t.to_vec()),
+            format: format.cloned(),
         }
     }
 
@@ -317,8 +340,9 @@ impl OllamaProvider {
         temperature: f64,
         should_auth: bool,
         tools: Option<&[serde_json::Value]>,
+        format: Option<&serde_json::Value>,
     ) -> anyhow::Result<ApiChatResponse> {
-        let request = self.build_chat_request(messages, model, temperature, tools);
+        let request = self.build_chat_request(messages, model, temperature, tools, format);
 
         let url = format!("{}/api/chat", self.base_url);
 
@@ -378,6 +402,92 @@ impl OllamaProvider {
         Ok(chat_response)
     }
 
+    /// Chat with native tools and an optional `format` schema, keeping usage counters.
+    async fn chat_native(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+        format: Option<&serde_json::Value>,
+    ) -> anyhow::Result<ChatResponse> {
+        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+
+        let api_messages = self.convert_messages(messages);
+
+        // Tools arrive pre-formatted in OpenAI/Ollama-compatible JSON from
+        // tools_to_openai_format() in loop_.rs — pass them through directly.
+        let tools_opt = if tools.is_empty() { None } else { Some(tools) };
+
+        let response = self
+            .send_request(
+                api_messages,
+                &normalized_model,
+                temperature,
+                should_auth,
+                tools_opt,
+                format,
+            )
+            .await?;
+        let usage = response.token_usage();
+
+        // Native tool calls returned by the model.
+        if !response.message.tool_calls.is_empty() {
+            let tool_calls: Vec<ToolCall> = response
+                .message
+                .tool_calls
+                .iter()
+                .map(|tc| {
+                    let (name, args) = self.extract_tool_name_and_args(tc);
+                    ToolCall {
+                        id: tc
+                            .id
+                            .clone()
+                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                        name,
+                        arguments: serde_json::to_string(&args)
+                            .unwrap_or_else(|_| "{}".to_string()),
+                    }
+                })
+                .collect();
+            let text = if response.message.content.is_empty() {
+                None
+            } else {
+                Some(response.message.content)
+            };
+            return Ok(ChatResponse {
+                text,
+                tool_calls,
+                usage,
+            });
+        }
+
+        // Plain text response.
+        let content = response.message.content;
+        if content.is_empty() {
+            if let Some(thinking) = &response.message.thinking {
+                tracing::warn!(
+                    "Ollama returned empty content with only thinking: '{}'. Model may have stopped prematurely.",
+                    if thinking.len() > 100 { &thinking[..100] } else { thinking }
+                );
+                return Ok(ChatResponse {
+                    text: Some(format!(
+                        "I was thinking about this: {}... but I didn't complete my response. Could you try asking again?",
+                        if thinking.len() > 200 { &thinking[..200] } else { thinking }
+                    )),
+                    tool_calls: vec![],
+                    usage,
+                });
+            }
+            tracing::warn!("Ollama returned empty content with no tool calls");
+        }
+        Ok(ChatResponse {
+            text: Some(content),
+            tool_calls: vec![],
+            usage,
+        })
+    }
+
     /// Convert Ollama tool calls to the JSON format expected by parse_tool_calls in loop_.rs
     ///
     /// Handles quirky model behavior where tool calls are wrapped:
@@ -490,7 +600,14 @@ impl Provider for OllamaProvider {
         });
 
         let response = self
-            .send_request(messages, &normalized_model, temperature, should_auth, None)
+            .send_request(
+                messages,
+                &normalized_model,
+                temperature,
+                should_auth,
+                None,
+                None,
+            )
             .await?;
 
         // If model returned tool calls, format them for loop_.rs's parse_tool_calls
@@ -540,6 +657,7 @@ impl Provider for OllamaProvider {
                 temperature,
                 should_auth,
                 None,
+                None,
             )
             .await?;
 
@@ -582,73 +700,8 @@ impl Provider for OllamaProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
-
-        let api_messages = self.convert_messages(messages);
-
-        // Tools arrive pre-formatted in OpenAI/Ollama-compatible JSON from
-        // tools_to_openai_format() in loop_.rs — pass them through directly.
-        let tools_opt = if tools.is_empty() { None } else { Some(tools) };
-
-        let response = self
-            .send_request(
-                api_messages,
-                &normalized_model,
-                temperature,
-                should_auth,
-                tools_opt,
-            )
-            .await?;
-
-        // Native tool calls returned by the model.
-        if !response.message.tool_calls.is_empty() {
-            let tool_calls: Vec<ToolCall> = response
-                .message
-                .tool_calls
-                .iter()
-                .map(|tc| {
-                    let (name, args) = self.extract_tool_name_and_args(tc);
-                    ToolCall {
-                        id: tc
-                            .id
-                            .clone()
-                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
-                        name,
-                        arguments: serde_json::to_string(&args)
-                            .unwrap_or_else(|_| "{}".to_string()),
-                    }
-                })
-                .collect();
-            let text = if response.message.content.is_empty() {
-                None
-            } else {
-                Some(response.message.content)
-            };
-            return Ok(ChatResponse { text, tool_calls });
-        }
-
-        // Plain text response.
-        let content = response.message.content;
-        if content.is_empty() {
-            if let Some(thinking) = &response.message.thinking {
-                tracing::warn!(
-                    "Ollama returned empty content with only thinking: '{}'. Model may have stopped prematurely.",
-                    if thinking.len() > 100 { &thinking[..100] } else { thinking }
-                );
-                return Ok(ChatResponse {
-                    text: Some(format!(
-                        "I was thinking about this: {}... but I didn't complete my response. Could you try asking again?",
-                        if thinking.len() > 200 { &thinking[..200] } else { thinking }
-                    )),
-                    tool_calls: vec![],
-                });
-            }
-            tracing::warn!("Ollama returned empty content with no tool calls");
-        }
-        Ok(ChatResponse {
-            text: Some(content),
-            tool_calls: vec![],
-        })
+        self.chat_native(messages, tools, model, temperature, None)
+            .await
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -664,7 +717,10 @@ impl Provider for OllamaProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        // Convert ToolSpec to OpenAI-compatible JSON and delegate to chat_with_tools.
+        // Ollama's `format` accepts a JSON schema directly.
+        let format = request.response_format.map(|f| &f.schema);
+
+        // Convert ToolSpec to OpenAI-compatible JSON and delegate to chat_native.
         if let Some(specs) = request.tools {
             if !specs.is_empty() {
                 let tools: Vec<serde_json::Value> = specs
@@ -681,19 +737,15 @@ impl Provider for OllamaProvider {
                     })
                     .collect();
                 return self
-                    .chat_with_tools(request.messages, &tools, model, temperature)
+                    .chat_native(request.messages, &tools, model, temperature, format)
                     .await;
             }
         }
 
-        // No tools — fall back to plain text chat.
-        let text = self
-            .chat_with_history(request.messages, model, temperature)
-            .await?;
-        Ok(ChatResponse {
-            text: Some(text),
-            tool_calls: vec![],
-        })
+        // No tools — send the plain history through the same path so usage
+        // counters are preserved.
+        self.chat_native(request.messages, &[], model, temperature, format)
+            .await
     }
 }
 
@@ -799,6 +851,7 @@ mod tests {
             "llama3",
             0.7,
             None,
+            None,
         );
 
         let json = serde_json::to_value(request).unwrap();
@@ -819,17 +872,38 @@ mod tests {
             "llama3",
             0.7,
             None,
+            None,
         );
 
         let json = serde_json::to_value(request).unwrap();
         assert_eq!(json.get("think"), Some(&serde_json::json!(false)));
     }
 
+    #[test]
+    fn request_includes_format_schema_when_structured_output_requested() {
+        let provider = OllamaProvider::new(None, None);
+        let schema = serde_json::json!({"type": "object", "required": ["ok"]});
+        let request = provider.build_chat_request(vec![], "llama3", 0.7, None, Some(&schema));
+
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json.get("format"), Some(&schema));
+    }
+
     #[test]
     fn response_deserializes() {
         let json = r#"{"message":{"role":"assistant","content":"Hello from Ollama!"}}"#;
         let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
         assert_eq!(resp.message.content, "Hello from Ollama!");
+        assert!(resp.token_usage().is_none());
+    }
+
+    #[test]
+    fn response_eval_counts_map_to_token_usage() {
+        let json = r#"{"message":{"role":"assistant","content":"hi"},"prompt_eval_count":26,"eval_count":298}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let usage = resp.token_usage().unwrap();
+        assert_eq!(usage.input_tokens, Some(26));
+        assert_eq!(usage.output_tokens, Some(298));
     }
 
     #[test]
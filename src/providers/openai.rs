@@ -1,7 +1,8 @@
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ToolCall as ProviderToolCall,
+    Provider, TokenUsage, ToolCall as ProviderToolCall,
 };
+use crate::providers::OpenAiUsage;
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -62,6 +63,8 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -121,6 +124,8 @@ struct NativeFunctionCall {
 #[derive(Debug, Deserialize)]
 struct NativeChatResponse {
     choices: Vec<NativeChoice>,
+    #[serde(default)]
+    usage: Option<OpenAiUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -247,7 +252,10 @@ impl OpenAiProvider {
             .collect()
     }
 
-    fn parse_native_response(message: NativeResponseMessage) -> ProviderChatResponse {
+    fn parse_native_response(
+        message: NativeResponseMessage,
+        usage: Option<OpenAiUsage>,
+    ) -> ProviderChatResponse {
         let text = message.effective_content();
         let tool_calls = message
             .tool_calls
@@ -260,7 +268,11 @@ impl OpenAiProvider {
             })
             .collect::<Vec<_>>();
 
-        ProviderChatResponse { text, tool_calls }
+        ProviderChatResponse {
+            text,
+            tool_calls,
+            usage: usage.map(TokenUsage::from),
+        }
     }
 
     fn http_client(&self) -> Client {
@@ -340,6 +352,9 @@ impl Provider for OpenAiProvider {
             temperature,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: request
+                .response_format
+                .map(|format| format.openai_payload()),
         };
 
         let response = self
@@ -355,13 +370,14 @@ impl Provider for OpenAiProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
+        let usage = native_response.usage;
         let message = native_response
             .choices
             .into_iter()
             .next()
             .map(|c| c.message)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        Ok(Self::parse_native_response(message))
+        Ok(Self::parse_native_response(message, usage))
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -397,6 +413,7 @@ impl Provider for OpenAiProvider {
             temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
         };
 
         let response = self
@@ -412,13 +429,14 @@ impl Provider for OpenAiProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
+        let usage = native_response.usage;
         let message = native_response
             .choices
             .into_iter()
             .next()
             .map(|c| c.message)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        Ok(Self::parse_native_response(message))
+        Ok(Self::parse_native_response(message, usage))
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -605,6 +623,22 @@ mod tests {
         assert_eq!(msg.effective_content(), Some("Real answer".to_string()));
     }
 
+    #[test]
+    fn native_response_parses_usage() {
+        let json = r#"{
+            "choices":[{"message":{"content":"hi"}}],
+            "usage":{"prompt_tokens":120,"completion_tokens":30,"prompt_tokens_details":{"cached_tokens":64}}
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let usage = resp.usage;
+        let message = resp.choices.into_iter().next().unwrap().message;
+        let parsed = OpenAiProvider::parse_native_response(message, usage);
+        let usage = parsed.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(120));
+        assert_eq!(usage.output_tokens, Some(30));
+        assert_eq!(usage.cached_input_tokens, Some(64));
+    }
+
     #[tokio::test]
     async fn chat_with_tools_fails_without_key() {
         let p = OpenAiProvider::new(None);
@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 // ── Error Classification ─────────────────────────────────────────────────
@@ -190,6 +191,16 @@ fn failure_reason(rate_limited: bool, non_retryable: bool) -> &'static str {
     }
 }
 
+/// Backoff for the next attempt: Retry-After when present (capped at 30s),
+/// otherwise `base`.
+fn compute_backoff(base: u64, err: &anyhow::Error) -> u64 {
+    if let Some(retry_after) = parse_retry_after_ms(err) {
+        retry_after.min(30_000).max(base)
+    } else {
+        base
+    }
+}
+
 fn compact_error_detail(err: &anyhow::Error) -> String {
     super::sanitize_api_error(&err.to_string())
         .split_whitespace()
@@ -223,7 +234,7 @@ fn push_failure(
 
 /// Provider wrapper with retry, fallback, auth rotation, and model failover.
 pub struct ReliableProvider {
-    providers: Vec<(String, Box<dyn Provider>)>,
+    providers: Vec<(String, Arc<dyn Provider>)>,
     max_retries: u32,
     base_backoff_ms: u64,
     /// Extra API keys for rotation (index tracks round-robin position).
@@ -240,7 +251,10 @@ impl ReliableProvider {
         base_backoff_ms: u64,
     ) -> Self {
         Self {
-            providers,
+            providers: providers
+                .into_iter()
+                .map(|(name, provider)| (name, Arc::from(provider)))
+                .collect(),
             max_retries,
             base_backoff_ms: base_backoff_ms.max(50),
             api_keys: Vec::new(),
@@ -281,12 +295,7 @@ impl ReliableProvider {
 
     /// Compute backoff duration, respecting Retry-After if present.
     fn compute_backoff(&self, base: u64, err: &anyhow::Error) -> u64 {
-        if let Some(retry_after) = parse_retry_after_ms(err) {
-            // Use Retry-After but cap at 30s to avoid indefinite waits
-            retry_after.min(30_000).max(base)
-        } else {
-            base
-        }
+        compute_backoff(base, err)
     }
 }
 
@@ -554,6 +563,7 @@ impl Provider for ReliableProvider {
                     let req = ChatRequest {
                         messages: request.messages,
                         tools: request.tools,
+                        response_format: request.response_format,
                     };
                     match provider.chat(req, current_model, temperature).await {
                         Ok(resp) => {
@@ -842,6 +852,170 @@ impl Provider for ReliableProvider {
         })
         .boxed()
     }
+
+    fn supports_streaming_tool_calls(&self) -> bool {
+        self.providers
+            .first()
+            .is_some_and(|(_, p)| p.supports_streaming_tool_calls())
+    }
+
+    fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        // Walk the same model → provider → retry chain as `chat`, but only
+        // while nothing has been sent: once deltas reach the caller a retry
+        // would duplicate them, so later errors are passed through and the
+        // caller falls back to `chat`.
+        let providers: Vec<(String, Arc<dyn Provider>)> = self
+            .providers
+            .iter()
+            .filter(|(_, provider)| provider.supports_streaming_tool_calls())
+            .map(|(name, provider)| (name.clone(), Arc::clone(provider)))
+            .collect();
+        if providers.is_empty() {
+            return stream::once(async {
+                Err(super::traits::StreamError::Provider(
+                    "No provider supports streaming tool calls".to_string(),
+                ))
+            })
+            .boxed();
+        }
+
+        let models: Vec<String> = self
+            .model_chain(model)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let messages = request.messages.to_vec();
+        let tools = request.tools.map(<[_]>::to_vec);
+        let response_format = request.response_format.cloned();
+        let max_retries = self.max_retries;
+        let base_backoff_ms = self.base_backoff_ms;
+        let original_model = model.to_string();
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+        tokio::spawn(async move {
+            let mut failures = Vec::new();
+
+            for current_model in &models {
+                for (provider_name, provider) in &providers {
+                    let mut backoff_ms = base_backoff_ms;
+
+                    for attempt in 0..=max_retries {
+                        let req = ChatRequest {
+                            messages: &messages,
+                            tools: tools.as_deref(),
+                            response_format: response_format.as_ref(),
+                        };
+                        let mut stream =
+                            provider.stream_chat(req, current_model, temperature, options);
+                        let mut started = false;
+                        let mut early_error = None;
+                        while let Some(chunk) = stream.next().await {
+                            match chunk {
+                                Err(e) if !started => {
+                                    early_error = Some(e);
+                                    break;
+                                }
+                                chunk => {
+                                    if !started && (attempt > 0 || *current_model != original_model)
+                                    {
+                                        tracing::info!(
+                                            provider = provider_name,
+                                            model = current_model.as_str(),
+                                            attempt,
+                                            original_model = original_model.as_str(),
+                                            "Provider stream recovered (failover/retry)"
+                                        );
+                                    }
+                                    started = true;
+                                    if tx.send(chunk).await.is_err() {
+                                        return; // Receiver dropped
+                                    }
+                                }
+                            }
+                        }
+                        let Some(stream_error) = early_error else {
+                            return;
+                        };
+
+                        let e = anyhow::Error::new(stream_error);
+                        let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
+                        let non_retryable = is_non_retryable(&e) || non_retryable_rate_limit;
+                        let rate_limited = is_rate_limited(&e);
+                        let failure_reason = failure_reason(rate_limited, non_retryable);
+                        let error_detail = compact_error_detail(&e);
+
+                        push_failure(
+                            &mut failures,
+                            provider_name,
+                            current_model,
+                            attempt + 1,
+                            max_retries + 1,
+                            failure_reason,
+                            &error_detail,
+                        );
+
+                        if non_retryable {
+                            tracing::warn!(
+                                provider = provider_name,
+                                model = current_model.as_str(),
+                                error = %error_detail,
+                                "Non-retryable streaming error, moving on"
+                            );
+                            if is_context_window_exceeded(&e) {
+                                let _ = tx
+                                    .send(Err(super::traits::StreamError::Provider(format!(
+                                        "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
+                                        failures.join("\n")
+                                    ))))
+                                    .await;
+                                return;
+                            }
+                            break;
+                        }
+
+                        if attempt < max_retries {
+                            let wait = compute_backoff(backoff_ms, &e);
+                            tracing::warn!(
+                                provider = provider_name,
+                                model = current_model.as_str(),
+                                attempt = attempt + 1,
+                                backoff_ms = wait,
+                                reason = failure_reason,
+                                error = %error_detail,
+                                "Provider stream failed, retrying"
+                            );
+                            tokio::time::sleep(Duration::from_millis(wait)).await;
+                            backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                        }
+                    }
+
+                    tracing::warn!(
+                        provider = provider_name,
+                        model = current_model.as_str(),
+                        "Exhausted stream retries, trying next provider/model"
+                    );
+                }
+            }
+
+            let _ = tx
+                .send(Err(super::traits::StreamError::Provider(format!(
+                    "All providers/models failed. Attempts:\n{}",
+                    failures.join("\n")
+                ))))
+                .await;
+        });
+
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
+    }
 }
 
 #[cfg(test)]
@@ -1659,6 +1833,7 @@ mod tests {
             Ok(ChatResponse {
                 text: Some(self.response_text.to_string()),
                 tool_calls: self.tool_calls.clone(),
+                usage: None,
             })
         }
     }
@@ -1690,6 +1865,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1726,6 +1902,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1797,6 +1974,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let err = provider
             .chat(request, "test", 0.0)
@@ -1850,6 +2028,7 @@ mod tests {
             Ok(ChatResponse {
                 text: Some(self.response_text.to_string()),
                 tool_calls: vec![],
+                usage: None,
             })
         }
     }
@@ -1911,6 +2090,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let result = provider.chat(request, "claude-opus", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("ok from sonnet"));
@@ -1959,6 +2139,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let result = provider.chat(request, "test", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("from fallback"));
@@ -1966,4 +2147,180 @@ mod tests {
         assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
         assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
     }
+
+    /// Mock that streams `response` as one delta, failing the first
+    /// `fail_until_attempt` streams before any output and, with
+    /// `fail_mid_stream`, erroring after the delta.
+    struct StreamMock {
+        calls: Arc<AtomicUsize>,
+        fail_until_attempt: usize,
+        fail_mid_stream: bool,
+        response: &'static str,
+        error: &'static str,
+    }
+
+    #[async_trait]
+    impl Provider for StreamMock {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(self.response.to_string())
+        }
+
+        fn supports_streaming_tool_calls(&self) -> bool {
+            true
+        }
+
+        fn stream_chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+            _options: StreamOptions,
+        ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+            let attempt = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            let error = || {
+                Err(super::super::traits::StreamError::Provider(
+                    self.error.into(),
+                ))
+            };
+            let chunks = if attempt <= self.fail_until_attempt {
+                vec![error()]
+            } else if self.fail_mid_stream {
+                vec![Ok(StreamChunk::delta(self.response)), error()]
+            } else {
+                vec![
+                    Ok(StreamChunk::delta(self.response)),
+                    Ok(StreamChunk::final_chunk()),
+                ]
+            };
+            stream::iter(chunks).boxed()
+        }
+    }
+
+    fn stream_mock(
+        calls: &Arc<AtomicUsize>,
+        fail_until_attempt: usize,
+        response: &'static str,
+        error: &'static str,
+    ) -> Box<dyn Provider> {
+        Box::new(StreamMock {
+            calls: Arc::clone(calls),
+            fail_until_attempt,
+            fail_mid_stream: false,
+            response,
+            error,
+        })
+    }
+
+    async fn collect_stream(provider: &ReliableProvider) -> (String, Vec<String>) {
+        let messages = vec![ChatMessage::user("hello")];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: None,
+            response_format: None,
+        };
+        let mut stream = provider.stream_chat(request, "test", 0.0, StreamOptions::new(true));
+        let mut text = String::new();
+        let mut errors = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(chunk) => text.push_str(&chunk.delta),
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+        (text, errors)
+    }
+
+    #[tokio::test]
+    async fn stream_chat_retries_before_first_chunk() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                stream_mock(&calls, 1, "streamed", "500 boom"),
+            )],
+            2,
+            1,
+        );
+
+        let (text, errors) = collect_stream(&provider).await;
+        assert_eq!(text, "streamed");
+        assert!(errors.is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_falls_back_after_non_retryable_error() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "primary".into(),
+                    stream_mock(&primary_calls, usize::MAX, "never", "401 Unauthorized"),
+                ),
+                (
+                    "fallback".into(),
+                    stream_mock(&fallback_calls, 0, "from fallback", "unused"),
+                ),
+            ],
+            3,
+            1,
+        );
+
+        let (text, errors) = collect_stream(&provider).await;
+        assert_eq!(text, "from fallback");
+        assert!(errors.is_empty());
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_reports_aggregated_error_when_all_fail() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                stream_mock(&calls, usize::MAX, "never", "500 boom"),
+            )],
+            1,
+            1,
+        );
+
+        let (text, errors) = collect_stream(&provider).await;
+        assert!(text.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("All providers/models failed"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_does_not_retry_after_output_started() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                Box::new(StreamMock {
+                    calls: Arc::clone(&calls),
+                    fail_until_attempt: 0,
+                    fail_mid_stream: true,
+                    response: "partial",
+                    error: "500 connection reset",
+                }) as Box<dyn Provider>,
+            )],
+            3,
+            1,
+        );
+
+        let (text, errors) = collect_stream(&provider).await;
+        assert_eq!(text, "partial");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("connection reset"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,336 @@
+//! Structured output: JSON-schema constrained responses.
+//!
+//! A [`ResponseFormat`] rides on [`ChatRequest`](super::traits::ChatRequest) and
+//! each backend maps it to its native mechanism (OpenAI `json_schema`, Gemini
+//! `responseSchema`, Ollama `format`, Anthropic forced tool). Backends without
+//! a native mechanism get the schema as prompt instructions, and the helpers
+//! here validate the reply so callers can re-prompt on violations.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Maximum correction round-trips after a schema violation.
+pub const MAX_STRUCTURED_OUTPUT_RETRIES: usize = 2;
+
+/// Requested shape of the final text response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseFormat {
+    /// Short identifier for the schema (OpenAI `json_schema.name`, Anthropic tool name).
+    pub name: String,
+    /// JSON Schema the response must satisfy.
+    pub schema: Value,
+}
+
+impl ResponseFormat {
+    pub fn json_schema(name: impl Into<String>, schema: Value) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+        }
+    }
+
+    /// OpenAI Chat Completions `response_format` payload.
+    pub fn openai_payload(&self) -> Value {
+        serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": self.name,
+                "schema": self.schema,
+            }
+        })
+    }
+
+    /// Gemini `responseSchema`: the OpenAPI subset Gemini accepts.
+    pub fn gemini_schema(&self) -> Value {
+        to_openapi_subset(&self.schema)
+    }
+
+    /// System prompt text for backends that only follow instructions.
+    pub fn instructions(&self) -> String {
+        let schema = serde_json::to_string(&self.schema).unwrap_or_else(|_| "{}".to_string());
+        format!(
+            "## Response Format\n\n\
+             Reply with a single JSON value that conforms to this JSON Schema. \
+             Do not wrap it in Markdown and do not add any other text.\n\n\
+             Schema `{}`: `{schema}`",
+            self.name
+        )
+    }
+
+    /// Follow-up user message asking the model to fix a schema violation.
+    pub fn correction_prompt(&self, error: &str) -> String {
+        format!(
+            "Your previous reply did not match the required JSON schema `{}`: {error}\n\
+             Reply again with only the corrected JSON.",
+            self.name
+        )
+    }
+
+    /// Parse `text` as JSON and check it against the schema.
+    ///
+    /// Markdown code fences and surrounding prose are tolerated; the returned
+    /// value is the extracted JSON.
+    pub fn validate(&self, text: &str) -> Result<Value, String> {
+        let value = extract_json(text).ok_or_else(|| "response is not valid JSON".to_string())?;
+        let mut errors = Vec::new();
+        validate_value(&self.schema, &value, "$", &mut errors);
+        if errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// Pull the first JSON object or array out of a model reply.
+fn extract_json(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"));
+    if let Some(inner) = unfenced {
+        if let Ok(value) = serde_json::from_str(inner.trim()) {
+            return Some(value);
+        }
+    }
+
+    let start = trimmed.find(['{', '['])?;
+    let close = if trimmed[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = trimmed.rfind(close)?;
+    (end > start)
+        .then(|| serde_json::from_str(&trimmed[start..=end]).ok())
+        .flatten()
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+/// Check the commonly used JSON Schema keywords. Unknown keywords are ignored.
+fn validate_value(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| type_matches(t, value)) {
+            errors.push(format!("{path}: expected {}", allowed.join(" or ")));
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            errors.push(format!(
+                "{path}: value is not one of the allowed enum values"
+            ));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    errors.push(format!("{path}: missing required property `{key}`"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, child) in object {
+            let child_path = format!("{path}.{key}");
+            match properties.and_then(|p| p.get(key)) {
+                Some(child_schema) => validate_value(child_schema, child, &child_path, errors),
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        errors.push(format!("{path}: unexpected property `{key}`"));
+                    }
+                    Some(extra @ Value::Object(_)) => {
+                        validate_value(extra, child, &child_path, errors);
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                errors.push(format!("{path}: expected at least {min} items"));
+            }
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for (index, item) in items.iter().enumerate() {
+                validate_value(item_schema, item, &format!("{path}[{index}]"), errors);
+            }
+        }
+    }
+}
+
+/// Gemini rejects JSON Schema keywords outside its OpenAPI subset, and
+/// expresses `["T", "null"]` unions as `nullable`.
+pub(crate) fn to_openapi_subset(schema: &Value) -> Value {
+    const KEPT: &[&str] = &[
+        "type",
+        "format",
+        "description",
+        "nullable",
+        "enum",
+        "required",
+        "minItems",
+        "maxItems",
+        "propertyOrdering",
+    ];
+
+    let Some(object) = schema.as_object() else {
+        return schema.clone();
+    };
+
+    let mut out = serde_json::Map::new();
+    for (key, value) in object {
+        match key.as_str() {
+            "type" => {
+                if let Value::Array(types) = value {
+                    let non_null: Vec<&Value> = types
+                        .iter()
+                        .filter(|t| t.as_str() != Some("null"))
+                        .collect();
+                    if non_null.len() < types.len() {
+                        out.insert("nullable".into(), Value::Bool(true));
+                    }
+                    if let Some(first) = non_null.first() {
+                        out.insert("type".into(), (*first).clone());
+                    }
+                } else {
+                    out.insert(key.clone(), value.clone());
+                }
+            }
+            "properties" => {
+                let properties = value
+                    .as_object()
+                    .map(|props| {
+                        props
+                            .iter()
+                            .map(|(name, child)| (name.clone(), to_openapi_subset(child)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                out.insert(key.clone(), Value::Object(properties));
+            }
+            "items" => {
+                out.insert(key.clone(), to_openapi_subset(value));
+            }
+            _ if KEPT.contains(&key.as_str()) => {
+                out.insert(key.clone(), value.clone());
+            }
+            _ => {}
+        }
+    }
+    Value::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn report_format() -> ResponseFormat {
+        ResponseFormat::json_schema(
+            "report",
+            json!({
+                "type": "object",
+                "properties": {
+                    "status": {"type": "string", "enum": ["ok", "failed"]},
+                    "count": {"type": "integer"},
+                    "tags": {"type": "array", "items": {"type": "string"}}
+                },
+                "required": ["status", "count"],
+                "additionalProperties": false
+            }),
+        )
+    }
+
+    #[test]
+    fn validate_accepts_conforming_json_inside_fences() {
+        let value = report_format()
+            .validate("```json\n{\"status\": \"ok\", \"count\": 3, \"tags\": [\"a\"]}\n```")
+            .unwrap();
+        assert_eq!(value["count"], 3);
+    }
+
+    #[test]
+    fn validate_extracts_json_from_surrounding_prose() {
+        let value = report_format()
+            .validate("Here you go: {\"status\": \"failed\", \"count\": 0} — done.")
+            .unwrap();
+        assert_eq!(value["status"], "failed");
+    }
+
+    #[test]
+    fn validate_reports_each_violation_with_path() {
+        let err = report_format()
+            .validate(r#"{"status": "maybe", "tags": [1], "extra": true}"#)
+            .unwrap_err();
+        assert!(err.contains("$.status: value is not one of the allowed enum values"));
+        assert!(err.contains("missing required property `count`"));
+        assert!(err.contains("$.tags[0]: expected string"));
+        assert!(err.contains("unexpected property `extra`"));
+    }
+
+    #[test]
+    fn validate_rejects_non_json() {
+        assert_eq!(
+            report_format().validate("all good").unwrap_err(),
+            "response is not valid JSON"
+        );
+    }
+
+    #[test]
+    fn openai_payload_wraps_schema() {
+        let payload = report_format().openai_payload();
+        assert_eq!(payload["type"], "json_schema");
+        assert_eq!(payload["json_schema"]["name"], "report");
+        assert_eq!(payload["json_schema"]["schema"]["type"], "object");
+    }
+
+    #[test]
+    fn gemini_schema_drops_unsupported_keywords_and_maps_nullable() {
+        let format = ResponseFormat::json_schema(
+            "r",
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {"note": {"type": ["string", "null"]}}
+            }),
+        );
+        let schema = format.gemini_schema();
+        assert!(schema.get("$schema").is_none());
+        assert!(schema.get("additionalProperties").is_none());
+        assert_eq!(schema["properties"]["note"]["type"], "string");
+        assert_eq!(schema["properties"]["note"]["nullable"], true);
+    }
+}
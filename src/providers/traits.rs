@@ -1,3 +1,4 @@
+pub use super::structured::ResponseFormat;
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
@@ -49,6 +50,54 @@ pub struct ToolCall {
     pub arguments: String,
 }
 
+/// Token counts reported by a provider for a single request.
+///
+/// Fields are `None` when the backend did not report that count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    /// Prompt/input tokens billed for the request.
+    pub input_tokens: Option<u64>,
+    /// Completion/output tokens billed for the request.
+    pub output_tokens: Option<u64>,
+    /// Portion of the input served from a provider-side prompt cache.
+    pub cached_input_tokens: Option<u64>,
+    /// Portion of the input written to a provider-side prompt cache.
+    pub cache_write_input_tokens: Option<u64>,
+}
+
+impl TokenUsage {
+    /// True when the provider reported no counts at all.
+    pub fn is_empty(&self) -> bool {
+        self.input_tokens.is_none()
+            && self.output_tokens.is_none()
+            && self.cached_input_tokens.is_none()
+            && self.cache_write_input_tokens.is_none()
+    }
+
+    /// True when the provider reported any prompt-cache activity.
+    pub fn has_cache_activity(&self) -> bool {
+        self.cached_input_tokens.is_some() || self.cache_write_input_tokens.is_some()
+    }
+
+    /// Add `other`'s counts to these, e.g. across retried requests.
+    ///
+    /// A count stays `None` only when neither side reported it.
+    pub fn accumulate(&mut self, other: &TokenUsage) {
+        fn add(total: &mut Option<u64>, value: Option<u64>) {
+            if let Some(value) = value {
+                *total = Some(total.unwrap_or(0).saturating_add(value));
+            }
+        }
+        add(&mut self.input_tokens, other.input_tokens);
+        add(&mut self.output_tokens, other.output_tokens);
+        add(&mut self.cached_input_tokens, other.cached_input_tokens);
+        add(
+            &mut self.cache_write_input_tokens,
+            other.cache_write_input_tokens,
+        );
+    }
+}
+
 /// An LLM response that may contain text, tool calls, or both.
 #[derive(Debug, Clone)]
 pub struct ChatResponse {
@@ -56,6 +105,8 @@ pub struct ChatResponse {
     pub text: Option<String>,
     /// Tool calls requested by the LLM.
     pub tool_calls: Vec<ToolCall>,
+    /// Token usage reported by the provider, when available.
+    pub usage: Option<TokenUsage>,
 }
 
 impl ChatResponse {
@@ -75,6 +126,8 @@ impl ChatResponse {
 pub struct ChatRequest<'a> {
     pub messages: &'a [ChatMessage],
     pub tools: Option<&'a [ToolSpec]>,
+    /// Constrain the final text response to a JSON schema.
+    pub response_format: Option<&'a ResponseFormat>,
 }
 
 /// A tool result to feed back to the LLM.
@@ -108,6 +161,10 @@ pub struct StreamChunk {
     pub is_final: bool,
     /// Approximate token count for this chunk (estimated).
     pub token_count: usize,
+    /// Provider-reported usage, typically only present on the final chunk.
+    pub usage: Option<TokenUsage>,
+    /// Native tool-call fragments carried by this chunk.
+    pub tool_calls: Vec<ToolCallDelta>,
 }
 
 impl StreamChunk {
@@ -117,6 +174,19 @@ impl StreamChunk {
             delta: text.into(),
             is_final: false,
             token_count: 0,
+            usage: None,
+            tool_calls: Vec::new(),
+        }
+    }
+
+    /// Create a non-final chunk carrying a tool-call fragment.
+    pub fn tool_call(delta: ToolCallDelta) -> Self {
+        Self {
+            delta: String::new(),
+            is_final: false,
+            token_count: 0,
+            usage: None,
+            tool_calls: vec![delta],
         }
     }
 
@@ -126,6 +196,8 @@ impl StreamChunk {
             delta: String::new(),
             is_final: true,
             token_count: 0,
+            usage: None,
+            tool_calls: Vec::new(),
         }
     }
 
@@ -135,9 +207,17 @@ impl StreamChunk {
             delta: message.into(),
             is_final: true,
             token_count: 0,
+            usage: None,
+            tool_calls: Vec::new(),
         }
     }
 
+    /// Attach provider-reported usage to this chunk.
+    pub fn with_usage(mut self, usage: Option<TokenUsage>) -> Self {
+        self.usage = usage;
+        self
+    }
+
     /// Estimate tokens (rough approximation: ~4 chars per token).
     pub fn with_token_estimate(mut self) -> Self {
         self.token_count = self.delta.len().div_ceil(4);
@@ -145,6 +225,72 @@ impl StreamChunk {
     }
 }
 
+/// Fragment of a native tool call in a streaming response.
+///
+/// `index` identifies the call within the response. `id` and `name` usually
+/// arrive on the first fragment only; `arguments` carries the next slice of
+/// the JSON argument string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolCallDelta {
+    pub index: usize,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub arguments: String,
+}
+
+/// Assembles streamed [`ToolCallDelta`]s into complete [`ToolCall`]s.
+#[derive(Debug, Default)]
+pub struct ToolCallAssembler {
+    calls: std::collections::BTreeMap<usize, ToolCallDelta>,
+}
+
+impl ToolCallAssembler {
+    /// Merge a fragment. Returns the tool name the first time it is known.
+    pub fn push(&mut self, delta: &ToolCallDelta) -> Option<&str> {
+        let entry = self
+            .calls
+            .entry(delta.index)
+            .or_insert_with(|| ToolCallDelta {
+                index: delta.index,
+                ..ToolCallDelta::default()
+            });
+        if entry.id.is_none() {
+            entry.id.clone_from(&delta.id);
+        }
+        entry.arguments.push_str(&delta.arguments);
+        if entry.name.is_none() && delta.name.is_some() {
+            entry.name.clone_from(&delta.name);
+            return entry.name.as_deref();
+        }
+        None
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Complete calls in stream order. Fragments that never received a name
+    /// are dropped; missing ids are generated and empty arguments become `{}`.
+    pub fn finish(self) -> Vec<ToolCall> {
+        self.calls
+            .into_values()
+            .filter_map(|call| {
+                let name = call.name?;
+                let arguments = if call.arguments.trim().is_empty() {
+                    "{}".to_string()
+                } else {
+                    call.arguments
+                };
+                Some(ToolCall {
+                    id: call.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                    name,
+                    arguments,
+                })
+            })
+            .collect()
+    }
+}
+
 /// Options for streaming chat requests.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct StreamOptions {
@@ -311,6 +457,21 @@ pub trait Provider: Send + Sync {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
+        // Without a native structured-output mechanism, ask for the schema in
+        // the system prompt.
+        let formatted_messages;
+        let request = match request.response_format {
+            Some(format) => {
+                formatted_messages =
+                    messages_with_tool_instructions(request.messages, &format.instructions());
+                ChatRequest {
+                    messages: &formatted_messages,
+                    ..request
+                }
+            }
+            None => request,
+        };
+
         // If tools are provided but provider doesn't support native tools,
         // inject tool instructions into system prompt as fallback.
         if let Some(tools) = request.tools {
@@ -323,20 +484,8 @@ pub trait Provider: Send + Sync {
                         )
                     }
                 };
-                let mut modified_messages = request.messages.to_vec();
-
-                // Inject tool instructions into an existing system message.
-                // If none exists, prepend one to the conversation.
-                if let Some(system_message) =
-                    modified_messages.iter_mut().find(|m| m.role == "system")
-                {
-                    if !system_message.content.is_empty() {
-                        system_message.content.push_str("\n\n");
-                    }
-                    system_message.content.push_str(&tool_instructions);
-                } else {
-                    modified_messages.insert(0, ChatMessage::system(tool_instructions));
-                }
+                let modified_messages =
+                    messages_with_tool_instructions(request.messages, &tool_instructions);
 
                 let text = self
                     .chat_with_history(&modified_messages, model, temperature)
@@ -344,6 +493,7 @@ pub trait Provider: Send + Sync {
                 return Ok(ChatResponse {
                     text: Some(text),
                     tool_calls: Vec::new(),
+                    usage: None,
                 });
             }
         }
@@ -354,6 +504,7 @@ pub trait Provider: Send + Sync {
         Ok(ChatResponse {
             text: Some(text),
             tool_calls: Vec::new(),
+            usage: None,
         })
     }
 
@@ -387,6 +538,7 @@ pub trait Provider: Send + Sync {
         Ok(ChatResponse {
             text: Some(text),
             tool_calls: Vec::new(),
+            usage: None,
         })
     }
 
@@ -428,6 +580,50 @@ pub trait Provider: Send + Sync {
         let chunk = StreamChunk::error(format!("{} does not support streaming", provider_name));
         stream::once(async move { Ok(chunk) }).boxed()
     }
+
+    /// Whether [`Provider::stream_chat`] streams native tool calls.
+    /// Default implementation returns false.
+    fn supports_streaming_tool_calls(&self) -> bool {
+        false
+    }
+
+    /// Streaming variant of [`Provider::chat`].
+    ///
+    /// Yields text deltas and [`ToolCallDelta`] fragments as they arrive; feed
+    /// the fragments to a [`ToolCallAssembler`] to recover the tool calls.
+    /// Only called when [`Provider::supports_streaming_tool_calls`] is true.
+    fn stream_chat(
+        &self,
+        _request: ChatRequest<'_>,
+        _model: &str,
+        _temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        stream::once(async move {
+            Err(StreamError::Provider(
+                "provider does not support streaming tool calls".to_string(),
+            ))
+        })
+        .boxed()
+    }
+}
+
+/// Inject prompt-guided tool instructions into an existing system message,
+/// or prepend one to the conversation if none exists.
+pub(crate) fn messages_with_tool_instructions(
+    messages: &[ChatMessage],
+    tool_instructions: &str,
+) -> Vec<ChatMessage> {
+    let mut modified_messages = messages.to_vec();
+    if let Some(system_message) = modified_messages.iter_mut().find(|m| m.role == "system") {
+        if !system_message.content.is_empty() {
+            system_message.content.push_str("\n\n");
+        }
+        system_message.content.push_str(tool_instructions);
+    } else {
+        modified_messages.insert(0, ChatMessage::system(tool_instructions));
+    }
+    modified_messages
 }
 
 /// Build tool instructions text for prompt-guided tool calling.
@@ -510,6 +706,7 @@ mod tests {
         let empty = ChatResponse {
             text: None,
             tool_calls: vec![],
+            usage: None,
         };
         assert!(!empty.has_tool_calls());
         assert_eq!(empty.text_or_empty(), "");
@@ -521,11 +718,31 @@ mod tests {
                 name: "shell".into(),
                 arguments: "{}".into(),
             }],
+            usage: None,
         };
         assert!(with_tools.has_tool_calls());
         assert_eq!(with_tools.text_or_empty(), "Let me check");
     }
 
+    #[test]
+    fn token_usage_accumulate_sums_reported_counts() {
+        let mut total = TokenUsage {
+            input_tokens: Some(100),
+            output_tokens: Some(20),
+            ..TokenUsage::default()
+        };
+        total.accumulate(&TokenUsage {
+            input_tokens: Some(150),
+            output_tokens: Some(30),
+            cached_input_tokens: Some(80),
+            cache_write_input_tokens: None,
+        });
+        assert_eq!(total.input_tokens, Some(250));
+        assert_eq!(total.output_tokens, Some(50));
+        assert_eq!(total.cached_input_tokens, Some(80));
+        assert_eq!(total.cache_write_input_tokens, None);
+    }
+
     #[test]
     fn tool_call_serialization() {
         let tc = ToolCall {
@@ -731,6 +948,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -748,6 +966,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: None,
+            response_format: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -848,6 +1067,7 @@ mod tests {
                 ChatMessage::system("BASE_SYSTEM_PROMPT"),
             ],
             tools: Some(&tools),
+            response_format: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -857,6 +1077,33 @@ mod tests {
         assert!(text.contains("Tool Use Protocol"));
     }
 
+    #[tokio::test]
+    async fn provider_chat_adds_response_format_instructions_to_system_prompt() {
+        let provider = EchoSystemProvider {
+            supports_native: false,
+        };
+        let format = ResponseFormat::json_schema(
+            "report",
+            serde_json::json!({"type": "object", "required": ["status"]}),
+        );
+
+        let request = ChatRequest {
+            messages: &[ChatMessage::system("BASE"), ChatMessage::user("Hello")],
+            tools: None,
+            response_format: Some(&format),
+        };
+
+        let text = provider
+            .chat(request, "model", 0.7)
+            .await
+            .unwrap()
+            .text
+            .unwrap_or_default();
+        assert!(text.contains("BASE"));
+        assert!(text.contains("Response Format"));
+        assert!(text.contains(r#""required":["status"]"#));
+    }
+
     #[tokio::test]
     async fn provider_chat_prompt_guided_uses_convert_tools_override() {
         let provider = CustomConvertProvider;
@@ -870,6 +1117,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::system("BASE"), ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -892,6 +1140,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
         };
 
         let err = provider.chat(request, "model", 0.7).await.unwrap_err();
@@ -899,4 +1148,45 @@ mod tests {
 
         assert!(message.contains("non-prompt-guided"));
     }
+
+    #[test]
+    fn tool_call_assembler_merges_fragments_by_index() {
+        let mut assembler = ToolCallAssembler::default();
+        let first = ToolCallDelta {
+            index: 0,
+            id: Some("call_1".into()),
+            name: Some("shell".into()),
+            arguments: "{\"comm".into(),
+        };
+        assert_eq!(assembler.push(&first), Some("shell"));
+        assert_eq!(
+            assembler.push(&ToolCallDelta {
+                index: 1,
+                name: Some("file_read".into()),
+                ..ToolCallDelta::default()
+            }),
+            Some("file_read")
+        );
+        assert_eq!(
+            assembler.push(&ToolCallDelta {
+                index: 0,
+                arguments: "and\":\"ls\"}".into(),
+                ..ToolCallDelta::default()
+            }),
+            None
+        );
+        assembler.push(&ToolCallDelta {
+            index: 2,
+            arguments: "{}".into(),
+            ..ToolCallDelta::default()
+        });
+
+        let calls = assembler.finish();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "call_1");
+        assert_eq!(calls[0].arguments, r#"{"command":"ls"}"#);
+        assert_eq!(calls[1].name, "file_read");
+        assert_eq!(calls[1].arguments, "{}");
+        assert!(!calls[1].id.is_empty());
+    }
 }
@@ -21,6 +21,8 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    ToolExecution,
+    ApprovalDecision,
 }
 
 /// Actor information (who performed the action)
@@ -162,6 +164,46 @@ pub struct CommandExecutionLog<'a> {
     pub duration_ms: u64,
 }
 
+/// Structured tool invocation details for audit logging.
+#[derive(Debug, Clone)]
+pub struct ToolExecutionLog<'a> {
+    pub channel: &'a str,
+    pub tool: &'a str,
+    /// Short, truncated rendering of the call arguments.
+    pub arguments: &'a str,
+    pub approved: bool,
+    pub allowed: bool,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub error: Option<&'a str>,
+}
+
+/// Structured file modification details for audit logging.
+#[derive(Debug, Clone)]
+pub struct FileAccessLog<'a> {
+    /// Tool that touched the file, e.g. `file_edit`.
+    pub tool: &'a str,
+    /// Workspace-relative path as requested by the caller.
+    pub path: &'a str,
+    /// Short description of the change, e.g. `replace 1 occurrence`.
+    pub operation: &'a str,
+    pub allowed: bool,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub error: Option<&'a str>,
+}
+
+/// Structured approval decision details for audit logging.
+#[derive(Debug, Clone)]
+pub struct ApprovalDecisionLog<'a> {
+    pub channel: &'a str,
+    pub tool: &'a str,
+    pub arguments: &'a str,
+    pub approved: bool,
+    /// `cli`, `<channel>:<sender>`, `gateway`, or `timeout`.
+    pub resolved_by: &'a str,
+}
+
 impl AuditLogger {
     /// Create a new audit logger
     pub fn new(config: AuditConfig, zeroclaw_dir: PathBuf) -> Result<Self> {
@@ -210,6 +252,64 @@ impl AuditLogger {
         self.log(&event)
     }
 
+    /// Log a tool invocation event.
+    pub fn log_tool_event(&self, entry: ToolExecutionLog<'_>) -> Result<()> {
+        let mut event = AuditEvent::new(AuditEventType::ToolExecution)
+            .with_actor(entry.channel.to_string(), None, None)
+            .with_result(
+                entry.success,
+                None,
+                entry.duration_ms,
+                entry.error.map(str::to_string),
+            );
+        event.action = Some(Action {
+            command: Some(format!("{}({})", entry.tool, entry.arguments)),
+            risk_level: None,
+            approved: entry.approved,
+            allowed: entry.allowed,
+        });
+
+        self.log(&event)
+    }
+
+    /// Log a file modification made (or refused) on behalf of a tool.
+    pub fn log_file_event(&self, entry: FileAccessLog<'_>) -> Result<()> {
+        let mut event = AuditEvent::new(AuditEventType::FileAccess)
+            .with_actor(entry.tool.to_string(), None, None)
+            .with_result(
+                entry.success,
+                None,
+                entry.duration_ms,
+                entry.error.map(str::to_string),
+            );
+        event.action = Some(Action {
+            command: Some(format!("{} {}", entry.operation, entry.path)),
+            risk_level: None,
+            approved: false,
+            allowed: entry.allowed,
+        });
+        event.security.policy_violation = !entry.allowed;
+
+        self.log(&event)
+    }
+
+    /// Log a human-in-the-loop approval decision.
+    pub fn log_approval_event(&self, entry: ApprovalDecisionLog<'_>) -> Result<()> {
+        let mut event = AuditEvent::new(AuditEventType::ApprovalDecision).with_actor(
+            entry.channel.to_string(),
+            Some(entry.resolved_by.to_string()),
+            None,
+        );
+        event.action = Some(Action {
+            command: Some(format!("{}({})", entry.tool, entry.arguments)),
+            risk_level: None,
+            approved: entry.approved,
+            allowed: entry.approved,
+        });
+
+        self.log(&event)
+    }
+
     /// Backward-compatible helper to log a command execution event.
     #[allow(clippy::too_many_arguments)]
     pub fn log_command(
@@ -396,6 +496,72 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn audit_log_tool_event_writes_structured_entry() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            max_size_mb: 10,
+            ..Default::default()
+        };
+        let logger = AuditLogger::new(config, tmp.path().to_path_buf())?;
+
+        logger.log_tool_event(ToolExecutionLog {
+            channel: "mcp",
+            tool: "file_read",
+            arguments: "path: notes.md",
+            approved: false,
+            allowed: true,
+            success: false,
+            duration_ms: 7,
+            error: Some("not found"),
+        })?;
+
+        let content = tokio::fs::read_to_string(tmp.path().join("audit.log")).await?;
+        let parsed: AuditEvent = serde_json::from_str(content.trim())?;
+
+        assert!(matches!(parsed.event_type, AuditEventType::ToolExecution));
+        let action = parsed.action.unwrap();
+        assert_eq!(action.command.as_deref(), Some("file_read(path: notes.md)"));
+        assert!(action.risk_level.is_none());
+        let result = parsed.result.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("not found"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn audit_log_approval_event_records_resolver() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            max_size_mb: 10,
+            ..Default::default()
+        };
+        let logger = AuditLogger::new(config, tmp.path().to_path_buf())?;
+
+        logger.log_approval_event(ApprovalDecisionLog {
+            channel: "telegram",
+            tool: "shell",
+            arguments: "command: ls",
+            approved: false,
+            resolved_by: "timeout",
+        })?;
+
+        let content = tokio::fs::read_to_string(tmp.path().join("audit.log")).await?;
+        let parsed: AuditEvent = serde_json::from_str(content.trim())?;
+
+        assert!(matches!(
+            parsed.event_type,
+            AuditEventType::ApprovalDecision
+        ));
+        let actor = parsed.actor.unwrap();
+        assert_eq!(actor.channel, "telegram");
+        assert_eq!(actor.user_id.as_deref(), Some("timeout"));
+        assert!(!parsed.action.unwrap().approved);
+        Ok(())
+    }
+
     #[test]
     fn audit_rotation_creates_numbered_backup() -> Result<()> {
         let tmp = TempDir::new()?;
@@ -5,7 +5,7 @@
 //! `--features browser-native` and selected through config.
 //! Computer-use (OS-level) actions are supported via an optional sidecar endpoint.
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::security::SecurityPolicy;
 use anyhow::Context;
 use async_trait::async_trait;
@@ -18,6 +18,9 @@ use std::time::Duration;
 use tokio::process::Command;
 use tracing::debug;
 
+/// Dispatcher ceiling for one browser action, covering slow page loads.
+const BROWSER_ACTION_TIMEOUT_SECS: u64 = 120;
+
 /// Computer-use sidecar settings.
 #[derive(Clone)]
 pub struct ComputerUseConfig {
@@ -634,7 +637,7 @@ impl BrowserTool {
         {
             let mut state = self.native_state.lock().await;
 
-            let output = state
+            let mut output = state
                 .execute_action(
                     action,
                     self.native_headless,
@@ -643,10 +646,31 @@ impl BrowserTool {
                 )
                 .await?;
 
+            // Screenshots travel as an image part instead of inline base64 text.
+            let mut parts = Vec::new();
+            if let Some(Value::String(encoded)) = output
+                .as_object_mut()
+                .and_then(|payload| payload.remove("png_base64"))
+            {
+                output["image_attached"] = Value::Bool(true);
+                parts.push(super::traits::ToolContent::Image {
+                    mime_type: "image/png".to_string(),
+                    data: encoded,
+                });
+            } else if output["action"] == "screenshot" {
+                if let Some(path) = output["path"].as_str() {
+                    parts.push(super::traits::ToolContent::File {
+                        path: path.to_string(),
+                        mime_type: Some("image/png".to_string()),
+                    });
+                }
+            }
+
             Ok(ToolResult {
                 success: true,
                 output: serde_json::to_string_pretty(&output).unwrap_or_default(),
                 error: None,
+                parts,
             })
         }
 
@@ -794,6 +818,7 @@ impl BrowserTool {
                     success: true,
                     output,
                     error: None,
+                    parts: Vec::new(),
                 });
             }
 
@@ -811,6 +836,7 @@ impl BrowserTool {
                 success: false,
                 output: String::new(),
                 error,
+                parts: Vec::new(),
             });
         }
 
@@ -819,6 +845,7 @@ impl BrowserTool {
                 success: true,
                 output: body,
                 error: None,
+                parts: Vec::new(),
             });
         }
 
@@ -829,6 +856,7 @@ impl BrowserTool {
                 "computer-use sidecar request failed with status {status}: {}",
                 body.trim()
             )),
+            parts: Vec::new(),
         })
     }
 
@@ -857,12 +885,14 @@ impl BrowserTool {
                 success: true,
                 output,
                 error: None,
+                parts: Vec::new(),
             })
         } else {
             Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: resp.error,
+                parts: Vec::new(),
             })
         }
     }
@@ -997,6 +1027,12 @@ impl Tool for BrowserTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating()
+            .with_network()
+            .with_timeout_secs(BROWSER_ACTION_TIMEOUT_SECS)
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         // Security checks
         if !self.security.can_act() {
@@ -1004,6 +1040,7 @@ impl Tool for BrowserTool {
                 success: false,
                 output: String::new(),
                 error: Some("Action blocked: autonomy is read-only".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -1012,6 +1049,7 @@ impl Tool for BrowserTool {
                 success: false,
                 output: String::new(),
                 error: Some("Action blocked: rate limit exceeded".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -1022,6 +1060,7 @@ impl Tool for BrowserTool {
                     success: false,
                     output: String::new(),
                     error: Some(error.to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -1037,6 +1076,7 @@ impl Tool for BrowserTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Unknown action: {action_str}")),
+                parts: Vec::new(),
             });
         }
 
@@ -1049,6 +1089,7 @@ impl Tool for BrowserTool {
                 success: false,
                 output: String::new(),
                 error: Some(unavailable_action_for_backend_error(action_str, backend)),
+                parts: Vec::new(),
             });
         }
 
@@ -1059,6 +1100,7 @@ impl Tool for BrowserTool {
                     success: false,
                     output: String::new(),
                     error: Some(e.to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
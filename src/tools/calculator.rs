@@ -82,6 +82,7 @@ impl Tool for CalculatorTool {
                     "Expression too long: {} chars (max {MAX_EXPRESSION_LEN})",
                     expression.len()
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -90,6 +91,7 @@ impl Tool for CalculatorTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+                parts: Vec::new(),
             });
         }
         if !self.security.record_action() {
@@ -97,6 +99,7 @@ impl Tool for CalculatorTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -114,6 +117,7 @@ impl Tool for CalculatorTool {
                 success: false,
                 output: String::new(),
                 error: Some("Expression result is not finite (possibly division by zero)".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -130,6 +134,7 @@ impl Tool for CalculatorTool {
             success: true,
             output,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -6,7 +6,7 @@
 // This is opt-in. Users who prefer sovereign/local-only mode skip this entirely.
 // The Composio API key is stored in the encrypted secret store.
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use anyhow::Context;
@@ -21,6 +21,8 @@ use std::sync::Arc;
 const COMPOSIO_API_BASE_V2: &str = "https://backend.composio.dev/api/v2";
 const COMPOSIO_API_BASE_V3: &str = "https://backend.composio.dev/api/v3";
 const COMPOSIO_TOOL_VERSION_LATEST: &str = "latest";
+/// Dispatcher ceiling for one action; an execute may chain several 60s requests.
+const COMPOSIO_CALL_TIMEOUT_SECS: u64 = 180;
 
 fn ensure_https(url: &str) -> anyhow::Result<()> {
     if !url.starts_with("https://") {
@@ -608,6 +610,12 @@ impl Tool for ComposioTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating()
+            .with_network()
+            .with_timeout_secs(COMPOSIO_CALL_TIMEOUT_SECS)
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let action = args
             .get("action")
@@ -650,12 +658,14 @@ impl Tool for ComposioTool {
                             success: true,
                             output,
                             error: None,
+                            parts: Vec::new(),
                         })
                     }
                     Err(e) => Ok(ToolResult {
                         success: false,
                         output: String::new(),
                         error: Some(format!("Failed to list actions: {e}")),
+                        parts: Vec::new(),
                     }),
                 }
             }
@@ -675,6 +685,7 @@ impl Tool for ComposioTool {
                                     "No connected accounts found{app_hint} for entity '{entity_id}'. Run action='connect' first."
                                 ),
                                 error: None,
+                                parts: Vec::new(),
                             });
                         }
 
@@ -700,12 +711,14 @@ impl Tool for ComposioTool {
                             success: true,
                             output,
                             error: None,
+                            parts: Vec::new(),
                         })
                     }
                     Err(e) => Ok(ToolResult {
                         success: false,
                         output: String::new(),
                         error: Some(format!("Failed to list connected accounts: {e}")),
+                        parts: Vec::new(),
                     }),
                 }
             }
@@ -719,6 +732,7 @@ impl Tool for ComposioTool {
                         success: false,
                         output: String::new(),
                         error: Some(error),
+                        parts: Vec::new(),
                     });
                 }
 
@@ -745,12 +759,14 @@ impl Tool for ComposioTool {
                             success: true,
                             output,
                             error: None,
+                            parts: Vec::new(),
                         })
                     }
                     Err(e) => Ok(ToolResult {
                         success: false,
                         output: String::new(),
                         error: Some(format!("Action execution failed: {e}")),
+                        parts: Vec::new(),
                     }),
                 }
             }
@@ -764,6 +780,7 @@ impl Tool for ComposioTool {
                         success: false,
                         output: String::new(),
                         error: Some(error),
+                        parts: Vec::new(),
                     });
                 }
 
@@ -797,12 +814,14 @@ impl Tool for ComposioTool {
                             success: true,
                             output,
                             error: None,
+                            parts: Vec::new(),
                         })
                     }
                     Err(e) => Ok(ToolResult {
                         success: false,
                         output: String::new(),
                         error: Some(format!("Failed to get connection URL: {e}")),
+                        parts: Vec::new(),
                     }),
                 }
             }
@@ -813,6 +832,7 @@ impl Tool for ComposioTool {
                 error: Some(format!(
                     "Unknown action '{action}'. Use 'list', 'list_accounts', 'execute', or 'connect'."
                 )),
+                parts: Vec::new(),
             }),
         }
     }
@@ -0,0 +1,687 @@
+use super::traits::{Tool, ToolMetadata, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use regex::{Regex, RegexBuilder};
+use serde_json::json;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DEFAULT_MAX_RESULTS: usize = 200;
+const MAX_RESULTS: usize = 1000;
+const MAX_CONTEXT_LINES: usize = 10;
+const MAX_FILE_SIZE_BYTES: u64 = 2 * 1024 * 1024;
+const MAX_LINE_CHARS: usize = 300;
+const MAX_OUTPUT_CHARS: usize = 50_000;
+const MAX_REGEX_SIZE: usize = 1 << 20;
+/// Bytes inspected for NUL when deciding whether a file is binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// File-type names accepted by the `type` parameter, mapped to extensions.
+const FILE_TYPES: &[(&str, &[&str])] = &[
+    ("c", &["c", "h"]),
+    ("cpp", &["cc", "cpp", "cxx", "hh", "hpp", "hxx", "h"]),
+    ("css", &["css", "scss", "sass", "less"]),
+    ("go", &["go"]),
+    ("html", &["html", "htm"]),
+    ("java", &["java"]),
+    ("js", &["js", "jsx", "mjs", "cjs"]),
+    ("json", &["json", "jsonc"]),
+    ("kotlin", &["kt", "kts"]),
+    ("md", &["md", "markdown"]),
+    ("py", &["py", "pyi"]),
+    ("rb", &["rb"]),
+    ("rust", &["rs"]),
+    ("sh", &["sh", "bash", "zsh"]),
+    ("sql", &["sql"]),
+    ("swift", &["swift"]),
+    ("toml", &["toml"]),
+    ("ts", &["ts", "tsx", "mts", "cts"]),
+    ("yaml", &["yaml", "yml"]),
+];
+
+/// Search file contents by regex within the workspace, respecting `.gitignore`.
+pub struct ContentSearchTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ContentSearchTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+/// One `.gitignore` pattern, scoped to the directory that declared it.
+#[derive(Debug)]
+struct IgnoreRule {
+    /// Workspace-relative directory holding the `.gitignore`.
+    base: PathBuf,
+    pattern: glob::Pattern,
+    negated: bool,
+    dir_only: bool,
+    /// Patterns containing a `/` match against the path below `base`;
+    /// others match the entry name at any depth.
+    anchored: bool,
+}
+
+impl IgnoreRule {
+    fn parse(base: &Path, line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.strip_prefix('/').unwrap_or(line);
+        Some(Self {
+            base: base.to_path_buf(),
+            pattern: glob::Pattern::new(line).ok()?,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, rel: &Path, is_dir: bool) -> bool {
+        const OPTIONS: glob::MatchOptions = glob::MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let Ok(below) = rel.strip_prefix(&self.base) else {
+            return false;
+        };
+        if self.anchored {
+            self.pattern.matches_path_with(below, OPTIONS)
+        } else {
+            below
+                .file_name()
+                .is_some_and(|name| self.pattern.matches_with(&name.to_string_lossy(), OPTIONS))
+        }
+    }
+}
+
+fn load_ignore_rules(dir: &Path, rel: &Path) -> Vec<IgnoreRule> {
+    std::fs::read_to_string(dir.join(".gitignore"))
+        .map(|text| {
+            text.lines()
+                .filter_map(|line| IgnoreRule::parse(rel, line))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Later rules override earlier ones, so the last match decides.
+fn is_ignored(rules: &[IgnoreRule], rel: &Path, is_dir: bool) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(rel, is_dir))
+        .is_some_and(|rule| !rule.negated)
+}
+
+struct SearchOptions {
+    regex: Regex,
+    glob: Option<glob::Pattern>,
+    extensions: Option<&'static [&'static str]>,
+    context: usize,
+    max_results: usize,
+    files_only: bool,
+    include_ignored: bool,
+}
+
+#[derive(Default)]
+struct SearchOutput {
+    text: String,
+    matches: usize,
+    files: usize,
+    truncated: bool,
+}
+
+impl SearchOutput {
+    fn is_full(&self, options: &SearchOptions) -> bool {
+        let results = if options.files_only {
+            self.files
+        } else {
+            self.matches
+        };
+        results >= options.max_results || self.text.len() >= MAX_OUTPUT_CHARS
+    }
+}
+
+struct Searcher<'a> {
+    workspace: &'a Path,
+    security: &'a SecurityPolicy,
+    options: SearchOptions,
+    rules: Vec<IgnoreRule>,
+    output: SearchOutput,
+}
+
+impl Searcher<'_> {
+    /// Walk `rel` (workspace-relative) depth-first in name order. Returns
+    /// `false` once the result budget is spent.
+    fn walk_dir(&mut self, rel: &Path) -> bool {
+        let dir = self.workspace.join(rel);
+        let rules_before = self.rules.len();
+        if !self.options.include_ignored {
+            self.rules.extend(load_ignore_rules(&dir, rel));
+        }
+
+        let mut entries: Vec<_> = std::fs::read_dir(&dir)
+            .map(|entries| entries.filter_map(Result::ok).collect())
+            .unwrap_or_default();
+        entries.sort_by_key(std::fs::DirEntry::file_name);
+
+        let mut keep_going = true;
+        for entry in entries {
+            // Symlinks are not followed, so the walk cannot leave the workspace.
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_symlink() || entry.file_name() == ".git" {
+                continue;
+            }
+            let child = rel.join(entry.file_name());
+            if !self.options.include_ignored && is_ignored(&self.rules, &child, file_type.is_dir())
+            {
+                continue;
+            }
+            keep_going = if file_type.is_dir() {
+                self.walk_dir(&child)
+            } else {
+                self.search_file(&child)
+            };
+            if !keep_going {
+                break;
+            }
+        }
+
+        self.rules.truncate(rules_before);
+        keep_going
+    }
+
+    fn wants_file(&self, rel: &Path) -> bool {
+        if let Some(extensions) = self.options.extensions {
+            let extension = rel.extension().and_then(|e| e.to_str()).unwrap_or("");
+            if !extensions.contains(&extension) {
+                return false;
+            }
+        }
+        self.options.glob.as_ref().map_or(true, |glob| {
+            let name = rel.file_name().map(|n| n.to_string_lossy());
+            glob.matches_path(rel) || name.is_some_and(|name| glob.matches(&name))
+        })
+    }
+
+    fn search_file(&mut self, rel: &Path) -> bool {
+        if !self.wants_file(rel) {
+            return true;
+        }
+        let path = self.workspace.join(rel);
+        if !self.security.is_resolved_path_allowed(&path) {
+            return true;
+        }
+        match std::fs::metadata(&path) {
+            Ok(meta) if meta.len() <= MAX_FILE_SIZE_BYTES => {}
+            _ => return true,
+        }
+        let Ok(bytes) = std::fs::read(&path) else {
+            return true;
+        };
+        if bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+            return true;
+        }
+        let content = String::from_utf8_lossy(&bytes);
+        let lines: Vec<&str> = content.lines().collect();
+        let hits: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| self.options.regex.is_match(line))
+            .map(|(index, _)| index)
+            .collect();
+        if hits.is_empty() {
+            return true;
+        }
+
+        let display = rel.to_string_lossy();
+        self.output.files += 1;
+        if self.options.files_only {
+            self.output.matches += hits.len();
+            let _ = writeln!(self.output.text, "{display}");
+            if self.output.is_full(&self.options) {
+                self.output.truncated = true;
+                return false;
+            }
+            return true;
+        }
+
+        let context = self.options.context;
+        let mut printed_until = None;
+        for &hit in &hits {
+            let start = hit.saturating_sub(context);
+            let start = printed_until.map_or(start, |until: usize| start.max(until));
+            if context > 0 && printed_until.is_some_and(|until| start > until) {
+                self.output.text.push_str("--\n");
+            }
+            let end = (hit + context + 1).min(lines.len());
+            for (index, line) in lines.iter().enumerate().take(end).skip(start) {
+                let separator = if self.options.regex.is_match(line) {
+                    ':'
+                } else {
+                    '-'
+                };
+                let line = crate::util::truncate_with_ellipsis(line, MAX_LINE_CHARS);
+                let _ = writeln!(
+                    self.output.text,
+                    "{display}{separator}{}{separator}{line}",
+                    index + 1
+                );
+            }
+            printed_until = Some(end);
+            self.output.matches += 1;
+            if self.output.is_full(&self.options) {
+                self.output.truncated = true;
+                return false;
+            }
+        }
+        if context > 0 {
+            self.output.text.push_str("--\n");
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl Tool for ContentSearchTool {
+    fn name(&self) -> &str {
+        "content_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search file contents in the workspace with a regular expression, like ripgrep. \
+         Skips .gitignore'd, binary and oversized files. Returns 'path:line:text' matches \
+         (context lines use '-'). Filter with 'type' (e.g. 'rust', 'py') or 'glob' (e.g. '*.toml')."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let types: Vec<&str> = FILE_TYPES.iter().map(|(name, _)| *name).collect();
+        json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Regular expression (Rust regex syntax) to search for"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Relative file or directory to search (default: workspace root)"
+                },
+                "type": {
+                    "type": "string",
+                    "enum": types,
+                    "description": "Only search files of this type"
+                },
+                "glob": {
+                    "type": "string",
+                    "description": "Only search files whose name or relative path matches this glob, e.g. '*.rs' or 'src/**/*.ts'"
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Match case-insensitively (default: false)"
+                },
+                "context": {
+                    "type": "integer",
+                    "description": "Lines of context to show around each match (default: 0, max: 10)"
+                },
+                "files_only": {
+                    "type": "boolean",
+                    "description": "List matching files instead of matching lines (default: false)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum matches (or files with files_only) to return (default: 200, max: 1000)"
+                },
+                "include_ignored": {
+                    "type": "boolean",
+                    "description": "Also search files excluded by .gitignore (default: false)"
+                }
+            },
+            "required": ["pattern"]
+        })
+    }
+
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let pattern = args
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' parameter"))?;
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let flag = |name: &str| {
+            args.get(name)
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false)
+        };
+        let number = |name: &str| {
+            args.get(name)
+                .and_then(serde_json::Value::as_u64)
+                .map(|n| usize::try_from(n).unwrap_or(usize::MAX))
+        };
+
+        // Rate limit check (fast path)
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult::err(format!(
+                "Path not allowed by security policy: {path}"
+            )));
+        }
+
+        let regex = match RegexBuilder::new(pattern)
+            .case_insensitive(flag("case_insensitive"))
+            .size_limit(MAX_REGEX_SIZE)
+            .build()
+        {
+            Ok(regex) => regex,
+            Err(e) => return Ok(ToolResult::err(format!("Invalid regex pattern: {e}"))),
+        };
+
+        let extensions = match args.get("type").and_then(|v| v.as_str()) {
+            None => None,
+            Some(name) => match FILE_TYPES.iter().find(|(known, _)| *known == name) {
+                Some((_, extensions)) => Some(*extensions),
+                None => {
+                    let known: Vec<&str> = FILE_TYPES.iter().map(|(name, _)| *name).collect();
+                    return Ok(ToolResult::err(format!(
+                        "Unknown file type '{name}'. Known types: {}",
+                        known.join(", ")
+                    )));
+                }
+            },
+        };
+
+        let glob = match args.get("glob").and_then(|v| v.as_str()) {
+            None => None,
+            Some(glob) => match glob::Pattern::new(glob) {
+                Ok(glob) => Some(glob),
+                Err(e) => return Ok(ToolResult::err(format!("Invalid glob pattern: {e}"))),
+            },
+        };
+
+        // Record action to consume rate limit budget
+        if !self.security.record_action() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        let workspace = match tokio::fs::canonicalize(&self.security.workspace_dir).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult::err(format!(
+                    "Cannot resolve workspace directory: {e}"
+                )))
+            }
+        };
+        let resolved = match tokio::fs::canonicalize(workspace.join(path)).await {
+            Ok(p) => p,
+            Err(e) => return Ok(ToolResult::err(format!("Failed to resolve path: {e}"))),
+        };
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Ok(ToolResult::err(format!(
+                "Resolved path escapes workspace: {}",
+                resolved.display()
+            )));
+        }
+        let Ok(start) = resolved.strip_prefix(&workspace).map(Path::to_path_buf) else {
+            return Ok(ToolResult::err(format!(
+                "Path is outside the workspace: {path}"
+            )));
+        };
+
+        let options = SearchOptions {
+            regex,
+            glob,
+            extensions,
+            context: number("context").unwrap_or(0).min(MAX_CONTEXT_LINES),
+            max_results: number("max_results")
+                .unwrap_or(DEFAULT_MAX_RESULTS)
+                .clamp(1, MAX_RESULTS),
+            files_only: flag("files_only"),
+            include_ignored: flag("include_ignored"),
+        };
+        let security = self.security.clone();
+        let outcome = tokio::task::spawn_blocking(move || {
+            let mut searcher = Searcher {
+                workspace: &workspace,
+                security: &security,
+                options,
+                rules: Vec::new(),
+                output: SearchOutput::default(),
+            };
+            if resolved.is_dir() {
+                // .gitignore files above the search root still apply to it.
+                if !searcher.options.include_ignored {
+                    let mut ancestor = PathBuf::new();
+                    for component in start.components() {
+                        let rules = load_ignore_rules(&workspace.join(&ancestor), &ancestor);
+                        searcher.rules.extend(rules);
+                        ancestor.push(component);
+                    }
+                }
+                searcher.walk_dir(&start);
+            } else {
+                searcher.search_file(&start);
+            }
+            searcher.output
+        })
+        .await?;
+
+        let output = if outcome.files == 0 {
+            format!("No matches for pattern '{pattern}' in {path}.")
+        } else {
+            let mut buf = outcome.text.trim_end_matches("--\n").trim_end().to_string();
+            if outcome.truncated {
+                let _ = write!(
+                    buf,
+                    "\n\n[Results truncated: narrow the search with 'path', 'type' or 'glob', or raise 'max_results']"
+                );
+            }
+            let _ = write!(
+                buf,
+                "\n\nTotal: {} matches in {} files",
+                outcome.matches, outcome.files
+            );
+            buf
+        };
+
+        Ok(ToolResult::ok(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(workspace: PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn write(dir: &TempDir, rel: &str, content: &str) {
+        let path = dir.path().join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn content_search_name_and_schema() {
+        let tool = ContentSearchTool::new(test_security(std::env::temp_dir()));
+        assert_eq!(tool.name(), "content_search");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["pattern"].is_object());
+        assert!(schema["properties"]["type"]["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("rust")));
+        assert_eq!(schema["required"], json!(["pattern"]));
+    }
+
+    #[test]
+    fn gitignore_rules_follow_git_semantics() {
+        let base = Path::new("");
+        let rules: Vec<IgnoreRule> = ["target/", "*.log", "!keep.log", "/build", "docs/*.tmp"]
+            .iter()
+            .filter_map(|line| IgnoreRule::parse(base, line))
+            .collect();
+
+        assert!(is_ignored(&rules, Path::new("crates/a/target"), true));
+        assert!(!is_ignored(&rules, Path::new("target"), false));
+        assert!(is_ignored(&rules, Path::new("src/debug.log"), false));
+        assert!(!is_ignored(&rules, Path::new("src/keep.log"), false));
+        assert!(is_ignored(&rules, Path::new("build"), true));
+        assert!(!is_ignored(&rules, Path::new("src/build"), true));
+        assert!(is_ignored(&rules, Path::new("docs/a.tmp"), false));
+        assert!(!is_ignored(&rules, Path::new("docs/sub/a.tmp"), false));
+    }
+
+    #[tokio::test]
+    async fn content_search_finds_matches_with_line_numbers() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "src/lib.rs", "fn alpha() {}\nfn beta() {}\n");
+        write(&dir, "notes.md", "alpha notes\n");
+
+        let tool = ContentSearchTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool
+            .execute(json!({"pattern": "fn \\w+\\(", "type": "rust"}))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("src/lib.rs:1:fn alpha() {}"));
+        assert!(result.output.contains("src/lib.rs:2:fn beta() {}"));
+        assert!(!result.output.contains("notes.md"));
+        assert!(result.output.contains("Total: 2 matches in 1 files"));
+    }
+
+    #[tokio::test]
+    async fn content_search_shows_context_and_respects_gitignore() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, ".gitignore", "target/\n");
+        write(&dir, "target/out.txt", "needle\n");
+        write(&dir, "a.txt", "one\ntwo\nneedle\nfour\nfive\n");
+
+        let tool = ContentSearchTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool
+            .execute(json!({"pattern": "needle", "context": 1}))
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result
+            .output
+            .contains("a.txt-2-two\na.txt:3:needle\na.txt-4-four"));
+        assert!(!result.output.contains("target"));
+
+        let result = tool
+            .execute(json!({"pattern": "needle", "include_ignored": true, "files_only": true}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("target/out.txt"));
+    }
+
+    #[tokio::test]
+    async fn content_search_caps_results() {
+        let dir = TempDir::new().unwrap();
+        write(&dir, "many.txt", &"hit\n".repeat(50));
+
+        let tool = ContentSearchTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool
+            .execute(json!({"pattern": "hit", "max_results": 5}))
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.output.matches("many.txt:").count(), 5);
+        assert!(result.output.contains("[Results truncated"));
+    }
+
+    #[tokio::test]
+    async fn content_search_skips_binary_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("blob.bin"), b"needle\0\x01").unwrap();
+
+        let tool = ContentSearchTool::new(test_security(dir.path().to_path_buf()));
+        let result = tool.execute(json!({"pattern": "needle"})).await.unwrap();
+
+        assert!(result.success);
+        assert!(result.output.contains("No matches"));
+    }
+
+    #[tokio::test]
+    async fn content_search_rejects_paths_outside_workspace() {
+        let dir = TempDir::new().unwrap();
+        let tool = ContentSearchTool::new(test_security(dir.path().to_path_buf()));
+
+        let result = tool
+            .execute(json!({"pattern": "root", "path": "../../etc"}))
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn content_search_rejects_invalid_regex() {
+        let dir = TempDir::new().unwrap();
+        let tool = ContentSearchTool::new(test_security(dir.path().to_path_buf()));
+
+        let result = tool.execute(json!({"pattern": "(unclosed"})).await.unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Invalid regex"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn content_search_does_not_follow_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let root = TempDir::new().unwrap();
+        let workspace = root.path().join("workspace");
+        let outside = root.path().join("outside");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret.txt"), "needle").unwrap();
+        symlink(&outside, workspace.join("escape")).unwrap();
+
+        let tool = ContentSearchTool::new(test_security(workspace));
+        let result = tool.execute(json!({"pattern": "needle"})).await.unwrap();
+
+        assert!(result.success);
+        assert!(result.output.contains("No matches"));
+    }
+}
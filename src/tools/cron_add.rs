@@ -1,6 +1,7 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::config::Config;
 use crate::cron::{self, DeliveryConfig, JobType, Schedule, SessionTarget};
+use crate::providers::ResponseFormat;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -24,6 +25,7 @@ impl CronAddTool {
                 error: Some(format!(
                     "Security policy: read-only mode, cannot perform '{action}'"
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -32,6 +34,7 @@ impl CronAddTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -40,6 +43,7 @@ impl CronAddTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -71,6 +75,10 @@ impl Tool for CronAddTool {
                 "prompt": { "type": "string" },
                 "session_target": { "type": "string", "enum": ["isolated", "main"] },
                 "model": { "type": "string" },
+                "response_format": {
+                    "type": "object",
+                    "description": "Agent jobs only: {name, schema} JSON schema the final reply must satisfy"
+                },
                 "delivery": { "type": "object" },
                 "delete_after_run": { "type": "boolean" },
                 "approved": {
@@ -83,12 +91,17 @@ impl Tool for CronAddTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some("cron is disabled by config (cron.enabled=false)".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -100,6 +113,7 @@ impl Tool for CronAddTool {
                         success: false,
                         output: String::new(),
                         error: Some(format!("Invalid schedule: {e}")),
+                        parts: Vec::new(),
                     });
                 }
             },
@@ -108,6 +122,7 @@ impl Tool for CronAddTool {
                     success: false,
                     output: String::new(),
                     error: Some("Missing 'schedule' parameter".to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -125,6 +140,7 @@ impl Tool for CronAddTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Invalid job_type: {other}")),
+                    parts: Vec::new(),
                 });
             }
             None => {
@@ -155,6 +171,7 @@ impl Tool for CronAddTool {
                             success: false,
                             output: String::new(),
                             error: Some("Missing 'command' for shell job".to_string()),
+                            parts: Vec::new(),
                         });
                     }
                 };
@@ -164,6 +181,7 @@ impl Tool for CronAddTool {
                         success: false,
                         output: String::new(),
                         error: Some(reason),
+                        parts: Vec::new(),
                     });
                 }
 
@@ -181,6 +199,7 @@ impl Tool for CronAddTool {
                             success: false,
                             output: String::new(),
                             error: Some("Missing 'prompt' for agent job".to_string()),
+                            parts: Vec::new(),
                         });
                     }
                 };
@@ -193,6 +212,7 @@ impl Tool for CronAddTool {
                                 success: false,
                                 output: String::new(),
                                 error: Some(format!("Invalid session_target: {e}")),
+                                parts: Vec::new(),
                             });
                         }
                     },
@@ -204,6 +224,16 @@ impl Tool for CronAddTool {
                     .and_then(serde_json::Value::as_str)
                     .map(str::to_string);
 
+                let response_format = match args.get("response_format") {
+                    Some(v) => match serde_json::from_value::<ResponseFormat>(v.clone()) {
+                        Ok(format) => Some(format),
+                        Err(e) => {
+                            return Ok(ToolResult::err(format!("Invalid response_format: {e}")));
+                        }
+                    },
+                    None => None,
+                };
+
                 let delivery = match args.get("delivery") {
                     Some(v) => match serde_json::from_value::<DeliveryConfig>(v.clone()) {
                         Ok(cfg) => Some(cfg),
@@ -212,6 +242,7 @@ impl Tool for CronAddTool {
                                 success: false,
                                 output: String::new(),
                                 error: Some(format!("Invalid delivery config: {e}")),
+                                parts: Vec::new(),
                             });
                         }
                     },
@@ -229,6 +260,7 @@ impl Tool for CronAddTool {
                     prompt,
                     session_target,
                     model,
+                    response_format,
                     delivery,
                     delete_after_run,
                 )
@@ -247,11 +279,13 @@ impl Tool for CronAddTool {
                     "enabled": job.enabled
                 }))?,
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(e.to_string()),
+                parts: Vec::new(),
             }),
         }
     }
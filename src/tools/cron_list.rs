@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::config::Config;
 use crate::cron;
 use async_trait::async_trait;
@@ -33,12 +33,17 @@ impl Tool for CronListTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some("cron is disabled by config (cron.enabled=false)".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -47,11 +52,13 @@ impl Tool for CronListTool {
                 success: true,
                 output: serde_json::to_string_pretty(&jobs)?,
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(e.to_string()),
+                parts: Vec::new(),
             }),
         }
     }
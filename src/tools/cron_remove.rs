@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::config::Config;
 use crate::cron;
 use crate::security::SecurityPolicy;
@@ -24,6 +24,7 @@ impl CronRemoveTool {
                 error: Some(format!(
                     "Security policy: read-only mode, cannot perform '{action}'"
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -32,6 +33,7 @@ impl CronRemoveTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -40,6 +42,7 @@ impl CronRemoveTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -67,12 +70,17 @@ impl Tool for CronRemoveTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_idempotent(true)
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some("cron is disabled by config (cron.enabled=false)".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -83,6 +91,7 @@ impl Tool for CronRemoveTool {
                     success: false,
                     output: String::new(),
                     error: Some("Missing 'job_id' parameter".to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -96,11 +105,13 @@ impl Tool for CronRemoveTool {
                 success: true,
                 output: format!("Removed cron job {job_id}"),
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(e.to_string()),
+                parts: Vec::new(),
             }),
         }
     }
@@ -49,6 +49,7 @@ impl Tool for CronRunTool {
                 success: false,
                 output: String::new(),
                 error: Some("cron is disabled by config (cron.enabled=false)".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -59,6 +60,7 @@ impl Tool for CronRunTool {
                     success: false,
                     output: String::new(),
                     error: Some("Missing 'job_id' parameter".to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -72,6 +74,7 @@ impl Tool for CronRunTool {
                 success: false,
                 output: String::new(),
                 error: Some("Security policy: read-only mode, cannot perform 'cron_run'".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -80,6 +83,7 @@ impl Tool for CronRunTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -90,6 +94,7 @@ impl Tool for CronRunTool {
                     success: false,
                     output: String::new(),
                     error: Some(e.to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -103,6 +108,7 @@ impl Tool for CronRunTool {
                     success: false,
                     output: String::new(),
                     error: Some(reason),
+                    parts: Vec::new(),
                 });
             }
         }
@@ -112,6 +118,7 @@ impl Tool for CronRunTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -145,6 +152,7 @@ impl Tool for CronRunTool {
             } else {
                 Some("cron job execution failed".to_string())
             },
+            parts: Vec::new(),
         })
     }
 }
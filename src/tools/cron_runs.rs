@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::config::Config;
 use crate::cron;
 use async_trait::async_trait;
@@ -50,12 +50,17 @@ impl Tool for CronRunsTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some("cron is disabled by config (cron.enabled=false)".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -66,6 +71,7 @@ impl Tool for CronRunsTool {
                     success: false,
                     output: String::new(),
                     error: Some("Missing 'job_id' parameter".to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -94,12 +100,14 @@ impl Tool for CronRunsTool {
                     success: true,
                     output: serde_json::to_string_pretty(&runs)?,
                     error: None,
+                    parts: Vec::new(),
                 })
             }
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(e.to_string()),
+                parts: Vec::new(),
             }),
         }
     }
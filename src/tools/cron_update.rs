@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::config::Config;
 use crate::cron::{self, CronJobPatch};
 use crate::security::SecurityPolicy;
@@ -24,6 +24,7 @@ impl CronUpdateTool {
                 error: Some(format!(
                     "Security policy: read-only mode, cannot perform '{action}'"
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -32,6 +33,7 @@ impl CronUpdateTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -40,6 +42,7 @@ impl CronUpdateTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -73,12 +76,17 @@ impl Tool for CronUpdateTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_idempotent(true)
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some("cron is disabled by config (cron.enabled=false)".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -89,6 +97,7 @@ impl Tool for CronUpdateTool {
                     success: false,
                     output: String::new(),
                     error: Some("Missing 'job_id' parameter".to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -100,6 +109,7 @@ impl Tool for CronUpdateTool {
                     success: false,
                     output: String::new(),
                     error: Some("Missing 'patch' parameter".to_string()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -111,6 +121,7 @@ impl Tool for CronUpdateTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Invalid patch payload: {e}")),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -125,6 +136,7 @@ impl Tool for CronUpdateTool {
                     success: false,
                     output: String::new(),
                     error: Some(reason),
+                    parts: Vec::new(),
                 });
             }
         }
@@ -138,11 +150,13 @@ impl Tool for CronUpdateTool {
                 success: true,
                 output: serde_json::to_string_pretty(&job)?,
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(e.to_string()),
+                parts: Vec::new(),
             }),
         }
     }
@@ -1,9 +1,8 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::agent::loop_::run_tool_call_loop_with_policy;
 use crate::config::DelegateAgentConfig;
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
-use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -159,6 +158,10 @@ impl Tool for DelegateTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_network()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let agent_name = args
             .get("agent")
@@ -171,6 +174,7 @@ impl Tool for DelegateTool {
                 success: false,
                 output: String::new(),
                 error: Some("'agent' parameter must not be empty".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -185,6 +189,7 @@ impl Tool for DelegateTool {
                 success: false,
                 output: String::new(),
                 error: Some("'prompt' parameter must not be empty".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -211,6 +216,7 @@ impl Tool for DelegateTool {
                             available.join(", ")
                         }
                     )),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -226,17 +232,19 @@ impl Tool for DelegateTool {
                     depth = self.depth,
                     max = agent_config.max_depth
                 )),
+                parts: Vec::new(),
             });
         }
 
         if let Err(error) = self
             .security
-            .enforce_tool_operation(ToolOperation::Act, "delegate")
+            .enforce_tool_operation(self.metadata().operation(), "delegate")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(error),
+                parts: Vec::new(),
             });
         }
 
@@ -262,6 +270,7 @@ impl Tool for DelegateTool {
                         "Failed to create provider '{}' for agent '{agent_name}': {e}",
                         agent_config.provider
                     )),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -309,6 +318,7 @@ impl Tool for DelegateTool {
                     error: Some(format!(
                         "Agent '{agent_name}' timed out after {DELEGATE_TIMEOUT_SECS}s"
                     )),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -328,17 +338,26 @@ impl Tool for DelegateTool {
                         model = agent_config.model
                     ),
                     error: None,
+                    parts: Vec::new(),
                 })
             }
-            Err(e) => Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Agent '{agent_name}' failed: {e}",)),
-            }),
+            Err(e) => Ok(ToolResult::err(format!("Agent '{agent_name}' failed: {e}"))),
         }
     }
 }
 
+/// Whether an `allowed_tools` entry admits `tool`: either its exact name or one
+/// of the metadata selectors `@read_only`, `@offline` and `@idempotent`.
+fn allowlist_admits(entry: &str, tool: &dyn Tool) -> bool {
+    let metadata = tool.metadata();
+    match entry {
+        "@read_only" => metadata.is_read_only(),
+        "@offline" => !metadata.network,
+        "@idempotent" => metadata.idempotent,
+        name => name == tool.name(),
+    }
+}
+
 impl DelegateTool {
     async fn execute_agentic(
         &self,
@@ -355,6 +374,7 @@ impl DelegateTool {
                 error: Some(format!(
                     "Agent '{agent_name}' has agentic=true but allowed_tools is empty"
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -368,7 +388,11 @@ impl DelegateTool {
         let sub_tools: Vec<Box<dyn Tool>> = self
             .parent_tools
             .iter()
-            .filter(|tool| allowed.contains(tool.name()))
+            .filter(|tool| {
+                allowed
+                    .iter()
+                    .any(|entry| allowlist_admits(entry, tool.as_ref()))
+            })
             .filter(|tool| tool.name() != "delegate")
             .map(|tool| Box::new(ToolArcRef::new(tool.clone())) as Box<dyn Tool>)
             .collect();
@@ -381,6 +405,7 @@ impl DelegateTool {
                     "Agent '{agent_name}' has no executable tools after filtering allowlist ({})",
                     agent_config.allowed_tools.join(", ")
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -435,12 +460,14 @@ impl DelegateTool {
                         model = agent_config.model
                     ),
                     error: None,
+                    parts: Vec::new(),
                 })
             }
             Ok(Err(e)) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Agent '{agent_name}' failed: {e}")),
+                parts: Vec::new(),
             }),
             Err(_) => Ok(ToolResult {
                 success: false,
@@ -448,6 +475,7 @@ impl DelegateTool {
                 error: Some(format!(
                     "Agent '{agent_name}' timed out after {DELEGATE_AGENTIC_TIMEOUT_SECS}s"
                 )),
+                parts: Vec::new(),
             }),
         }
     }
@@ -575,6 +603,7 @@ mod tests {
                 success: true,
                 output: format!("echo:{value}"),
                 error: None,
+                parts: Vec::new(),
             })
         }
     }
@@ -604,6 +633,7 @@ mod tests {
                 Ok(ChatResponse {
                     text: Some("done".to_string()),
                     tool_calls: Vec::new(),
+                    usage: None,
                 })
             } else {
                 Ok(ChatResponse {
@@ -613,6 +643,7 @@ mod tests {
                         name: "echo_tool".to_string(),
                         arguments: "{\"value\":\"ping\"}".to_string(),
                     }],
+                    usage: None,
                 })
             }
         }
@@ -645,6 +676,7 @@ mod tests {
                     name: "echo_tool".to_string(),
                     arguments: "{\"value\":\"x\"}".to_string(),
                 }],
+                usage: None,
             })
         }
     }
@@ -989,6 +1021,18 @@ mod tests {
             .contains("allowed_tools is empty"));
     }
 
+    #[test]
+    fn allowlist_selectors_match_tool_metadata() {
+        assert!(allowlist_admits("echo_tool", &EchoTool));
+        assert!(allowlist_admits("@offline", &EchoTool));
+        assert!(!allowlist_admits("@read_only", &EchoTool));
+        assert!(!allowlist_admits("@idempotent", &EchoTool));
+        assert!(!allowlist_admits(
+            "@offline",
+            &DelegateTool::new(HashMap::new(), None, test_security())
+        ));
+    }
+
     #[tokio::test]
     async fn agentic_mode_rejects_unmatched_allowed_tools() {
         let mut agents = HashMap::new();
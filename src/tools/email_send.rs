@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::config::EmailToolConfig;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -187,6 +187,10 @@ impl Tool for EmailSendTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_network()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         // ── Security gates ──────────────────────────────────────
         if !self.security.can_act() {
@@ -194,6 +198,7 @@ impl Tool for EmailSendTool {
                 success: false,
                 output: String::new(),
                 error: Some("Action blocked: autonomy is read-only".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -202,6 +207,7 @@ impl Tool for EmailSendTool {
                 success: false,
                 output: String::new(),
                 error: Some("Action blocked: rate limit exceeded".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -242,6 +248,7 @@ impl Tool for EmailSendTool {
                     "Recipient '{}' is not in allowed_recipients list",
                     to
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -251,6 +258,7 @@ impl Tool for EmailSendTool {
                 success: false,
                 output: String::new(),
                 error: Some("SMTP host is not configured".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -259,6 +267,7 @@ impl Tool for EmailSendTool {
                 success: false,
                 output: String::new(),
                 error: Some("From address is not configured".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -311,6 +320,7 @@ impl Tool for EmailSendTool {
                 error: Some(
                     "SMTP password is not configured (set in config.toml or EMAIL_TOOL_PASSWORD env var)".into(),
                 ),
+                parts: Vec::new(),
             });
         }
 
@@ -345,11 +355,13 @@ impl Tool for EmailSendTool {
                 success: true,
                 output: format!("Email sent successfully to {}", to),
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Failed to send email: {}", e)),
+                parts: Vec::new(),
             }),
         }
     }
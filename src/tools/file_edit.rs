@@ -0,0 +1,762 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::audit::FileAccessLog;
+use crate::security::{AuditLogger, SecurityPolicy};
+use crate::util::truncate_with_ellipsis;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Instant;
+
+const MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_PREVIEW_CHARS: usize = 4_000;
+
+/// Edit files in place with exact-string replacement or unified-diff patches
+pub struct FileEditTool {
+    security: Arc<SecurityPolicy>,
+    audit: Option<Arc<AuditLogger>>,
+}
+
+impl FileEditTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            security,
+            audit: None,
+        }
+    }
+
+    /// Record applied edits and policy refusals in the audit log.
+    pub fn with_audit_logger(mut self, logger: Arc<AuditLogger>) -> Self {
+        self.audit = Some(logger);
+        self
+    }
+
+    fn audit(
+        &self,
+        path: &str,
+        operation: &str,
+        allowed: bool,
+        started: Instant,
+        error: Option<&str>,
+    ) {
+        let Some(logger) = &self.audit else {
+            return;
+        };
+        let entry = FileAccessLog {
+            tool: "file_edit",
+            path,
+            operation,
+            allowed,
+            success: allowed && error.is_none(),
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            error,
+        };
+        if let Err(e) = logger.log_file_event(entry) {
+            tracing::warn!("Failed to write file_edit audit event: {e}");
+        }
+    }
+}
+
+/// One edited region, rendered in the preview.
+#[derive(Debug, PartialEq, Eq)]
+struct Change {
+    /// 1-based line where the region starts in the original file.
+    line: usize,
+    removed: Vec<String>,
+    added: Vec<String>,
+}
+
+/// Replace `old` with `new`, requiring a unique match unless `replace_all`.
+fn apply_replace(
+    content: &str,
+    old: &str,
+    new: &str,
+    replace_all: bool,
+) -> Result<(String, Vec<Change>), String> {
+    if old.is_empty() {
+        return Err("old_string must not be empty".into());
+    }
+    if old == new {
+        return Err("old_string and new_string are identical".into());
+    }
+
+    let matches: Vec<usize> = content.match_indices(old).map(|(index, _)| index).collect();
+    match matches.len() {
+        0 => return Err("old_string was not found in the file; re-read the file and copy the exact text, including whitespace".into()),
+        1 => {}
+        count if !replace_all => {
+            return Err(format!(
+                "old_string matches {count} locations; include more surrounding context to make it unique, or set replace_all"
+            ));
+        }
+        _ => {}
+    }
+
+    let changes = matches
+        .iter()
+        .map(|&index| Change {
+            line: content[..index].matches('\n').count() + 1,
+            removed: old.lines().map(str::to_string).collect(),
+            added: new.lines().map(str::to_string).collect(),
+        })
+        .collect();
+    Ok((content.replace(old, new), changes))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+#[derive(Debug, Default)]
+struct Hunk {
+    /// 1-based start line on the old side, when the header carries one.
+    old_start: Option<usize>,
+    /// Remaining (old, new) line counts from the header, when it carries them.
+    remaining: Option<(usize, usize)>,
+    lines: Vec<HunkLine>,
+}
+
+impl Hunk {
+    fn from_header(header: &str) -> Self {
+        // `@@ -12,5 +12,7 @@ optional section heading`
+        let mut old_range = None;
+        let mut new_range = None;
+        for part in header.trim_start_matches('@').split_whitespace() {
+            if part.starts_with("@@") {
+                break;
+            }
+            if let Some(range) = part.strip_prefix('-') {
+                old_range = parse_range(range);
+            } else if let Some(range) = part.strip_prefix('+') {
+                new_range = parse_range(range);
+            }
+        }
+        Self {
+            old_start: old_range.map(|(start, _)| start),
+            remaining: old_range
+                .zip(new_range)
+                .map(|((_, old), (_, new))| (old, new)),
+            lines: Vec::new(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.remaining == Some((0, 0))
+    }
+
+    fn push(&mut self, line: HunkLine) {
+        if let Some((old, new)) = self.remaining.as_mut() {
+            match line {
+                HunkLine::Context(_) => {
+                    *old = old.saturating_sub(1);
+                    *new = new.saturating_sub(1);
+                }
+                HunkLine::Remove(_) => *old = old.saturating_sub(1),
+                HunkLine::Add(_) => *new = new.saturating_sub(1),
+            }
+        }
+        self.lines.push(line);
+    }
+
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+}
+
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// Parse a single-file unified diff. `---`/`+++` headers are optional, and
+/// hunk headers without line numbers (`@@ @@`) are accepted; such hunks are
+/// located purely by their context.
+fn parse_patch(patch: &str) -> Result<Vec<Hunk>, String> {
+    let mut hunks = Vec::new();
+    let mut current: Option<Hunk> = None;
+    let mut file_headers = 0;
+    let mut lines = patch.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if line.starts_with("@@") {
+            hunks.extend(current.replace(Hunk::from_header(line)));
+            continue;
+        }
+
+        let next_is_new_file = lines.peek().is_some_and(|next| next.starts_with("+++ "));
+        if line.starts_with("--- ") && next_is_new_file {
+            hunks.extend(current.take());
+            continue;
+        }
+
+        let Some(hunk) = current.as_mut() else {
+            if line.starts_with("+++ ") {
+                file_headers += 1;
+            }
+            continue;
+        };
+        // Header counts are often off by a line or two in generated patches,
+        // so they only decide where unprefixed lines stop belonging to a hunk.
+        if hunk.is_complete() && !line.starts_with(['+', '-', ' ']) {
+            hunks.extend(current.take());
+            continue;
+        }
+        if let Some(text) = line.strip_prefix('+') {
+            hunk.push(HunkLine::Add(text.to_string()));
+        } else if let Some(text) = line.strip_prefix('-') {
+            hunk.push(HunkLine::Remove(text.to_string()));
+        } else if line.starts_with('\\') {
+            // "\ No newline at end of file"
+        } else {
+            // Context lines should start with a space, but editors and models
+            // often strip it from blank or unindented lines.
+            let text = line.strip_prefix(' ').unwrap_or(line);
+            hunk.push(HunkLine::Context(text.to_string()));
+        }
+    }
+    hunks.extend(current);
+
+    if file_headers > 1 {
+        return Err("patch touches more than one file; send one patch per file".into());
+    }
+    if hunks.iter().all(|hunk| hunk.lines.is_empty()) {
+        return Err("patch contains no hunks".into());
+    }
+    Ok(hunks)
+}
+
+/// Line comparisons from strict to lenient, tried in order when locating a hunk.
+const LINE_MATCHERS: [fn(&str, &str) -> bool; 3] = [
+    |a, b| a == b,
+    |a, b| a.trim_end() == b.trim_end(),
+    |a, b| a.split_whitespace().eq(b.split_whitespace()),
+];
+
+/// Find where `old` occurs in `lines`, preferring the position closest to
+/// `expected` and never starting before `min_start`.
+fn locate_hunk(lines: &[String], old: &[&str], expected: usize, min_start: usize) -> Option<usize> {
+    let last_start = lines.len().checked_sub(old.len())?;
+    if min_start > last_start {
+        return None;
+    }
+    let expected = expected.clamp(min_start, last_start);
+    let matches_at = |start: usize, matcher: fn(&str, &str) -> bool| {
+        old.iter()
+            .zip(&lines[start..])
+            .all(|(want, have)| matcher(want, have))
+    };
+
+    LINE_MATCHERS.into_iter().find_map(|matcher| {
+        (0..=last_start - min_start).find_map(|distance| {
+            let after = expected + distance;
+            if after <= last_start && matches_at(after, matcher) {
+                return Some(after);
+            }
+            let before = expected.checked_sub(distance)?;
+            (before >= min_start && distance > 0 && matches_at(before, matcher)).then_some(before)
+        })
+    })
+}
+
+/// Apply a unified diff. Hunks are applied in order; each is matched exactly
+/// first, then ignoring trailing whitespace, then ignoring all whitespace
+/// differences, searching outward from the line its header names.
+fn apply_patch(content: &str, patch: &str) -> Result<(String, Vec<Change>), String> {
+    let hunks = parse_patch(patch)?;
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut changes = Vec::new();
+    let mut min_start = 0;
+    let (mut grown, mut shrunk) = (0usize, 0usize);
+
+    for (number, hunk) in hunks.iter().enumerate() {
+        let old = hunk.old_lines();
+        let expected = hunk.old_start.map_or(min_start, |start| {
+            (start.saturating_sub(1) + grown).saturating_sub(shrunk)
+        });
+        let position = if old.is_empty() {
+            expected.clamp(min_start, lines.len())
+        } else {
+            locate_hunk(&lines, &old, expected, min_start).ok_or_else(|| {
+                let near = hunk
+                    .old_start
+                    .map(|start| format!(" near line {start}"))
+                    .unwrap_or_default();
+                format!(
+                    "hunk {} does not match the file{near}; re-read the file and regenerate the patch",
+                    number + 1
+                )
+            })?
+        };
+
+        let mut replacement = Vec::new();
+        let mut change = Change {
+            line: (position + shrunk).saturating_sub(grown) + 1,
+            removed: Vec::new(),
+            added: Vec::new(),
+        };
+        let mut cursor = position;
+        for line in &hunk.lines {
+            match line {
+                HunkLine::Context(_) => {
+                    // Keep the file's own text so fuzzy matches don't rewrite
+                    // whitespace on untouched lines.
+                    replacement.push(lines[cursor].clone());
+                    cursor += 1;
+                }
+                HunkLine::Remove(_) => {
+                    change.removed.push(lines[cursor].clone());
+                    cursor += 1;
+                }
+                HunkLine::Add(text) => {
+                    replacement.push(text.clone());
+                    change.added.push(text.clone());
+                }
+            }
+        }
+
+        let (old_len, new_len) = (cursor - position, replacement.len());
+        lines.splice(position..cursor, replacement);
+        min_start = position + new_len;
+        grown += new_len;
+        shrunk += old_len;
+        changes.push(change);
+    }
+
+    let line_ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut patched = lines.join(line_ending);
+    if content.ends_with('\n') || (content.is_empty() && !patched.is_empty()) {
+        patched.push_str(line_ending);
+    }
+    Ok((patched, changes))
+}
+
+fn render_preview(changes: &[Change]) -> String {
+    let mut preview = String::new();
+    for change in changes {
+        let _ = writeln!(preview, "@@ line {} @@", change.line);
+        for line in &change.removed {
+            let _ = writeln!(preview, "-{line}");
+        }
+        for line in &change.added {
+            let _ = writeln!(preview, "+{line}");
+        }
+    }
+    truncate_with_ellipsis(&preview, MAX_PREVIEW_CHARS)
+}
+
+#[async_trait]
+impl Tool for FileEditTool {
+    fn name(&self) -> &str {
+        "file_edit"
+    }
+
+    fn description(&self) -> &str {
+        "Edit an existing file in the workspace without rewriting it: replace an exact string (old_string/new_string) or apply a unified diff (patch). Set dry_run to preview the change."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Relative path to the file within the workspace"
+                },
+                "old_string": {
+                    "type": "string",
+                    "description": "Exact text to replace, including whitespace. Must match exactly once unless replace_all is set"
+                },
+                "new_string": {
+                    "type": "string",
+                    "description": "Replacement text for old_string"
+                },
+                "replace_all": {
+                    "type": "boolean",
+                    "description": "Replace every occurrence of old_string (default: false)"
+                },
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff for this file (one or more @@ hunks). Use instead of old_string/new_string"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Preview the change without writing the file (default: false)"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let started = Instant::now();
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let old_string = args.get("old_string").and_then(|v| v.as_str());
+        let new_string = args.get("new_string").and_then(|v| v.as_str());
+        let patch = args.get("patch").and_then(|v| v.as_str());
+        let replace_all = args
+            .get("replace_all")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let dry_run = args
+            .get("dry_run")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        match (
+            old_string.is_some() || new_string.is_some(),
+            patch.is_some(),
+        ) {
+            (true, true) => {
+                return Ok(ToolResult::err(
+                    "Provide either old_string/new_string or patch, not both",
+                ))
+            }
+            (false, false) => {
+                return Ok(ToolResult::err(
+                    "Provide old_string and new_string, or a unified diff in patch",
+                ))
+            }
+            (true, false) if old_string.is_none() || new_string.is_none() => {
+                return Ok(ToolResult::err(
+                    "old_string and new_string must be provided together",
+                ))
+            }
+            _ => {}
+        }
+
+        if !dry_run && !self.security.can_act() {
+            return Ok(ToolResult::err("Action blocked: autonomy is read-only"));
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        // Security check: validate path is within workspace
+        if !self.security.is_path_allowed(path) {
+            let message = format!("Path not allowed by security policy: {path}");
+            self.audit(path, "edit", false, started, Some(&message));
+            return Ok(ToolResult::err(message));
+        }
+
+        // Record action BEFORE canonicalization so probing for file existence
+        // costs rate limit budget, as in file_read.
+        if !self.security.record_action() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+
+        if let Ok(meta) = tokio::fs::symlink_metadata(&full_path).await {
+            if meta.file_type().is_symlink() {
+                return Ok(ToolResult::err(format!(
+                    "Refusing to edit through symlink: {path}"
+                )));
+            }
+        }
+
+        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult::err(format!(
+                    "Failed to resolve file path: {e}. Use file_write to create new files"
+                )));
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved_path) {
+            let message = format!(
+                "Resolved path escapes workspace: {}",
+                resolved_path.display()
+            );
+            self.audit(path, "edit", false, started, Some(&message));
+            return Ok(ToolResult::err(message));
+        }
+
+        match tokio::fs::metadata(&resolved_path).await {
+            Ok(meta) if meta.len() > MAX_FILE_SIZE_BYTES => {
+                return Ok(ToolResult::err(format!(
+                    "File too large: {} bytes (limit: {MAX_FILE_SIZE_BYTES} bytes)",
+                    meta.len()
+                )));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Ok(ToolResult::err(format!(
+                    "Failed to read file metadata: {e}"
+                )));
+            }
+        }
+
+        let content = match tokio::fs::read_to_string(&resolved_path).await {
+            Ok(content) => content,
+            Err(e) => return Ok(ToolResult::err(format!("Failed to read file: {e}"))),
+        };
+
+        let (edited, changes, operation) = match (old_string, new_string, patch) {
+            (Some(old), Some(new), None) => {
+                let (edited, changes) = match apply_replace(&content, old, new, replace_all) {
+                    Ok(result) => result,
+                    Err(e) => return Ok(ToolResult::err(e)),
+                };
+                let operation = format!("replace {} occurrence(s)", changes.len());
+                (edited, changes, operation)
+            }
+            (_, _, Some(patch)) => {
+                let (edited, changes) = match apply_patch(&content, patch) {
+                    Ok(result) => result,
+                    Err(e) => return Ok(ToolResult::err(e)),
+                };
+                let operation = format!("patch {} hunk(s)", changes.len());
+                (edited, changes, operation)
+            }
+            _ => unreachable!("argument combinations are validated above"),
+        };
+
+        if edited == content {
+            return Ok(ToolResult::err("Edit produces no changes"));
+        }
+
+        let preview = render_preview(&changes);
+        if dry_run {
+            return Ok(ToolResult::ok(format!(
+                "Dry run ({operation}) for {path}; nothing was written.\n\n{preview}"
+            )));
+        }
+
+        match tokio::fs::write(&resolved_path, &edited).await {
+            Ok(()) => {
+                self.audit(path, &operation, true, started, None);
+                Ok(ToolResult::ok(format!(
+                    "Edited {path} ({operation})\n\n{preview}"
+                )))
+            }
+            Err(e) => {
+                let message = format!("Failed to write file: {e}");
+                self.audit(path, &operation, true, started, Some(&message));
+                Ok(ToolResult::err(message))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AuditConfig;
+    use crate::security::{AuditEvent, AuditEventType, AutonomyLevel};
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn file_edit_name_and_schema() {
+        let tool = FileEditTool::new(test_security(std::env::temp_dir()));
+        assert_eq!(tool.name(), "file_edit");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["old_string"].is_object());
+        assert!(schema["properties"]["patch"].is_object());
+        assert!(schema["properties"]["dry_run"].is_object());
+        assert_eq!(schema["required"], json!(["path"]));
+    }
+
+    #[test]
+    fn replace_requires_unique_match_unless_replace_all() {
+        let content = "let a = 1;\nlet b = 1;\n";
+        let err = apply_replace(content, "= 1", "= 2", false).unwrap_err();
+        assert!(err.contains("matches 2 locations"));
+
+        let (edited, changes) = apply_replace(content, "= 1", "= 2", true).unwrap();
+        assert_eq!(edited, "let a = 2;\nlet b = 2;\n");
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].line, 2);
+
+        let err = apply_replace(content, "missing", "x", false).unwrap_err();
+        assert!(err.contains("not found"));
+    }
+
+    #[test]
+    fn patch_applies_multiple_hunks_with_line_drift() {
+        let content = "fn a() {\n    1\n}\n\nfn b() {\n    2\n}\n\nfn c() {\n    3\n}\n";
+        // Line numbers are off by two, as if the model counted from a stale view.
+        let patch = "--- a/lib.rs\n+++ b/lib.rs\n@@ -4,3 +4,3 @@\n fn a() {\n-    1\n+    10\n }\n@@ -11,3 +11,4 @@\n fn c() {\n-    3\n+    30\n+    31\n }\n";
+
+        let (edited, changes) = apply_patch(content, patch).unwrap();
+
+        assert_eq!(
+            edited,
+            "fn a() {\n    10\n}\n\nfn b() {\n    2\n}\n\nfn c() {\n    30\n    31\n}\n"
+        );
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].removed, vec!["    1"]);
+        assert_eq!(changes[1].added, vec!["    30", "    31"]);
+    }
+
+    #[test]
+    fn patch_context_matches_fuzzily_on_whitespace() {
+        let content = "if x {\n\treturn  y;   \n}\n";
+        let patch = "@@ @@\n if x {\n-    return y;\n+\treturn z;\n }\n";
+
+        let (edited, _) = apply_patch(content, patch).unwrap();
+
+        assert_eq!(edited, "if x {\n\treturn z;\n}\n");
+    }
+
+    #[test]
+    fn patch_preserves_crlf_line_endings() {
+        let content = "one\r\ntwo\r\nthree\r\n";
+        let (edited, _) = apply_patch(content, "@@ -2 +2 @@\n-two\n+TWO\n").unwrap();
+        assert_eq!(edited, "one\r\nTWO\r\nthree\r\n");
+    }
+
+    #[test]
+    fn patch_reports_unmatched_hunk_and_rejects_multi_file_diffs() {
+        let err = apply_patch("a\nb\n", "@@ -1,2 +1,2 @@\n a\n-c\n+d\n").unwrap_err();
+        assert!(err.contains("hunk 1 does not match the file near line 1"));
+
+        let multi =
+            "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n--- a/y\n+++ b/y\n@@ -1 +1 @@\n-a\n+b\n";
+        let err = apply_patch("a\n", multi).unwrap_err();
+        assert!(err.contains("more than one file"));
+    }
+
+    #[test]
+    fn patch_removed_line_starting_with_dashes_is_not_a_header() {
+        let content = "keep\n-- note\nend\n";
+        let patch = "@@ -1,3 +1,2 @@\n keep\n--- note\n end\n";
+        let (edited, _) = apply_patch(content, patch).unwrap();
+        assert_eq!(edited, "keep\nend\n");
+    }
+
+    #[tokio::test]
+    async fn file_edit_dry_run_leaves_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("notes.txt"), "hello world\n")
+            .await
+            .unwrap();
+        let tool = FileEditTool::new(test_security(dir.path().to_path_buf()));
+
+        let result = tool
+            .execute(json!({
+                "path": "notes.txt",
+                "old_string": "world",
+                "new_string": "there",
+                "dry_run": true
+            }))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Dry run"));
+        assert!(result.output.contains("+hello there") || result.output.contains("+there"));
+        let content = tokio::fs::read_to_string(dir.path().join("notes.txt"))
+            .await
+            .unwrap();
+        assert_eq!(content, "hello world\n");
+    }
+
+    #[tokio::test]
+    async fn file_edit_writes_and_records_audit_event() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("main.rs"), "fn main() {}\n")
+            .await
+            .unwrap();
+        let audit_dir = tempfile::tempdir().unwrap();
+        let logger = AuditLogger::new(
+            AuditConfig {
+                enabled: true,
+                ..AuditConfig::default()
+            },
+            audit_dir.path().to_path_buf(),
+        )
+        .unwrap();
+        let tool = FileEditTool::new(test_security(dir.path().to_path_buf()))
+            .with_audit_logger(Arc::new(logger));
+
+        let result = tool
+            .execute(json!({
+                "path": "main.rs",
+                "patch": "@@ -1 +1 @@\n-fn main() {}\n+fn main() {\n+    println!(\"hi\");\n+}\n"
+            }))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        let content = tokio::fs::read_to_string(dir.path().join("main.rs"))
+            .await
+            .unwrap();
+        assert_eq!(content, "fn main() {\n    println!(\"hi\");\n}\n");
+
+        let log = tokio::fs::read_to_string(audit_dir.path().join("audit.log"))
+            .await
+            .unwrap();
+        let event: AuditEvent = serde_json::from_str(log.trim()).unwrap();
+        assert!(matches!(event.event_type, AuditEventType::FileAccess));
+        assert_eq!(
+            event.action.unwrap().command.as_deref(),
+            Some("patch 1 hunk(s) main.rs")
+        );
+    }
+
+    #[tokio::test]
+    async fn file_edit_blocks_paths_outside_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = FileEditTool::new(test_security(dir.path().to_path_buf()));
+
+        let result = tool
+            .execute(json!({"path": "../etc/passwd", "old_string": "a", "new_string": "b"}))
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn file_edit_blocks_readonly_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("a.txt"), "x\n")
+            .await
+            .unwrap();
+        let tool = FileEditTool::new(Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: dir.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        }));
+
+        let result = tool
+            .execute(json!({"path": "a.txt", "old_string": "x", "new_string": "y"}))
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
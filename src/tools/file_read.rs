@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -48,6 +48,10 @@ impl Tool for FileReadTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let path = args
             .get("path")
@@ -59,6 +63,7 @@ impl Tool for FileReadTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -68,6 +73,7 @@ impl Tool for FileReadTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Path not allowed by security policy: {path}")),
+                parts: Vec::new(),
             });
         }
 
@@ -79,6 +85,7 @@ impl Tool for FileReadTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -92,6 +99,7 @@ impl Tool for FileReadTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Failed to resolve file path: {e}")),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -104,6 +112,7 @@ impl Tool for FileReadTool {
                     "Resolved path escapes workspace: {}",
                     resolved_path.display()
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -118,6 +127,7 @@ impl Tool for FileReadTool {
                             "File too large: {} bytes (limit: {MAX_FILE_SIZE_BYTES} bytes)",
                             meta.len()
                         )),
+                        parts: Vec::new(),
                     });
                 }
             }
@@ -126,6 +136,7 @@ impl Tool for FileReadTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Failed to read file metadata: {e}")),
+                    parts: Vec::new(),
                 });
             }
         }
@@ -140,6 +151,7 @@ impl Tool for FileReadTool {
                         success: true,
                         output: String::new(),
                         error: None,
+                        parts: Vec::new(),
                     });
                 }
 
@@ -167,6 +179,7 @@ impl Tool for FileReadTool {
                         success: true,
                         output: format!("[No lines in range, file has {total} lines]"),
                         error: None,
+                        parts: Vec::new(),
                     });
                 }
 
@@ -188,12 +201,14 @@ impl Tool for FileReadTool {
                     success: true,
                     output: format!("{numbered}{summary}"),
                     error: None,
+                    parts: Vec::new(),
                 })
             }
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Failed to read file: {e}")),
+                parts: Vec::new(),
             }),
         }
     }
@@ -128,6 +128,7 @@ impl GitOperationsTool {
             success: true,
             output: serde_json::to_string_pretty(&result).unwrap_or_default(),
             error: None,
+            parts: Vec::new(),
         })
     }
 
@@ -207,6 +208,7 @@ impl GitOperationsTool {
             success: true,
             output: serde_json::to_string_pretty(&result).unwrap_or_default(),
             error: None,
+            parts: Vec::new(),
         })
     }
 
@@ -244,6 +246,7 @@ impl GitOperationsTool {
             output: serde_json::to_string_pretty(&json!({ "commits": commits }))
                 .unwrap_or_default(),
             error: None,
+            parts: Vec::new(),
         })
     }
 
@@ -276,6 +279,7 @@ impl GitOperationsTool {
             }))
             .unwrap_or_default(),
             error: None,
+            parts: Vec::new(),
         })
     }
 
@@ -315,11 +319,13 @@ impl GitOperationsTool {
                 success: true,
                 output: format!("Committed: {message}"),
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Commit failed: {e}")),
+                parts: Vec::new(),
             }),
         }
     }
@@ -340,11 +346,13 @@ impl GitOperationsTool {
                 success: true,
                 output: format!("Staged: {paths}"),
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Add failed: {e}")),
+                parts: Vec::new(),
             }),
         }
     }
@@ -376,11 +384,13 @@ impl GitOperationsTool {
                 success: true,
                 output: format!("Switched to branch: {branch_name}"),
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Checkout failed: {e}")),
+                parts: Vec::new(),
             }),
         }
     }
@@ -413,11 +423,13 @@ impl GitOperationsTool {
                 success: true,
                 output: out,
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Stash {action} failed: {e}")),
+                parts: Vec::new(),
             }),
         }
     }
@@ -488,6 +500,7 @@ impl Tool for GitOperationsTool {
                     success: false,
                     output: String::new(),
                     error: Some("Missing 'operation' parameter".into()),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -510,6 +523,7 @@ impl Tool for GitOperationsTool {
                     success: false,
                     output: String::new(),
                     error: Some("Not in a git repository".into()),
+                    parts: Vec::new(),
                 });
             }
         }
@@ -523,6 +537,7 @@ impl Tool for GitOperationsTool {
                     error: Some(
                         "Action blocked: git write operations require higher autonomy level".into(),
                     ),
+                    parts: Vec::new(),
                 });
             }
 
@@ -532,6 +547,7 @@ impl Tool for GitOperationsTool {
                         success: false,
                         output: String::new(),
                         error: Some("Action blocked: read-only mode".into()),
+                        parts: Vec::new(),
                     });
                 }
                 AutonomyLevel::Supervised | AutonomyLevel::Full => {}
@@ -544,6 +560,7 @@ impl Tool for GitOperationsTool {
                 success: false,
                 output: String::new(),
                 error: Some("Action blocked: rate limit exceeded".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -561,6 +578,7 @@ impl Tool for GitOperationsTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Unknown operation: {operation}")),
+                parts: Vec::new(),
             }),
         }
     }
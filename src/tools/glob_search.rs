@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -42,6 +42,10 @@ impl Tool for GlobSearchTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let pattern = args
             .get("pattern")
@@ -54,6 +58,7 @@ impl Tool for GlobSearchTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -63,6 +68,7 @@ impl Tool for GlobSearchTool {
                 success: false,
                 output: String::new(),
                 error: Some("Absolute paths are not allowed. Use a relative glob pattern.".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -72,6 +78,7 @@ impl Tool for GlobSearchTool {
                 success: false,
                 output: String::new(),
                 error: Some("Path traversal ('..') is not allowed in glob patterns.".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -81,6 +88,7 @@ impl Tool for GlobSearchTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -95,6 +103,7 @@ impl Tool for GlobSearchTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Invalid glob pattern: {e}")),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -106,6 +115,7 @@ impl Tool for GlobSearchTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Cannot resolve workspace directory: {e}")),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -166,6 +176,7 @@ impl Tool for GlobSearchTool {
             success: true,
             output,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -3,7 +3,7 @@
 //! Use when user asks "what board do I have?", "board info", "connected hardware", etc.
 //! Uses probe-rs for Nucleo when available; otherwise static datasheet info.
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use async_trait::async_trait;
 use serde_json::json;
 
@@ -86,6 +86,10 @@ impl Tool for HardwareBoardInfoTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let board = args
             .get("board")
@@ -103,6 +107,7 @@ impl Tool for HardwareBoardInfoTool {
                     "No peripherals configured. Add boards to config.toml [peripherals.boards]."
                         .into(),
                 ),
+                parts: Vec::new(),
             });
         }
 
@@ -121,6 +126,7 @@ impl Tool for HardwareBoardInfoTool {
                         success: true,
                         output: info,
                         error: None,
+                        parts: Vec::new(),
                     });
                 }
                 Err(e) => {
@@ -151,6 +157,7 @@ impl Tool for HardwareBoardInfoTool {
             success: true,
             output,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
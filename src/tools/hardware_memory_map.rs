@@ -4,7 +4,7 @@
 //! returns the memory map. Uses probe-rs for Nucleo/STM32 when available; otherwise
 //! returns static maps from datasheets.
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use async_trait::async_trait;
 use serde_json::json;
 
@@ -72,6 +72,10 @@ impl Tool for HardwareMemoryMapTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let board = args
             .get("board")
@@ -89,6 +93,7 @@ impl Tool for HardwareMemoryMapTool {
                     "No peripherals configured. Add boards to config.toml [peripherals.boards]."
                         .into(),
                 ),
+                parts: Vec::new(),
             });
         }
 
@@ -139,6 +144,7 @@ impl Tool for HardwareMemoryMapTool {
             success: true,
             output,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
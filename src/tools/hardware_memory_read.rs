@@ -3,7 +3,7 @@
 //! Use when user asks to "read register values", "read memory at address", "dump lower memory", etc.
 //! Requires probe feature and Nucleo connected via USB.
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use async_trait::async_trait;
 use serde_json::json;
 
@@ -59,6 +59,10 @@ impl Tool for HardwareMemoryReadTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if self.boards.is_empty() {
             return Ok(ToolResult {
@@ -68,6 +72,7 @@ impl Tool for HardwareMemoryReadTool {
                     "No peripherals configured. Add nucleo-f401re to config.toml [peripherals.boards]."
                         .into(),
                 ),
+                parts: Vec::new(),
             });
         }
 
@@ -87,6 +92,7 @@ impl Tool for HardwareMemoryReadTool {
                     "Memory read only supports nucleo-f401re, nucleo-f411re. Got: {}",
                     board
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -109,6 +115,7 @@ impl Tool for HardwareMemoryReadTool {
                         success: true,
                         output,
                         error: None,
+                        parts: Vec::new(),
                     });
                 }
                 Err(e) => {
@@ -119,6 +126,7 @@ impl Tool for HardwareMemoryReadTool {
                             "probe-rs read failed: {}. Ensure Nucleo is connected via USB and built with --features probe.",
                             e
                         )),
+                        parts: Vec::new(),
                     });
                 }
             }
@@ -133,6 +141,7 @@ impl Tool for HardwareMemoryReadTool {
                     "Memory read requires probe feature. Build with: cargo build --features hardware,probe"
                         .into(),
                 ),
+                parts: Vec::new(),
             })
         }
     }
@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -186,6 +186,13 @@ impl Tool for HttpRequestTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        // Leave the client's own timeout room to fire and report first.
+        ToolMetadata::mutating()
+            .with_network()
+            .with_timeout_secs(self.timeout_secs.saturating_add(10))
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let url = args
             .get("url")
@@ -201,6 +208,7 @@ impl Tool for HttpRequestTool {
                 success: false,
                 output: String::new(),
                 error: Some("Action blocked: autonomy is read-only".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -209,6 +217,7 @@ impl Tool for HttpRequestTool {
                 success: false,
                 output: String::new(),
                 error: Some("Action blocked: rate limit exceeded".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -219,6 +228,7 @@ impl Tool for HttpRequestTool {
                     success: false,
                     output: String::new(),
                     error: Some(e.to_string()),
+                    parts: Vec::new(),
                 })
             }
         };
@@ -230,6 +240,7 @@ impl Tool for HttpRequestTool {
                     success: false,
                     output: String::new(),
                     error: Some(e.to_string()),
+                    parts: Vec::new(),
                 })
             }
         };
@@ -280,12 +291,14 @@ impl Tool for HttpRequestTool {
                     } else {
                         None
                     },
+                    parts: Vec::new(),
                 })
             }
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("HTTP request failed: {e}")),
+                parts: Vec::new(),
             }),
         }
     }
@@ -452,6 +465,13 @@ mod tests {
         )
     }
 
+    #[test]
+    fn metadata_timeout_outlasts_the_client_timeout() {
+        let metadata = test_tool(vec!["example.com"]).metadata();
+        assert!(metadata.network);
+        assert_eq!(metadata.default_timeout_secs, Some(40));
+    }
+
     #[test]
     fn normalize_domain_strips_scheme_path_and_case() {
         let got = normalize_domain("  HTTPS://Docs.Example.com/path ").unwrap();
@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolContent, ToolMetadata, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -125,7 +125,7 @@ impl Tool for ImageInfoTool {
     }
 
     fn description(&self) -> &str {
-        "Read image file metadata (format, dimensions, size) and optionally return base64-encoded data."
+        "Read image file metadata (format, dimensions, size) and optionally attach the image itself."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -138,13 +138,17 @@ impl Tool for ImageInfoTool {
                 },
                 "include_base64": {
                     "type": "boolean",
-                    "description": "Include base64-encoded image data in output (default: false)"
+                    "description": "Attach the image so a vision-capable model can see it (default: false)"
                 }
             },
             "required": ["path"]
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let path_str = args
             .get("path")
@@ -166,6 +170,7 @@ impl Tool for ImageInfoTool {
                 error: Some(format!(
                     "Path not allowed: {path_str} (must be within workspace)"
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -174,6 +179,7 @@ impl Tool for ImageInfoTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!("File not found: {path_str}")),
+                parts: Vec::new(),
             });
         }
 
@@ -190,6 +196,7 @@ impl Tool for ImageInfoTool {
                 error: Some(format!(
                     "Image too large: {file_size} bytes (max {MAX_IMAGE_BYTES} bytes)"
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -206,9 +213,8 @@ impl Tool for ImageInfoTool {
             let _ = write!(output, "\nDimensions: {w}x{h}");
         }
 
+        let mut parts = Vec::new();
         if include_base64 {
-            use base64::Engine;
-            let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
             let mime = match format {
                 "png" => "image/png",
                 "jpeg" => "image/jpeg",
@@ -217,13 +223,15 @@ impl Tool for ImageInfoTool {
                 "bmp" => "image/bmp",
                 _ => "application/octet-stream",
             };
-            let _ = write!(output, "\ndata:{mime};base64,{encoded}");
+            let _ = write!(output, "\nImage attached ({mime})");
+            parts.push(ToolContent::image(mime, &bytes));
         }
 
         Ok(ToolResult {
             success: true,
             output,
             error: None,
+            parts,
         })
     }
 }
@@ -459,6 +467,7 @@ mod tests {
         assert!(result.output.contains("Format: png"));
         assert!(result.output.contains("Dimensions: 1x1"));
         assert!(!result.output.contains("data:"));
+        assert!(result.parts.is_empty());
 
         // Clean up
         let _ = tokio::fs::remove_dir_all(&dir).await;
@@ -486,7 +495,12 @@ mod tests {
             .await
             .unwrap();
         assert!(result.success);
-        assert!(result.output.contains("data:image/png;base64,"));
+        assert!(!result.output.contains("data:"));
+        assert_eq!(result.parts.len(), 1);
+        assert!(result.parts[0]
+            .image_data_uri()
+            .unwrap()
+            .starts_with("data:image/png;base64,iVBOR"));
 
         let _ = tokio::fs::remove_dir_all(&dir).await;
     }
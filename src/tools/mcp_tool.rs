@@ -0,0 +1,301 @@
+use super::traits::{Tool, ToolMetadata, ToolResult};
+use crate::config::McpConfig;
+use crate::mcp::protocol::McpToolDefinition;
+use crate::mcp::McpClient;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Maximum tool name length accepted by provider function-calling APIs.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Adapter exposing one remote MCP tool as a local [`Tool`].
+///
+/// Registered as `<server>__<tool>`, so `autonomy.auto_approve` /
+/// `always_ask` entries can target individual remote tools.
+pub struct McpTool {
+    client: Arc<McpClient>,
+    security: Arc<SecurityPolicy>,
+    name: String,
+    remote_name: String,
+    description: String,
+    input_schema: serde_json::Value,
+    read_only: bool,
+}
+
+impl McpTool {
+    /// `trust_annotations` decides whether the server's `readOnlyHint` is
+    /// believed; otherwise the tool is treated as mutating.
+    pub fn new(
+        client: Arc<McpClient>,
+        definition: &McpToolDefinition,
+        trust_annotations: bool,
+        security: Arc<SecurityPolicy>,
+    ) -> Self {
+        let server = client.server_name().to_string();
+        let description = definition
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+            .map_or_else(
+                || format!("{} (MCP server '{server}')", definition.name),
+                |description| format!("{description} (MCP server '{server}')"),
+            );
+        Self {
+            name: registered_tool_name(&server, &definition.name),
+            remote_name: definition.name.clone(),
+            description,
+            input_schema: definition.input_schema.clone(),
+            read_only: trust_annotations
+                && definition
+                    .annotations
+                    .as_ref()
+                    .and_then(|annotations| annotations.read_only_hint)
+                    .unwrap_or(false),
+            client,
+            security,
+        }
+    }
+}
+
+/// Build the local tool name for a remote tool: `<server>__<tool>`, restricted
+/// to `[A-Za-z0-9_-]` and truncated to provider limits.
+pub fn registered_tool_name(server: &str, tool: &str) -> String {
+    format!("{server}__{tool}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_TOOL_NAME_LEN)
+        .collect()
+}
+
+#[async_trait]
+impl Tool for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.input_schema.clone()
+    }
+
+    fn metadata(&self) -> ToolMetadata {
+        // Only a trusted server's read-only annotation relaxes the default.
+        let metadata = if self.read_only {
+            ToolMetadata::read_only()
+        } else {
+            ToolMetadata::mutating()
+        };
+        // The server may reach anything; treat it as networked.
+        metadata.with_network()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(error) = self.security.enforce_tool_operation(
+            self.metadata().operation(),
+            &format!("mcp.{}.{}", self.client.server_name(), self.remote_name),
+        ) {
+            return Ok(ToolResult::err(error));
+        }
+
+        let args = if args.is_null() {
+            serde_json::json!({})
+        } else {
+            args
+        };
+
+        match self.client.call_tool(&self.remote_name, args).await {
+            Ok(result) if result.is_error => Ok(ToolResult::err(result.to_text())),
+            Ok(result) => Ok(ToolResult::ok(result.to_text()).with_parts(result.to_parts())),
+            Err(error) => Ok(ToolResult::err(format!("{error:#}"))),
+        }
+    }
+}
+
+/// Connect every configured MCP server and wrap its tools.
+///
+/// Servers that fail to connect are logged and skipped so one broken server
+/// does not take down the whole tool registry.
+pub fn mcp_tools(config: &McpConfig, security: &Arc<SecurityPolicy>) -> Vec<Arc<dyn Tool>> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    let mut tools: Vec<Arc<dyn Tool>> = Vec::new();
+    let clients = crate::mcp::connect_all_blocking(&config.servers);
+    for (server, client) in config.servers.iter().zip(clients) {
+        let client = match client {
+            Ok(client) => Arc::new(client),
+            Err(error) => {
+                tracing::warn!(server = %server.name, "Skipping MCP server: {error:#}");
+                continue;
+            }
+        };
+
+        for definition in client.tools() {
+            if !server.allowed_tools.is_empty()
+                && !server.allowed_tools.iter().any(|t| t == &definition.name)
+            {
+                continue;
+            }
+            tools.push(Arc::new(McpTool::new(
+                client.clone(),
+                definition,
+                server.trust_annotations,
+                security.clone(),
+            )));
+        }
+        tracing::info!(
+            server = %server.name,
+            tools = client.tools().len(),
+            "Connected MCP server"
+        );
+    }
+    tools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{McpServerConfig, McpTransport};
+    use crate::security::AutonomyLevel;
+    use std::collections::HashMap;
+
+    const FAKE_SERVER: &str = r#"
+read init
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{"tools":{}},"serverInfo":{"name":"fake","version":"0.1"}}}'
+read initialized
+read list
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"lookup","description":"Look up a record","annotations":{"readOnlyHint":true}},{"name":"delete.record","inputSchema":{"type":"object"}}]}}'
+read call
+echo '{"jsonrpc":"2.0","id":3,"result":{"content":[{"type":"text","text":"not found"}],"isError":true}}'
+"#;
+
+    fn fake_server(allowed_tools: Vec<String>, trust_annotations: bool) -> McpConfig {
+        McpConfig {
+            enabled: true,
+            servers: vec![McpServerConfig {
+                name: "crm".into(),
+                transport: McpTransport::Stdio,
+                command: Some("sh".into()),
+                args: vec!["-c".into(), FAKE_SERVER.into()],
+                env: HashMap::new(),
+                url: None,
+                headers: HashMap::new(),
+                timeout_secs: 5,
+                allowed_tools,
+                trust_annotations,
+            }],
+        }
+    }
+
+    fn policy(level: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn registered_name_is_prefixed_and_sanitized() {
+        assert_eq!(registered_tool_name("crm", "lookup"), "crm__lookup");
+        assert_eq!(
+            registered_tool_name("crm", "delete.record"),
+            "crm__delete_record"
+        );
+        assert_eq!(
+            registered_tool_name("s", &"x".repeat(100)).len(),
+            MAX_TOOL_NAME_LEN
+        );
+    }
+
+    #[test]
+    fn disabled_config_registers_nothing() {
+        let mut config = fake_server(Vec::new(), false);
+        config.enabled = false;
+        assert!(mcp_tools(&config, &policy(AutonomyLevel::Full)).is_empty());
+    }
+
+    #[test]
+    fn unreachable_server_is_skipped() {
+        let mut config = fake_server(Vec::new(), false);
+        config.servers[0].args = vec!["-c".into(), "exit 1".into()];
+        assert!(mcp_tools(&config, &policy(AutonomyLevel::Full)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn remote_tools_are_registered_and_errors_surface() {
+        let tools = mcp_tools(
+            &fake_server(Vec::new(), false),
+            &policy(AutonomyLevel::Full),
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names, vec!["crm__lookup", "crm__delete_record"]);
+        assert!(tools[0].description().contains("Look up a record"));
+        assert_eq!(tools[1].parameters_schema()["type"], "object");
+
+        let result = tools[0]
+            .execute(serde_json::json!({ "id": 1 }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("not found"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn registry_connects_from_multi_thread_runtime() {
+        let tools = mcp_tools(
+            &fake_server(Vec::new(), false),
+            &policy(AutonomyLevel::Full),
+        );
+        assert_eq!(tools.len(), 2);
+        let result = tools[0].execute(serde_json::json!({})).await.unwrap();
+        assert_eq!(result.error.as_deref(), Some("not found"));
+    }
+
+    #[test]
+    fn allowed_tools_filters_registration() {
+        let tools = mcp_tools(
+            &fake_server(vec!["lookup".into()], false),
+            &policy(AutonomyLevel::Full),
+        );
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "crm__lookup");
+    }
+
+    #[tokio::test]
+    async fn read_only_policy_blocks_mutating_remote_tools() {
+        let tools = mcp_tools(
+            &fake_server(vec!["delete.record".into()], false),
+            &policy(AutonomyLevel::ReadOnly),
+        );
+        let result = tools[0].execute(serde_json::json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[test]
+    fn read_only_hint_is_ignored_unless_server_is_trusted() {
+        let untrusted = mcp_tools(
+            &fake_server(vec!["lookup".into()], false),
+            &policy(AutonomyLevel::Full),
+        );
+        assert!(!untrusted[0].metadata().is_read_only());
+
+        let trusted = mcp_tools(
+            &fake_server(vec!["lookup".into()], true),
+            &policy(AutonomyLevel::Full),
+        );
+        assert!(trusted[0].metadata().is_read_only());
+    }
+}
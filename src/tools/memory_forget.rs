@@ -1,6 +1,5 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::memory::Memory;
-use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -41,6 +40,10 @@ impl Tool for MemoryForgetTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_idempotent(true)
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let key = args
             .get("key")
@@ -49,12 +52,13 @@ impl Tool for MemoryForgetTool {
 
         if let Err(error) = self
             .security
-            .enforce_tool_operation(ToolOperation::Act, "memory_forget")
+            .enforce_tool_operation(self.metadata().operation(), "memory_forget")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(error),
+                parts: Vec::new(),
             });
         }
 
@@ -63,16 +67,19 @@ impl Tool for MemoryForgetTool {
                 success: true,
                 output: format!("Forgot memory: {key}"),
                 error: None,
+                parts: Vec::new(),
             }),
             Ok(false) => Ok(ToolResult {
                 success: true,
                 output: format!("No memory found with key: {key}"),
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Failed to forget memory: {e}")),
+                parts: Vec::new(),
             }),
         }
     }
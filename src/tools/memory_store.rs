@@ -1,6 +1,5 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::memory::{Memory, MemoryCategory};
-use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use crate::gateway::CURRENT_SESSION_ID;
 use async_trait::async_trait;
@@ -50,6 +49,10 @@ impl Tool for MemoryStoreTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating().with_idempotent(true)
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let key = args
             .get("key")
@@ -70,12 +73,13 @@ impl Tool for MemoryStoreTool {
 
         if let Err(error) = self
             .security
-            .enforce_tool_operation(ToolOperation::Act, "memory_store")
+            .enforce_tool_operation(self.metadata().operation(), "memory_store")
         {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(error),
+                parts: Vec::new(),
             });
         }
 
@@ -89,11 +93,13 @@ impl Tool for MemoryStoreTool {
                 success: true,
                 output: format!("Stored memory: {key}"),
                 error: None,
+                parts: Vec::new(),
             }),
             Err(e) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Failed to store memory: {e}")),
+                parts: Vec::new(),
             }),
         }
     }
@@ -19,6 +19,7 @@ pub mod browser;
 pub mod browser_open;
 pub mod email_send;
 pub mod composio;
+pub mod content_search;
 pub mod cron_add;
 pub mod cron_list;
 pub mod cron_remove;
@@ -26,6 +27,7 @@ pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
+pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
 pub mod git_operations;
@@ -35,15 +37,19 @@ pub mod hardware_memory_map;
 pub mod hardware_memory_read;
 pub mod http_request;
 pub mod image_info;
+pub mod mcp_tool;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
 pub mod pdf_read;
+pub mod plan;
+pub mod process;
 pub mod proxy_config;
 pub mod pushover;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
+pub mod send_file;
 pub mod shell;
 pub mod traits;
 pub mod web_search_tool;
@@ -51,6 +57,7 @@ pub mod web_search_tool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
 pub use composio::ComposioTool;
+pub use content_search::ContentSearchTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
 pub use cron_remove::CronRemoveTool;
@@ -59,6 +66,7 @@ pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
 pub use email_send::EmailSendTool;
+pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use git_operations::GitOperationsTool;
@@ -68,20 +76,25 @@ pub use hardware_memory_map::HardwareMemoryMapTool;
 pub use hardware_memory_read::HardwareMemoryReadTool;
 pub use http_request::HttpRequestTool;
 pub use image_info::ImageInfoTool;
+#[allow(unused_imports)]
+pub use mcp_tool::McpTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
 pub use pdf_read::PdfReadTool;
+pub use plan::PlanTool;
+pub use process::ProcessTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
 pub use schedule::ScheduleTool;
 #[allow(unused_imports)]
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
+pub use send_file::SendFileTool;
 pub use shell::ShellTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
-pub use traits::{ToolResult, ToolSpec};
+pub use traits::{ToolContent, ToolEffect, ToolMetadata, ToolResult, ToolSpec};
 pub use web_search_tool::WebSearchTool;
 
 use crate::config::{Config, DelegateAgentConfig};
@@ -140,7 +153,9 @@ pub fn default_tools_with_runtime(
         Box::new(ShellTool::new(security.clone(), runtime)),
         Box::new(FileReadTool::new(security.clone())),
         Box::new(FileWriteTool::new(security.clone())),
-        Box::new(GlobSearchTool::new(security)),
+        Box::new(FileEditTool::new(security.clone())),
+        Box::new(GlobSearchTool::new(security.clone())),
+        Box::new(ContentSearchTool::new(security)),
     ]
 }
 
@@ -191,11 +206,32 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    let mut file_edit = FileEditTool::new(security.clone());
+    if let Some(dir) = root_config.config_path.parent() {
+        match crate::security::AuditLogger::new(
+            root_config.security.audit.clone(),
+            dir.to_path_buf(),
+        ) {
+            Ok(logger) => file_edit = file_edit.with_audit_logger(Arc::new(logger)),
+            Err(err) => tracing::warn!("file_edit audit logging disabled: {err:#}"),
+        }
+    }
+
+    let process = ProcessTool::new(
+        security.clone(),
+        runtime.clone(),
+        root_config.security.resources.clone(),
+    )
+    .with_sandbox(crate::security::create_sandbox(&root_config.security));
+
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
         Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(process),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
+        Arc::new(file_edit),
         Arc::new(GlobSearchTool::new(security.clone())),
+        Arc::new(ContentSearchTool::new(security.clone())),
         Arc::new(CronAddTool::new(config.clone(), security.clone())),
         Arc::new(CronListTool::new(config.clone())),
         Arc::new(CronRemoveTool::new(config.clone(), security.clone())),
@@ -206,6 +242,7 @@ pub fn all_tools_with_runtime(
         Arc::new(MemoryRecallTool::new(memory.clone())),
         Arc::new(MemoryForgetTool::new(memory, security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
+        Arc::new(PlanTool::new(security.clone(), workspace_dir)),
         Arc::new(ProxyConfigTool::new(config.clone(), security.clone())),
         Arc::new(GitOperationsTool::new(
             security.clone(),
@@ -278,6 +315,9 @@ pub fn all_tools_with_runtime(
     tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
     tool_arcs.push(Arc::new(ImageInfoTool::new(security.clone())));
 
+    // Channel replies can carry workspace files as attachments
+    tool_arcs.push(Arc::new(SendFileTool::new(security.clone())));
+
     if let Some(key) = composio_key {
         if !key.is_empty() {
             tool_arcs.push(Arc::new(ComposioTool::new(
@@ -288,6 +328,21 @@ pub fn all_tools_with_runtime(
         }
     }
 
+    // External MCP server tools (registered before delegation so sub-agents can use them)
+    for tool in mcp_tool::mcp_tools(&root_config.mcp, security) {
+        if tool_arcs
+            .iter()
+            .any(|existing| existing.name() == tool.name())
+        {
+            tracing::warn!(
+                tool = tool.name(),
+                "Skipping MCP tool that shadows an existing tool"
+            );
+            continue;
+        }
+        tool_arcs.push(tool);
+    }
+
     // Add delegation tool when agents are configured
     if !agents.is_empty() {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
@@ -339,7 +394,7 @@ mod tests {
     fn default_tools_has_expected_count() {
         let security = Arc::new(SecurityPolicy::default());
         let tools = default_tools(security);
-        assert_eq!(tools.len(), 4);
+        assert_eq!(tools.len(), 6);
     }
 
     #[test]
@@ -378,6 +433,8 @@ mod tests {
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"browser_open"));
         assert!(names.contains(&"schedule"));
+        assert!(names.contains(&"process"));
+        assert!(names.contains(&"plan"));
         assert!(names.contains(&"pushover"));
         assert!(names.contains(&"proxy_config"));
     }
@@ -429,7 +486,9 @@ mod tests {
         assert!(names.contains(&"shell"));
         assert!(names.contains(&"file_read"));
         assert!(names.contains(&"file_write"));
+        assert!(names.contains(&"file_edit"));
         assert!(names.contains(&"glob_search"));
+        assert!(names.contains(&"content_search"));
     }
 
     #[test]
@@ -482,6 +541,7 @@ mod tests {
             success: true,
             output: "hello".into(),
             error: None,
+            parts: Vec::new(),
         };
         let json = serde_json::to_string(&result).unwrap();
         let parsed: ToolResult = serde_json::from_str(&json).unwrap();
@@ -496,6 +556,7 @@ mod tests {
             success: false,
             output: String::new(),
             error: Some("boom".into()),
+            parts: Vec::new(),
         };
         let json = serde_json::to_string(&result).unwrap();
         let parsed: ToolResult = serde_json::from_str(&json).unwrap();
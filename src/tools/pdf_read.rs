@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -59,6 +59,10 @@ impl Tool for PdfReadTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::read_only()
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let path = args
             .get("path")
@@ -80,6 +84,7 @@ impl Tool for PdfReadTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -88,6 +93,7 @@ impl Tool for PdfReadTool {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Path not allowed by security policy: {path}")),
+                parts: Vec::new(),
             });
         }
 
@@ -97,6 +103,7 @@ impl Tool for PdfReadTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -109,6 +116,7 @@ impl Tool for PdfReadTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Failed to resolve file path: {e}")),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -121,6 +129,7 @@ impl Tool for PdfReadTool {
                     "Resolved path escapes workspace: {}",
                     resolved_path.display()
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -136,6 +145,7 @@ impl Tool for PdfReadTool {
                             "PDF too large: {} bytes (limit: {MAX_PDF_BYTES} bytes)",
                             meta.len()
                         )),
+                        parts: Vec::new(),
                     });
                 }
             }
@@ -144,6 +154,7 @@ impl Tool for PdfReadTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Failed to read file metadata: {e}")),
+                    parts: Vec::new(),
                 });
             }
         }
@@ -155,6 +166,7 @@ impl Tool for PdfReadTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Failed to read PDF file: {e}")),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -173,6 +185,7 @@ impl Tool for PdfReadTool {
                         success: false,
                         output: String::new(),
                         error: Some(format!("PDF extraction failed: {e}")),
+                        parts: Vec::new(),
                     });
                 }
                 Err(e) => {
@@ -180,6 +193,7 @@ impl Tool for PdfReadTool {
                         success: false,
                         output: String::new(),
                         error: Some(format!("PDF extraction task panicked: {e}")),
+                        parts: Vec::new(),
                     });
                 }
             };
@@ -192,6 +206,7 @@ impl Tool for PdfReadTool {
                     output: "PDF contains no extractable text (may be image-only or encrypted)"
                         .into(),
                     error: None,
+                    parts: Vec::new(),
                 });
             }
 
@@ -208,6 +223,7 @@ impl Tool for PdfReadTool {
                 success: true,
                 output,
                 error: None,
+                parts: Vec::new(),
             });
         }
 
@@ -223,6 +239,7 @@ impl Tool for PdfReadTool {
                      Rebuild with: cargo build --features rag-pdf"
                         .into(),
                 ),
+                parts: Vec::new(),
             })
         }
     }
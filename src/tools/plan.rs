@@ -0,0 +1,699 @@
+//! Agent-maintained task plan.
+//!
+//! The `plan` tool lets the model keep an explicit checklist for multi-step
+//! work. Plans are kept per session (the gateway session, channel
+//! conversation, or cron run in [`CURRENT_SESSION_ID`]) and workspace in a
+//! process-wide table so the tool loop can render the latest state into every round's
+//! prompt, attach it to the trace, and stream it to channel drafts. Named
+//! sessions are also persisted under `workspace/state/plans/`.
+
+use super::traits::{Tool, ToolResult};
+use crate::gateway::CURRENT_SESSION_ID;
+use crate::security::SecurityPolicy;
+use crate::util::truncate_with_ellipsis;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+pub const PLAN_TOOL_NAME: &str = "plan";
+
+const MAX_PLAN_STEPS: usize = 30;
+const MAX_STEP_CHARS: usize = 200;
+const MAX_GOAL_CHARS: usize = 300;
+/// Sessions kept in memory; the least recently used are reloaded from disk.
+const MAX_CACHED_PLANS: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanStepStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Skipped,
+}
+
+impl PlanStepStatus {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "in_progress" => Some(Self::InProgress),
+            "completed" | "done" => Some(Self::Completed),
+            "skipped" => Some(Self::Skipped),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::InProgress => "in progress",
+            Self::Completed => "done",
+            Self::Skipped => "skipped",
+        }
+    }
+
+    fn marker(self) -> &'static str {
+        match self {
+            Self::Pending => "⬜",
+            Self::InProgress => "🔄",
+            Self::Completed => "✅",
+            Self::Skipped => "⏭️",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanStep {
+    pub id: usize,
+    pub title: String,
+    pub status: PlanStepStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskPlan {
+    pub goal: String,
+    pub steps: Vec<PlanStep>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TaskPlan {
+    fn count(&self, status: PlanStepStatus) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.status == status)
+            .count()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.steps.iter().all(|step| {
+            matches!(
+                step.status,
+                PlanStepStatus::Completed | PlanStepStatus::Skipped
+            )
+        })
+    }
+
+    /// Plan state appended to the prompt on every tool-loop round.
+    pub fn prompt_block(&self) -> String {
+        let mut block = String::from("## Task Plan\n");
+        let _ = writeln!(block, "Goal: {}", self.goal);
+        for step in &self.steps {
+            let _ = write!(
+                block,
+                "{}. [{}] {}",
+                step.id,
+                step.status.label(),
+                step.title
+            );
+            if let Some(note) = &step.note {
+                let _ = write!(block, " — {note}");
+            }
+            block.push('\n');
+        }
+        if self.is_finished() {
+            block.push_str("All steps are done; give the final answer or create a new plan.\n");
+        } else {
+            block.push_str(
+                "Keep this plan current with the `plan` tool: mark a step in_progress before working on it and completed when it is done.\n",
+            );
+        }
+        block
+    }
+
+    /// Compact checklist for channel drafts and tool output.
+    pub fn checklist(&self) -> String {
+        let mut text = format!(
+            "📋 {} ({}/{})\n",
+            self.goal,
+            self.count(PlanStepStatus::Completed),
+            self.steps.len()
+        );
+        for step in &self.steps {
+            let _ = write!(text, "{} {}", step.status.marker(), step.title);
+            if let Some(note) = &step.note {
+                let _ = write!(text, " — {note}");
+            }
+            text.push('\n');
+        }
+        text.truncate(text.trim_end().len());
+        text
+    }
+}
+
+/// Cached plan for one session; `None` records a session known to have no plan.
+struct CachedPlan {
+    plan: Option<TaskPlan>,
+    used_at: Instant,
+}
+
+/// Plans by plan directory and session, bounded to [`MAX_CACHED_PLANS`] entries.
+struct PlanStore {
+    plans: Mutex<HashMap<(PathBuf, String), CachedPlan>>,
+}
+
+// Process-wide so the tool loop can read what the tool wrote without a handle
+// to the tool instance.
+fn plan_store() -> &'static PlanStore {
+    static STORE: OnceLock<PlanStore> = OnceLock::new();
+    STORE.get_or_init(|| PlanStore {
+        plans: Mutex::new(HashMap::new()),
+    })
+}
+
+/// Key for runs without a session (interactive CLI); never persisted.
+fn unnamed_session_key() -> &'static str {
+    static KEY: OnceLock<String> = OnceLock::new();
+    KEY.get_or_init(|| format!("local-{}", std::process::id()))
+}
+
+fn current_session_key() -> String {
+    CURRENT_SESSION_ID
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| unnamed_session_key().to_string())
+}
+
+impl PlanStore {
+    fn get_or_load(
+        &self,
+        dir: &Path,
+        session: &str,
+        load: impl FnOnce() -> Option<TaskPlan>,
+    ) -> Option<TaskPlan> {
+        let key = (dir.to_path_buf(), session.to_string());
+        if let Some(cached) = self.plans.lock().get_mut(&key) {
+            cached.used_at = Instant::now();
+            return cached.plan.clone();
+        }
+        let loaded = load();
+        self.insert(dir, session, loaded.clone());
+        loaded
+    }
+
+    fn insert(&self, dir: &Path, session: &str, plan: Option<TaskPlan>) {
+        let key = (dir.to_path_buf(), session.to_string());
+        let mut plans = self.plans.lock();
+        if !plans.contains_key(&key) && plans.len() >= MAX_CACHED_PLANS {
+            // The unnamed session is not persisted, so it is never evicted.
+            let evict_key = plans
+                .iter()
+                .filter(|((_, session), _)| session != unnamed_session_key())
+                .min_by_key(|(_, cached)| cached.used_at)
+                .map(|(key, _)| key.clone());
+            if let Some(evict_key) = evict_key {
+                plans.remove(&evict_key);
+            }
+        }
+        plans.insert(
+            key,
+            CachedPlan {
+                plan,
+                used_at: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Where the `plan` tool of `workspace_dir` persists plans.
+pub fn plan_dir(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join("plans")
+}
+
+/// Plan file for `session`: a readable prefix plus a hash of the full id, so
+/// ids that sanitize to the same prefix (`a:b`, `a_b`) do not share a file.
+fn plan_file(dir: &Path, session: &str) -> PathBuf {
+    let prefix: String = session
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect();
+    let digest = hex::encode(Sha256::digest(session.as_bytes()));
+    dir.join(format!("{prefix}-{}.json", &digest[..16]))
+}
+
+fn load_plan(dir: &Path, session: &str) -> Option<TaskPlan> {
+    if session == unnamed_session_key() {
+        return None;
+    }
+    let text = std::fs::read_to_string(plan_file(dir, session)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn cached_or_load(dir: &Path, session: &str) -> Option<TaskPlan> {
+    plan_store().get_or_load(dir, session, || load_plan(dir, session))
+}
+
+/// Plan the `plan` tool stored in `dir` for the current session, if any.
+pub fn current_plan(dir: &Path) -> Option<TaskPlan> {
+    cached_or_load(dir, &current_session_key())
+}
+
+/// Manage the agent's task plan for the current session.
+pub struct PlanTool {
+    security: Arc<SecurityPolicy>,
+    storage_dir: PathBuf,
+}
+
+impl PlanTool {
+    pub fn new(security: Arc<SecurityPolicy>, workspace_dir: &Path) -> Self {
+        Self {
+            security,
+            storage_dir: plan_dir(workspace_dir),
+        }
+    }
+
+    async fn save(&self, session: &str, plan: Option<TaskPlan>) -> anyhow::Result<()> {
+        plan_store().insert(&self.storage_dir, session, plan.clone());
+        if session == unnamed_session_key() {
+            return Ok(());
+        }
+        let path = plan_file(&self.storage_dir, session);
+        match plan {
+            Some(plan) => {
+                tokio::fs::create_dir_all(&self.storage_dir).await?;
+                tokio::fs::write(&path, serde_json::to_vec_pretty(&plan)?).await?;
+            }
+            None => {
+                if let Err(e) = tokio::fs::remove_file(&path).await {
+                    if e.kind() != std::io::ErrorKind::NotFound {
+                        return Err(e.into());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn step_titles(args: &serde_json::Value) -> Result<Vec<String>, String> {
+    let steps = args
+        .get("steps")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| "Missing 'steps' parameter (array of step descriptions)".to_string())?;
+    let titles: Vec<String> = steps
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .map(|title| truncate_with_ellipsis(title, MAX_STEP_CHARS))
+        .collect();
+    if titles.is_empty() {
+        return Err("'steps' must contain at least one non-empty string".into());
+    }
+    Ok(titles)
+}
+
+/// Apply one `plan` action to the session's current plan.
+fn apply_action(
+    plan: Option<TaskPlan>,
+    action: &str,
+    args: &serde_json::Value,
+) -> Result<Option<TaskPlan>, String> {
+    let now = Utc::now();
+    let require_plan = |plan: Option<TaskPlan>| {
+        plan.ok_or_else(|| "No plan exists yet; use action \"create\" first".to_string())
+    };
+    let step_id = || {
+        args.get("step")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|id| usize::try_from(id).ok())
+            .ok_or_else(|| "Missing 'step' parameter (step number)".to_string())
+    };
+
+    match action {
+        "create" => {
+            let goal = args
+                .get("goal")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|goal| !goal.is_empty())
+                .ok_or_else(|| "Missing 'goal' parameter".to_string())?;
+            let titles = step_titles(args)?;
+            if titles.len() > MAX_PLAN_STEPS {
+                return Err(format!("A plan can have at most {MAX_PLAN_STEPS} steps"));
+            }
+            let steps = titles
+                .into_iter()
+                .enumerate()
+                .map(|(index, title)| PlanStep {
+                    id: index + 1,
+                    title,
+                    status: PlanStepStatus::Pending,
+                    note: None,
+                })
+                .collect();
+            Ok(Some(TaskPlan {
+                goal: truncate_with_ellipsis(goal, MAX_GOAL_CHARS),
+                steps,
+                updated_at: now,
+            }))
+        }
+        "add" => {
+            let mut plan = require_plan(plan)?;
+            let titles = step_titles(args)?;
+            if plan.steps.len() + titles.len() > MAX_PLAN_STEPS {
+                return Err(format!("A plan can have at most {MAX_PLAN_STEPS} steps"));
+            }
+            let next_id = plan.steps.iter().map(|step| step.id).max().unwrap_or(0) + 1;
+            plan.steps.extend(
+                titles
+                    .into_iter()
+                    .enumerate()
+                    .map(|(offset, title)| PlanStep {
+                        id: next_id + offset,
+                        title,
+                        status: PlanStepStatus::Pending,
+                        note: None,
+                    }),
+            );
+            plan.updated_at = now;
+            Ok(Some(plan))
+        }
+        "update" | "complete" => {
+            let mut plan = require_plan(plan)?;
+            let id = step_id()?;
+            let step = plan
+                .steps
+                .iter_mut()
+                .find(|step| step.id == id)
+                .ok_or_else(|| format!("Plan has no step {id}"))?;
+            let status = if action == "complete" {
+                Some(PlanStepStatus::Completed)
+            } else {
+                match args.get("status").and_then(|v| v.as_str()) {
+                    Some(value) => Some(PlanStepStatus::parse(value).ok_or_else(|| {
+                        format!(
+                            "Unknown status '{value}'. Use pending, in_progress, completed or skipped"
+                        )
+                    })?),
+                    None => None,
+                }
+            };
+            if let Some(status) = status {
+                step.status = status;
+            }
+            if let Some(title) = args.get("title").and_then(|v| v.as_str()) {
+                step.title = truncate_with_ellipsis(title.trim(), MAX_STEP_CHARS);
+            }
+            if let Some(note) = args.get("note").and_then(|v| v.as_str()) {
+                let note = note.trim();
+                step.note =
+                    (!note.is_empty()).then(|| truncate_with_ellipsis(note, MAX_STEP_CHARS));
+            }
+            plan.updated_at = now;
+            Ok(Some(plan))
+        }
+        "clear" => Ok(None),
+        other => Err(format!(
+            "Unknown action '{other}'. Use create, add, update, complete, show or clear"
+        )),
+    }
+}
+
+#[async_trait]
+impl Tool for PlanTool {
+    fn name(&self) -> &str {
+        PLAN_TOOL_NAME
+    }
+
+    fn description(&self) -> &str {
+        "Maintain a step-by-step plan for multi-step tasks. The current plan is shown to you every round \
+         and to the user as a live checklist. Actions: create (goal + steps), add (steps), \
+         update (step + status/note/title), complete (step), show, clear."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["create", "add", "update", "complete", "show", "clear"],
+                    "description": "Operation to perform"
+                },
+                "goal": {
+                    "type": "string",
+                    "description": "Overall objective of the plan (create)"
+                },
+                "steps": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Step descriptions in order (create, add)"
+                },
+                "step": {
+                    "type": "integer",
+                    "description": "Step number to change (update, complete)"
+                },
+                "status": {
+                    "type": "string",
+                    "enum": ["pending", "in_progress", "completed", "skipped"],
+                    "description": "New step status (update)"
+                },
+                "note": {
+                    "type": "string",
+                    "description": "Short note on the step, e.g. a result or blocker; empty to clear (update, complete)"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Replacement step description (update)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        let session = current_session_key();
+        let plan = cached_or_load(&self.storage_dir, &session);
+
+        if action == "show" {
+            return Ok(ToolResult::ok(plan.map_or_else(
+                || "No plan yet.".to_string(),
+                |plan| plan.checklist(),
+            )));
+        }
+
+        // Planning only touches agent bookkeeping, but still counts against
+        // the action budget so a looping model cannot spin on it for free.
+        if self.security.is_rate_limited() || !self.security.record_action() {
+            return Ok(ToolResult::err(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        let updated = match apply_action(plan, action, &args) {
+            Ok(updated) => updated,
+            Err(e) => return Ok(ToolResult::err(e)),
+        };
+        if let Err(e) = self.save(&session, updated.clone()).await {
+            tracing::warn!("Failed to persist plan for session {session:?}: {e}");
+        }
+
+        Ok(ToolResult::ok(updated.map_or_else(
+            || "Plan cleared.".to_string(),
+            |plan| plan.checklist(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_tool(workspace: &Path) -> PlanTool {
+        PlanTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy: AutonomyLevel::Supervised,
+                workspace_dir: workspace.to_path_buf(),
+                ..SecurityPolicy::default()
+            }),
+            workspace,
+        )
+    }
+
+    async fn in_session<F: std::future::Future>(session: &str, future: F) -> F::Output {
+        CURRENT_SESSION_ID.scope(session.to_string(), future).await
+    }
+
+    #[test]
+    fn apply_action_updates_and_completes_steps() {
+        let plan = apply_action(
+            None,
+            "create",
+            &json!({"goal": "Ship release", "steps": ["Run tests", "Tag", "Publish"]}),
+        )
+        .unwrap();
+        let plan = apply_action(
+            plan,
+            "update",
+            &json!({"step": 1, "status": "in_progress", "note": "CI running"}),
+        )
+        .unwrap();
+        let plan = apply_action(plan, "complete", &json!({"step": 2}))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(plan.steps[0].status, PlanStepStatus::InProgress);
+        assert_eq!(plan.steps[0].note.as_deref(), Some("CI running"));
+        assert_eq!(plan.steps[1].status, PlanStepStatus::Completed);
+        assert_eq!(
+            plan.checklist(),
+            "📋 Ship release (1/3)\n🔄 Run tests — CI running\n✅ Tag\n⬜ Publish"
+        );
+        assert!(plan
+            .prompt_block()
+            .contains("1. [in progress] Run tests — CI running"));
+    }
+
+    #[test]
+    fn apply_action_rejects_unknown_steps_and_missing_plan() {
+        let err = apply_action(None, "complete", &json!({"step": 1})).unwrap_err();
+        assert!(err.contains("create"));
+
+        let plan = apply_action(None, "create", &json!({"goal": "g", "steps": ["a"]})).unwrap();
+        let err =
+            apply_action(plan, "update", &json!({"step": 9, "status": "completed"})).unwrap_err();
+        assert!(err.contains("no step 9"));
+    }
+
+    #[tokio::test]
+    async fn plan_tool_persists_per_session_and_feeds_current_plan() {
+        let dir = TempDir::new().unwrap();
+        let tool = test_tool(dir.path());
+
+        let result = in_session("plan-test-a", async {
+            tool.execute(
+                json!({"action": "create", "goal": "Migrate DB", "steps": ["Backup", "Migrate"]}),
+            )
+            .await
+            .unwrap()
+        })
+        .await;
+        assert!(result.success, "{:?}", result.error);
+
+        let plans = plan_dir(dir.path());
+        let plan = in_session("plan-test-a", async { current_plan(&plans) })
+            .await
+            .unwrap();
+        assert_eq!(plan.goal, "Migrate DB");
+        assert!(in_session("plan-test-b", async { current_plan(&plans) })
+            .await
+            .is_none());
+
+        let path = plan_file(&plans, "plan-test-a");
+        let saved = std::fs::read_to_string(&path).unwrap();
+        let saved: TaskPlan = serde_json::from_str(&saved).unwrap();
+        assert_eq!(saved, plan);
+
+        in_session("plan-test-a", async {
+            tool.execute(json!({"action": "clear"})).await.unwrap()
+        })
+        .await;
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn plan_file_names_do_not_collide_after_sanitizing() {
+        let dir = Path::new("/plans");
+        assert_ne!(plan_file(dir, "a:b"), plan_file(dir, "a_b"));
+        assert_eq!(plan_file(dir, "a:b"), plan_file(dir, "a:b"));
+        let name = plan_file(dir, "telegram_alice/1");
+        let name = name.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("telegram_alice_1-"));
+        assert!(name.ends_with(".json"));
+    }
+
+    #[test]
+    fn plan_store_evicts_least_recently_used_sessions() {
+        let store = PlanStore {
+            plans: Mutex::new(HashMap::new()),
+        };
+        let dir = Path::new("/plans");
+        let key = |session: &str| (dir.to_path_buf(), session.to_string());
+        store.insert(dir, unnamed_session_key(), None);
+        store.insert(dir, "first", None);
+        let stale = Instant::now() - std::time::Duration::from_secs(60);
+        store.plans.lock().get_mut(&key("first")).unwrap().used_at = stale;
+        for i in 0..MAX_CACHED_PLANS {
+            store.insert(dir, &format!("session-{i}"), None);
+        }
+
+        assert_eq!(store.plans.lock().len(), MAX_CACHED_PLANS);
+        let plans = store.plans.lock();
+        assert!(plans.contains_key(&key(unnamed_session_key())));
+        assert!(!plans.contains_key(&key("first")));
+    }
+
+    #[tokio::test]
+    async fn plan_tool_loads_persisted_plan_for_resumed_session() {
+        let dir = TempDir::new().unwrap();
+        let plans = dir.path().join("state/plans");
+        std::fs::create_dir_all(&plans).unwrap();
+        let plan = TaskPlan {
+            goal: "Resume".into(),
+            steps: vec![PlanStep {
+                id: 1,
+                title: "Continue".into(),
+                status: PlanStepStatus::Pending,
+                note: None,
+            }],
+            updated_at: Utc::now(),
+        };
+        std::fs::write(
+            plan_file(&plans, "telegram_alice/1"),
+            serde_json::to_string(&plan).unwrap(),
+        )
+        .unwrap();
+
+        let tool = test_tool(dir.path());
+        let result = in_session("telegram_alice/1", async {
+            tool.execute(json!({"action": "show"})).await.unwrap()
+        })
+        .await;
+
+        assert!(result.output.contains("⬜ Continue"));
+    }
+
+    #[tokio::test]
+    async fn plans_are_kept_per_workspace() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let first_tool = test_tool(first.path());
+        let _second_tool = test_tool(second.path());
+
+        in_session("plan-test-workspaces", async {
+            first_tool
+                .execute(json!({"action": "create", "goal": "First", "steps": ["One"]}))
+                .await
+                .unwrap();
+            assert_eq!(
+                current_plan(&plan_dir(first.path())).map(|plan| plan.goal),
+                Some("First".to_string())
+            );
+            assert!(current_plan(&plan_dir(second.path())).is_none());
+        })
+        .await;
+    }
+}
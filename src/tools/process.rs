@@ -0,0 +1,769 @@
+use super::shell::SAFE_ENV_VARS;
+use super::traits::{Tool, ToolResult};
+use crate::config::ResourceLimitsConfig;
+use crate::gateway::CURRENT_SESSION_ID;
+use crate::runtime::RuntimeAdapter;
+use crate::security::{Sandbox, SecurityPolicy};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::process::{ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::{Child, ChildStdin};
+use tokio::sync::oneshot;
+
+/// Bytes of combined stdout/stderr retained per process; older output is dropped.
+const OUTPUT_BUFFER_BYTES: usize = 256 * 1024;
+/// Maximum bytes returned by a single `read`.
+const MAX_READ_BYTES: usize = 64 * 1024;
+/// Finished processes kept for `list`/`read` before the oldest are forgotten.
+const MAX_FINISHED_PROCESSES: usize = 16;
+/// How long `start` waits to capture early output or an immediate exit.
+const START_SETTLE: Duration = Duration::from_millis(300);
+/// Grace period between SIGTERM and SIGKILL when stopping a process.
+const KILL_GRACE: Duration = Duration::from_secs(2);
+const MEMORY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Owner recorded for processes started outside a gateway session.
+const LOCAL_OWNER: &str = "local";
+
+/// Combined output of a background process, kept as a bounded ring buffer.
+#[derive(Debug, Default)]
+struct OutputBuffer {
+    data: VecDeque<u8>,
+    /// Bytes evicted from the front of the buffer so far.
+    dropped: usize,
+    /// Offset up to which output has been returned by `read`.
+    read_cursor: usize,
+}
+
+impl OutputBuffer {
+    fn push(&mut self, bytes: &[u8]) {
+        self.data.extend(bytes);
+        let overflow = self.data.len().saturating_sub(OUTPUT_BUFFER_BYTES);
+        if overflow > 0 {
+            self.data.drain(..overflow);
+            self.dropped += overflow;
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.dropped + self.data.len()
+    }
+
+    /// Output from `offset` onwards, capped to the last `MAX_READ_BYTES`.
+    /// Also returns how many requested bytes were no longer available.
+    fn read_from(&self, offset: usize) -> (String, usize) {
+        let start = offset
+            .max(self.dropped)
+            .max(self.total().saturating_sub(MAX_READ_BYTES));
+        let skipped = start.saturating_sub(offset);
+        let bytes: Vec<u8> = self.data.range(start - self.dropped..).copied().collect();
+        (String::from_utf8_lossy(&bytes).into_owned(), skipped)
+    }
+}
+
+/// A command started by the `process` tool.
+struct ManagedProcess {
+    id: u32,
+    owner: String,
+    command: String,
+    pid: Option<u32>,
+    started: Instant,
+    output: Arc<Mutex<OutputBuffer>>,
+    stdin: tokio::sync::Mutex<Option<ChildStdin>>,
+    exit: Arc<Mutex<Option<String>>>,
+    /// Dropping the sender (with the process table) also stops the process.
+    kill: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl ManagedProcess {
+    fn is_running(&self) -> bool {
+        self.exit.lock().is_none()
+    }
+
+    fn status_line(&self) -> String {
+        let state = self
+            .exit
+            .lock()
+            .clone()
+            .unwrap_or_else(|| format!("running for {}s", self.started.elapsed().as_secs()));
+        let pid = self
+            .pid
+            .map(|pid| format!(" pid {pid}"))
+            .unwrap_or_default();
+        format!("[{}]{pid} {state}: {}", self.id, self.command)
+    }
+}
+
+/// Start, inspect and stop long-running commands (dev servers, watchers,
+/// log tails) that outlive a single `shell` call.
+pub struct ProcessTool {
+    security: Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    sandbox: Option<Arc<dyn Sandbox>>,
+    limits: ResourceLimitsConfig,
+    processes: Mutex<Vec<Arc<ManagedProcess>>>,
+    next_id: AtomicU32,
+}
+
+impl ProcessTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        limits: ResourceLimitsConfig,
+    ) -> Self {
+        Self {
+            security,
+            runtime,
+            sandbox: None,
+            limits,
+            processes: Mutex::new(Vec::new()),
+            next_id: AtomicU32::new(1),
+        }
+    }
+
+    /// Wrap started commands with an OS-level sandbox backend.
+    pub fn with_sandbox(mut self, sandbox: Arc<dyn Sandbox>) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// Look up a process owned by the calling session.
+    fn find(&self, args: &serde_json::Value, owner: &str) -> Result<Arc<ManagedProcess>, String> {
+        let id = args
+            .get("id")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| "Missing 'id' parameter".to_string())?;
+        self.processes
+            .lock()
+            .iter()
+            .find(|process| u64::from(process.id) == id && process.owner == owner)
+            .cloned()
+            .ok_or_else(|| format!("No process with id {id} in this session"))
+    }
+
+    fn check_mutation(&self) -> Option<ToolResult> {
+        if !self.security.can_act() {
+            return Some(ToolResult::err("Action blocked: autonomy is read-only"));
+        }
+        if self.security.is_rate_limited() {
+            return Some(ToolResult::err(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if !self.security.record_action() {
+            return Some(ToolResult::err(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+        None
+    }
+
+    async fn start(&self, args: &serde_json::Value, owner: String) -> ToolResult {
+        let Some(command) = args.get("command").and_then(|v| v.as_str()) else {
+            return ToolResult::err("Missing 'command' parameter");
+        };
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        if !self.runtime.supports_long_running() {
+            return ToolResult::err(format!(
+                "The {} runtime does not support background processes",
+                self.runtime.name()
+            ));
+        }
+        if self.security.is_rate_limited() {
+            return ToolResult::err("Rate limit exceeded: too many actions in the last hour");
+        }
+        if let Err(reason) = self.security.validate_command_execution(command, approved) {
+            return ToolResult::err(reason);
+        }
+
+        let running = self
+            .processes
+            .lock()
+            .iter()
+            .filter(|process| process.is_running())
+            .count();
+        let max_running = usize::try_from(self.limits.max_subprocesses).unwrap_or(usize::MAX);
+        if running >= max_running {
+            return ToolResult::err(format!(
+                "Too many background processes ({running} running, limit {max_running}); kill one first"
+            ));
+        }
+
+        if !self.security.record_action() {
+            return ToolResult::err("Rate limit exceeded: action budget exhausted");
+        }
+
+        let mut cmd = match self
+            .runtime
+            .build_shell_command(command, &self.security.workspace_dir)
+        {
+            Ok(cmd) => cmd,
+            Err(e) => return ToolResult::err(format!("Failed to build runtime command: {e}")),
+        };
+        if let Some(sandbox) = &self.sandbox {
+            if let Err(e) = sandbox.wrap_command(cmd.as_std_mut()) {
+                return ToolResult::err(format!("Failed to apply {} sandbox: {e}", sandbox.name()));
+            }
+        }
+        // Sandbox wrappers may rebuild the command, so configure it afterwards.
+        // Same environment hygiene as `shell`: never leak API keys (CWE-200).
+        cmd.current_dir(&self.security.workspace_dir)
+            .env_clear()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        for var in SAFE_ENV_VARS {
+            if let Ok(val) = std::env::var(var) {
+                cmd.env(var, val);
+            }
+        }
+        #[cfg(unix)]
+        {
+            // Own process group so `kill` reaches everything the command spawns.
+            cmd.process_group(0);
+            apply_cpu_limit(&mut cmd, self.limits.max_cpu_time_seconds);
+        }
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => return ToolResult::err(format!("Failed to start command: {e}")),
+        };
+
+        let output = Arc::new(Mutex::new(OutputBuffer::default()));
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(pump_output(stdout, output.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(pump_output(stderr, output.clone()));
+        }
+
+        let (kill_tx, kill_rx) = oneshot::channel();
+        let exit = Arc::new(Mutex::new(None));
+        let pid = child.id();
+        let memory_limit_mb = self
+            .limits
+            .memory_monitoring
+            .then_some(self.limits.max_memory_mb)
+            .filter(|mb| *mb > 0);
+        let process = Arc::new(ManagedProcess {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            owner,
+            command: command.to_string(),
+            pid,
+            started: Instant::now(),
+            output: output.clone(),
+            stdin: tokio::sync::Mutex::new(child.stdin.take()),
+            exit: exit.clone(),
+            kill: Mutex::new(Some(kill_tx)),
+        });
+        tokio::spawn(supervise(child, kill_rx, memory_limit_mb, output, exit));
+
+        {
+            let mut processes = self.processes.lock();
+            processes.push(process.clone());
+            prune_finished(&mut processes);
+        }
+
+        // Give the command a moment so immediate failures and startup banners
+        // show up in the result instead of needing a separate read.
+        tokio::time::sleep(START_SETTLE).await;
+        let early_output = take_unread(&process);
+        let mut text = format!("Started process {}", process.status_line());
+        if !early_output.is_empty() {
+            let _ = write!(text, "\n\n{early_output}");
+        }
+        let _ = write!(
+            text,
+            "\n\nUse action \"read\" with id {} to see further output.",
+            process.id
+        );
+        ToolResult::ok(text)
+    }
+
+    fn list(&self, owner: &str) -> ToolResult {
+        let lines: Vec<String> = self
+            .processes
+            .lock()
+            .iter()
+            .filter(|process| process.owner == owner)
+            .map(|process| process.status_line())
+            .collect();
+        ToolResult::ok(if lines.is_empty() {
+            "No background processes in this session.".into()
+        } else {
+            lines.join("\n")
+        })
+    }
+
+    fn read(&self, args: &serde_json::Value, owner: &str) -> ToolResult {
+        let process = match self.find(args, owner) {
+            Ok(process) => process,
+            Err(e) => return ToolResult::err(e),
+        };
+        let from_start = args
+            .get("from_start")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if from_start {
+            process.output.lock().read_cursor = 0;
+        }
+        let output = take_unread(&process);
+        ToolResult::ok(format!(
+            "{}\n\n{}",
+            process.status_line(),
+            if output.is_empty() {
+                "(no new output)"
+            } else {
+                &output
+            }
+        ))
+    }
+
+    async fn send(&self, args: &serde_json::Value, owner: &str) -> ToolResult {
+        let process = match self.find(args, owner) {
+            Ok(process) => process,
+            Err(e) => return ToolResult::err(e),
+        };
+        let Some(input) = args.get("input").and_then(|v| v.as_str()) else {
+            return ToolResult::err("Missing 'input' parameter");
+        };
+        if !process.is_running() {
+            return ToolResult::err(format!("Process {} is not running", process.id));
+        }
+        if let Some(result) = self.check_mutation() {
+            return result;
+        }
+
+        let newline = args
+            .get("newline")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+        let mut payload = input.to_string();
+        if newline {
+            payload.push('\n');
+        }
+
+        let mut stdin = process.stdin.lock().await;
+        let Some(pipe) = stdin.as_mut() else {
+            return ToolResult::err(format!("Process {} has closed its input", process.id));
+        };
+        let written = async {
+            pipe.write_all(payload.as_bytes()).await?;
+            pipe.flush().await
+        }
+        .await;
+        match written {
+            Ok(()) => ToolResult::ok(format!(
+                "Sent {} bytes to process {}",
+                payload.len(),
+                process.id
+            )),
+            Err(e) => {
+                *stdin = None;
+                ToolResult::err(format!("Failed to write to process {}: {e}", process.id))
+            }
+        }
+    }
+
+    async fn kill(&self, args: &serde_json::Value, owner: &str) -> ToolResult {
+        let process = match self.find(args, owner) {
+            Ok(process) => process,
+            Err(e) => return ToolResult::err(e),
+        };
+        if let Some(result) = self.check_mutation() {
+            return result;
+        }
+        if let Some(kill) = process.kill.lock().take() {
+            let _ = kill.send(());
+        }
+
+        let deadline = Instant::now() + KILL_GRACE + Duration::from_secs(1);
+        while process.is_running() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        ToolResult::ok(process.status_line())
+    }
+}
+
+fn take_unread(process: &ManagedProcess) -> String {
+    let mut buffer = process.output.lock();
+    let (text, skipped) = buffer.read_from(buffer.read_cursor);
+    buffer.read_cursor = buffer.total();
+    if skipped > 0 {
+        format!("[{skipped} earlier bytes dropped]\n{text}")
+    } else {
+        text
+    }
+}
+
+/// Forget the oldest finished processes beyond `MAX_FINISHED_PROCESSES`.
+fn prune_finished(processes: &mut Vec<Arc<ManagedProcess>>) {
+    let finished = processes.iter().filter(|p| !p.is_running()).count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_PROCESSES);
+    processes.retain(|process| {
+        if excess > 0 && !process.is_running() {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
+
+async fn pump_output(mut stream: impl AsyncRead + Unpin, output: Arc<Mutex<OutputBuffer>>) {
+    let mut chunk = [0u8; 4096];
+    while let Ok(read) = stream.read(&mut chunk).await {
+        if read == 0 {
+            break;
+        }
+        output.lock().push(&chunk[..read]);
+    }
+}
+
+/// Wait for the process to exit, stopping it on request, when the tool is
+/// dropped, or when it exceeds the configured memory limit.
+async fn supervise(
+    mut child: Child,
+    kill_rx: oneshot::Receiver<()>,
+    memory_limit_mb: Option<u32>,
+    output: Arc<Mutex<OutputBuffer>>,
+    exit: Arc<Mutex<Option<String>>>,
+) {
+    let pid = child.id();
+    let outcome = tokio::select! {
+        status = child.wait() => describe_exit(status),
+        _ = kill_rx => {
+            terminate(&mut child).await;
+            "killed".to_string()
+        }
+        rss_mb = watch_memory(pid, memory_limit_mb) => {
+            terminate(&mut child).await;
+            let message = format!("killed: memory limit exceeded ({rss_mb} MB > {} MB)", memory_limit_mb.unwrap_or_default());
+            output.lock().push(format!("\n[{message}]\n").as_bytes());
+            message
+        }
+    };
+    *exit.lock() = Some(outcome);
+}
+
+fn describe_exit(status: std::io::Result<ExitStatus>) -> String {
+    match status {
+        Ok(status) => match status.code() {
+            Some(code) => format!("exited with code {code}"),
+            None => "terminated by signal".to_string(),
+        },
+        Err(e) => format!("lost track of process: {e}"),
+    }
+}
+
+/// Ask the whole process group to stop, escalating to SIGKILL after a grace period.
+async fn terminate(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id().and_then(|pid| libc::pid_t::try_from(pid).ok()) {
+        // SAFETY: signalling our own child's process group has no memory-safety
+        // implications; a stale pid only makes kill(2) fail.
+        unsafe {
+            libc::kill(-pid, libc::SIGTERM);
+        }
+        if tokio::time::timeout(KILL_GRACE, child.wait()).await.is_ok() {
+            return;
+        }
+        // SAFETY: as above.
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+    }
+    let _ = child.kill().await;
+}
+
+/// Resolve with the resident size in MB once it exceeds `limit_mb`. Never
+/// resolves when monitoring is off or unsupported on this platform.
+async fn watch_memory(pid: Option<u32>, limit_mb: Option<u32>) -> u64 {
+    let (Some(pid), Some(limit_mb)) = (pid, limit_mb) else {
+        return std::future::pending().await;
+    };
+    let status_path = format!("/proc/{pid}/status");
+    let mut interval = tokio::time::interval(MEMORY_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let Ok(status) = tokio::fs::read_to_string(&status_path).await else {
+            return std::future::pending().await;
+        };
+        let rss_kb = status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))
+            .and_then(|value| {
+                value
+                    .trim()
+                    .trim_end_matches("kB")
+                    .trim()
+                    .parse::<u64>()
+                    .ok()
+            });
+        if let Some(rss_mb) = rss_kb.map(|kb| kb / 1024) {
+            if rss_mb > u64::from(limit_mb) {
+                return rss_mb;
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn apply_cpu_limit(cmd: &mut tokio::process::Command, seconds: u64) {
+    if seconds == 0 {
+        return;
+    }
+    let limit = libc::rlimit {
+        rlim_cur: seconds as libc::rlim_t,
+        rlim_max: seconds as libc::rlim_t,
+    };
+    // SAFETY: the closure runs in the forked child before exec and only calls
+    // setrlimit(2), which is async-signal-safe.
+    unsafe {
+        cmd.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_CPU, &raw const limit) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl Tool for ProcessTool {
+    fn name(&self) -> &str {
+        "process"
+    }
+
+    fn description(&self) -> &str {
+        "Manage long-running background commands (dev servers, watchers, builds). \
+         Actions: start (run a command in the background), list, read (new output since the last read), \
+         send (write a line to stdin), kill."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["start", "list", "read", "send", "kill"],
+                    "description": "Operation to perform"
+                },
+                "command": {
+                    "type": "string",
+                    "description": "Shell command to run in the background (start)"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true to explicitly approve medium/high-risk commands in supervised mode (start)",
+                    "default": false
+                },
+                "id": {
+                    "type": "integer",
+                    "description": "Process id returned by start (read, send, kill)"
+                },
+                "input": {
+                    "type": "string",
+                    "description": "Text to write to the process's stdin (send)"
+                },
+                "newline": {
+                    "type": "boolean",
+                    "description": "Append a newline to input (send, default: true)"
+                },
+                "from_start": {
+                    "type": "boolean",
+                    "description": "Return all retained output instead of only new output (read)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let owner = CURRENT_SESSION_ID
+            .try_with(Clone::clone)
+            .unwrap_or_else(|_| LOCAL_OWNER.to_string());
+
+        Ok(match action {
+            "start" => self.start(&args, owner).await,
+            "list" => self.list(&owner),
+            "read" => self.read(&args, &owner),
+            "send" => self.send(&args, &owner).await,
+            "kill" => self.kill(&args, &owner).await,
+            other => ToolResult::err(format!(
+                "Unknown action '{other}'. Use start, list, read, send or kill"
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::NativeRuntime;
+    use crate::security::AutonomyLevel;
+
+    fn test_tool(autonomy: AutonomyLevel) -> ProcessTool {
+        ProcessTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy,
+                workspace_dir: std::env::temp_dir(),
+                ..SecurityPolicy::default()
+            }),
+            Arc::new(NativeRuntime::new()),
+            ResourceLimitsConfig::default(),
+        )
+    }
+
+    async fn wait_for_output(tool: &ProcessTool, id: u32, needle: &str) -> String {
+        let mut seen = String::new();
+        for _ in 0..50 {
+            let result = tool
+                .execute(json!({"action": "read", "id": id}))
+                .await
+                .unwrap();
+            seen.push_str(&result.output);
+            if seen.contains(needle) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        seen
+    }
+
+    #[test]
+    fn output_buffer_drops_oldest_bytes() {
+        let mut buffer = OutputBuffer::default();
+        buffer.push(&vec![b'a'; OUTPUT_BUFFER_BYTES]);
+        buffer.push(b"tail");
+
+        assert_eq!(buffer.dropped, 4);
+        let (text, skipped) = buffer.read_from(OUTPUT_BUFFER_BYTES);
+        assert_eq!((text.as_str(), skipped), ("tail", 0));
+        let (text, skipped) = buffer.read_from(0);
+        assert_eq!(text.len(), MAX_READ_BYTES);
+        assert_eq!(skipped, OUTPUT_BUFFER_BYTES + 4 - MAX_READ_BYTES);
+    }
+
+    #[test]
+    fn process_tool_schema_lists_actions() {
+        let tool = test_tool(AutonomyLevel::Supervised);
+        assert_eq!(tool.name(), "process");
+        let schema = tool.parameters_schema();
+        assert_eq!(
+            schema["properties"]["action"]["enum"],
+            json!(["start", "list", "read", "send", "kill"])
+        );
+    }
+
+    #[tokio::test]
+    async fn process_start_read_and_exit() {
+        let tool = test_tool(AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"action": "start", "command": "echo hello"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Started process [1]"));
+
+        let seen = wait_for_output(&tool, 1, "hello").await;
+        assert!(seen.contains("hello"));
+
+        let list = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(list.output.contains("echo hello"));
+    }
+
+    #[tokio::test]
+    async fn process_send_input_and_kill() {
+        let tool = test_tool(AutonomyLevel::Supervised);
+        tool.execute(json!({"action": "start", "command": "cat"}))
+            .await
+            .unwrap();
+
+        let sent = tool
+            .execute(json!({"action": "send", "id": 1, "input": "ping"}))
+            .await
+            .unwrap();
+        assert!(sent.success, "{:?}", sent.error);
+        assert!(wait_for_output(&tool, 1, "ping").await.contains("ping"));
+
+        let killed = tool
+            .execute(json!({"action": "kill", "id": 1}))
+            .await
+            .unwrap();
+        assert!(killed.success);
+        assert!(killed.output.contains("killed"));
+    }
+
+    #[tokio::test]
+    async fn process_is_scoped_to_owning_session() {
+        let tool = test_tool(AutonomyLevel::Supervised);
+        CURRENT_SESSION_ID
+            .scope("session-a".to_string(), async {
+                tool.execute(json!({"action": "start", "command": "cat"}))
+                    .await
+                    .unwrap();
+            })
+            .await;
+
+        let result = CURRENT_SESSION_ID
+            .scope("session-b".to_string(), async {
+                tool.execute(json!({"action": "read", "id": 1}))
+                    .await
+                    .unwrap()
+            })
+            .await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("No process with id 1"));
+
+        let list = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(list.output.contains("No background processes"));
+    }
+
+    #[tokio::test]
+    async fn process_start_enforces_concurrency_limit() {
+        let mut tool = test_tool(AutonomyLevel::Supervised);
+        tool.limits.max_subprocesses = 1;
+        tool.execute(json!({"action": "start", "command": "cat"}))
+            .await
+            .unwrap();
+
+        let result = tool
+            .execute(json!({"action": "start", "command": "cat"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("Too many background processes"));
+    }
+
+    #[tokio::test]
+    async fn process_start_respects_command_policy() {
+        let tool = test_tool(AutonomyLevel::ReadOnly);
+        let result = tool
+            .execute(json!({"action": "start", "command": "ls"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+}
@@ -130,6 +130,7 @@ impl Tool for ScheduleTool {
                 error: Some(format!(
                     "Unknown action '{other}'. Use create/add/once/list/get/cancel/remove/pause/resume."
                 )),
+                parts: Vec::new(),
             }),
         }
     }
@@ -144,6 +145,7 @@ impl ScheduleTool {
                 error: Some(format!(
                     "cron is disabled by config (cron.enabled=false); cannot perform '{action}'"
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -154,6 +156,7 @@ impl ScheduleTool {
                 error: Some(format!(
                     "Security policy: read-only mode, cannot perform '{action}'"
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -162,6 +165,7 @@ impl ScheduleTool {
                 success: false,
                 output: String::new(),
                 error: Some("Rate limit exceeded: action budget exhausted".to_string()),
+                parts: Vec::new(),
             });
         }
 
@@ -175,6 +179,7 @@ impl ScheduleTool {
                 success: true,
                 output: "No scheduled jobs.".to_string(),
                 error: None,
+                parts: Vec::new(),
             });
         }
 
@@ -208,6 +213,7 @@ impl ScheduleTool {
             success: true,
             output: format!("Scheduled jobs ({}):\n{}", lines.len(), lines.join("\n")),
             error: None,
+            parts: Vec::new(),
         })
     }
 
@@ -228,12 +234,14 @@ impl ScheduleTool {
                     success: true,
                     output: serde_json::to_string_pretty(&detail)?,
                     error: None,
+                    parts: Vec::new(),
                 })
             }
             Err(_) => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!("Job '{id}' not found")),
+                parts: Vec::new(),
             }),
         }
     }
@@ -255,6 +263,7 @@ impl ScheduleTool {
                 success: false,
                 output: String::new(),
                 error: Some(reason),
+                parts: Vec::new(),
             });
         }
 
@@ -269,6 +278,7 @@ impl ScheduleTool {
                         success: false,
                         output: String::new(),
                         error: Some("'add' requires 'expression' and forbids delay/run_at".into()),
+                        parts: Vec::new(),
                     });
                 }
             }
@@ -278,6 +288,7 @@ impl ScheduleTool {
                         success: false,
                         output: String::new(),
                         error: Some("'once' requires exactly one of 'delay' or 'run_at'".into()),
+                        parts: Vec::new(),
                     });
                 }
                 if delay.is_some() && run_at.is_some() {
@@ -285,6 +296,7 @@ impl ScheduleTool {
                         success: false,
                         output: String::new(),
                         error: Some("'once' supports either delay or run_at, not both".into()),
+                        parts: Vec::new(),
                     });
                 }
             }
@@ -301,6 +313,7 @@ impl ScheduleTool {
                             "Exactly one of 'expression', 'delay', or 'run_at' must be provided"
                                 .into(),
                         ),
+                        parts: Vec::new(),
                     });
                 }
             }
@@ -318,6 +331,7 @@ impl ScheduleTool {
                     job.command
                 ),
                 error: None,
+                parts: Vec::new(),
             });
         }
 
@@ -332,6 +346,7 @@ impl ScheduleTool {
                     job.command
                 ),
                 error: None,
+                parts: Vec::new(),
             });
         }
 
@@ -350,6 +365,7 @@ impl ScheduleTool {
                 job.command
             ),
             error: None,
+            parts: Vec::new(),
         })
     }
 
@@ -359,11 +375,13 @@ impl ScheduleTool {
                 success: true,
                 output: format!("Cancelled job {id}"),
                 error: None,
+                parts: Vec::new(),
             },
             Err(error) => ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(error.to_string()),
+                parts: Vec::new(),
             },
         }
     }
@@ -384,11 +402,13 @@ impl ScheduleTool {
                     format!("Resumed job {id}")
                 },
                 error: None,
+                parts: Vec::new(),
             },
             Err(error) => ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(error.to_string()),
+                parts: Vec::new(),
             },
         }
     }
@@ -0,0 +1,186 @@
+use super::traits::{Tool, ToolContent, ToolMetadata, ToolResult};
+use crate::channels::traits::{OutboundAttachment, MAX_OUTBOUND_ATTACHMENT_BYTES};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Attach a workspace file to the reply on the current channel.
+///
+/// The tool only validates the path and returns a `File` content part; the
+/// channel dispatcher uploads it with the final reply.
+pub struct SendFileTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl SendFileTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for SendFileTool {
+    fn name(&self) -> &str {
+        "send_file"
+    }
+
+    fn description(&self) -> &str {
+        "Send a file from the workspace (chart, screenshot, log, document) to the user as an attachment on the current chat reply."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Relative path to the file within the workspace"
+                },
+                "mime_type": {
+                    "type": "string",
+                    "description": "MIME type override (default: guessed from the extension)"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::mutating()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(self.metadata().operation(), "send_file")
+        {
+            return Ok(ToolResult::err(error));
+        }
+
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult::err(format!(
+                "Path not allowed by security policy: {path}"
+            )));
+        }
+
+        let resolved_path =
+            match tokio::fs::canonicalize(self.security.workspace_dir.join(path)).await {
+                Ok(p) => p,
+                Err(e) => return Ok(ToolResult::err(format!("Failed to resolve file path: {e}"))),
+            };
+
+        if !self.security.is_resolved_path_allowed(&resolved_path) {
+            return Ok(ToolResult::err(format!(
+                "Resolved path escapes workspace: {}",
+                resolved_path.display()
+            )));
+        }
+
+        match tokio::fs::metadata(&resolved_path).await {
+            Ok(meta) if !meta.is_file() => {
+                return Ok(ToolResult::err(format!("Not a regular file: {path}")));
+            }
+            Ok(meta) if meta.len() > MAX_OUTBOUND_ATTACHMENT_BYTES as u64 => {
+                return Ok(ToolResult::err(format!(
+                    "File too large: {} bytes (limit: {MAX_OUTBOUND_ATTACHMENT_BYTES} bytes)",
+                    meta.len()
+                )));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Ok(ToolResult::err(format!(
+                    "Failed to read file metadata: {e}"
+                )))
+            }
+        }
+
+        let mut attachment = OutboundAttachment::from_path(&resolved_path);
+        if let Some(mime_type) = args
+            .get("mime_type")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|m| m.contains('/'))
+        {
+            attachment = attachment.with_mime_type(mime_type);
+        }
+
+        Ok(ToolResult::ok(format!(
+            "{} ({}) will be attached to the reply",
+            attachment.file_name, attachment.mime_type
+        ))
+        .with_parts(vec![ToolContent::File {
+            path: resolved_path.display().to_string(),
+            mime_type: Some(attachment.mime_type),
+        }]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn tool_in(dir: &std::path::Path, autonomy: AutonomyLevel) -> SendFileTool {
+        SendFileTool::new(Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: dir.to_path_buf(),
+            ..SecurityPolicy::default()
+        }))
+    }
+
+    #[tokio::test]
+    async fn workspace_file_becomes_file_part() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("chart.png"), b"png").unwrap();
+
+        let result = tool_in(dir.path(), AutonomyLevel::Full)
+            .execute(json!({ "path": "chart.png" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("chart.png (image/png)"));
+        match &result.parts[..] {
+            [ToolContent::File { path, mime_type }] => {
+                assert!(path.ends_with("chart.png"));
+                assert_eq!(mime_type.as_deref(), Some("image/png"));
+            }
+            other => panic!("unexpected parts: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_workspace_and_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("logs")).unwrap();
+        let tool = tool_in(dir.path(), AutonomyLevel::Full);
+
+        let escaped = tool
+            .execute(json!({ "path": "../etc/passwd" }))
+            .await
+            .unwrap();
+        assert!(!escaped.success);
+
+        let directory = tool.execute(json!({ "path": "logs" })).await.unwrap();
+        assert!(!directory.success);
+        assert!(directory.error.unwrap().contains("Not a regular file"));
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_sending() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"hi").unwrap();
+
+        let result = tool_in(dir.path(), AutonomyLevel::ReadOnly)
+            .execute(json!({ "path": "notes.txt" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.parts.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::security::policy::ToolOperation;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -5,8 +6,96 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolResult {
     pub success: bool,
+    /// Plain-text rendering, always sent to the model
     pub output: String,
     pub error: Option<String>,
+    /// Typed content beyond `output`; images reach vision-capable providers
+    /// natively and images/files can be forwarded to the user by channels.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<ToolContent>,
+}
+
+impl ToolResult {
+    /// Successful result with plain-text `output`
+    pub fn ok(output: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            output: output.into(),
+            error: None,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Failed result carrying `error` and no output
+    pub fn err(error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            output: String::new(),
+            error: Some(error.into()),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Attach a typed content part
+    #[must_use]
+    pub fn with_part(mut self, part: ToolContent) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Attach several typed content parts
+    #[must_use]
+    pub fn with_parts(mut self, parts: impl IntoIterator<Item = ToolContent>) -> Self {
+        self.parts.extend(parts);
+        self
+    }
+}
+
+/// Typed piece of tool output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolContent {
+    Text {
+        text: String,
+    },
+    /// Base64-encoded image bytes
+    Image {
+        mime_type: String,
+        data: String,
+    },
+    /// File on disk produced by the tool
+    File {
+        path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mime_type: Option<String>,
+    },
+    Json {
+        value: serde_json::Value,
+    },
+}
+
+impl ToolContent {
+    /// Image part from raw bytes
+    pub fn image(mime_type: impl Into<String>, bytes: &[u8]) -> Self {
+        use base64::Engine;
+        Self::Image {
+            mime_type: mime_type.into(),
+            data: base64::engine::general_purpose::STANDARD.encode(bytes),
+        }
+    }
+
+    /// `data:` URI for image parts
+    pub fn image_data_uri(&self) -> Option<String> {
+        match self {
+            Self::Image { mime_type, data } => Some(format!("data:{mime_type};base64,{data}")),
+            _ => None,
+        }
+    }
+
+    /// Images and files are worth delivering to the user as attachments
+    pub fn is_attachment(&self) -> bool {
+        matches!(self, Self::Image { .. } | Self::File { .. })
+    }
 }
 
 /// Description of a tool for the LLM
@@ -17,6 +106,88 @@ pub struct ToolSpec {
     pub parameters: serde_json::Value,
 }
 
+/// Whether a tool only observes state or can change it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolEffect {
+    ReadOnly,
+    Mutating,
+}
+
+/// Static properties a tool declares for approval, dispatch and policy gating.
+///
+/// The default is deliberately conservative (mutating, not idempotent) so an
+/// undeclared tool is gated like any other action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolMetadata {
+    pub effect: ToolEffect,
+    /// Reaches hosts outside the machine (HTTP, email, remote APIs).
+    pub network: bool,
+    /// Hard ceiling the dispatcher enforces on a single call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_timeout_secs: Option<u64>,
+    /// Repeating a call with the same arguments has no further effect.
+    pub idempotent: bool,
+}
+
+impl ToolMetadata {
+    /// Observes state only; safe to repeat.
+    pub const fn read_only() -> Self {
+        Self {
+            effect: ToolEffect::ReadOnly,
+            network: false,
+            default_timeout_secs: None,
+            idempotent: true,
+        }
+    }
+
+    /// Changes state outside the conversation.
+    pub const fn mutating() -> Self {
+        Self {
+            effect: ToolEffect::Mutating,
+            network: false,
+            default_timeout_secs: None,
+            idempotent: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_network(mut self) -> Self {
+        self.network = true;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_timeout_secs(mut self, secs: u64) -> Self {
+        self.default_timeout_secs = Some(secs);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.effect == ToolEffect::ReadOnly
+    }
+
+    /// Operation class for [`crate::security::SecurityPolicy::enforce_tool_operation`].
+    pub fn operation(&self) -> ToolOperation {
+        match self.effect {
+            ToolEffect::ReadOnly => ToolOperation::Read,
+            ToolEffect::Mutating => ToolOperation::Act,
+        }
+    }
+}
+
+impl Default for ToolMetadata {
+    fn default() -> Self {
+        Self::mutating()
+    }
+}
+
 /// Core tool trait — implement for any capability
 #[async_trait]
 pub trait Tool: Send + Sync {
@@ -29,6 +200,11 @@ pub trait Tool: Send + Sync {
     /// JSON schema for parameters
     fn parameters_schema(&self) -> serde_json::Value;
 
+    /// Side effects, network use, timeout and idempotency
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::default()
+    }
+
     /// Execute the tool with given arguments
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult>;
 
@@ -76,6 +252,7 @@ mod tests {
                     .unwrap_or_default()
                     .to_string(),
                 error: None,
+                parts: Vec::new(),
             })
         }
     }
@@ -91,6 +268,20 @@ mod tests {
         assert_eq!(spec.parameters["properties"]["value"]["type"], "string");
     }
 
+    #[test]
+    fn undeclared_metadata_is_conservative() {
+        let metadata = DummyTool.metadata();
+
+        assert!(!metadata.is_read_only());
+        assert!(!metadata.idempotent);
+        assert_eq!(metadata.operation(), ToolOperation::Act);
+        assert_eq!(ToolMetadata::read_only().operation(), ToolOperation::Read);
+
+        let json = serde_json::to_value(ToolMetadata::read_only().with_timeout_secs(5)).unwrap();
+        assert_eq!(json["effect"], "read_only");
+        assert_eq!(json["default_timeout_secs"], 5);
+    }
+
     #[tokio::test]
     async fn execute_returns_expected_output() {
         let tool = DummyTool;
@@ -104,12 +295,42 @@ mod tests {
         assert!(result.error.is_none());
     }
 
+    #[test]
+    fn tool_content_parts_serialize_tagged_and_default_empty() {
+        let result = ToolResult {
+            success: true,
+            output: "captured".into(),
+            error: None,
+            parts: Vec::new(),
+        }
+        .with_part(ToolContent::image("image/png", b"\x89PNG"))
+        .with_part(ToolContent::Json {
+            value: serde_json::json!({"ok": true}),
+        });
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["parts"][0]["type"], "image");
+        assert_eq!(json["parts"][0]["mime_type"], "image/png");
+        assert_eq!(json["parts"][1]["type"], "json");
+        assert_eq!(
+            result.parts[0].image_data_uri().as_deref(),
+            Some("data:image/png;base64,iVBORw==")
+        );
+        assert!(result.parts[0].is_attachment());
+        assert!(!result.parts[1].is_attachment());
+
+        let legacy: ToolResult =
+            serde_json::from_str(r#"{"success":true,"output":"x","error":null}"#).unwrap();
+        assert!(legacy.parts.is_empty());
+    }
+
     #[test]
     fn tool_result_serialization_roundtrip() {
         let result = ToolResult {
             success: false,
             output: String::new(),
             error: Some("boom".into()),
+            parts: Vec::new(),
         };
 
         let json = serde_json::to_string(&result).unwrap();
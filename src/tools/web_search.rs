@@ -77,6 +77,7 @@ impl Tool for WebSearchTool {
                 success: false,
                 output: String::new(),
                 error: Some("Search query cannot be empty".into()),
+                parts: Vec::new(),
             });
         }
 
@@ -94,6 +95,7 @@ impl Tool for WebSearchTool {
                     "Brave API key not configured. Set [web_search].api_key in config.toml or BRAVE_API_KEY env var."
                         .into(),
                 ),
+                parts: Vec::new(),
             });
         }
 
@@ -115,6 +117,7 @@ impl Tool for WebSearchTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("HTTP request failed: {e}")),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -133,6 +136,7 @@ impl Tool for WebSearchTool {
                     status.as_u16(),
                     truncate_string(&error_body, 500)
                 )),
+                parts: Vec::new(),
             });
         }
 
@@ -143,6 +147,7 @@ impl Tool for WebSearchTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!("Failed to parse response JSON: {e}")),
+                    parts: Vec::new(),
                 });
             }
         };
@@ -187,6 +192,7 @@ impl Tool for WebSearchTool {
             success: true,
             output,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -1,4 +1,4 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Tool, ToolMetadata, ToolResult};
 use async_trait::async_trait;
 use regex::Regex;
 use serde_json::json;
@@ -204,6 +204,13 @@ impl Tool for WebSearchTool {
         })
     }
 
+    fn metadata(&self) -> ToolMetadata {
+        // Leave the client's own timeout room to fire and report first.
+        ToolMetadata::read_only()
+            .with_network()
+            .with_timeout_secs(self.timeout_secs.saturating_add(10))
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let query = args
             .get("query")
@@ -229,6 +236,7 @@ impl Tool for WebSearchTool {
             success: true,
             output: result,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -0,0 +1,338 @@
+use crate::config::TranscriptionConfig;
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Trait for speech-to-text backends — convert an audio file to text
+#[async_trait]
+pub trait Transcriber: Send + Sync {
+    /// Backend name
+    fn name(&self) -> &str;
+
+    /// Transcribe a local audio file
+    async fn transcribe(&self, audio: &Path, mime_type: &str) -> anyhow::Result<String>;
+}
+
+// ── Stub backend (offline testing) ───────────────────────────
+
+/// Returns a fixed transcript naming the file, without decoding any audio.
+pub struct StubTranscriber;
+
+#[async_trait]
+impl Transcriber for StubTranscriber {
+    fn name(&self) -> &str {
+        "stub"
+    }
+
+    async fn transcribe(&self, audio: &Path, _mime_type: &str) -> anyhow::Result<String> {
+        let file_name = audio
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(format!("stub transcript of {file_name}"))
+    }
+}
+
+// ── OpenAI-compatible /audio/transcriptions ──────────────────
+
+pub struct OpenAiTranscriber {
+    base_url: String,
+    api_key: String,
+    model: String,
+    language: Option<String>,
+    timeout_secs: u64,
+}
+
+impl OpenAiTranscriber {
+    pub fn new(
+        base_url: &str,
+        api_key: &str,
+        model: &str,
+        language: Option<String>,
+        timeout_secs: u64,
+    ) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+            language,
+            timeout_secs,
+        }
+    }
+
+    fn transcriptions_url(&self) -> String {
+        if self.base_url.ends_with("/audio/transcriptions") {
+            self.base_url.clone()
+        } else {
+            format!("{}/audio/transcriptions", self.base_url)
+        }
+    }
+}
+
+#[async_trait]
+impl Transcriber for OpenAiTranscriber {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    async fn transcribe(&self, audio: &Path, mime_type: &str) -> anyhow::Result<String> {
+        let bytes = tokio::fs::read(audio)
+            .await
+            .with_context(|| format!("Failed to read audio file {}", audio.display()))?;
+        let file_name = audio
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "audio".into());
+        let mime_type = if mime_type.contains('/') {
+            mime_type
+        } else {
+            "application/octet-stream"
+        };
+        let part = reqwest::multipart::Part::bytes(bytes)
+            .file_name(file_name)
+            .mime_str(mime_type)?;
+
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", part)
+            .text("model", self.model.clone())
+            .text("response_format", "json");
+        if let Some(language) = &self.language {
+            form = form.text("language", language.clone());
+        }
+
+        let mut request = crate::config::build_runtime_proxy_client_with_timeouts(
+            "provider.transcription",
+            self.timeout_secs,
+            10,
+        )
+        .post(self.transcriptions_url())
+        .multipart(form);
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+
+        let resp = request.send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            bail!(
+                "Transcription API error {status}: {}",
+                crate::providers::sanitize_api_error(&text)
+            );
+        }
+
+        let json: serde_json::Value = resp.json().await?;
+        let text = json
+            .get("text")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid transcription response: missing 'text'"))?;
+        Ok(text.trim().to_string())
+    }
+}
+
+// ── Local command (whisper.cpp and friends) ──────────────────
+
+/// Runs a local program and reads the transcript from its stdout.
+///
+/// `{file}` in the arguments is replaced with the audio path; when no argument
+/// contains it, the path is appended.
+pub struct CommandTranscriber {
+    command: String,
+    args: Vec<String>,
+    timeout_secs: u64,
+}
+
+impl CommandTranscriber {
+    pub fn new(command: &str, args: Vec<String>, timeout_secs: u64) -> Self {
+        Self {
+            command: command.to_string(),
+            args,
+            timeout_secs,
+        }
+    }
+
+    fn command_args(&self, audio: &Path) -> Vec<String> {
+        let file = audio.display().to_string();
+        let mut args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| arg.replace("{file}", &file))
+            .collect();
+        if !self.args.iter().any(|arg| arg.contains("{file}")) {
+            args.push(file);
+        }
+        args
+    }
+}
+
+#[async_trait]
+impl Transcriber for CommandTranscriber {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    async fn transcribe(&self, audio: &Path, _mime_type: &str) -> anyhow::Result<String> {
+        let output = tokio::time::timeout(
+            Duration::from_secs(self.timeout_secs),
+            tokio::process::Command::new(&self.command)
+                .args(self.command_args(audio))
+                .stdin(std::process::Stdio::null())
+                .kill_on_drop(true)
+                .output(),
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Transcription command timed out after {}s",
+                self.timeout_secs
+            )
+        })?
+        .with_context(|| format!("Failed to run transcription command '{}'", self.command))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!(
+                "Transcription command exited with {}: {}",
+                output.status,
+                crate::util::truncate_with_ellipsis(stderr.trim(), 500)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+// ── Factory ──────────────────────────────────────────────────
+
+/// Build the configured transcriber, or `None` when transcription is disabled.
+pub fn create_transcriber(
+    config: &TranscriptionConfig,
+) -> anyhow::Result<Option<Arc<dyn Transcriber>>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let transcriber: Arc<dyn Transcriber> = match config.backend.trim() {
+        "openai" => {
+            let api_key = config
+                .api_key
+                .clone()
+                .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+                .unwrap_or_default();
+            Arc::new(OpenAiTranscriber::new(
+                &config.api_url,
+                &api_key,
+                &config.model,
+                config.language.clone(),
+                config.timeout_secs,
+            ))
+        }
+        "command" => {
+            let command = config
+                .command
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .context(
+                    "multimodal.transcription.command is required for backend = \"command\"",
+                )?;
+            Arc::new(CommandTranscriber::new(
+                command,
+                config.args.clone(),
+                config.timeout_secs,
+            ))
+        }
+        "stub" => Arc::new(StubTranscriber),
+        other => bail!(
+            "Unknown multimodal.transcription.backend '{other}' (expected openai, command or stub)"
+        ),
+    };
+    Ok(Some(transcriber))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_config_builds_no_transcriber() {
+        assert!(create_transcriber(&TranscriptionConfig::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn factory_validates_backend_settings() {
+        let mut config = TranscriptionConfig {
+            enabled: true,
+            backend: "command".into(),
+            ..TranscriptionConfig::default()
+        };
+        assert!(create_transcriber(&config).is_err());
+
+        config.command = Some("whisper-cli".into());
+        assert_eq!(
+            create_transcriber(&config).unwrap().unwrap().name(),
+            "command"
+        );
+
+        config.backend = "carrier-pigeon".into();
+        assert!(create_transcriber(&config).is_err());
+    }
+
+    #[test]
+    fn openai_url_appends_transcriptions_path_once() {
+        let base = OpenAiTranscriber::new("https://api.groq.com/openai/v1/", "", "m", None, 60);
+        assert_eq!(
+            base.transcriptions_url(),
+            "https://api.groq.com/openai/v1/audio/transcriptions"
+        );
+        let full = OpenAiTranscriber::new(
+            "http://localhost:8000/v1/audio/transcriptions",
+            "",
+            "m",
+            None,
+            60,
+        );
+        assert_eq!(
+            full.transcriptions_url(),
+            "http://localhost:8000/v1/audio/transcriptions"
+        );
+    }
+
+    #[test]
+    fn command_args_substitute_or_append_file() {
+        let audio = Path::new("/tmp/voice.ogg");
+        let templated = CommandTranscriber::new(
+            "whisper-cli",
+            vec!["-f".into(), "{file}".into(), "-nt".into()],
+            60,
+        );
+        assert_eq!(
+            templated.command_args(audio),
+            vec!["-f", "/tmp/voice.ogg", "-nt"]
+        );
+
+        let bare = CommandTranscriber::new("transcribe", vec!["--quiet".into()], 60);
+        assert_eq!(bare.command_args(audio), vec!["--quiet", "/tmp/voice.ogg"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_backend_reads_stdout() {
+        let transcriber = CommandTranscriber::new("echo", vec!["heard {file}".into()], 10);
+        let text = transcriber
+            .transcribe(Path::new("note.ogg"), "audio/ogg")
+            .await
+            .unwrap();
+        assert_eq!(text, "heard note.ogg");
+
+        let failing = CommandTranscriber::new("false", Vec::new(), 10);
+        assert!(failing
+            .transcribe(Path::new("note.ogg"), "audio/ogg")
+            .await
+            .is_err());
+    }
+}
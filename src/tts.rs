@@ -0,0 +1,408 @@
+use crate::config::TtsConfig;
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Trait for text-to-speech backends — render text to an audio clip
+#[async_trait]
+pub trait SpeechSynthesizer: Send + Sync {
+    /// Backend name
+    fn name(&self) -> &str;
+
+    /// Audio format produced by `synthesize`
+    fn format(&self) -> AudioFormat;
+
+    /// Render text to audio bytes
+    async fn synthesize(&self, text: &str) -> anyhow::Result<Vec<u8>>;
+}
+
+/// Output container for synthesized speech.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    /// Ogg/Opus, sent as a native voice note where supported
+    Opus,
+    Mp3,
+    Wav,
+    Aac,
+    Flac,
+}
+
+impl AudioFormat {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "opus" | "ogg" => Some(Self::Opus),
+            "mp3" => Some(Self::Mp3),
+            "wav" => Some(Self::Wav),
+            "aac" => Some(Self::Aac),
+            "flac" => Some(Self::Flac),
+            _ => None,
+        }
+    }
+
+    /// Value of the `response_format` field on `/audio/speech`.
+    pub fn api_name(self) -> &'static str {
+        match self {
+            Self::Opus => "opus",
+            Self::Mp3 => "mp3",
+            Self::Wav => "wav",
+            Self::Aac => "aac",
+            Self::Flac => "flac",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Opus => "ogg",
+            other => other.api_name(),
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Opus => "audio/ogg",
+            Self::Mp3 => "audio/mpeg",
+            Self::Wav => "audio/wav",
+            Self::Aac => "audio/aac",
+            Self::Flac => "audio/flac",
+        }
+    }
+}
+
+/// Reduce a markdown reply to text that reads naturally when spoken.
+///
+/// Code blocks and image markers are dropped and inline formatting
+/// characters are removed.
+pub fn speakable_text(response: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    for line in response.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let (without_images, _) = crate::multimodal::parse_image_markers(line);
+        let cleaned: String = without_images
+            .trim_start_matches(|c: char| c == '#' || c == '>' || c.is_whitespace())
+            .chars()
+            .filter(|c| !matches!(c, '*' | '_' | '`' | '~'))
+            .collect();
+        let cleaned = cleaned.trim();
+        if !cleaned.is_empty() {
+            lines.push(cleaned.to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+// ── Stub backend (offline testing) ───────────────────────────
+
+/// Returns the text bytes as "audio", without running a speech engine.
+pub struct StubSpeech;
+
+#[async_trait]
+impl SpeechSynthesizer for StubSpeech {
+    fn name(&self) -> &str {
+        "stub"
+    }
+
+    fn format(&self) -> AudioFormat {
+        AudioFormat::Wav
+    }
+
+    async fn synthesize(&self, text: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(text.as_bytes().to_vec())
+    }
+}
+
+// ── OpenAI-compatible /audio/speech ──────────────────────────
+
+pub struct OpenAiSpeech {
+    base_url: String,
+    api_key: String,
+    model: String,
+    voice: String,
+    format: AudioFormat,
+    timeout_secs: u64,
+}
+
+impl OpenAiSpeech {
+    pub fn new(
+        base_url: &str,
+        api_key: &str,
+        model: &str,
+        voice: &str,
+        format: AudioFormat,
+        timeout_secs: u64,
+    ) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+            voice: voice.to_string(),
+            format,
+            timeout_secs,
+        }
+    }
+
+    fn speech_url(&self) -> String {
+        if self.base_url.ends_with("/audio/speech") {
+            self.base_url.clone()
+        } else {
+            format!("{}/audio/speech", self.base_url)
+        }
+    }
+}
+
+#[async_trait]
+impl SpeechSynthesizer for OpenAiSpeech {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    async fn synthesize(&self, text: &str) -> anyhow::Result<Vec<u8>> {
+        let mut request = crate::config::build_runtime_proxy_client_with_timeouts(
+            "provider.tts",
+            self.timeout_secs,
+            10,
+        )
+        .post(self.speech_url())
+        .json(&serde_json::json!({
+            "model": self.model,
+            "voice": self.voice,
+            "input": text,
+            "response_format": self.format.api_name(),
+        }));
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+
+        let resp = request.send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            bail!(
+                "Speech API error {status}: {}",
+                crate::providers::sanitize_api_error(&text)
+            );
+        }
+
+        Ok(resp.bytes().await?.to_vec())
+    }
+}
+
+// ── Local command (piper and friends) ────────────────────────
+
+/// Runs a local program with the text on stdin.
+///
+/// When an argument contains `{output}`, it is replaced with a temporary file
+/// path and the audio is read from that file; otherwise audio is read from
+/// stdout.
+pub struct CommandSpeech {
+    command: String,
+    args: Vec<String>,
+    format: AudioFormat,
+    timeout_secs: u64,
+}
+
+impl CommandSpeech {
+    pub fn new(command: &str, args: Vec<String>, format: AudioFormat, timeout_secs: u64) -> Self {
+        Self {
+            command: command.to_string(),
+            args,
+            format,
+            timeout_secs,
+        }
+    }
+
+    async fn run(&self, text: &str, output_path: &std::path::Path) -> anyhow::Result<Vec<u8>> {
+        let output_arg = output_path.display().to_string();
+        let writes_file = self.args.iter().any(|arg| arg.contains("{output}"));
+        let mut child = tokio::process::Command::new(&self.command)
+            .args(
+                self.args
+                    .iter()
+                    .map(|arg| arg.replace("{output}", &output_arg)),
+            )
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to run speech command '{}'", self.command))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes()).await?;
+        }
+        let output = child.wait_with_output().await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!(
+                "Speech command exited with {}: {}",
+                output.status,
+                crate::util::truncate_with_ellipsis(stderr.trim(), 500)
+            );
+        }
+
+        let audio = if writes_file {
+            tokio::fs::read(output_path)
+                .await
+                .context("Speech command did not write its output file")?
+        } else {
+            output.stdout
+        };
+        if audio.is_empty() {
+            bail!("Speech command produced no audio");
+        }
+        Ok(audio)
+    }
+}
+
+#[async_trait]
+impl SpeechSynthesizer for CommandSpeech {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    async fn synthesize(&self, text: &str) -> anyhow::Result<Vec<u8>> {
+        let output_path = std::env::temp_dir().join(format!(
+            "zeroclaw-tts-{}.{}",
+            uuid::Uuid::new_v4(),
+            self.format.extension()
+        ));
+        let result = tokio::time::timeout(
+            Duration::from_secs(self.timeout_secs),
+            self.run(text, &output_path),
+        )
+        .await
+        .with_context(|| format!("Speech command timed out after {}s", self.timeout_secs));
+        let _ = tokio::fs::remove_file(&output_path).await;
+        result?
+    }
+}
+
+// ── Factory ──────────────────────────────────────────────────
+
+/// Build the configured synthesizer, or `None` when text-to-speech is disabled.
+pub fn create_synthesizer(
+    config: &TtsConfig,
+) -> anyhow::Result<Option<Arc<dyn SpeechSynthesizer>>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let format = AudioFormat::parse(&config.format).with_context(|| {
+        format!(
+            "Unknown multimodal.tts.format '{}' (expected opus, mp3, wav, aac or flac)",
+            config.format
+        )
+    })?;
+
+    let synthesizer: Arc<dyn SpeechSynthesizer> = match config.backend.trim() {
+        "openai" => {
+            let api_key = config
+                .api_key
+                .clone()
+                .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+                .unwrap_or_default();
+            Arc::new(OpenAiSpeech::new(
+                &config.api_url,
+                &api_key,
+                &config.model,
+                &config.voice,
+                format,
+                config.timeout_secs,
+            ))
+        }
+        "command" => {
+            let command = config
+                .command
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .context("multimodal.tts.command is required for backend = \"command\"")?;
+            Arc::new(CommandSpeech::new(
+                command,
+                config.args.clone(),
+                format,
+                config.timeout_secs,
+            ))
+        }
+        "stub" => Arc::new(StubSpeech),
+        other => {
+            bail!("Unknown multimodal.tts.backend '{other}' (expected openai, command or stub)")
+        }
+    };
+    Ok(Some(synthesizer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factory_validates_backend_and_format() {
+        assert!(create_synthesizer(&TtsConfig::default()).unwrap().is_none());
+
+        let mut config = TtsConfig {
+            enabled: true,
+            backend: "command".into(),
+            ..TtsConfig::default()
+        };
+        assert!(create_synthesizer(&config).is_err());
+
+        config.command = Some("piper".into());
+        config.format = "wav".into();
+        let synthesizer = create_synthesizer(&config).unwrap().unwrap();
+        assert_eq!(synthesizer.name(), "command");
+        assert_eq!(synthesizer.format(), AudioFormat::Wav);
+
+        config.format = "midi".into();
+        assert!(create_synthesizer(&config).is_err());
+    }
+
+    #[test]
+    fn opus_is_sent_as_ogg_voice_note() {
+        let format = AudioFormat::parse("opus").unwrap();
+        assert_eq!(format.extension(), "ogg");
+        assert_eq!(format.mime_type(), "audio/ogg");
+        assert_eq!(AudioFormat::Mp3.mime_type(), "audio/mpeg");
+    }
+
+    #[test]
+    fn speakable_text_drops_code_and_formatting() {
+        let reply = "## Result\n**Done.** See `main.rs`:\n```rust\nfn main() {}\n```\n> All _good_ [IMAGE:/tmp/chart.png]";
+        assert_eq!(
+            speakable_text(reply),
+            "Result\nDone. See main.rs:\nAll good"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_backend_reads_stdout_or_output_file() {
+        let stdout = CommandSpeech::new("cat", Vec::new(), AudioFormat::Wav, 10);
+        assert_eq!(stdout.synthesize("hello").await.unwrap(), b"hello");
+
+        let to_file = CommandSpeech::new(
+            "sh",
+            vec!["-c".into(), "cat > {output}".into()],
+            AudioFormat::Wav,
+            10,
+        );
+        assert_eq!(to_file.synthesize("hi there").await.unwrap(), b"hi there");
+    }
+}
@@ -64,6 +64,7 @@ impl Provider for MockProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -99,6 +100,7 @@ impl Tool for EchoTool {
             success: true,
             output: msg,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -138,6 +140,7 @@ impl Tool for CountingTool {
             success: true,
             output: format!("call #{}", *c),
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -188,6 +191,7 @@ impl Provider for RecordingProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -235,6 +239,7 @@ fn text_response(text: &str) -> ChatResponse {
     ChatResponse {
         text: Some(text.into()),
         tool_calls: vec![],
+        usage: None,
     }
 }
 
@@ -242,6 +247,7 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
     ChatResponse {
         text: Some(String::new()),
         tool_calls: calls,
+        usage: None,
     }
 }
 
@@ -365,6 +371,7 @@ async fn e2e_xml_dispatcher_tool_call() {
                     .into(),
             ),
             tool_calls: vec![],
+            usage: None,
         },
         text_response("XML tool executed"),
     ]));
@@ -59,6 +59,7 @@ impl Provider for MockProvider {
             return Ok(ChatResponse {
                 text: Some("done".into()),
                 tool_calls: vec![],
+                usage: None,
             });
         }
         Ok(guard.remove(0))
@@ -93,6 +94,7 @@ impl Tool for EchoTool {
             success: true,
             output: msg,
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -116,6 +118,7 @@ impl Tool for FailingTool {
             success: false,
             output: String::new(),
             error: Some("Service unavailable: connection timeout".into()),
+            parts: Vec::new(),
         })
     }
 }
@@ -155,6 +158,7 @@ impl Tool for CountingTool {
             success: true,
             output: format!("call #{}", *c),
             error: None,
+            parts: Vec::new(),
         })
     }
 }
@@ -179,6 +183,7 @@ fn text_response(text: &str) -> ChatResponse {
     ChatResponse {
         text: Some(text.into()),
         tool_calls: vec![],
+        usage: None,
     }
 }
 
@@ -186,6 +191,7 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
     ChatResponse {
         text: Some(String::new()),
         tool_calls: calls,
+        usage: None,
     }
 }
 
@@ -353,6 +359,7 @@ async fn agent_handles_empty_provider_response() {
     let provider = Box::new(MockProvider::new(vec![ChatResponse {
         text: Some(String::new()),
         tool_calls: vec![],
+        usage: None,
     }]));
 
     let mut agent = build_agent(provider, vec![Box::new(EchoTool)]);
@@ -366,6 +373,7 @@ async fn agent_handles_none_text_response() {
     let provider = Box::new(MockProvider::new(vec![ChatResponse {
         text: None,
         tool_calls: vec![],
+        usage: None,
     }]));
 
     let mut agent = build_agent(provider, vec![Box::new(EchoTool)]);